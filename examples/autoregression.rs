@@ -0,0 +1,73 @@
+/*
+ * autoregression.rs
+ *
+ * Exemplo de previsão de série temporal com a API da biblioteca.
+ *
+ * Gera uma série AR(2), y[t] = 1.2 y[t-1] - 0.5 y[t-2] + ruído,
+ * monta amostras com sliding_window (os 2 valores anteriores como
+ * entrada e o próximo como saída esperada) e treina um neurônio
+ * com ativação identidade nas primeiras amostras. As últimas
+ * amostras ficam de fora, em ordem temporal, e servem para comparar
+ * a previsão de um passo com a base ingênua "repete o último valor".
+ *
+ * Execução:
+ *   cargo run --example autoregression
+ */
+
+use perceptron::data::sliding_window;
+use perceptron::prelude::*;
+use perceptron::utils::{randn_with, seeded_rng};
+
+const SERIES_LEN: usize = 400;
+const TEST_LEN: usize = 80;
+
+fn main() {
+    let mut rng = seeded_rng(5);
+    let mut series: Vec<f32> = vec![0.0, 0.0];
+    for t in 2..SERIES_LEN {
+        let next = 1.2 * series[t - 1] - 0.5 * series[t - 2] + randn_with(&mut rng, 0.0, 0.1);
+        series.push(next);
+    }
+
+    let data = match sliding_window(&series, 2, 1) {
+        Ok(data) => data,
+        Err(err) => panic!("série inválida: {}", err),
+    };
+
+    // Sem embaralhar: o teste é o final da série
+    let n_train = data.len() - TEST_LEN;
+    let (train_x, test_x) = data.inputs().split_at(n_train);
+    let (train_y, test_y) = data.targets().split_at(n_train);
+    let train_data = match Dataset::new(train_x.to_vec(), train_y.to_vec()) {
+        Ok(data) => data,
+        Err(err) => panic!("dados de treino inválidos: {}", err),
+    };
+
+    let mut neuron: Neuron32 = Neuron::new_seeded(Activation::Identity, 2, 5);
+    let config = TrainConfig {
+        epochs: 3000,
+        learning_rate: 0.5,
+        seed: Some(5),
+        ..TrainConfig::default()
+    };
+
+    println!("***Antes do treinamento***");
+    println!("Custo no treino     : {}", try_compute_cost_on(&neuron, &train_data, mse).expect("dados válidos"));
+
+    if let Err(err) = fit_on(&mut neuron, mse, &train_data, &config) {
+        panic!("falha no treinamento: {}", err);
+    }
+
+    println!("***Depois do treinamento***");
+    println!("Custo no treino     : {}", try_compute_cost_on(&neuron, &train_data, mse).expect("dados válidos"));
+    println!("O neurônio          : {:.4}", neuron);
+    println!("Coeficientes reais  : y[t-2] = -0.5, y[t-1] = 1.2");
+
+    // A base ingênua prevê o último valor da janela
+    let predicted = neuron.predict_batch(test_x).expect("janelas de 2 valores");
+    let last_value: Vec<f32> = test_x.iter().map(|window| window[1]).collect();
+
+    println!("*** Previsão de um passo nas últimas {} amostras ***", TEST_LEN);
+    println!("Custo do neurônio   : {}", mse(test_y, &predicted, TEST_LEN));
+    println!("Custo da base       : {}", mse(test_y, &last_value, TEST_LEN));
+}
@@ -0,0 +1,67 @@
+/*
+ * classification.rs
+ *
+ * Exemplo de classificação binária com a API da biblioteca.
+ *
+ * Gera dois grupos gaussianos com make_blobs, treina um neurônio
+ * sigmoid com fit_on e avalia as amostras de teste com limiar 0.5,
+ * exibindo a acurácia e a matriz de confusão.
+ *
+ * Execução:
+ *   cargo run --example classification
+ */
+
+use perceptron::data::make_blobs;
+use perceptron::prelude::*;
+
+fn main() {
+    let centers = vec![vec![1.0, 1.0], vec![4.0, 4.0]];
+    let (x, labels) = match make_blobs(200, &centers, 0.8, 11) {
+        Ok(blobs) => blobs,
+        Err(err) => panic!("falha ao gerar os dados: {}", err),
+    };
+    let targets = labels.iter().map(|&label| label as f32).collect();
+
+    let data = match Dataset::new(x, targets) {
+        Ok(data) => data,
+        Err(err) => panic!("dados inválidos: {}", err),
+    };
+    let (train_data, test_data) = match train_test_split(&data, 0.25, Some(11)) {
+        Ok(split) => split,
+        Err(err) => panic!("divisão treino/teste inválida: {}", err),
+    };
+
+    let mut neuron: Neuron32 = Neuron::new_seeded(Activation::Sigmoid, 2, 11);
+    let config = TrainConfig {
+        epochs: 3000,
+        learning_rate: 0.5,
+        seed: Some(11),
+        ..TrainConfig::default()
+    };
+
+    println!("***Antes do treinamento***");
+    println!("Custo no treino     : {}", try_compute_cost_on(&neuron, &train_data, mse).expect("dados válidos"));
+
+    if let Err(err) = fit_on(&mut neuron, mse, &train_data, &config) {
+        panic!("falha no treinamento: {}", err);
+    }
+
+    println!("***Depois do treinamento***");
+    println!("Custo no treino     : {}", try_compute_cost_on(&neuron, &train_data, mse).expect("dados válidos"));
+    println!("O neurônio          : {:.4}", neuron);
+
+    // confusion[real][previsto], com a classe 1 como positiva
+    let mut confusion = [[0usize; 2]; 2];
+    let predicted = neuron.predict_labels(test_data.inputs(), 0.5);
+    for (&label, &target) in predicted.iter().zip(test_data.targets()) {
+        confusion[usize::from(target >= 0.5)][usize::from(label)] += 1;
+    }
+    let correct = confusion[0][0] + confusion[1][1];
+
+    println!("*** Teste ***");
+    println!("Acurácia            : {:.2}% ({}/{})", 100.0 * correct as f32 / test_data.len() as f32, correct, test_data.len());
+    println!("Matriz de confusão  :   prev 0   prev 1");
+    for (actual, row) in confusion.iter().enumerate() {
+        println!("  real {}            : {:>8} {:>8}", actual, row[0], row[1]);
+    }
+}
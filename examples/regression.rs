@@ -0,0 +1,48 @@
+/*
+ * regression.rs
+ *
+ * Exemplo de regressão linear com a API da biblioteca.
+ *
+ * Gera amostras de y = 3x₁ + 2x₂ + 5, separa uma parte para teste,
+ * treina um neurônio com ativação identidade usando fit_on e um
+ * TrainConfig e exibe o relatório do modelo aprendido.
+ *
+ * Execução:
+ *   cargo run --example regression
+ */
+
+use perceptron::data::make_linear;
+use perceptron::prelude::*;
+
+fn main() {
+    // 200 amostras exatas (sem ruído) com entradas em [0, 1]
+    let linear = match make_linear(200, &[3.0, 2.0], 5.0, 0.0, (0.0, 1.0), 7) {
+        Ok(linear) => linear,
+        Err(err) => panic!("falha ao gerar os dados: {}", err),
+    };
+
+    let (train_data, test_data) = match train_test_split(&linear.data, 0.25, Some(7)) {
+        Ok(split) => split,
+        Err(err) => panic!("divisão treino/teste inválida: {}", err),
+    };
+
+    let mut neuron: Neuron32 = Neuron::new_seeded(Activation::Identity, 2, 7);
+    let config = TrainConfig {
+        epochs: 5000,
+        learning_rate: 0.1,
+        seed: Some(7),
+        ..TrainConfig::default()
+    };
+
+    println!("***Antes do treinamento***");
+    println!("Custo no treino     : {}", try_compute_cost_on(&neuron, &train_data, mse).expect("dados válidos"));
+
+    if let Err(err) = fit_on(&mut neuron, mse, &train_data, &config) {
+        panic!("falha no treinamento: {}", err);
+    }
+
+    println!("***Depois do treinamento***");
+    print!("{}", neuron.report_with(Some(&train_data), Some(&["x1", "x2"])));
+    println!("Custo no teste      : {}", try_compute_cost_on(&neuron, &test_data, mse).expect("dados válidos"));
+    println!("Pesos verdadeiros   : {:?}, bias {}", linear.weights, linear.bias);
+}
@@ -0,0 +1,3179 @@
+/*
+ * data.rs
+ *
+ * Módulo de dados de treinamento.
+ *
+ * Este módulo implementa:
+ *   - Estrutura Dataset, que agrupa entradas e saídas esperadas
+ *     garantindo que os tamanhos sejam consistentes
+ *   - Leitura de conjuntos de dados numéricos em CSV, com separador,
+ *     vírgula decimal e marcadores de ausência configuráveis
+ *   - Leitura de arquivos no formato svmlight/libsvm
+ *   - Leitura e gravação em JSON (feature "serde")
+ *   - Conversão de matrizes do ndarray (feature "ndarray") e do
+ *     nalgebra (feature "nalgebra")
+ *   - Preenchimento de entradas ausentes (Imputer)
+ *   - Estatísticas por coluna (Dataset::describe)
+ *   - Resumo de conteúdo para reprodutibilidade (DatasetFingerprint)
+ *   - Aumento de dados com ruído gaussiano (Dataset::augment_gaussian)
+ *   - Auditoria de dados (Dataset::validate): NaN/infinitos, colunas
+ *     constantes e linhas duplicadas
+ *   - Embaralhamento reproduzível das amostras
+ *   - Iteração em mini-lotes sem cópia das amostras (batches)
+ *   - Seleção de entradas por correlação com a saída
+ *     (select_by_correlation e apply_selection)
+ *   - Tratamento de classes desbalanceadas (balanced_weights e
+ *     Dataset::oversample_minority)
+ *   - Divisão em conjuntos de treino e teste, aleatória
+ *     (train_test_split) ou estratificada por classe (stratified_split)
+ *   - Normalização min-max das entradas (MinMaxScaler)
+ *   - Padronização z-score das entradas e das saídas (StandardScaler)
+ *   - Codificação one-hot de rótulos (one_hot) e de entradas
+ *     categóricas (OneHotEncoder)
+ *   - Conversão de nomes de classes em rótulos inteiros (LabelEncoder)
+ *   - Amostras de séries temporais por janela deslizante (sliding_window)
+ *   - Geração de dados sintéticos de regressão linear (make_linear)
+ *     e de classificação em grupos gaussianos (make_blobs)
+ */
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::io;
+use std::ops::Range;
+use std::path::Path;
+use std::rc::Rc;
+use std::str::FromStr;
+
+#[cfg(feature = "nalgebra")]
+use nalgebra::{DMatrix, DVector};
+#[cfg(feature = "ndarray")]
+use ndarray::{ArrayView1, ArrayView2};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+use crate::error::CeptronError;
+use crate::modelio::read_text;
+#[cfg(feature = "serde")]
+use crate::modelio::write_file;
+use crate::netmath::{from_f64, Float, Scalar};
+use crate::neuralnet::ClassLabelError;
+use crate::neuron::InputDimError;
+use crate::utils::{default_rng, randn_with, randomize_with, rng_from, seeded_rng};
+
+/*
+ * Erros de construção de um Dataset.
+ *
+ * Variantes:
+ *   Empty - nenhuma amostra
+ *   LengthMismatch { inputs, targets } - número de entradas diferente
+ *                                        do número de saídas esperadas
+ *   RowWidth { row, expected, actual } - linha com número de valores
+ *                                        diferente da primeira linha
+ *   NaN { row, column } - valor NaN na entrada (column = Some(j))
+ *                         ou na saída esperada (column = None)
+ *   FeatureWidth { expected, actual } - ao juntar dois conjuntos, o
+ *                                       segundo tem actual entradas
+ *                                       e o primeiro, expected
+ *   FeatureNames { expected, actual } - número de nomes de entrada
+ *                                       diferente do número de entradas
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub enum DataError {
+    Empty,
+    LengthMismatch { inputs: usize, targets: usize },
+    RowWidth { row: usize, expected: usize, actual: usize },
+    NaN { row: usize, column: Option<usize> },
+    FeatureWidth { expected: usize, actual: usize },
+    FeatureNames { expected: usize, actual: usize },
+}
+
+impl fmt::Display for DataError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DataError::Empty => write!(f, "o conjunto de dados não tem amostras"),
+            DataError::LengthMismatch { inputs, targets } => write!(
+                f,
+                "{} amostras de entrada para {} saídas esperadas",
+                inputs, targets
+            ),
+            DataError::RowWidth { row, expected, actual } => write!(
+                f,
+                "a amostra {} tem {} valores, mas as anteriores têm {}",
+                row, actual, expected
+            ),
+            DataError::NaN { row, column: Some(column) } => {
+                write!(f, "valor NaN na amostra {}, entrada {}", row, column)
+            }
+            DataError::NaN { row, column: None } => {
+                write!(f, "saída esperada NaN na amostra {}", row)
+            }
+            DataError::FeatureWidth { expected, actual } => write!(
+                f,
+                "o conjunto acrescentado tem {} entradas, mas o original tem {}",
+                actual, expected
+            ),
+            DataError::FeatureNames { expected, actual } => write!(
+                f,
+                "{} nomes para {} entradas",
+                actual, expected
+            ),
+        }
+    }
+}
+
+impl std::error::Error for DataError {}
+
+/*
+ * Erros de leitura de um conjunto de dados em CSV.
+ *
+ * Variantes:
+ *   Io - falha ao ler o arquivo (a mensagem inclui o caminho)
+ *   Parse { line, column, value } - campo que não é um número
+ *   ColumnCount { line, expected, actual } - linha com número de
+ *                                            colunas diferente da
+ *                                            primeira linha
+ *   UnterminatedQuote { line } - aspas abertas e não fechadas
+ *   TargetColumn { target_column, n_columns } - coluna alvo inexistente
+ *   Data(DataError) - os valores lidos violam os invariantes do
+ *                     Dataset (ex: arquivo sem amostras)
+ *
+ * line e column começam em 1, como em um editor de texto; line
+ * conta o cabeçalho e as linhas em branco.
+ */
+#[derive(Debug)]
+pub enum CsvError {
+    Io(io::Error),
+    Parse { line: usize, column: usize, value: String },
+    ColumnCount { line: usize, expected: usize, actual: usize },
+    UnterminatedQuote { line: usize },
+    TargetColumn { target_column: usize, n_columns: usize },
+    Data(DataError),
+}
+
+impl fmt::Display for CsvError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CsvError::Io(err) => write!(f, "erro de E/S: {}", err),
+            CsvError::Parse { line, column, value } => write!(
+                f,
+                "linha {}, coluna {}: \"{}\" não é um número",
+                line, column, value
+            ),
+            CsvError::ColumnCount { line, expected, actual } => write!(
+                f,
+                "linha {}: {} colunas, mas as anteriores têm {}",
+                line, actual, expected
+            ),
+            CsvError::UnterminatedQuote { line } => {
+                write!(f, "linha {}: aspas não fechadas", line)
+            }
+            CsvError::TargetColumn { target_column, n_columns } => write!(
+                f,
+                "coluna alvo {} não existe em um arquivo com {} colunas",
+                target_column, n_columns
+            ),
+            CsvError::Data(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl std::error::Error for CsvError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            CsvError::Io(err) => Some(err),
+            CsvError::Data(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for CsvError {
+    fn from(err: io::Error) -> Self {
+        CsvError::Io(err)
+    }
+}
+
+impl From<DataError> for CsvError {
+    fn from(err: DataError) -> Self {
+        CsvError::Data(err)
+    }
+}
+
+/*
+ * Opções de leitura de CSV (ver Dataset::from_csv_with_options).
+ *
+ * Campos:
+ *   delimiter - separador dos campos (ex: b',', b';' ou b'\t')
+ *   decimal_comma - se true, números usam vírgula decimal ("1,5");
+ *                   com delimiter b',', esses campos precisam estar
+ *                   entre aspas
+ *   skip_rows - número de linhas ignoradas no início do arquivo
+ *               (ex: um preâmbulo), antes do cabeçalho
+ *   has_header - se true, a linha após as ignoradas é o cabeçalho,
+ *                com os nomes das colunas (ver Dataset::feature_names)
+ *   na_strings - campos de entrada lidos como ausentes (NaN), ex:
+ *                "NA" ou "" (campo vazio); na coluna alvo continuam
+ *                sendo erro
+ *
+ * O valor padrão lê CSV comum: separador ',', ponto decimal, sem
+ * cabeçalho e sem valores ausentes.
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub struct CsvOptions {
+    pub delimiter: u8,
+    pub decimal_comma: bool,
+    pub skip_rows: usize,
+    pub has_header: bool,
+    pub na_strings: Vec<String>,
+}
+
+impl Default for CsvOptions {
+    fn default() -> Self {
+        Self {
+            delimiter: b',',
+            decimal_comma: false,
+            skip_rows: 0,
+            has_header: false,
+            na_strings: Vec::new(),
+        }
+    }
+}
+
+/*
+ * Identificação de um conjunto de dados, gravada com o modelo para
+ * saber com quais dados ele foi treinado.
+ *
+ * Campos:
+ *   rows - número de amostras
+ *   features - número de entradas por amostra
+ *   hash - hash FNV-1a dos valores (entradas e saídas esperadas);
+ *          muda se qualquer valor mudar
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DatasetFingerprint {
+    pub rows: usize,
+    pub features: usize,
+    pub hash: u64,
+}
+
+impl DatasetFingerprint {
+
+    /*
+     * Calcula a identificação de um conjunto de amostras.
+     *
+     * Parâmetros:
+     *   x - entradas de cada amostra
+     *   y - saída esperada de cada amostra
+     */
+    pub fn of<T: Scalar>(x: &[Vec<T>], y: &[T]) -> Self {
+        let mut hash: u64 = 0xcbf29ce484222325;
+        let mut feed = |value: T| {
+            for byte in value.to_f64().unwrap_or(f64::NAN).to_bits().to_le_bytes() {
+                hash ^= u64::from(byte);
+                hash = hash.wrapping_mul(0x100000001b3);
+            }
+        };
+        for (row, &target) in x.iter().zip(y) {
+            row.iter().for_each(|&value| feed(value));
+            feed(target);
+        }
+        Self {
+            rows: y.len(),
+            features: x.first().map_or(0, Vec::len),
+            hash,
+        }
+    }
+}
+
+/*
+ * Erros de leitura de um arquivo svmlight/libsvm.
+ *
+ * Variantes:
+ *   Io - falha ao ler o arquivo (a mensagem inclui o caminho)
+ *   Label { line, value } - rótulo que não é um número
+ *   Pair { line, token } - item que não está no formato índice:valor
+ *   Index { line, token } - índice que não é um inteiro >= 1
+ *   Value { line, token } - valor que não é um número
+ *   NotIncreasing { line, index } - índices fora de ordem ou repetidos
+ *   IndexTooLarge { line, index, n_features } - índice maior que o
+ *                                               n_features informado
+ *   Data(DataError) - os valores lidos violam os invariantes do
+ *                     Dataset (ex: arquivo sem amostras)
+ *
+ * line começa em 1 e conta comentários e linhas em branco.
+ */
+#[derive(Debug)]
+pub enum LibsvmError {
+    Io(io::Error),
+    Label { line: usize, value: String },
+    Pair { line: usize, token: String },
+    Index { line: usize, token: String },
+    Value { line: usize, token: String },
+    NotIncreasing { line: usize, index: usize },
+    IndexTooLarge { line: usize, index: usize, n_features: usize },
+    Data(DataError),
+}
+
+impl fmt::Display for LibsvmError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LibsvmError::Io(err) => write!(f, "erro de E/S: {}", err),
+            LibsvmError::Label { line, value } => {
+                write!(f, "linha {}: rótulo \"{}\" não é um número", line, value)
+            }
+            LibsvmError::Pair { line, token } => {
+                write!(f, "linha {}: \"{}\" não está no formato índice:valor", line, token)
+            }
+            LibsvmError::Index { line, token } => {
+                write!(f, "linha {}: índice inválido em \"{}\" (esperado inteiro >= 1)", line, token)
+            }
+            LibsvmError::Value { line, token } => {
+                write!(f, "linha {}: valor inválido em \"{}\"", line, token)
+            }
+            LibsvmError::NotIncreasing { line, index } => {
+                write!(f, "linha {}: índice {} repetido ou fora de ordem", line, index)
+            }
+            LibsvmError::IndexTooLarge { line, index, n_features } => write!(
+                f,
+                "linha {}: índice {} maior que o número de entradas ({})",
+                line, index, n_features
+            ),
+            LibsvmError::Data(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl std::error::Error for LibsvmError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            LibsvmError::Io(err) => Some(err),
+            LibsvmError::Data(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for LibsvmError {
+    fn from(err: io::Error) -> Self {
+        LibsvmError::Io(err)
+    }
+}
+
+impl From<DataError> for LibsvmError {
+    fn from(err: DataError) -> Self {
+        LibsvmError::Data(err)
+    }
+}
+
+/*
+ * Erros de train_test_split.
+ *
+ * Variantes:
+ *   InvalidFraction { fraction } - fração fora do intervalo (0, 1)
+ *   EmptySplit { n_train, n_test } - a divisão deixaria uma das
+ *                                    partes sem amostras
+ *   TooFewSamples { class, count } - classe com menos de 2 amostras
+ *                                    (divisão estratificada)
+ *   LabelCount { samples, labels } - número de rótulos diferente do
+ *                                    número de amostras
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub enum SplitError {
+    InvalidFraction { fraction: f32 },
+    EmptySplit { n_train: usize, n_test: usize },
+    TooFewSamples { class: usize, count: usize },
+    LabelCount { samples: usize, labels: usize },
+}
+
+impl fmt::Display for SplitError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SplitError::InvalidFraction { fraction } => write!(
+                f,
+                "fração de teste {} fora do intervalo (0, 1)",
+                fraction
+            ),
+            SplitError::EmptySplit { n_train, n_test } => write!(
+                f,
+                "divisão com {} amostras de treino e {} de teste: as duas partes precisam de amostras",
+                n_train, n_test
+            ),
+            SplitError::TooFewSamples { class, count } => write!(
+                f,
+                "a classe {} tem {} amostra(s); a divisão estratificada precisa de pelo menos 2",
+                class, count
+            ),
+            SplitError::LabelCount { samples, labels } => {
+                write!(f, "{} rótulos para {} amostras", labels, samples)
+            }
+        }
+    }
+}
+
+impl std::error::Error for SplitError {}
+
+/*
+ * Erro retornado por Dataset::batches quando batch_size é 0.
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub struct BatchSizeError;
+
+impl fmt::Display for BatchSizeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "o tamanho do lote deve ser maior que zero")
+    }
+}
+
+impl std::error::Error for BatchSizeError {}
+
+/*
+ * Conjunto de dados de treinamento.
+ *
+ * Campos:
+ *   x - entradas de cada amostra (todas com n_features valores)
+ *   y - saída esperada (gabarito) de cada amostra
+ *   feature_names - nome de cada entrada (ex: o cabeçalho do CSV),
+ *                   se conhecido; usado por export_predictions e pelo
+ *                   relatório do modelo no lugar de x1, x2, ...
+ *
+ * Os campos são privados: um Dataset só pode ser criado por
+ * Dataset::new, que garante x.len() == y.len(), linhas do mesmo
+ * tamanho e nenhum NaN. Assim o número de amostras não precisa
+ * mais ser passado separadamente para o treinamento.
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub struct Dataset<T = Float> {
+    x: Vec<Vec<T>>,
+    y: Vec<T>,
+    feature_names: Option<Vec<String>>,
+}
+
+impl<T: Scalar> Dataset<T> {
+
+    /*
+     * Cria um conjunto de dados validando seus invariantes.
+     *
+     * Parâmetros:
+     *   x - entradas de cada amostra
+     *   y - saída esperada de cada amostra
+     *
+     * Retorno:
+     *   O conjunto de dados, ou CeptronError::Data (ver DataError) se
+     *   estiver vazio, se os tamanhos de x e y forem diferentes, se as
+     *   linhas de x tiverem tamanhos diferentes ou se houver algum NaN
+     */
+    pub fn new(x: Vec<Vec<T>>, y: Vec<T>) -> Result<Self, CeptronError> {
+        Ok(Self::build(x, y, false)?)
+    }
+
+    /*
+     * Cria um conjunto de dados a partir de matrizes do ndarray (uma
+     * amostra por linha de x), com as mesmas validações de new.
+     *
+     * Parâmetros:
+     *   x - entradas, uma amostra por linha (views não contíguas são
+     *       aceitas)
+     *   y - saída esperada de cada amostra
+     *
+     * Retorno:
+     *   O Dataset criado, ou CeptronError::Data (ex: LengthMismatch
+     *   se x e y tiverem números de amostras diferentes)
+     */
+    #[cfg(feature = "ndarray")]
+    pub fn from_ndarray(x: ArrayView2<T>, y: ArrayView1<T>) -> Result<Self, CeptronError> {
+        let rows = x.rows().into_iter().map(|row| row.to_vec()).collect();
+        Self::new(rows, y.to_vec())
+    }
+
+    /*
+     * Cria um conjunto de dados a partir de matrizes do nalgebra (uma
+     * amostra por linha de x), com as mesmas validações de new.
+     *
+     * Retorno:
+     *   O Dataset criado, ou CeptronError::Data (ex: LengthMismatch
+     *   se x e y tiverem números de amostras diferentes)
+     */
+    #[cfg(feature = "nalgebra")]
+    pub fn from_dmatrix(x: &DMatrix<T>, y: &DVector<T>) -> Result<Self, CeptronError>
+    where
+        T: nalgebra::Scalar,
+    {
+        let rows = x.row_iter().map(|row| row.iter().copied().collect()).collect();
+        Self::new(rows, y.iter().copied().collect())
+    }
+
+    /*
+     * Converte as entradas em uma DMatrix (uma amostra por linha).
+     */
+    #[cfg(feature = "nalgebra")]
+    pub fn to_dmatrix(&self) -> DMatrix<T>
+    where
+        T: nalgebra::Scalar,
+    {
+        DMatrix::from_fn(self.len(), self.n_features(), |row, column| self.x[row][column])
+    }
+
+    /*
+     * Cria um conjunto de dados que pode ter entradas ausentes,
+     * representadas por NaN (ex: campos vazios lidos por
+     * from_csv_with_missing).
+     *
+     * O Dataset resultante não deve ser usado diretamente no
+     * treinamento: as entradas NaN precisam antes ser preenchidas
+     * por um Imputer. As saídas esperadas continuam sem NaN.
+     *
+     * Retorno:
+     *   O mesmo que new, exceto que NaN é aceito nas entradas
+     */
+    pub fn new_with_missing(x: Vec<Vec<T>>, y: Vec<T>) -> Result<Self, CeptronError> {
+        Ok(Self::build(x, y, true)?)
+    }
+
+    /*
+     * Valida os invariantes e cria o Dataset (ver new).
+     */
+    fn build(x: Vec<Vec<T>>, y: Vec<T>, allow_missing: bool) -> Result<Self, DataError> {
+        if x.is_empty() {
+            return Err(DataError::Empty);
+        }
+        if x.len() != y.len() {
+            return Err(DataError::LengthMismatch { inputs: x.len(), targets: y.len() });
+        }
+
+        let expected = x[0].len();
+        for (row, (sample, target)) in x.iter().zip(&y).enumerate() {
+            if sample.len() != expected {
+                return Err(DataError::RowWidth { row, expected, actual: sample.len() });
+            }
+            if !allow_missing
+                && let Some(column) = sample.iter().position(|value| value.is_nan())
+            {
+                return Err(DataError::NaN { row, column: Some(column) });
+            }
+            if target.is_nan() {
+                return Err(DataError::NaN { row, column: None });
+            }
+        }
+
+        Ok(Self { x, y, feature_names: None })
+    }
+
+    /*
+     * Cria um conjunto de dados a partir de pares (entrada, saída
+     * esperada), validando cada par à medida que é lido.
+     *
+     * Parâmetros:
+     *   iter - qualquer fonte de pares (ex: um parser em streaming)
+     *
+     * Retorno:
+     *   O conjunto de dados, ou CeptronError::Data indicando o
+     *   primeiro par inválido (tamanho diferente do primeiro par ou
+     *   NaN), ou com DataError::Empty se não houver pares
+     */
+    pub fn from_pairs<I: IntoIterator<Item = (Vec<T>, T)>>(iter: I) -> Result<Self, CeptronError> {
+        let mut pairs = iter.into_iter();
+        let (first_x, first_y) = pairs.next().ok_or(DataError::Empty)?;
+        let mut data = Self::build(vec![first_x], vec![first_y], false)?;
+
+        for (sample, target) in pairs {
+            data.push(sample, target)?;
+        }
+        Ok(data)
+    }
+
+    /*
+     * Acrescenta uma amostra ao final do conjunto de dados.
+     *
+     * Parâmetros:
+     *   sample - vetor de entrada (n_features valores)
+     *   target - saída esperada
+     *
+     * Retorno:
+     *   Ok(()), ou CeptronError::Data se sample tiver outro tamanho
+     *   ou houver NaN; o erro indica a posição que a amostra ocuparia
+     *   (len()) e o Dataset não é alterado
+     */
+    pub fn push(&mut self, sample: Vec<T>, target: T) -> Result<(), CeptronError> {
+        let row = self.len();
+        let expected = self.x[0].len();
+        if sample.len() != expected {
+            return Err(DataError::RowWidth { row, expected, actual: sample.len() }.into());
+        }
+        if let Some(column) = sample.iter().position(|value| value.is_nan()) {
+            return Err(DataError::NaN { row, column: Some(column) }.into());
+        }
+        if target.is_nan() {
+            return Err(DataError::NaN { row, column: None }.into());
+        }
+
+        self.x.push(sample);
+        self.y.push(target);
+        Ok(())
+    }
+
+    /*
+     * Junta dois conjuntos de dados (ex: partes de um mesmo CSV
+     * gravado em vários arquivos).
+     *
+     * Parâmetros:
+     *   a - primeiras amostras
+     *   b - amostras acrescentadas depois das de a
+     *
+     * Retorno:
+     *   Um novo Dataset com as amostras de a seguidas das de b, ou
+     *   CeptronError::Data (DataError::FeatureWidth) se o número de
+     *   entradas for diferente
+     */
+    pub fn concat(a: &Self, b: &Self) -> Result<Self, CeptronError> {
+        let mut data = a.clone();
+        data.extend(b)?;
+        Ok(data)
+    }
+
+    /*
+     * Acrescenta ao final as amostras de outro conjunto de dados.
+     *
+     * Parâmetros:
+     *   other - amostras a acrescentar (na mesma ordem)
+     *
+     * Retorno:
+     *   Ok(()), ou CeptronError::Data (DataError::FeatureWidth) se
+     *   other tiver outro número de entradas (o Dataset não é
+     *   alterado)
+     *
+     * Entradas ausentes (NaN) de other são copiadas como estão.
+     */
+    pub fn extend(&mut self, other: &Self) -> Result<(), CeptronError> {
+        if other.n_features() != self.n_features() {
+            return Err(DataError::FeatureWidth { expected: self.n_features(), actual: other.n_features() }.into());
+        }
+        self.x.extend_from_slice(&other.x);
+        self.y.extend_from_slice(&other.y);
+        Ok(())
+    }
+
+    /*
+     * Resumo do conteúdo do conjunto (ver DatasetFingerprint::of).
+     */
+    pub fn fingerprint(&self) -> DatasetFingerprint {
+        DatasetFingerprint::of(&self.x, &self.y)
+    }
+
+    /*
+     * Calcula estatísticas de cada entrada e da saída esperada, em
+     * uma única passada (variância pelo método de Welford, estável
+     * mesmo com valores grandes).
+     *
+     * Retorno:
+     *   Um Summary com uma linha por entrada e, por último, a saída
+     *   esperada. Como Dataset::new rejeita conjuntos vazios, todas
+     *   as estatísticas estão definidas (nunca há divisão por zero).
+     */
+    pub fn describe(&self) -> Summary<T> {
+        let n_features = self.x[0].len();
+        let mut accumulators = vec![Welford::new(); n_features + 1];
+
+        for (sample, &target) in self.x.iter().zip(&self.y) {
+            for (accumulator, &value) in accumulators.iter_mut().zip(sample) {
+                accumulator.add(value);
+            }
+            accumulators[n_features].add(target);
+        }
+
+        let columns = accumulators
+            .into_iter()
+            .enumerate()
+            .map(|(j, accumulator)| {
+                let column = if j < n_features { Column::Input(j) } else { Column::Target };
+                accumulator.finish(column)
+            })
+            .collect();
+        Summary { columns }
+    }
+
+    /*
+     * Audita o conjunto de dados, relatando todos os problemas (e
+     * não só o primeiro).
+     *
+     * Retorno:
+     *   Ok(()), ou a lista de problemas: células NaN ou infinitas
+     *   (com linha e coluna), entradas constantes, amostras
+     *   duplicadas e saída esperada constante
+     */
+    pub fn validate(&self) -> Result<(), Vec<DataIssue>> {
+        let mut issues = Vec::new();
+
+        for (row, (sample, &target)) in self.x.iter().zip(&self.y).enumerate() {
+            let cells = sample
+                .iter()
+                .enumerate()
+                .map(|(j, &value)| (Column::Input(j), value))
+                .chain([(Column::Target, target)]);
+            for (column, value) in cells {
+                if value.is_nan() {
+                    issues.push(DataIssue::NaN { row, column });
+                } else if value.is_infinite() {
+                    issues.push(DataIssue::Infinite { row, column });
+                }
+            }
+        }
+
+        if self.len() > 1 {
+            for column in 0..self.x[0].len() {
+                let first = self.x[0][column];
+                if self.x.iter().all(|sample| sample[column] == first) {
+                    issues.push(DataIssue::ConstantColumn { column });
+                }
+            }
+        }
+
+        let mut seen: HashMap<Vec<u64>, usize> = HashMap::new();
+        for (row, (sample, &target)) in self.x.iter().zip(&self.y).enumerate() {
+            let key = sample
+                .iter()
+                .chain([&target])
+                .map(|value| value.to_f64().map_or(0, f64::to_bits))
+                .collect();
+            match seen.get(&key) {
+                Some(&first) => issues.push(DataIssue::DuplicateRow { row, first }),
+                None => {
+                    seen.insert(key, row);
+                }
+            }
+        }
+
+        if self.len() > 1 && self.y.iter().all(|&target| target == self.y[0]) {
+            issues.push(DataIssue::ConstantTarget);
+        }
+
+        if issues.is_empty() { Ok(()) } else { Err(issues) }
+    }
+
+    /*
+     * Remove as amostras com alguma célula NaN ou infinita (na
+     * entrada ou na saída esperada).
+     *
+     * Retorno:
+     *   O número de amostras removidas, ou CeptronError::Data
+     *   (DataError::Empty) se todas forem inválidas (um Dataset não
+     *   pode ficar vazio; nesse caso nada é removido)
+     */
+    pub fn drop_invalid_rows(&mut self) -> Result<usize, CeptronError> {
+        let valid: Vec<bool> = self
+            .x
+            .iter()
+            .zip(&self.y)
+            .map(|(sample, target)| target.is_finite() && sample.iter().all(|value| value.is_finite()))
+            .collect();
+        let removed = valid.iter().filter(|&&valid| !valid).count();
+        if removed == self.len() {
+            return Err(DataError::Empty.into());
+        }
+        if removed == 0 {
+            return Ok(0);
+        }
+
+        let mut keep = valid.iter();
+        self.x.retain(|_| *keep.next().unwrap_or(&true));
+        let mut keep = valid.iter();
+        self.y.retain(|_| *keep.next().unwrap_or(&true));
+        Ok(removed)
+    }
+
+    /*
+     * Verifica se alguma entrada está ausente (NaN).
+     */
+    pub fn has_missing(&self) -> bool {
+        self.x.iter().flatten().any(|value| value.is_nan())
+    }
+
+    /*
+     * Retorna o número de amostras.
+     */
+    pub fn len(&self) -> usize {
+        self.y.len()
+    }
+
+    /*
+     * Sempre false: Dataset::new rejeita conjuntos vazios.
+     */
+    pub fn is_empty(&self) -> bool {
+        self.y.is_empty()
+    }
+
+    /*
+     * Retorna o número de valores de entrada de cada amostra.
+     */
+    pub fn n_features(&self) -> usize {
+        self.x[0].len()
+    }
+
+    /*
+     * Retorna as entradas de todas as amostras.
+     */
+    pub fn inputs(&self) -> &[Vec<T>] {
+        &self.x
+    }
+
+    /*
+     * Retorna as saídas esperadas de todas as amostras.
+     */
+    pub fn targets(&self) -> &[T] {
+        &self.y
+    }
+
+    /*
+     * Retorna os nomes das entradas, se conhecidos (ex: lidos do
+     * cabeçalho do CSV ou definidos por with_feature_names).
+     */
+    pub fn feature_names(&self) -> Option<&[String]> {
+        self.feature_names.as_deref()
+    }
+
+    /*
+     * Dá nomes às entradas (ex: para que a equação do modelo use
+     * "area" e "rooms" em vez de x1 e x2).
+     *
+     * Parâmetros:
+     *   names - um nome por entrada, na ordem das colunas
+     *
+     * Retorno:
+     *   O conjunto de dados com os nomes, ou CeptronError::Data
+     *   (DataError::FeatureNames) se names não tiver um nome por
+     *   entrada
+     */
+    pub fn with_feature_names(mut self, names: Vec<String>) -> Result<Self, CeptronError> {
+        if names.len() != self.n_features() {
+            return Err(DataError::FeatureNames { expected: self.n_features(), actual: names.len() }.into());
+        }
+        self.feature_names = Some(names);
+        Ok(self)
+    }
+
+    /*
+     * Retorna a amostra i como (entrada, saída esperada).
+     *
+     * Entra em pânico se i >= len().
+     */
+    pub fn sample(&self, i: usize) -> (&[T], T) {
+        (&self.x[i], self.y[i])
+    }
+
+    /*
+     * Embaralha as amostras (Fisher–Yates), mantendo cada entrada
+     * junto de sua saída esperada.
+     *
+     * Parâmetros:
+     *   seed - semente do embaralhamento (None usa um gerador
+     *          aleatório); a mesma semente produz sempre a mesma
+     *          permutação
+     */
+    pub fn shuffle(&mut self, seed: Option<u64>) {
+        match seed {
+            Some(seed) => self.shuffle_with(&mut seeded_rng(seed)),
+            None => self.shuffle_with(&mut default_rng()),
+        }
+    }
+
+    /*
+     * Retorna uma cópia embaralhada do conjunto de dados (ver shuffle).
+     */
+    pub fn shuffled(&self, seed: Option<u64>) -> Self {
+        let mut data = self.clone();
+        data.shuffle(seed);
+        data
+    }
+
+    /*
+     * Embaralha as amostras usando o gerador fornecido.
+     */
+    fn shuffle_with(&mut self, rng: &mut impl Rng) {
+        for i in (1..self.len()).rev() {
+            let j = rng.gen_range(0..=i);
+            self.x.swap(i, j);
+            self.y.swap(i, j);
+        }
+    }
+
+    /*
+     * Aumenta o conjunto de dados com cópias ruidosas das amostras
+     * (útil para conjuntos muito pequenos).
+     *
+     * Parâmetros:
+     *   copies - número de cópias ruidosas de cada amostra
+     *   noise_std - desvio padrão do ruído Normal(0, std) de cada
+     *               entrada; 0.0 mantém a entrada intacta (ex: colunas
+     *               categóricas ou one-hot)
+     *   target_std - desvio padrão do ruído da saída esperada, ou
+     *                None para manter a saída original
+     *   seed - semente do gerador; a mesma semente gera as mesmas cópias
+     *
+     * Retorno:
+     *   Um novo Dataset com len() * (1 + copies) amostras (as
+     *   originais seguidas das cópias), ou InputDimError se noise_std
+     *   não tiver um valor por entrada
+     */
+    pub fn augment_gaussian(
+        &self,
+        copies: usize,
+        noise_std: &[T],
+        target_std: Option<T>,
+        seed: u64
+    ) -> Result<Self, InputDimError> {
+        if noise_std.len() != self.n_features() {
+            return Err(InputDimError { expected: self.n_features(), actual: noise_std.len() });
+        }
+
+        let mut rng = seeded_rng(seed);
+        let mut data = self.clone();
+        data.x.reserve(self.len() * copies);
+        data.y.reserve(self.len() * copies);
+
+        for _copy in 0..copies {
+            for (sample, &target) in self.x.iter().zip(&self.y) {
+                let noisy = sample
+                    .iter()
+                    .zip(noise_std)
+                    .map(|(&value, &std)| {
+                        if std == T::zero() { value } else { randn_with(&mut rng, value, std) }
+                    })
+                    .collect();
+                let target = match target_std {
+                    Some(std) if std != T::zero() => randn_with(&mut rng, target, std),
+                    _ => target,
+                };
+                data.x.push(noisy);
+                data.y.push(target);
+            }
+        }
+
+        Ok(data)
+    }
+
+    /*
+     * Equilibra as classes duplicando amostras das classes
+     * minoritárias (sorteadas com reposição) até que todas tenham
+     * tantas amostras quanto a maior. A classe de cada amostra é a
+     * sua saída esperada (ex: 0.0 e 1.0 em classificação binária).
+     *
+     * Parâmetros:
+     *   seed - semente do sorteio; a mesma semente gera o mesmo conjunto
+     *
+     * Retorno:
+     *   Um novo Dataset com as amostras originais seguidas das
+     *   duplicadas (use shuffled para misturá-las)
+     */
+    pub fn oversample_minority(&self, seed: u64) -> Self {
+        let mut classes: Vec<(T, Vec<usize>)> = Vec::new();
+        for (i, &target) in self.y.iter().enumerate() {
+            match classes.iter_mut().find(|(class, _)| *class == target) {
+                Some((_, members)) => members.push(i),
+                None => classes.push((target, vec![i])),
+            }
+        }
+
+        let largest = classes.iter().map(|(_, members)| members.len()).max().unwrap_or(0);
+        let mut rng = seeded_rng(seed);
+        let mut data = self.clone();
+        for (_, members) in &classes {
+            for _i in members.len()..largest {
+                let chosen = members[rng.gen_range(0..members.len())];
+                data.x.push(self.x[chosen].clone());
+                data.y.push(self.y[chosen]);
+            }
+        }
+        data
+    }
+
+    /*
+     * Percorre as amostras em lotes consecutivos.
+     *
+     * Parâmetros:
+     *   batch_size - número de amostras por lote
+     *   drop_last - se true, o último lote é descartado quando tem
+     *               menos de batch_size amostras
+     *
+     * Retorno:
+     *   Um iterador de DatasetView (referências às amostras, sem
+     *   cópia), ou BatchSizeError se batch_size for 0
+     */
+    pub fn batches(&self, batch_size: usize, drop_last: bool) -> Result<Batches<'_, T>, BatchSizeError> {
+        Batches::new(self, None, batch_size, drop_last)
+    }
+
+    /*
+     * Percorre as amostras em lotes, em uma ordem embaralhada
+     * (ver batches e shuffle). Só os índices são embaralhados:
+     * as amostras não são copiadas.
+     *
+     * Parâmetros:
+     *   batch_size - número de amostras por lote
+     *   drop_last - se true, descarta o último lote incompleto
+     *   seed - semente do embaralhamento (None usa um gerador aleatório)
+     */
+    pub fn batches_shuffled(
+        &self,
+        batch_size: usize,
+        drop_last: bool,
+        seed: Option<u64>
+    ) -> Result<Batches<'_, T>, BatchSizeError> {
+        let mut order: Vec<usize> = (0..self.len()).collect();
+        match seed {
+            Some(seed) => order.shuffle(&mut seeded_rng(seed)),
+            None => order.shuffle(&mut default_rng()),
+        }
+        Batches::new(self, Some(order.into()), batch_size, drop_last)
+    }
+
+    /*
+     * Cria um Dataset com as amostras indicadas, clonando apenas
+     * essas linhas.
+     *
+     * Parâmetros:
+     *   indices - índices das amostras, na ordem desejada
+     *
+     * Uso interno: os chamadores (divisões, folds e lotes) garantem
+     * que indices não está vazio e que todo índice é < len(), para
+     * que o resultado mantenha os invariantes de Dataset::new.
+     */
+    pub(crate) fn subset(&self, indices: &[usize]) -> Self {
+        debug_assert!(!indices.is_empty(), "Dataset::subset sem índices");
+        Self {
+            x: indices.iter().map(|&i| self.x[i].clone()).collect(),
+            y: indices.iter().map(|&i| self.y[i]).collect(),
+            feature_names: self.feature_names.clone(),
+        }
+    }
+}
+
+/*
+ * Permite montar um Dataset ao fim de uma cadeia de iteradores:
+ *   let data = pares.into_iter().filter(...).try_collect_dataset()?;
+ *
+ * Não há impl FromIterator: collect() não tem como reportar um par
+ * inválido. Para fontes que já produzem Result (ex: linhas de um
+ * parser), trate o erro de leitura antes, com map/collect, ou use
+ * Dataset::from_pairs.
+ */
+pub trait TryCollectDataset<T>: Iterator<Item = (Vec<T>, T)> + Sized {
+
+    /*
+     * Consome o iterador e monta o Dataset (ver Dataset::from_pairs).
+     *
+     * Retorno:
+     *   O conjunto de dados, ou CeptronError::Data indicando o
+     *   índice do primeiro par inválido
+     */
+    fn try_collect_dataset(self) -> Result<Dataset<T>, CeptronError>;
+}
+
+impl<T: Scalar, I: Iterator<Item = (Vec<T>, T)>> TryCollectDataset<T> for I {
+    fn try_collect_dataset(self) -> Result<Dataset<T>, CeptronError> {
+        Dataset::from_pairs(self)
+    }
+}
+
+/*
+ * Coluna de um Dataset: uma entrada (pelo índice, a partir de 0) ou
+ * a saída esperada.
+ *
+ * A formatação (Display) usa os mesmos nomes do relatório e da
+ * equação do modelo: x1, x2, ... (a partir de 1) e y.
+ */
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Column {
+    Input(usize),
+    Target,
+}
+
+impl fmt::Display for Column {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Column::Input(j) => write!(f, "x{}", j + 1),
+            Column::Target => write!(f, "y"),
+        }
+    }
+}
+
+/*
+ * Estatísticas de uma coluna (ver Dataset::describe).
+ *
+ * Campos:
+ *   column - a coluna descrita
+ *   count - número de valores
+ *   min, max - menor e maior valor
+ *   mean - média
+ *   std - desvio padrão populacional (0.0 para colunas constantes)
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub struct FeatureStats<T = Float> {
+    pub column: Column,
+    pub count: usize,
+    pub min: T,
+    pub max: T,
+    pub mean: T,
+    pub std: T,
+}
+
+/*
+ * Resumo de um Dataset: uma linha de estatísticas por coluna.
+ *
+ * A formatação (Display) imprime uma tabela alinhada, útil para
+ * perceber quando as entradas precisam de normalização.
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub struct Summary<T = Float> {
+    pub columns: Vec<FeatureStats<T>>,
+}
+
+impl<T: Scalar> fmt::Display for Summary<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let precision = f.precision().unwrap_or(4);
+        writeln!(
+            f,
+            "{:<6} {:>6} {:>12} {:>12} {:>12} {:>12}",
+            "coluna", "n", "mín", "máx", "média", "desvio"
+        )?;
+        for stats in &self.columns {
+            writeln!(
+                f,
+                "{:<6} {:>6} {:>12.p$} {:>12.p$} {:>12.p$} {:>12.p$}",
+                stats.column.to_string(),
+                stats.count,
+                stats.min,
+                stats.max,
+                stats.mean,
+                stats.std,
+                p = precision
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/*
+ * Acumulador de média e variância pelo método de Welford.
+ */
+#[derive(Clone)]
+struct Welford<T> {
+    count: usize,
+    min: T,
+    max: T,
+    mean: T,
+    m2: T,
+}
+
+impl<T: Scalar> Welford<T> {
+    fn new() -> Self {
+        Self {
+            count: 0,
+            min: T::infinity(),
+            max: T::neg_infinity(),
+            mean: T::zero(),
+            m2: T::zero(),
+        }
+    }
+
+    fn add(&mut self, value: T) {
+        self.count += 1;
+        self.min = self.min.min(value);
+        self.max = self.max.max(value);
+        let delta = value - self.mean;
+        self.mean += delta / from_f64(self.count as f64);
+        self.m2 += delta * (value - self.mean);
+    }
+
+    fn finish(self, column: Column) -> FeatureStats<T> {
+        let variance = self.m2 / from_f64(self.count as f64);
+        FeatureStats {
+            column,
+            count: self.count,
+            min: self.min,
+            max: self.max,
+            mean: self.mean,
+            std: variance.sqrt(),
+        }
+    }
+}
+
+/*
+ * Problema encontrado por Dataset::validate.
+ *
+ * Variantes:
+ *   NaN { row, column } - valor NaN na célula (row, column)
+ *   Infinite { row, column } - valor infinito na célula (row, column)
+ *   ConstantColumn { column } - entrada com o mesmo valor em todas
+ *                               as amostras (não ajuda a prever y)
+ *   DuplicateRow { row, first } - a amostra row (entrada e saída) é
+ *                                 idêntica à amostra first
+ *   ConstantTarget - a saída esperada tem variância zero
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub enum DataIssue {
+    NaN { row: usize, column: Column },
+    Infinite { row: usize, column: Column },
+    ConstantColumn { column: usize },
+    DuplicateRow { row: usize, first: usize },
+    ConstantTarget,
+}
+
+impl fmt::Display for DataIssue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DataIssue::NaN { row, column } => write!(f, "NaN na amostra {}, coluna {}", row, column),
+            DataIssue::Infinite { row, column } => {
+                write!(f, "valor infinito na amostra {}, coluna {}", row, column)
+            }
+            DataIssue::ConstantColumn { column } => {
+                write!(f, "a entrada {} é constante", Column::Input(*column))
+            }
+            DataIssue::DuplicateRow { row, first } => {
+                write!(f, "a amostra {} repete a amostra {}", row, first)
+            }
+            DataIssue::ConstantTarget => write!(f, "a saída esperada é constante"),
+        }
+    }
+}
+
+impl std::error::Error for DataIssue {}
+
+/*
+ * Procura o primeiro NaN nas amostras (verificação barata feita
+ * por fit antes de treinar).
+ *
+ * Parâmetros:
+ *   x - entradas de cada amostra
+ *   y - saídas esperadas
+ *
+ * Retorno:
+ *   DataIssue::NaN com as coordenadas do primeiro NaN, ou None
+ */
+pub fn find_nan<T: Scalar>(x: &[Vec<T>], y: &[T]) -> Option<DataIssue> {
+    for (row, sample) in x.iter().enumerate() {
+        if let Some(j) = sample.iter().position(|value| value.is_nan()) {
+            return Some(DataIssue::NaN { row, column: Column::Input(j) });
+        }
+        if y.get(row).is_some_and(|target| target.is_nan()) {
+            return Some(DataIssue::NaN { row, column: Column::Target });
+        }
+    }
+    None
+}
+
+/*
+ * Iterador de lotes de um Dataset (ver Dataset::batches).
+ */
+pub struct Batches<'a, T = Float> {
+    data: &'a Dataset<T>,
+    order: Option<Rc<[usize]>>,
+    batch_size: usize,
+    end: usize,
+    start: usize,
+}
+
+impl<'a, T: Scalar> Batches<'a, T> {
+
+    /*
+     * Cria o iterador, já descontando o lote incompleto se
+     * drop_last for true.
+     */
+    fn new(
+        data: &'a Dataset<T>,
+        order: Option<Rc<[usize]>>,
+        batch_size: usize,
+        drop_last: bool
+    ) -> Result<Self, BatchSizeError> {
+        if batch_size == 0 {
+            return Err(BatchSizeError);
+        }
+        let len = data.len();
+        let end = if drop_last { len - len % batch_size } else { len };
+        Ok(Self { data, order, batch_size, end, start: 0 })
+    }
+}
+
+impl<'a, T: Scalar> Iterator for Batches<'a, T> {
+    type Item = DatasetView<'a, T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.start >= self.end {
+            return None;
+        }
+        let end = (self.start + self.batch_size).min(self.end);
+        let view = DatasetView {
+            data: self.data,
+            order: self.order.clone(),
+            range: self.start..end,
+        };
+        self.start = end;
+        Some(view)
+    }
+}
+
+/*
+ * Lote de amostras de um Dataset, sem cópia: guarda apenas o
+ * intervalo de posições (e, nos lotes embaralhados, a ordem
+ * compartilhada entre todos os lotes).
+ */
+#[derive(Debug, Clone)]
+pub struct DatasetView<'a, T = Float> {
+    data: &'a Dataset<T>,
+    order: Option<Rc<[usize]>>,
+    range: Range<usize>,
+}
+
+impl<'a, T: Scalar> DatasetView<'a, T> {
+
+    /*
+     * Retorna o número de amostras do lote.
+     */
+    pub fn len(&self) -> usize {
+        self.range.len()
+    }
+
+    /*
+     * Sempre false: Dataset::batches não produz lotes vazios.
+     */
+    pub fn is_empty(&self) -> bool {
+        self.range.is_empty()
+    }
+
+    /*
+     * Retorna a amostra i do lote como (entrada, saída esperada).
+     *
+     * Entra em pânico se i >= len().
+     */
+    pub fn sample(&self, i: usize) -> (&'a [T], T) {
+        assert!(i < self.len(), "amostra {} fora de um lote com {} amostras", i, self.len());
+        let position = self.range.start + i;
+        let row = match &self.order {
+            Some(order) => order[position],
+            None => position,
+        };
+        (&self.data.x[row], self.data.y[row])
+    }
+
+    /*
+     * Percorre as amostras do lote como (entrada, saída esperada).
+     */
+    pub fn iter(&self) -> impl Iterator<Item = (&'a [T], T)> + '_ {
+        (0..self.len()).map(|i| self.sample(i))
+    }
+
+    /*
+     * Retorna as entradas e saídas do lote como fatias do Dataset
+     * original, sem cópia. Só é possível para lotes não
+     * embaralhados (None caso contrário).
+     */
+    pub fn as_slices(&self) -> Option<(&'a [Vec<T>], &'a [T])> {
+        match self.order {
+            Some(_) => None,
+            None => Some((&self.data.x[self.range.clone()], &self.data.y[self.range.clone()])),
+        }
+    }
+
+    /*
+     * Copia as amostras do lote para um novo Dataset.
+     */
+    pub fn to_dataset(&self) -> Dataset<T> {
+        match &self.order {
+            Some(order) => self.data.subset(&order[self.range.clone()]),
+            None => self.data.subset(&self.range.clone().collect::<Vec<_>>()),
+        }
+    }
+}
+
+impl<T: Scalar + FromStr> Dataset<T> {
+
+    /*
+     * Lê um conjunto de dados numérico de um arquivo CSV.
+     *
+     * Parâmetros:
+     *   path - caminho do arquivo
+     *   target_column - índice (a partir de 0) da coluna usada como
+     *                   saída esperada; as demais colunas, na ordem,
+     *                   formam a entrada
+     *   has_header - se true, a primeira linha é o cabeçalho: não é
+     *                lida como amostra e dá os nomes das entradas
+     *
+     * Retorno:
+     *   O conjunto de dados, ou CeptronError::Csv (ver CsvError)
+     *   indicando a linha e a coluna do problema
+     *
+     * Campos podem estar entre aspas ("1.5"), com "" representando
+     * uma aspa literal; espaços ao redor dos campos são ignorados.
+     * Linhas em branco (inclusive as do final do arquivo) são
+     * ignoradas. Campos entre aspas não podem conter quebras de linha.
+     */
+    pub fn from_csv(path: &Path, target_column: usize, has_header: bool) -> Result<Self, CeptronError> {
+        Self::from_csv_with_options(path, target_column, &CsvOptions { has_header, ..CsvOptions::default() })
+    }
+
+    /*
+     * Como from_csv, mas campos de entrada vazios são lidos como
+     * NaN (valor ausente) em vez de causar CsvError::Parse. Um campo
+     * vazio na coluna alvo continua sendo erro.
+     *
+     * Retorno:
+     *   Um Dataset criado por new_with_missing; preencha os valores
+     *   ausentes com um Imputer antes de treinar
+     */
+    pub fn from_csv_with_missing(path: &Path, target_column: usize, has_header: bool) -> Result<Self, CeptronError> {
+        let options = CsvOptions { has_header, na_strings: vec![String::new()], ..CsvOptions::default() };
+        Self::from_csv_with_options(path, target_column, &options)
+    }
+
+    /*
+     * Lê um conjunto de dados de um arquivo CSV com separador,
+     * formato numérico e valores ausentes configuráveis (ex: planilhas
+     * exportadas com ';' e vírgula decimal).
+     *
+     * Parâmetros:
+     *   path - caminho do arquivo
+     *   target_column - índice (a partir de 0) da coluna usada como
+     *                   saída esperada
+     *   options - separador, vírgula decimal, linhas iniciais
+     *             ignoradas, cabeçalho e marcadores de valor ausente
+     *
+     * Retorno:
+     *   O conjunto de dados, ou CeptronError::Csv indicando a linha do
+     *   arquivo e a coluna do problema. Se options.na_strings não for vazio,
+     *   o Dataset é criado por new_with_missing. Os nomes do cabeçalho
+     *   (sem o da coluna alvo) ficam em feature_names quando ele tem
+     *   tantos campos quanto as amostras
+     */
+    pub fn from_csv_with_options(path: &Path, target_column: usize, options: &CsvOptions) -> Result<Self, CeptronError> {
+        Ok(Self::parse_csv(path, target_column, options)?)
+    }
+
+    /*
+     * Implementação de from_csv_with_options, com o erro do módulo.
+     */
+    fn parse_csv(path: &Path, target_column: usize, options: &CsvOptions) -> Result<Self, CsvError> {
+        let text = read_text(path)?;
+        let allow_missing = !options.na_strings.is_empty();
+        let header_lines = options.skip_rows + usize::from(options.has_header);
+        let mut x = Vec::new();
+        let mut y = Vec::new();
+        let mut n_columns = None;
+        let mut header = None;
+
+        for (index, raw) in text.lines().enumerate() {
+            let line = index + 1;
+            if options.has_header && index == options.skip_rows {
+                header = split_csv_line(raw, options.delimiter as char);
+            }
+            if index < header_lines || raw.trim().is_empty() {
+                continue;
+            }
+
+            let fields = split_csv_line(raw, options.delimiter as char).ok_or(CsvError::UnterminatedQuote { line })?;
+            let expected = *n_columns.get_or_insert(fields.len());
+            if fields.len() != expected {
+                return Err(CsvError::ColumnCount { line, expected, actual: fields.len() });
+            }
+            if target_column >= expected {
+                return Err(CsvError::TargetColumn { target_column, n_columns: expected });
+            }
+
+            let mut sample = Vec::with_capacity(expected - 1);
+            for (column, field) in fields.iter().enumerate() {
+                if column != target_column && options.na_strings.contains(field) {
+                    sample.push(T::nan());
+                    continue;
+                }
+                let parsed = if options.decimal_comma {
+                    field.replace(',', ".").parse()
+                } else {
+                    field.parse()
+                };
+                let value: T = parsed.map_err(|_| CsvError::Parse {
+                    line,
+                    column: column + 1,
+                    value: field.clone(),
+                })?;
+                if column == target_column {
+                    y.push(value);
+                } else {
+                    sample.push(value);
+                }
+            }
+            x.push(sample);
+        }
+
+        let mut data = Self::build(x, y, allow_missing)?;
+        if let Some(header) = header.filter(|header| Some(header.len()) == n_columns) {
+            let names = header
+                .into_iter()
+                .enumerate()
+                .filter(|&(column, _)| column != target_column)
+                .map(|(_, name)| name)
+                .collect();
+            data.feature_names = Some(names);
+        }
+        Ok(data)
+    }
+
+    /*
+     * Lê um conjunto de dados no formato svmlight/libsvm:
+     *   rótulo índice:valor índice:valor ...  # comentário opcional
+     *
+     * Parâmetros:
+     *   path - caminho do arquivo
+     *   n_features - número de entradas; None usa o maior índice
+     *                encontrado no arquivo
+     *
+     * Retorno:
+     *   O conjunto de dados (denso: entradas ausentes valem 0.0), ou
+     *   CeptronError::Libsvm (ver LibsvmError) indicando a linha do
+     *   problema
+     */
+    pub fn from_libsvm(path: &Path, n_features: Option<usize>) -> Result<Self, CeptronError> {
+        let text = read_text(path).map_err(LibsvmError::from)?;
+        Self::from_libsvm_str(&text, n_features)
+    }
+
+    /*
+     * Como from_libsvm, mas lê o conteúdo de uma string.
+     *
+     * Os índices começam em 1 e devem ser estritamente crescentes
+     * em cada linha; o rótulo vira a saída esperada. Tudo após '#'
+     * é ignorado, assim como linhas em branco.
+     */
+    pub fn from_libsvm_str(text: &str, n_features: Option<usize>) -> Result<Self, CeptronError> {
+        Ok(Self::parse_libsvm(text, n_features)?)
+    }
+
+    /*
+     * Implementação de from_libsvm_str, com o erro do módulo.
+     */
+    fn parse_libsvm(text: &str, n_features: Option<usize>) -> Result<Self, LibsvmError> {
+        let mut rows: Vec<(Vec<(usize, T)>, T)> = Vec::new();
+        let mut max_index = 0;
+
+        for (index, raw) in text.lines().enumerate() {
+            let line = index + 1;
+            let content = raw.split('#').next().unwrap_or("").trim();
+            let mut tokens = content.split_whitespace();
+            let Some(label) = tokens.next() else {
+                continue;
+            };
+            let target: T = label
+                .parse()
+                .map_err(|_| LibsvmError::Label { line, value: label.to_string() })?;
+
+            let mut pairs = Vec::new();
+            let mut previous = 0;
+            for token in tokens {
+                let (index, value) = token
+                    .split_once(':')
+                    .ok_or(LibsvmError::Pair { line, token: token.to_string() })?;
+                let index: usize = match index.parse() {
+                    Ok(index) if index >= 1 => index,
+                    _ => return Err(LibsvmError::Index { line, token: token.to_string() }),
+                };
+                let value: T = value
+                    .parse()
+                    .map_err(|_| LibsvmError::Value { line, token: token.to_string() })?;
+                if index <= previous {
+                    return Err(LibsvmError::NotIncreasing { line, index });
+                }
+                if let Some(n_features) = n_features
+                    && index > n_features
+                {
+                    return Err(LibsvmError::IndexTooLarge { line, index, n_features });
+                }
+                previous = index;
+                pairs.push((index, value));
+            }
+            max_index = max_index.max(previous);
+            rows.push((pairs, target));
+        }
+
+        let width = n_features.unwrap_or(max_index);
+        let mut x = Vec::with_capacity(rows.len());
+        let mut y = Vec::with_capacity(rows.len());
+        for (pairs, target) in rows {
+            let mut sample = vec![T::zero(); width];
+            for (index, value) in pairs {
+                sample[index - 1] = value;
+            }
+            x.push(sample);
+            y.push(target);
+        }
+
+        Ok(Self::build(x, y, false)?)
+    }
+}
+
+/*
+ * Separa uma linha de CSV em campos, tratando aspas.
+ *
+ * Parâmetros:
+ *   line - a linha, sem a quebra de linha
+ *   delimiter - separador dos campos (fora de aspas)
+ *
+ * Retorno:
+ *   Os campos (sem aspas e sem espaços nas bordas), ou None se
+ *   alguma aspa não for fechada
+ */
+fn split_csv_line(line: &str, delimiter: char) -> Option<Vec<String>> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = line.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if in_quotes && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => in_quotes = !in_quotes,
+            _ if c == delimiter && !in_quotes => {
+                fields.push(field.trim().to_string());
+                field.clear();
+            }
+            _ => field.push(c),
+        }
+    }
+    if in_quotes {
+        return None;
+    }
+    fields.push(field.trim().to_string());
+    Some(fields)
+}
+
+/*
+ * Escreve um campo de CSV que split_csv_line lê de volta igual: entre
+ * aspas (com as aspas internas dobradas) se tiver o delimitador, aspas
+ * ou quebra de linha, e sem alteração caso contrário.
+ *
+ * Parâmetros:
+ *   field - o valor do campo
+ *   delimiter - separador dos campos
+ */
+pub(crate) fn quote_csv_field(field: &str, delimiter: char) -> String {
+    if field.contains([delimiter, '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/*
+ * Divide um conjunto de dados em treino e teste, embaralhando as
+ * amostras.
+ *
+ * Parâmetros:
+ *   dataset - conjunto de dados original (não é alterado)
+ *   test_fraction - fração das amostras reservada para teste,
+ *                   em (0, 1); o número de amostras de teste é
+ *                   arredondado para o inteiro mais próximo
+ *   seed - semente do embaralhamento (None usa um gerador aleatório)
+ *
+ * Retorno:
+ *   (treino, teste), sem amostras em comum, ou SplitError se a
+ *   fração for inválida ou se alguma das partes ficar vazia
+ */
+pub fn train_test_split<T: Scalar>(
+    dataset: &Dataset<T>,
+    test_fraction: f32,
+    seed: Option<u64>
+) -> Result<(Dataset<T>, Dataset<T>), SplitError> {
+    match seed {
+        Some(seed) => train_test_split_with(dataset, test_fraction, &mut seeded_rng(seed)),
+        None => train_test_split_with(dataset, test_fraction, &mut default_rng()),
+    }
+}
+
+/*
+ * Como train_test_split, embaralhando com o gerador fornecido (ex:
+ * um utils::CeptronRng).
+ */
+pub fn train_test_split_with<T: Scalar>(
+    dataset: &Dataset<T>,
+    test_fraction: f32,
+    rng: &mut impl Rng
+) -> Result<(Dataset<T>, Dataset<T>), SplitError> {
+    if !(test_fraction > 0.0 && test_fraction < 1.0) {
+        return Err(SplitError::InvalidFraction { fraction: test_fraction });
+    }
+
+    let len = dataset.len();
+    let n_test = (len as f64 * test_fraction as f64).round() as usize;
+    let n_train = len - n_test.min(len);
+    if n_test == 0 || n_train == 0 {
+        return Err(SplitError::EmptySplit { n_train, n_test });
+    }
+
+    let mut indices: Vec<usize> = (0..len).collect();
+    indices.shuffle(rng);
+
+    let (test, train) = indices.split_at(n_test);
+    Ok((dataset.subset(train), dataset.subset(test)))
+}
+
+/*
+ * Agrupa os índices das amostras por rótulo, em ordem crescente de
+ * rótulo. Só os rótulos presentes aparecem, então rótulos esparsos
+ * (ou usize::MAX) não alocam grupos vazios.
+ *
+ * Parâmetros:
+ *   labels - rótulo de cada amostra
+ *
+ * Retorno:
+ *   (rótulo, índices das amostras com esse rótulo) de cada classe
+ */
+pub(crate) fn group_by_label(labels: &[usize]) -> Vec<(usize, Vec<usize>)> {
+    let mut groups: BTreeMap<usize, Vec<usize>> = BTreeMap::new();
+    for (index, &label) in labels.iter().enumerate() {
+        groups.entry(label).or_default().push(index);
+    }
+    groups.into_iter().collect()
+}
+
+/*
+ * Parte de uma divisão estratificada: entradas e rótulos de classe.
+ */
+pub type LabeledSplit<T> = (Vec<Vec<T>>, Vec<usize>);
+
+/*
+ * Divide amostras de classificação em treino e teste preservando
+ * a proporção de cada classe.
+ *
+ * Cada classe é embaralhada e dividida separadamente: ela contribui
+ * com round(count * test_fraction) amostras para o teste, limitado
+ * a [1, count - 1] para que a classe apareça nas duas partes.
+ *
+ * Parâmetros:
+ *   x - entradas de cada amostra
+ *   labels - classe de cada amostra
+ *   test_fraction - fração reservada para teste, em (0, 1)
+ *   seed - semente do embaralhamento (None usa um gerador aleatório)
+ *
+ * Retorno:
+ *   ((x_treino, rótulos_treino), (x_teste, rótulos_teste)), ou
+ *   SplitError se a fração for inválida, se os tamanhos forem
+ *   diferentes ou se alguma classe tiver menos de 2 amostras
+ */
+pub fn stratified_split<T: Scalar>(
+    x: &[Vec<T>],
+    labels: &[usize],
+    test_fraction: f32,
+    seed: Option<u64>
+) -> Result<(LabeledSplit<T>, LabeledSplit<T>), SplitError> {
+    if !(test_fraction > 0.0 && test_fraction < 1.0) {
+        return Err(SplitError::InvalidFraction { fraction: test_fraction });
+    }
+    if x.len() != labels.len() {
+        return Err(SplitError::LabelCount { samples: x.len(), labels: labels.len() });
+    }
+
+    let mut rng = rng_from(seed);
+    let mut train: LabeledSplit<T> = (Vec::new(), Vec::new());
+    let mut test: LabeledSplit<T> = (Vec::new(), Vec::new());
+
+    for (class, mut indices) in group_by_label(labels) {
+        let count = indices.len();
+        if count < 2 {
+            return Err(SplitError::TooFewSamples { class, count });
+        }
+
+        indices.shuffle(&mut rng);
+        let n_test = ((count as f64 * test_fraction as f64).round() as usize).clamp(1, count - 1);
+        for (position, &index) in indices.iter().enumerate() {
+            let part = if position < n_test { &mut test } else { &mut train };
+            part.0.push(x[index].clone());
+            part.1.push(class);
+        }
+    }
+
+    Ok((train, test))
+}
+
+/*
+ * Estratégia de preenchimento de entradas ausentes.
+ *
+ * Variantes:
+ *   Mean - média dos valores presentes da entrada
+ *   Median - mediana dos valores presentes da entrada
+ *   Constant(value) - um valor fixo
+ */
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ImputeStrategy<T = Float> {
+    Mean,
+    Median,
+    Constant(T),
+}
+
+/*
+ * Erro retornado por Imputer::fit quando uma entrada não tem
+ * nenhum valor presente (só com Mean e Median).
+ *
+ * Campos:
+ *   column - índice da entrada totalmente ausente
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub struct MissingColumnError {
+    pub column: usize,
+}
+
+impl fmt::Display for MissingColumnError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "a entrada {} não tem nenhum valor presente", self.column)
+    }
+}
+
+impl std::error::Error for MissingColumnError {}
+
+/*
+ * Preenche entradas ausentes (NaN) com valores calculados sobre o
+ * conjunto de treino.
+ *
+ * Os valores de preenchimento são calculados uma vez, em fit, e
+ * reutilizados em transform: dados de teste são preenchidos com as
+ * estatísticas do treino.
+ *
+ * Campos:
+ *   fill - valor usado para cada entrada
+ */
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Imputer<T = Float> {
+    pub fill: Vec<T>,
+}
+
+impl<T: Scalar> Imputer<T> {
+
+    /*
+     * Calcula o valor de preenchimento de cada entrada, ignorando
+     * os valores ausentes.
+     *
+     * Parâmetros:
+     *   data - conjunto de treino (ex: de from_csv_with_missing)
+     *   strategy - estratégia de preenchimento
+     *
+     * Retorno:
+     *   O Imputer ajustado, ou MissingColumnError se alguma entrada
+     *   estiver totalmente ausente (com Mean ou Median)
+     */
+    pub fn fit(data: &Dataset<T>, strategy: ImputeStrategy<T>) -> Result<Self, MissingColumnError> {
+        let n_features = data.x[0].len();
+        let mut fill = Vec::with_capacity(n_features);
+
+        for column in 0..n_features {
+            let mut present: Vec<T> = data
+                .x
+                .iter()
+                .map(|sample| sample[column])
+                .filter(|value| !value.is_nan())
+                .collect();
+
+            let value = match strategy {
+                ImputeStrategy::Constant(value) => value,
+                _ if present.is_empty() => return Err(MissingColumnError { column }),
+                ImputeStrategy::Mean => {
+                    let n: T = from_f64(present.len() as f64);
+                    present.iter().fold(T::zero(), |sum, &value| sum + value) / n
+                }
+                ImputeStrategy::Median => {
+                    present.sort_by(|a, b| a.partial_cmp(b).expect("valores presentes não são NaN"));
+                    let middle = present.len() / 2;
+                    if present.len().is_multiple_of(2) {
+                        (present[middle - 1] + present[middle]) / from_f64(2.0)
+                    } else {
+                        present[middle]
+                    }
+                }
+            };
+            fill.push(value);
+        }
+
+        Ok(Self { fill })
+    }
+
+    /*
+     * Preenche as entradas ausentes de um conjunto de dados.
+     *
+     * Retorno:
+     *   Um novo Dataset sem NaN, ou InputDimError se o número de
+     *   entradas for diferente do usado em fit
+     */
+    pub fn transform(&self, data: &Dataset<T>) -> Result<Dataset<T>, InputDimError> {
+        let expected = self.fill.len();
+        let actual = data.x[0].len();
+        if actual != expected {
+            return Err(InputDimError { expected, actual });
+        }
+
+        let x = data
+            .x
+            .iter()
+            .map(|sample| {
+                sample
+                    .iter()
+                    .zip(&self.fill)
+                    .map(|(&value, &fill)| if value.is_nan() { fill } else { value })
+                    .collect()
+            })
+            .collect();
+        Ok(Dataset { x, y: data.y.clone(), feature_names: data.feature_names.clone() })
+    }
+}
+
+/*
+ * Normalização min-max das entradas.
+ *
+ * Cada entrada j é mapeada para (x[j] - min[j]) / (max[j] - min[j]),
+ * com min e max calculados sobre o conjunto de treino. Os mesmos
+ * parâmetros são aplicados depois aos dados de teste e a novas
+ * amostras; valores fora do intervalo visto no treino resultam em
+ * valores fora de [0, 1] (não há saturação).
+ *
+ * Entradas constantes (min == max) são mapeadas para 0.0, e não
+ * para NaN; inverse_transform as devolve ao valor constante.
+ *
+ * As saídas esperadas (targets) não são alteradas.
+ *
+ * Campos:
+ *   min - menor valor de cada entrada no conjunto de treino
+ *   max - maior valor de cada entrada no conjunto de treino
+ */
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct MinMaxScaler<T = Float> {
+    pub min: Vec<T>,
+    pub max: Vec<T>,
+}
+
+impl<T: Scalar> MinMaxScaler<T> {
+
+    /*
+     * Calcula o mínimo e o máximo de cada entrada.
+     *
+     * Parâmetros:
+     *   data - conjunto de treino
+     *
+     * Retorno:
+     *   O normalizador ajustado a data
+     */
+    pub fn fit(data: &Dataset<T>) -> Self {
+        let mut min = data.x[0].clone();
+        let mut max = data.x[0].clone();
+
+        for sample in &data.x[1..] {
+            for (j, &value) in sample.iter().enumerate() {
+                min[j] = min[j].min(value);
+                max[j] = max[j].max(value);
+            }
+        }
+        Self { min, max }
+    }
+
+    /*
+     * Ajusta o normalizador a data e devolve data normalizado.
+     */
+    pub fn fit_transform(data: &Dataset<T>) -> (Self, Dataset<T>) {
+        let scaler = Self::fit(data);
+        let scaled = Dataset {
+            x: data.x.iter().map(|sample| scaler.scale(sample)).collect(),
+            y: data.y.clone(),
+            feature_names: data.feature_names.clone(),
+        };
+        (scaler, scaled)
+    }
+
+    /*
+     * Normaliza uma amostra.
+     *
+     * Parâmetros:
+     *   x - vetor de entrada com n_features valores
+     *
+     * Retorno:
+     *   A amostra normalizada, ou InputDimError se o tamanho de x
+     *   for diferente do número de entradas usado em fit
+     */
+    pub fn transform_sample(&self, x: &[T]) -> Result<Vec<T>, InputDimError> {
+        self.check_width(x)?;
+        Ok(self.scale(x))
+    }
+
+    /*
+     * Normaliza todas as entradas de um conjunto de dados.
+     *
+     * Retorno:
+     *   Um novo Dataset com as entradas normalizadas e as mesmas
+     *   saídas esperadas, ou InputDimError se o número de entradas
+     *   for diferente do usado em fit
+     */
+    pub fn transform(&self, data: &Dataset<T>) -> Result<Dataset<T>, InputDimError> {
+        self.check_width(&data.x[0])?;
+        Ok(Dataset {
+            x: data.x.iter().map(|sample| self.scale(sample)).collect(),
+            y: data.y.clone(),
+            feature_names: data.feature_names.clone(),
+        })
+    }
+
+    /*
+     * Desfaz a normalização de uma amostra.
+     *
+     * Parâmetros:
+     *   x - vetor de entrada normalizado
+     *
+     * Retorno:
+     *   A amostra na escala original, ou InputDimError se o tamanho
+     *   de x for diferente do número de entradas usado em fit
+     */
+    pub fn inverse_transform_sample(&self, x: &[T]) -> Result<Vec<T>, InputDimError> {
+        self.check_width(x)?;
+        Ok(self.unscale(x))
+    }
+
+    /*
+     * Desfaz a normalização de todas as entradas de um conjunto
+     * de dados (ver transform).
+     */
+    pub fn inverse_transform(&self, data: &Dataset<T>) -> Result<Dataset<T>, InputDimError> {
+        self.check_width(&data.x[0])?;
+        Ok(Dataset {
+            x: data.x.iter().map(|sample| self.unscale(sample)).collect(),
+            y: data.y.clone(),
+            feature_names: data.feature_names.clone(),
+        })
+    }
+
+    /*
+     * Verifica se x tem o número de entradas usado em fit.
+     */
+    fn check_width(&self, x: &[T]) -> Result<(), InputDimError> {
+        let expected = self.min.len();
+        if x.len() != expected {
+            return Err(InputDimError { expected, actual: x.len() });
+        }
+        Ok(())
+    }
+
+    /*
+     * Normaliza uma amostra já validada.
+     */
+    fn scale(&self, x: &[T]) -> Vec<T> {
+        x.iter()
+            .zip(self.min.iter().zip(&self.max))
+            .map(|(&value, (&min, &max))| {
+                if max == min { T::zero() } else { (value - min) / (max - min) }
+            })
+            .collect()
+    }
+
+    /*
+     * Desfaz a normalização de uma amostra já validada.
+     */
+    fn unscale(&self, x: &[T]) -> Vec<T> {
+        x.iter()
+            .zip(self.min.iter().zip(&self.max))
+            .map(|(&value, (&min, &max))| value * (max - min) + min)
+            .collect()
+    }
+}
+
+/*
+ * Padronização z-score.
+ *
+ * Cada entrada j é mapeada para (x[j] - mean[j]) / std[j], com a
+ * média e o desvio padrão (populacional) calculados sobre o
+ * conjunto de treino. Entradas com std == 0 são mapeadas para 0.0
+ * e marcadas em `constant`.
+ *
+ * Opcionalmente (fit_target), as saídas esperadas também são
+ * padronizadas, o que ajuda o treinamento com passo fixo quando y
+ * tem uma escala grande; as predições do neurônio voltam à escala
+ * original com inverse_transform_target.
+ *
+ * Campos:
+ *   mean - média de cada entrada no conjunto de treino
+ *   std - desvio padrão de cada entrada no conjunto de treino
+ *   constant - true para as entradas com std == 0
+ *   target - (média, desvio padrão) das saídas esperadas, ou None
+ *            se fit_target não foi chamado (as saídas não mudam)
+ */
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct StandardScaler<T = Float> {
+    pub mean: Vec<T>,
+    pub std: Vec<T>,
+    pub constant: Vec<bool>,
+    pub target: Option<(T, T)>,
+}
+
+impl<T: Scalar> StandardScaler<T> {
+
+    /*
+     * Calcula a média e o desvio padrão de cada entrada.
+     *
+     * Parâmetros:
+     *   data - conjunto de treino
+     *
+     * Retorno:
+     *   O padronizador ajustado às entradas de data (as saídas
+     *   esperadas só são padronizadas depois de fit_target)
+     */
+    pub fn fit(data: &Dataset<T>) -> Self {
+        let n_features = data.x[0].len();
+        let mut mean = Vec::with_capacity(n_features);
+        let mut std = Vec::with_capacity(n_features);
+
+        for j in 0..n_features {
+            let (column_mean, column_std) = mean_std(data.x.iter().map(|sample| sample[j]));
+            mean.push(column_mean);
+            std.push(column_std);
+        }
+
+        let constant = std.iter().map(|&s| s == T::zero()).collect();
+        Self { mean, std, constant, target: None }
+    }
+
+    /*
+     * Calcula a média e o desvio padrão das saídas esperadas, para
+     * que transform também as padronize.
+     *
+     * Parâmetros:
+     *   data - conjunto de treino
+     */
+    pub fn fit_target(&mut self, data: &Dataset<T>) {
+        self.target = Some(mean_std(data.y.iter().copied()));
+    }
+
+    /*
+     * Ajusta o padronizador às entradas de data e devolve data
+     * padronizado.
+     */
+    pub fn fit_transform(data: &Dataset<T>) -> (Self, Dataset<T>) {
+        let scaler = Self::fit(data);
+        let scaled = Dataset {
+            x: data.x.iter().map(|sample| scaler.scale(sample)).collect(),
+            y: data.y.clone(),
+            feature_names: data.feature_names.clone(),
+        };
+        (scaler, scaled)
+    }
+
+    /*
+     * Padroniza uma amostra.
+     *
+     * Parâmetros:
+     *   x - vetor de entrada com n_features valores
+     *
+     * Retorno:
+     *   A amostra padronizada, ou InputDimError se o tamanho de x
+     *   for diferente do número de entradas usado em fit
+     */
+    pub fn transform_sample(&self, x: &[T]) -> Result<Vec<T>, InputDimError> {
+        self.check_width(x)?;
+        Ok(self.scale(x))
+    }
+
+    /*
+     * Padroniza as entradas de um conjunto de dados e, se
+     * fit_target foi chamado, também as saídas esperadas.
+     *
+     * Retorno:
+     *   Um novo Dataset padronizado, ou InputDimError se o número
+     *   de entradas for diferente do usado em fit
+     */
+    pub fn transform(&self, data: &Dataset<T>) -> Result<Dataset<T>, InputDimError> {
+        self.check_width(&data.x[0])?;
+        Ok(Dataset {
+            x: data.x.iter().map(|sample| self.scale(sample)).collect(),
+            y: data.y.iter().map(|&y| self.transform_target(y)).collect(),
+            feature_names: data.feature_names.clone(),
+        })
+    }
+
+    /*
+     * Desfaz a padronização de uma amostra.
+     *
+     * Parâmetros:
+     *   x - vetor de entrada padronizado
+     *
+     * Retorno:
+     *   A amostra na escala original, ou InputDimError se o tamanho
+     *   de x for diferente do número de entradas usado em fit
+     */
+    pub fn inverse_transform_sample(&self, x: &[T]) -> Result<Vec<T>, InputDimError> {
+        self.check_width(x)?;
+        Ok(self.unscale(x))
+    }
+
+    /*
+     * Desfaz a padronização de um conjunto de dados (entradas e,
+     * se fit_target foi chamado, saídas esperadas).
+     */
+    pub fn inverse_transform(&self, data: &Dataset<T>) -> Result<Dataset<T>, InputDimError> {
+        self.check_width(&data.x[0])?;
+        Ok(Dataset {
+            x: data.x.iter().map(|sample| self.unscale(sample)).collect(),
+            y: data.y.iter().map(|&y| self.inverse_transform_target(y)).collect(),
+            feature_names: data.feature_names.clone(),
+        })
+    }
+
+    /*
+     * Padroniza uma saída esperada (inalterada sem fit_target).
+     */
+    pub fn transform_target(&self, y: T) -> T {
+        match self.target {
+            Some((_, std)) if std == T::zero() => T::zero(),
+            Some((mean, std)) => (y - mean) / std,
+            None => y,
+        }
+    }
+
+    /*
+     * Leva uma saída padronizada (ex: a predição de um neurônio
+     * treinado com dados padronizados) de volta à escala original
+     * (inalterada sem fit_target).
+     */
+    pub fn inverse_transform_target(&self, y: T) -> T {
+        match self.target {
+            Some((mean, std)) => y * std + mean,
+            None => y,
+        }
+    }
+
+    /*
+     * Verifica se x tem o número de entradas usado em fit.
+     */
+    fn check_width(&self, x: &[T]) -> Result<(), InputDimError> {
+        let expected = self.mean.len();
+        if x.len() != expected {
+            return Err(InputDimError { expected, actual: x.len() });
+        }
+        Ok(())
+    }
+
+    /*
+     * Padroniza uma amostra já validada.
+     */
+    fn scale(&self, x: &[T]) -> Vec<T> {
+        x.iter()
+            .zip(self.mean.iter().zip(&self.std))
+            .map(|(&value, (&mean, &std))| {
+                if std == T::zero() { T::zero() } else { (value - mean) / std }
+            })
+            .collect()
+    }
+
+    /*
+     * Desfaz a padronização de uma amostra já validada.
+     */
+    fn unscale(&self, x: &[T]) -> Vec<T> {
+        x.iter()
+            .zip(self.mean.iter().zip(&self.std))
+            .map(|(&value, (&mean, &std))| value * std + mean)
+            .collect()
+    }
+}
+
+/*
+ * Calcula a média e o desvio padrão populacional de uma sequência
+ * não vazia de valores.
+ */
+fn mean_std<T: Scalar>(values: impl Iterator<Item = T> + Clone) -> (T, T) {
+    let mut n = T::zero();
+    let mut sum = T::zero();
+    for value in values.clone() {
+        sum += value;
+        n += T::one();
+    }
+    let mean = sum / n;
+
+    let mut sum_squares = T::zero();
+    for value in values {
+        sum_squares += (value - mean) * (value - mean);
+    }
+    (mean, (sum_squares / n).sqrt())
+}
+
+/*
+ * Codifica rótulos de classe como vetores one-hot.
+ *
+ * Parâmetros:
+ *   labels - rótulo de cada amostra (0..n_classes)
+ *   n_classes - número de classes
+ *
+ * Retorno:
+ *   Um vetor por amostra, com 1.0 na posição do rótulo e 0.0 nas
+ *   demais, ou ClassLabelError indicando o primeiro rótulo inválido
+ */
+pub fn one_hot<T: Scalar>(labels: &[usize], n_classes: usize) -> Result<Vec<Vec<T>>, ClassLabelError> {
+    labels
+        .iter()
+        .enumerate()
+        .map(|(index, &label)| {
+            if label >= n_classes {
+                return Err(ClassLabelError { index, label, n_classes });
+            }
+            let mut row = vec![T::zero(); n_classes];
+            row[label] = T::one();
+            Ok(row)
+        })
+        .collect()
+}
+
+/*
+ * O que OneHotEncoder faz com uma categoria que não apareceu em fit.
+ *
+ * Variantes:
+ *   Zeros - codifica a amostra com todas as colunas em 0.0
+ *   Error - retorna EncodeError::UnknownCategory
+ */
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum UnknownCategory {
+    Zeros,
+    Error,
+}
+
+/*
+ * Erros de OneHotEncoder.
+ *
+ * Variantes:
+ *   UnknownCategory { row, value } - categoria não vista em fit (só
+ *                                    com UnknownCategory::Error)
+ *   LengthMismatch { rows, values } - número de categorias diferente
+ *                                     do número de linhas da matriz
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub enum EncodeError {
+    UnknownCategory { row: usize, value: String },
+    LengthMismatch { rows: usize, values: usize },
+}
+
+impl fmt::Display for EncodeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            EncodeError::UnknownCategory { row, value } => {
+                write!(f, "categoria desconhecida {} na amostra {}", value, row)
+            }
+            EncodeError::LengthMismatch { rows, values } => write!(
+                f,
+                "{} categorias para uma matriz com {} amostras",
+                values, rows
+            ),
+        }
+    }
+}
+
+impl std::error::Error for EncodeError {}
+
+/*
+ * Codificador one-hot para uma entrada categórica (textos, inteiros
+ * ou qualquer tipo ordenável).
+ *
+ * Cada categoria vira uma coluna; a ordem das colunas é a ordem
+ * crescente das categorias vistas em fit, de modo que dados de
+ * teste são codificados da mesma forma que os de treino.
+ *
+ * Campos:
+ *   categories - categorias conhecidas, sem repetição, em ordem
+ *   unknown - tratamento de categorias não vistas em fit
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub struct OneHotEncoder<C> {
+    pub categories: Vec<C>,
+    pub unknown: UnknownCategory,
+}
+
+impl<C: Ord + Clone + fmt::Debug> OneHotEncoder<C> {
+
+    /*
+     * Aprende as categorias de uma coluna.
+     *
+     * Parâmetros:
+     *   values - categoria de cada amostra de treino
+     *   unknown - tratamento de categorias desconhecidas em transform
+     *
+     * Retorno:
+     *   O codificador ajustado
+     */
+    pub fn fit(values: &[C], unknown: UnknownCategory) -> Self {
+        let mut categories = values.to_vec();
+        categories.sort();
+        categories.dedup();
+        Self { categories, unknown }
+    }
+
+    /*
+     * Retorna o número de colunas geradas (uma por categoria).
+     */
+    pub fn n_columns(&self) -> usize {
+        self.categories.len()
+    }
+
+    /*
+     * Codifica uma coluna de categorias.
+     *
+     * Parâmetros:
+     *   values - categoria de cada amostra
+     *
+     * Retorno:
+     *   Um vetor one-hot por amostra, ou EncodeError::UnknownCategory
+     *   (com UnknownCategory::Error) indicando a primeira categoria
+     *   desconhecida
+     */
+    pub fn transform<T: Scalar>(&self, values: &[C]) -> Result<Vec<Vec<T>>, EncodeError> {
+        values
+            .iter()
+            .enumerate()
+            .map(|(row, value)| {
+                let mut encoded = vec![T::zero(); self.n_columns()];
+                match self.categories.binary_search(value) {
+                    Ok(column) => encoded[column] = T::one(),
+                    Err(_) if self.unknown == UnknownCategory::Zeros => {}
+                    Err(_) => {
+                        return Err(EncodeError::UnknownCategory { row, value: format!("{:?}", value) })
+                    }
+                }
+                Ok(encoded)
+            })
+            .collect()
+    }
+
+    /*
+     * Codifica uma coluna de categorias e acrescenta as colunas
+     * geradas ao final de cada linha de uma matriz de entradas.
+     * Chamadas sucessivas (uma por entrada categórica) deixam a
+     * matriz com n_features + Σ n_columns() colunas.
+     *
+     * Parâmetros:
+     *   x - matriz de entradas, uma linha por amostra
+     *   values - categoria de cada amostra
+     *
+     * Retorno:
+     *   Ok(()), ou EncodeError se os tamanhos forem diferentes ou
+     *   houver categoria desconhecida (x não é alterado)
+     */
+    pub fn append_to<T: Scalar>(&self, x: &mut [Vec<T>], values: &[C]) -> Result<(), EncodeError> {
+        if x.len() != values.len() {
+            return Err(EncodeError::LengthMismatch { rows: x.len(), values: values.len() });
+        }
+
+        let encoded = self.transform::<T>(values)?;
+        for (row, columns) in x.iter_mut().zip(encoded) {
+            row.extend(columns);
+        }
+        Ok(())
+    }
+}
+
+/*
+ * Erro retornado por LabelEncoder::transform quando um nome de
+ * classe não foi visto em fit.
+ *
+ * Campos:
+ *   index - posição do nome desconhecido
+ *   label - o nome encontrado
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub struct UnknownLabel {
+    pub index: usize,
+    pub label: String,
+}
+
+impl fmt::Display for UnknownLabel {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "classe desconhecida \"{}\" na amostra {}", self.label, self.index)
+    }
+}
+
+impl std::error::Error for UnknownLabel {}
+
+/*
+ * Converte nomes de classes (ex: a coluna de classe de um CSV) nos
+ * rótulos inteiros 0..n_classes usados pelos classificadores.
+ *
+ * Os rótulos seguem a ordem alfabética (crescente) dos nomes, e não
+ * a ordem em que aparecem: assim o mesmo conjunto de classes gera
+ * sempre os mesmos rótulos, independentemente da ordem das amostras.
+ *
+ * Campos:
+ *   classes - nomes das classes, sem repetição; classes[i] é o nome
+ *             do rótulo i
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub struct LabelEncoder {
+    pub classes: Vec<String>,
+}
+
+impl LabelEncoder {
+
+    /*
+     * Aprende os nomes das classes.
+     *
+     * Parâmetros:
+     *   labels - nome da classe de cada amostra
+     *
+     * Retorno:
+     *   O codificador ajustado
+     */
+    pub fn fit(labels: &[String]) -> Self {
+        let mut classes = labels.to_vec();
+        classes.sort();
+        classes.dedup();
+        Self { classes }
+    }
+
+    /*
+     * Retorna o número de classes.
+     */
+    pub fn n_classes(&self) -> usize {
+        self.classes.len()
+    }
+
+    /*
+     * Converte nomes de classes em rótulos.
+     *
+     * Parâmetros:
+     *   labels - nome da classe de cada amostra
+     *
+     * Retorno:
+     *   O rótulo de cada amostra, ou UnknownLabel indicando o
+     *   primeiro nome não visto em fit
+     */
+    pub fn transform(&self, labels: &[String]) -> Result<Vec<usize>, UnknownLabel> {
+        labels
+            .iter()
+            .enumerate()
+            .map(|(index, label)| {
+                self.classes
+                    .binary_search(label)
+                    .map_err(|_| UnknownLabel { index, label: label.clone() })
+            })
+            .collect()
+    }
+
+    /*
+     * Converte rótulos de volta em nomes de classes.
+     *
+     * Parâmetros:
+     *   labels - rótulo de cada amostra (ex: saída de OneVsRest::predict)
+     *
+     * Retorno:
+     *   O nome da classe de cada amostra, ou ClassLabelError
+     *   indicando o primeiro rótulo >= n_classes
+     */
+    pub fn inverse_transform(&self, labels: &[usize]) -> Result<Vec<String>, ClassLabelError> {
+        let n_classes = self.n_classes();
+        labels
+            .iter()
+            .enumerate()
+            .map(|(index, &label)| {
+                self.classes
+                    .get(label)
+                    .cloned()
+                    .ok_or(ClassLabelError { index, label, n_classes })
+            })
+            .collect()
+    }
+}
+
+/*
+ * Seleciona as entradas correlacionadas com a saída esperada:
+ * mantém as colunas cuja correlação de Pearson com y tem valor
+ * absoluto de pelo menos min_abs_corr.
+ *
+ * Parâmetros:
+ *   dataset - conjunto de dados original (não é alterado)
+ *   min_abs_corr - correlação mínima (em valor absoluto), em [0, 1]
+ *
+ * Retorno:
+ *   (dados só com as colunas mantidas, índices das colunas
+ *   mantidas em ordem crescente). Use os índices com
+ *   apply_selection para transformar novas entradas.
+ *
+ * Colunas constantes (ou uma saída constante) não têm correlação
+ * definida e são sempre descartadas, assim como colunas com NaN.
+ */
+pub fn select_by_correlation<T: Scalar>(dataset: &Dataset<T>, min_abs_corr: T) -> (Dataset<T>, Vec<usize>) {
+    let n: T = from_f64(dataset.len() as f64);
+    let y = dataset.targets();
+    let y_mean = y.iter().fold(T::zero(), |acc, &target| acc + target) / n;
+
+    let indices: Vec<usize> = (0..dataset.n_features())
+        .filter(|&column| {
+            let x_mean = dataset.inputs().iter().fold(T::zero(), |acc, sample| acc + sample[column]) / n;
+            let mut cov = T::zero();
+            let mut x_var = T::zero();
+            let mut y_var = T::zero();
+            for (sample, &target) in dataset.inputs().iter().zip(y) {
+                let dx = sample[column] - x_mean;
+                let dy = target - y_mean;
+                cov += dx * dy;
+                x_var += dx * dx;
+                y_var += dy * dy;
+            }
+            if x_var == T::zero() || y_var == T::zero() {
+                return false;
+            }
+            // NaN falha na comparação e descarta a coluna
+            (cov / (x_var * y_var).sqrt()).abs() >= min_abs_corr
+        })
+        .collect();
+
+    let x = apply_selection(dataset.inputs(), &indices);
+    let feature_names = dataset
+        .feature_names()
+        .map(|names| indices.iter().map(|&column| names[column].clone()).collect());
+    let selected = Dataset { x, y: y.to_vec(), feature_names };
+    (selected, indices)
+}
+
+/*
+ * Mantém apenas as colunas indicadas de cada entrada (ex: os
+ * índices retornados por select_by_correlation).
+ *
+ * Parâmetros:
+ *   x - entradas de cada amostra, com todas as colunas
+ *   indices - colunas a manter, na ordem desejada
+ *
+ * Retorno:
+ *   As entradas reduzidas. Entra em pânico se algum índice não
+ *   existir em alguma amostra.
+ */
+pub fn apply_selection<T: Scalar>(x: &[Vec<T>], indices: &[usize]) -> Vec<Vec<T>> {
+    x.iter()
+        .map(|sample| indices.iter().map(|&column| sample[column]).collect())
+        .collect()
+}
+
+/*
+ * Calcula pesos de amostra inversamente proporcionais à frequência
+ * de cada classe: weight[i] = n / (n_classes * count[labels[i]]).
+ * Assim a soma dos pesos de cada classe é a mesma (n / n_classes),
+ * e uma classe rara pesa tanto no custo quanto uma comum.
+ *
+ * Parâmetros:
+ *   labels - rótulo de cada amostra
+ *
+ * Retorno:
+ *   O peso de cada amostra (para TrainConfig::sample_weights ou
+ *   train_weighted); n_classes conta apenas os rótulos presentes
+ */
+pub fn balanced_weights<T: Scalar>(labels: &[usize]) -> Vec<T> {
+    let classes = group_by_label(labels);
+    let mut weights = vec![from_f64::<T>(0.0); labels.len()];
+    for (_, indices) in &classes {
+        let weight = from_f64::<T>(labels.len() as f64 / (classes.len() * indices.len()) as f64);
+        for &index in indices {
+            weights[index] = weight;
+        }
+    }
+    weights
+}
+
+/*
+ * Erros de sliding_window.
+ *
+ * Variantes:
+ *   ZeroWindow - a janela precisa ter pelo menos um valor
+ *   ZeroHorizon - o horizonte precisa ser pelo menos 1 (o valor
+ *                 seguinte à janela)
+ *   TooShort { len, needed } - a série tem len valores, mas uma
+ *                              amostra precisa de needed
+ *   Data(DataError) - a série contém NaN
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub enum WindowError {
+    ZeroWindow,
+    ZeroHorizon,
+    TooShort { len: usize, needed: usize },
+    Data(DataError),
+}
+
+impl fmt::Display for WindowError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            WindowError::ZeroWindow => write!(f, "a janela precisa ter pelo menos um valor"),
+            WindowError::ZeroHorizon => write!(f, "o horizonte precisa ser pelo menos 1"),
+            WindowError::TooShort { len, needed } => write!(
+                f,
+                "a série tem {} valores, mas cada amostra precisa de {}",
+                len, needed
+            ),
+            WindowError::Data(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl std::error::Error for WindowError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            WindowError::Data(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<DataError> for WindowError {
+    fn from(err: DataError) -> Self {
+        WindowError::Data(err)
+    }
+}
+
+/*
+ * Monta um conjunto de dados para previsão de séries temporais
+ * (autorregressão): cada amostra tem como entradas `window` valores
+ * consecutivos da série e como saída esperada o valor `horizon`
+ * passos depois do último deles.
+ *
+ * Parâmetros:
+ *   series - valores da série, em ordem temporal
+ *   window - número de valores passados usados como entrada
+ *   horizon - distância do alvo ao fim da janela (1 = próximo valor)
+ *
+ * Retorno:
+ *   Um Dataset com series.len() - window - horizon + 1 amostras, na
+ *   ordem temporal (a amostra t usa series[t..t + window]), ou
+ *   WindowError se window ou horizon forem 0, se a série for curta
+ *   demais ou se tiver NaN
+ */
+pub fn sliding_window<T: Scalar>(series: &[T], window: usize, horizon: usize) -> Result<Dataset<T>, WindowError> {
+    if window == 0 {
+        return Err(WindowError::ZeroWindow);
+    }
+    if horizon == 0 {
+        return Err(WindowError::ZeroHorizon);
+    }
+    let needed = window + horizon;
+    if series.len() < needed {
+        return Err(WindowError::TooShort { len: series.len(), needed });
+    }
+
+    let n_samples = series.len() - needed + 1;
+    let x = (0..n_samples).map(|t| series[t..t + window].to_vec()).collect();
+    let y = (0..n_samples).map(|t| series[t + window + horizon - 1]).collect();
+    Ok(Dataset::build(x, y, false)?)
+}
+
+/*
+ * Dados sintéticos de regressão gerados por make_linear, junto com
+ * os parâmetros usados para gerá-los.
+ *
+ * Campos:
+ *   data - as amostras geradas
+ *   weights - pesos verdadeiros (um por entrada)
+ *   bias - bias verdadeiro
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub struct LinearData<T = Float> {
+    pub data: Dataset<T>,
+    pub weights: Vec<T>,
+    pub bias: T,
+}
+
+/*
+ * Gera um conjunto de dados de regressão linear:
+ *   y = Σ(weights[j] * x[j]) + bias + ruído, com ruído ~ Normal(0, noise_std)
+ *
+ * Parâmetros:
+ *   n_samples - número de amostras
+ *   weights - pesos verdadeiros; o número de entradas é weights.len()
+ *   bias - bias verdadeiro
+ *   noise_std - desvio padrão do ruído (0.0 gera dados exatos)
+ *   input_range - (min, max): cada entrada é sorteada em Uniform(min, max)
+ *   seed - semente do gerador; a mesma semente gera os mesmos dados
+ *
+ * Retorno:
+ *   Os dados e os parâmetros verdadeiros, ou DataError::Empty se
+ *   n_samples for 0
+ *
+ * Entra em pânico se min >= max.
+ */
+pub fn make_linear<T: Scalar>(
+    n_samples: usize,
+    weights: &[T],
+    bias: T,
+    noise_std: T,
+    input_range: (T, T),
+    seed: u64
+) -> Result<LinearData<T>, DataError> {
+    let (min, max) = input_range;
+    let mut rng = seeded_rng(seed);
+    let mut x = Vec::with_capacity(n_samples);
+    let mut y = Vec::with_capacity(n_samples);
+
+    for _i in 0..n_samples {
+        let sample: Vec<T> = weights.iter().map(|_| randomize_with(&mut rng, min, max)).collect();
+        let mut target = bias;
+        for (input, weight) in sample.iter().zip(weights) {
+            target += *input * *weight;
+        }
+        if noise_std != T::zero() {
+            target += randn_with(&mut rng, T::zero(), noise_std);
+        }
+        x.push(sample);
+        y.push(target);
+    }
+
+    Ok(LinearData {
+        data: Dataset::build(x, y, false)?,
+        weights: weights.to_vec(),
+        bias,
+    })
+}
+
+/*
+ * Erros de make_blobs.
+ *
+ * Variantes:
+ *   NoCenters - nenhum centro informado
+ *   CenterWidth { center, expected, actual } - centro com dimensão
+ *                                              diferente do primeiro
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub enum BlobError {
+    NoCenters,
+    CenterWidth { center: usize, expected: usize, actual: usize },
+}
+
+impl fmt::Display for BlobError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BlobError::NoCenters => write!(f, "make_blobs precisa de pelo menos um centro"),
+            BlobError::CenterWidth { center, expected, actual } => write!(
+                f,
+                "o centro {} tem dimensão {}, mas o primeiro tem {}",
+                center, actual, expected
+            ),
+        }
+    }
+}
+
+impl std::error::Error for BlobError {}
+
+/*
+ * Gera amostras de classificação em grupos gaussianos: cada amostra
+ * da classe c é centers[c] + ruído Normal(0, cluster_std) em cada
+ * coordenada.
+ *
+ * Parâmetros:
+ *   n_samples - número total de amostras
+ *   centers - centro de cada classe (todos com a mesma dimensão)
+ *   cluster_std - desvio padrão dos grupos
+ *   seed - semente do gerador; a mesma semente gera os mesmos dados
+ *
+ * Retorno:
+ *   (entradas, rótulos), com as amostras de cada classe em
+ *   sequência, ou BlobError se não houver centros ou se as
+ *   dimensões forem diferentes
+ *
+ * As amostras são distribuídas da forma mais igual possível: as
+ * primeiras n_samples % centers.len() classes recebem uma a mais.
+ */
+pub fn make_blobs<T: Scalar>(
+    n_samples: usize,
+    centers: &[Vec<T>],
+    cluster_std: T,
+    seed: u64
+) -> Result<(Vec<Vec<T>>, Vec<usize>), BlobError> {
+    let expected = match centers.first() {
+        Some(center) => center.len(),
+        None => return Err(BlobError::NoCenters),
+    };
+    if let Some((center, c)) = centers.iter().enumerate().find(|(_, c)| c.len() != expected) {
+        return Err(BlobError::CenterWidth { center, expected, actual: c.len() });
+    }
+
+    let n_classes = centers.len();
+    let mut rng = seeded_rng(seed);
+    let mut x = Vec::with_capacity(n_samples);
+    let mut labels = Vec::with_capacity(n_samples);
+
+    for (label, center) in centers.iter().enumerate() {
+        let count = n_samples / n_classes + if label < n_samples % n_classes { 1 } else { 0 };
+        for _i in 0..count {
+            x.push(center.iter().map(|&c| randn_with(&mut rng, c, cluster_std)).collect());
+            labels.push(label);
+        }
+    }
+
+    Ok((x, labels))
+}
+
+/*
+ * Formato de um Dataset em JSON.
+ *
+ * Variantes:
+ *   Records - lista de objetos: [{"features": [..], "target": ..}, ..]
+ *   Columns - objeto com colunas: {"x": [[..], ..], "y": [..]}
+ *
+ * JSON não tem NaN nem infinito: esses valores são gravados como as
+ * strings "NaN", "Infinity" e "-Infinity" (a convenção do módulo json
+ * do Python), aceitas na leitura.
+ */
+#[cfg(feature = "serde")]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum JsonLayout {
+    Records,
+    Columns,
+}
+
+/*
+ * Erros de leitura e gravação de um Dataset em JSON.
+ *
+ * Variantes:
+ *   Io - falha ao acessar o arquivo (a mensagem inclui o caminho)
+ *   Json - o arquivo não é um JSON válido
+ *   Schema { path, expected } - elemento fora do formato esperado;
+ *                               path é o caminho JSON do elemento
+ *                               (ex: "$[2].features[0]")
+ *   Data(DataError) - os valores lidos violam os invariantes do Dataset
+ */
+#[cfg(feature = "serde")]
+#[derive(Debug)]
+pub enum JsonDataError {
+    Io(io::Error),
+    Json(serde_json::Error),
+    Schema { path: String, expected: &'static str },
+    Data(DataError),
+}
+
+#[cfg(feature = "serde")]
+impl fmt::Display for JsonDataError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            JsonDataError::Io(err) => write!(f, "erro de E/S: {}", err),
+            JsonDataError::Json(err) => write!(f, "JSON inválido: {}", err),
+            JsonDataError::Schema { path, expected } => {
+                write!(f, "{}: esperado {}", path, expected)
+            }
+            JsonDataError::Data(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl std::error::Error for JsonDataError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            JsonDataError::Io(err) => Some(err),
+            JsonDataError::Json(err) => Some(err),
+            JsonDataError::Data(err) => Some(err),
+            JsonDataError::Schema { .. } => None,
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<io::Error> for JsonDataError {
+    fn from(err: io::Error) -> Self {
+        JsonDataError::Io(err)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<serde_json::Error> for JsonDataError {
+    fn from(err: serde_json::Error) -> Self {
+        JsonDataError::Json(err)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<DataError> for JsonDataError {
+    fn from(err: DataError) -> Self {
+        JsonDataError::Data(err)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T: Scalar> Dataset<T> {
+
+    /*
+     * Lê um conjunto de dados de um arquivo JSON, em qualquer um dos
+     * formatos de JsonLayout (detectado automaticamente: uma lista é
+     * Records, um objeto é Columns).
+     *
+     * Parâmetros:
+     *   path - caminho do arquivo
+     *
+     * Como em new_with_missing, as entradas podem ser NaN (gravadas
+     * como "NaN", ver JsonLayout); as saídas esperadas não.
+     *
+     * Retorno:
+     *   O conjunto de dados, ou CeptronError::Json (ver
+     *   JsonDataError); erros de formato indicam o caminho JSON do
+     *   elemento inválido
+     */
+    pub fn from_json(path: impl AsRef<Path>) -> Result<Self, CeptronError> {
+        let text = read_text(path.as_ref()).map_err(JsonDataError::from)?;
+        Self::from_json_str(&text)
+    }
+
+    /*
+     * Como from_json, mas lê o conteúdo de uma string.
+     */
+    pub fn from_json_str(text: &str) -> Result<Self, CeptronError> {
+        Ok(Self::parse_json(text)?)
+    }
+
+    /*
+     * Implementação de from_json_str, com o erro do módulo.
+     */
+    fn parse_json(text: &str) -> Result<Self, JsonDataError> {
+        use serde_json::Value;
+
+        let root: Value = serde_json::from_str(text)?;
+        let mut x = Vec::new();
+        let mut y = Vec::new();
+
+        match &root {
+            Value::Array(records) => {
+                for (i, record) in records.iter().enumerate() {
+                    let path = format!("$[{}]", i);
+                    let features = json_field(record, "features", &path)?;
+                    x.push(json_numbers(features, &format!("{}.features", path))?);
+                    let target = json_field(record, "target", &path)?;
+                    y.push(json_number(target, &format!("{}.target", path))?);
+                }
+            }
+            Value::Object(_) => {
+                let rows = json_field(&root, "x", "$")?;
+                let rows = rows.as_array().ok_or_else(|| JsonDataError::Schema {
+                    path: "$.x".to_string(),
+                    expected: "uma lista de amostras",
+                })?;
+                for (i, row) in rows.iter().enumerate() {
+                    x.push(json_numbers(row, &format!("$.x[{}]", i))?);
+                }
+                y = json_numbers(json_field(&root, "y", "$")?, "$.y")?;
+            }
+            _ => {
+                return Err(JsonDataError::Schema {
+                    path: "$".to_string(),
+                    expected: "uma lista de amostras ou um objeto {\"x\", \"y\"}",
+                });
+            }
+        }
+
+        Ok(Self::build(x, y, true)?)
+    }
+
+    /*
+     * Converte o conjunto de dados para JSON (ver from_json).
+     *
+     * Parâmetros:
+     *   layout - formato de saída
+     *
+     * Retorno:
+     *   O texto JSON, ou CeptronError::Json (ver JsonDataError)
+     */
+    pub fn to_json_string(&self, layout: JsonLayout) -> Result<String, CeptronError> {
+        Ok(self.encode_json(layout)?)
+    }
+
+    /*
+     * Implementação de to_json_string, com o erro do módulo.
+     */
+    fn encode_json(&self, layout: JsonLayout) -> Result<String, JsonDataError> {
+        use serde_json::{json, Value};
+
+        let row = |sample: &[T]| -> Vec<Value> { sample.iter().map(|&value| json_value(value)).collect() };
+        let value: Value = match layout {
+            JsonLayout::Records => self
+                .x
+                .iter()
+                .zip(&self.y)
+                .map(|(sample, &target)| json!({ "features": row(sample), "target": json_value(target) }))
+                .collect(),
+            JsonLayout::Columns => json!({
+                "x": self.x.iter().map(|sample| row(sample)).collect::<Vec<_>>(),
+                "y": row(&self.y),
+            }),
+        };
+        Ok(serde_json::to_string_pretty(&value)?)
+    }
+
+    /*
+     * Grava o conjunto de dados em um arquivo JSON, para que um
+     * experimento guarde exatamente os dados usados no treino.
+     *
+     * Parâmetros:
+     *   path - caminho do arquivo (sobrescrito se existir)
+     *   layout - formato de saída
+     *
+     * Retorno:
+     *   Ok(()), ou CeptronError::Json (ver JsonDataError)
+     */
+    pub fn to_json(&self, path: impl AsRef<Path>, layout: JsonLayout) -> Result<(), CeptronError> {
+        let text = self.encode_json(layout)?;
+        write_file(path.as_ref(), text).map_err(JsonDataError::from)?;
+        Ok(())
+    }
+}
+
+/*
+ * Busca um campo obrigatório de um objeto JSON.
+ */
+#[cfg(feature = "serde")]
+fn json_field<'a>(
+    value: &'a serde_json::Value,
+    name: &str,
+    path: &str
+) -> Result<&'a serde_json::Value, JsonDataError> {
+    value.get(name).ok_or_else(|| JsonDataError::Schema {
+        path: format!("{}.{}", path, name),
+        expected: "um campo obrigatório",
+    })
+}
+
+/*
+ * Valor JSON de um número, com NaN e infinitos como strings (ver
+ * JsonLayout).
+ */
+#[cfg(feature = "serde")]
+fn json_value<T: Scalar>(value: T) -> serde_json::Value {
+    match value.to_f64() {
+        Some(value) if value.is_finite() => serde_json::Value::from(value),
+        Some(value) if value == f64::INFINITY => "Infinity".into(),
+        Some(value) if value == f64::NEG_INFINITY => "-Infinity".into(),
+        _ => "NaN".into(),
+    }
+}
+
+/*
+ * Lê um número JSON (ou uma das strings de json_value).
+ */
+#[cfg(feature = "serde")]
+fn json_number<T: Scalar>(value: &serde_json::Value, path: &str) -> Result<T, JsonDataError> {
+    let number = match value.as_str() {
+        Some("NaN") => Some(f64::NAN),
+        Some("Infinity") => Some(f64::INFINITY),
+        Some("-Infinity") => Some(f64::NEG_INFINITY),
+        _ => value.as_f64(),
+    };
+    number.map(from_f64).ok_or_else(|| JsonDataError::Schema {
+        path: path.to_string(),
+        expected: "um número",
+    })
+}
+
+/*
+ * Lê uma lista JSON de números.
+ */
+#[cfg(feature = "serde")]
+fn json_numbers<T: Scalar>(value: &serde_json::Value, path: &str) -> Result<Vec<T>, JsonDataError> {
+    let items = value.as_array().ok_or_else(|| JsonDataError::Schema {
+        path: path.to_string(),
+        expected: "uma lista de números",
+    })?;
+    items
+        .iter()
+        .enumerate()
+        .map(|(i, item)| json_number(item, &format!("{}[{}]", path, i)))
+        .collect()
+}
@@ -0,0 +1,217 @@
+/*
+ * error.rs
+ *
+ * Módulo com o tipo de erro comum do crate.
+ *
+ * Este módulo implementa:
+ *   - Enum CeptronError, que reúne os erros de cada módulo (dados,
+ *     neurônio, treinamento e gravação de modelos)
+ *   - Conversões (From) de cada erro específico para CeptronError,
+ *     de modo que `?` funcione em uma função que retorna
+ *     Result<_, CeptronError>
+ *
+ * As APIs públicas que podem falhar retornam CeptronError: criação,
+ * leitura e alteração de Dataset (new, from_csv, from_json, push,
+ * extend, concat, with_feature_names, ...), as variantes try_ de
+ * custo e treinamento, fit (fit_on, fit_resume, fit_from,
+ * partial_fit, Pipeline::fit, Model) e a gravação e leitura de
+ * neurônios (save, load, save_bin, load_bin, ...). O erro do módulo,
+ * com todos os detalhes, continua disponível dentro da variante (ex:
+ * CeptronError::Csv(CsvError::Parse { .. })) e por Error::source.
+ * Funções de mais baixo nível (checkpoints, normalizadores,
+ * exportação) continuam retornando o erro do seu módulo.
+ */
+
+use std::fmt;
+
+#[cfg(feature = "serde")]
+use crate::data::JsonDataError;
+use crate::data::{CsvError, DataError, DataIssue, LibsvmError, SplitError};
+use crate::modelio::ModelIoError;
+use crate::neuralnet::{CvError, DivergenceError, FitError, OneVsRestError};
+use crate::neuron::{BatchInputError, InputDimError};
+
+/*
+ * Erro comum do crate.
+ *
+ * Variantes:
+ *   DimensionMismatch { expected, actual, row } - entrada com número
+ *                    de valores diferente do número de conexões (de
+ *                    InputDimError, BatchInputError ou
+ *                    FitError::FeatureWidth); row é a amostra do lote
+ *                    com o problema, quando houver
+ *   InvalidConfig(msg) - configuração de treinamento inválida (ex:
+ *                        TrainConfig::sample_weights sem um peso por
+ *                        amostra, taxa de aprendizado negativa ou
+ *                        scale_target com sigmoid)
+ *   Data(DataError) - conjunto de dados inválido
+ *   InvalidData(DataIssue) - amostra com NaN ou infinito (ver
+ *                            neuralnet::try_train e
+ *                            FitError::InvalidData)
+ *   Csv(CsvError) - falha ao ler um CSV de dados
+ *   Libsvm(LibsvmError) - falha ao ler um arquivo svmlight/libsvm
+ *   Json(JsonDataError) - falha ao ler ou gravar um Dataset em JSON
+ *                         (feature "serde")
+ *   Split(SplitError) - divisão treino/teste inválida
+ *   Io(ModelIoError) - falha ao gravar ou ler um modelo
+ *   Diverged(DivergenceError) - o treinamento divergiu
+ *   Fit(FitError) - demais erros de treinamento (ex: checkpoint)
+ *   CrossValidation(CvError) - erro de cross_validate
+ *   OneVsRest(OneVsRestError) - erro de OneVsRest::fit
+ */
+#[derive(Debug)]
+pub enum CeptronError {
+    DimensionMismatch { expected: usize, actual: usize, row: Option<usize> },
+    InvalidConfig(String),
+    Data(DataError),
+    InvalidData(DataIssue),
+    Csv(CsvError),
+    Libsvm(LibsvmError),
+    #[cfg(feature = "serde")]
+    Json(JsonDataError),
+    Split(SplitError),
+    Io(ModelIoError),
+    Diverged(DivergenceError),
+    Fit(FitError),
+    CrossValidation(CvError),
+    OneVsRest(OneVsRestError),
+}
+
+impl fmt::Display for CeptronError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CeptronError::DimensionMismatch { expected, actual, row } => {
+                if let Some(row) = row {
+                    write!(f, "amostra {}: ", row)?;
+                }
+                write!(f, "entrada com {} valores, mas o neurônio espera {}", actual, expected)
+            }
+            CeptronError::InvalidConfig(msg) => write!(f, "configuração inválida: {}", msg),
+            CeptronError::Data(err) => write!(f, "dados inválidos: {}", err),
+            CeptronError::InvalidData(issue) => write!(f, "dados inválidos: {}", issue),
+            CeptronError::Csv(err) => write!(f, "{}", err),
+            CeptronError::Libsvm(err) => write!(f, "{}", err),
+            #[cfg(feature = "serde")]
+            CeptronError::Json(err) => write!(f, "{}", err),
+            CeptronError::Split(err) => write!(f, "{}", err),
+            CeptronError::Io(err) => write!(f, "{}", err),
+            CeptronError::Diverged(err) => write!(f, "{}", err),
+            CeptronError::Fit(err) => write!(f, "{}", err),
+            CeptronError::CrossValidation(err) => write!(f, "{}", err),
+            CeptronError::OneVsRest(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl std::error::Error for CeptronError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            CeptronError::DimensionMismatch { .. } | CeptronError::InvalidConfig(_) => None,
+            CeptronError::Data(err) => Some(err),
+            CeptronError::InvalidData(issue) => Some(issue),
+            CeptronError::Csv(err) => Some(err),
+            CeptronError::Libsvm(err) => Some(err),
+            #[cfg(feature = "serde")]
+            CeptronError::Json(err) => Some(err),
+            CeptronError::Split(err) => Some(err),
+            CeptronError::Io(err) => Some(err),
+            CeptronError::Diverged(err) => Some(err),
+            CeptronError::Fit(err) => Some(err),
+            CeptronError::CrossValidation(err) => Some(err),
+            CeptronError::OneVsRest(err) => Some(err),
+        }
+    }
+}
+
+impl From<InputDimError> for CeptronError {
+    fn from(err: InputDimError) -> Self {
+        CeptronError::DimensionMismatch { expected: err.expected, actual: err.actual, row: None }
+    }
+}
+
+impl From<BatchInputError> for CeptronError {
+    fn from(err: BatchInputError) -> Self {
+        CeptronError::DimensionMismatch {
+            expected: err.source.expected,
+            actual: err.source.actual,
+            row: Some(err.index),
+        }
+    }
+}
+
+impl From<DataError> for CeptronError {
+    fn from(err: DataError) -> Self {
+        CeptronError::Data(err)
+    }
+}
+
+impl From<CsvError> for CeptronError {
+    fn from(err: CsvError) -> Self {
+        CeptronError::Csv(err)
+    }
+}
+
+impl From<LibsvmError> for CeptronError {
+    fn from(err: LibsvmError) -> Self {
+        CeptronError::Libsvm(err)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<JsonDataError> for CeptronError {
+    fn from(err: JsonDataError) -> Self {
+        CeptronError::Json(err)
+    }
+}
+
+impl From<SplitError> for CeptronError {
+    fn from(err: SplitError) -> Self {
+        CeptronError::Split(err)
+    }
+}
+
+impl From<ModelIoError> for CeptronError {
+    fn from(err: ModelIoError) -> Self {
+        CeptronError::Io(err)
+    }
+}
+
+impl From<DivergenceError> for CeptronError {
+    fn from(err: DivergenceError) -> Self {
+        CeptronError::Diverged(err)
+    }
+}
+
+/*
+ * Os casos de FitError que têm uma variante própria (divergência,
+ * dimensão, dados e configuração) são convertidos para ela; os demais
+ * ficam em CeptronError::Fit.
+ */
+impl From<FitError> for CeptronError {
+    fn from(err: FitError) -> Self {
+        match err {
+            FitError::Diverged(err) => CeptronError::Diverged(err),
+            FitError::FeatureWidth { expected, actual } => {
+                CeptronError::DimensionMismatch { expected, actual, row: None }
+            }
+            FitError::InvalidData(issue) => CeptronError::InvalidData(issue),
+            FitError::ScaleTargetActivation
+            | FitError::WeightCount { .. }
+            | FitError::SampleCount { .. }
+            | FitError::Hyperparameter { .. } => CeptronError::InvalidConfig(err.to_string()),
+            err => CeptronError::Fit(err),
+        }
+    }
+}
+
+impl From<CvError> for CeptronError {
+    fn from(err: CvError) -> Self {
+        CeptronError::CrossValidation(err)
+    }
+}
+
+impl From<OneVsRestError> for CeptronError {
+    fn from(err: OneVsRestError) -> Self {
+        CeptronError::OneVsRest(err)
+    }
+}
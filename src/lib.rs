@@ -0,0 +1,47 @@
+/*
+ * lib.rs
+ *
+ * Biblioteca do Perceptron: um perceptron implementado do zero,
+ * sem uso de bibliotecas externas de Machine Learning.
+ *
+ * A biblioteca está organizada nos seguintes módulos:
+ *   - data: conjunto de dados de treinamento (Dataset)
+ *   - neuron: estrutura do neurônio e funções de inicialização/computação
+ *   - neuralnet: funções de treinamento e cálculo de custo
+ *   - netmath: funções matemáticas (ativação, MSE)
+ *   - modelio: gravação e leitura de modelos treinados
+ *   - error: erro comum do crate (CeptronError)
+ *   - pipeline: normalização dos dados + neurônio (Pipeline)
+ *   - utils: utilitários (geração de números aleatórios)
+ *   - testutil: comparação aproximada de neurônios em testes
+ *   - prelude: reexporta os itens de uso mais comum
+ *
+ * O binário (main.rs) é apenas um exemplo de uso desta API.
+ *
+ * Sem a feature "std" (padrão) o crate é #![no_std] + alloc, para
+ * inferência em microcontroladores: restam neuron, netmath, utils e
+ * testutil (compute_out, ativações com libm, from_weights, a
+ * inicialização com semente e assert_neuron_approx_eq!).
+ * Treinamento, dados, gravação de modelos e o gerador do sistema
+ * exigem "std".
+ */
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+#[cfg(feature = "std")]
+pub mod data;
+pub mod neuron;
+#[cfg(feature = "std")]
+pub mod neuralnet;
+pub mod netmath;
+#[cfg(feature = "std")]
+pub mod modelio;
+#[cfg(feature = "std")]
+pub mod error;
+#[cfg(feature = "std")]
+pub mod pipeline;
+pub mod testutil;
+pub mod utils;
+pub mod prelude;
@@ -3,14 +3,9 @@
  *
  * Ponto de entrada da aplicação Perceptron.
  * 
- * Este projeto implementa um perceptron do zero,
- * sem uso de bibliotecas externas de Machine Learning.
- *
- * O projeto está organizado nos seguintes módulos:
- *   - neuron: estrutura do neurônio e funções de inicialização/computação
- *   - neuralnet: funções de treinamento e cálculo de custo
- *   - netmath: funções matemáticas (ativação, MSE)
- *   - utils: utilitários (geração de números aleatórios)
+ * Este binário é um exemplo de uso da biblioteca (ver lib.rs), que
+ * implementa um perceptron do zero, sem uso de bibliotecas externas
+ * de Machine Learning. Ele usa apenas a API pública do crate.
  *
  * O neurônio aprende relações lineares a partir de exemplos
  * de entrada/saída usando gradiente descendente.
@@ -18,14 +13,7 @@
  * Objetivo educacional: mostrar como tudo funciona "por baixo do capô".
  */
 
-mod neuron;
-mod neuralnet;
-mod netmath;
-mod utils;
-
-use crate::neuron::*;
-use crate::neuralnet::*;
-use crate::netmath::*;
+use perceptron::prelude::*;
 
 /*
  * Função principal - ponto de entrada do programa.
@@ -36,20 +24,35 @@ use crate::netmath::*;
  * Fluxo de execução:
  *   1. Cria um neurônio com 2 conexões e pesos aleatórios
  *   2. Define dados de treinamento (amostras de entrada x e saídas esperadas)
+ *      e separa um terço deles para teste
  *   3. Exibe o custo inicial (antes do treinamento)
  *   4. Treina o neurônio por 50.000 iterações usando gradiente descendente
- *   5. Exibe o custo final e os parâmetros aprendidos (pesos e bias)
- *   6. Exibe os resultados de teste para verificação
+ *   5. Exibe o relatório do neurônio treinado (parâmetros, equação
+ *      e custo final)
+ *   6. Exibe o custo e as saídas nas amostras de teste
+ *   7. Com a feature "serde", grava o neurônio em JSON, lê de volta
+ *      e confere que as predições não mudam
+ *
+ * Com a feature "log", instala um logger simples em stderr (nível
+ * lido de RUST_LOG, padrão warn) para os registros do treinamento.
  *
  * Resultado esperado após treinamento:
  *   - Pesos ajustados para aproximar a relação entre entradas e saídas
  *   - Custo próximo de zero (erro mínimo)
  */
+#[allow(deprecated)]
 fn main() {
-    const SAMPLE_SIZE: usize = 6;
-    const CONNECTIONS: u32 = 2;
+    #[cfg(feature = "log")]
+    logger::init();
+
+    const CONNECTIONS: usize = 2;
 
-    let mut neuron = Neuron::new(sigmoid, CONNECTIONS);
+    // Sem a feature "std-rand" não há gerador do sistema: os pesos
+    // iniciais vêm de uma semente fixa
+    #[cfg(feature = "std-rand")]
+    let mut neuron: Neuron<Float> = Neuron::new(Activation::Sigmoid, CONNECTIONS);
+    #[cfg(not(feature = "std-rand"))]
+    let mut neuron: Neuron<Float> = Neuron::new_seeded(Activation::Sigmoid, CONNECTIONS, 42);
 
     // Dados de treinamento: amostras de entrada (x₁, x₂)
     let x = vec![
@@ -65,36 +68,101 @@ fn main() {
         0.0, 0.0
     ];
 
-    // neuron.weights[0] = 2.5;
-    // neuron.bias = 6.0;
+    let data = match Dataset::new(x, out_true) {
+        Ok(data) => data,
+        Err(err) => panic!("dados de treinamento inválidos: {}", err),
+    };
 
-    let mut cost = compute_cost(&neuron, &x, &out_true, mse, SAMPLE_SIZE);
+    // Reserva um terço das amostras para avaliar o neurônio em
+    // dados que ele não viu durante o treinamento
+    let (train_data, test_data) = match train_test_split(&data, 1.0 / 3.0, None) {
+        Ok(split) => split,
+        Err(err) => panic!("divisão treino/teste inválida: {}", err),
+    };
+
+    // neuron.set_weight(0, 2.5);
+    // neuron.set_bias(6.0);
+
+    let cost = compute_cost_on(&neuron, &train_data, mse);
     
 
+    println!("***Dados de treinamento***");
+    print!("{}", train_data.describe());
+
     println!("***Antes do treinamento***");
     println!("O custo do neurônio : {}", cost);
-    for i in 0..CONNECTIONS as usize {
-        println!("O valor do weight {} : {}", i+1, neuron.weights[i]);
-    }
-    println!("O valor do bias     : {}", neuron.bias);
+    println!("O neurônio          : {:.4}", neuron);
 
     for _i in 0..50000 {
-        train(&mut neuron, mse, &x, &out_true, SAMPLE_SIZE);
+        train_on(&mut neuron, mse, &train_data);
     }
 
-    cost = compute_cost(&neuron, &x, &out_true, mse, SAMPLE_SIZE);
-
     println!("***Depois do treinamento***");
-    println!("O custo do neurônio : {}", cost);
-    for i in 0..CONNECTIONS as usize {
-        println!("O valor do weight {} : {}", i+1, neuron.weights[i]);
-    }
-    println!("O valor do bias     : {}", neuron.bias);
+    print!("{}", neuron.report_with(Some(&train_data), None));
+    println!("Custo no teste      : {}", compute_cost_on(&neuron, &test_data, mse));
 
 
     println!("*** Testes ***");
-    for i in 0..SAMPLE_SIZE {
-        println!("Entrada {} {} - Saída {}", x[i][0], x[i][1], neuron.compute_out(&x[i]));
+    for sample in test_data.inputs() {
+        println!("Entrada {} {} - Saída {}", sample[0], sample[1], neuron.compute_out(sample));
     }
-    
-}
\ No newline at end of file
+
+    #[cfg(feature = "serde")]
+    {
+        let path = std::env::temp_dir().join("perceptron.json");
+        if let Err(err) = neuron.save(&path) {
+            panic!("falha ao gravar o modelo: {}", err);
+        }
+        let loaded: Neuron<Float> = match Neuron::load(&path) {
+            Ok(loaded) => loaded,
+            Err(err) => panic!("falha ao ler o modelo: {}", err),
+        };
+
+        println!("*** Modelo gravado ***");
+        println!("Arquivo             : {}", path.display());
+        for sample in test_data.inputs() {
+            println!("Entrada {} {} - Saída {}", sample[0], sample[1], loaded.compute_out(sample));
+        }
+    }
+}
+/*
+ * Logger mínimo para o exemplo: escreve cada registro em stderr no
+ * formato "NÍVEL alvo: mensagem". Um projeto real usaria env_logger,
+ * tracing ou outro logger da fachada log.
+ */
+#[cfg(feature = "log")]
+mod logger {
+    use log::{LevelFilter, Log, Metadata, Record};
+
+    struct StderrLogger;
+
+    impl Log for StderrLogger {
+        fn enabled(&self, metadata: &Metadata) -> bool {
+            metadata.level() <= log::max_level()
+        }
+
+        fn log(&self, record: &Record) {
+            if self.enabled(record.metadata()) {
+                eprintln!("{} {}: {}", record.level(), record.target(), record.args());
+            }
+        }
+
+        fn flush(&self) {}
+    }
+
+    static LOGGER: StderrLogger = StderrLogger;
+
+    /*
+     * Instala o logger com o nível de RUST_LOG (error, warn, info,
+     * debug ou trace); sem a variável ou com valor inválido, usa warn.
+     */
+    pub fn init() {
+        let level = std::env::var("RUST_LOG")
+            .ok()
+            .and_then(|value| value.parse::<LevelFilter>().ok())
+            .unwrap_or(LevelFilter::Warn);
+        if log::set_logger(&LOGGER).is_ok() {
+            log::set_max_level(level);
+        }
+    }
+}
@@ -0,0 +1,2221 @@
+/*
+ * modelio.rs
+ *
+ * Módulo de persistência de modelos treinados.
+ *
+ * Este módulo implementa:
+ *   - Serialização do Neuron com serde (feature "serde")
+ *   - Gravação e leitura de neurônios em arquivos JSON, com versão
+ *     de formato (Neuron::save / Neuron::load)
+ *   - Envelope ModelFile, que identifica o formato e a versão de um
+ *     arquivo de modelo e migra versões antigas
+ *   - O mesmo envelope em MessagePack, mais compacto e rápido que
+ *     JSON (Neuron::save_msgpack / load_msgpack)
+ *   - Formato binário compacto "CEPT" (Neuron::save_bin / load_bin),
+ *     disponível mesmo sem a feature "serde"
+ *   - Exportação e importação dos parâmetros em CSV, para inspeção
+ *     em planilhas (Neuron::export_csv / import_csv)
+ *   - Importação de pesos de arquivos .npy do NumPy (Neuron::load_npy)
+ *   - Checkpoints de treinamento (Checkpoint, usado por fit e
+ *     fit_resume)
+ *   - Exportação mínima para ONNX (Neuron::export_onnx), codificando
+ *     o protobuf à mão, sem dependências
+ *   - Geração de código Rust com o modelo embutido (Neuron::to_rust_source)
+ *     e de um header C (Neuron::export_c_header)
+ *   - Relatório em texto do modelo treinado (Neuron::report)
+ *   - A equação aprendida em texto (Neuron::to_equation) e em XML no
+ *     estilo PMML (Neuron::to_pmml), para quem consome o modelo como
+ *     fórmula
+ *   - Grafo do neurônio no formato DOT do Graphviz (Neuron::to_dot)
+ *   - Metadados do treinamento gravados ao lado do modelo
+ *     (ModelMetadata): configuração, histórico de custo e dados
+ *   - Compressão gzip opcional dos arquivos de modelo e de
+ *     checkpoint (feature "gzip"): as funções de leitura detectam
+ *     arquivos comprimidos pelo cabeçalho gzip
+ *
+ * A função de ativação é gravada pelo nome ("identity", "sigmoid"),
+ * pois ponteiros de função não podem ser serializados. Neurônios
+ * com ativações definidas pelo usuário não podem ser gravados.
+ */
+
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::data::{Dataset, DatasetFingerprint};
+use crate::error::CeptronError;
+use crate::netmath::{from_f64, mse, Activation, Float, Scalar};
+#[allow(deprecated)]
+use crate::neuralnet::{compute_cost_on, TrainConfig};
+use crate::neuron::Neuron;
+
+/*
+ * Erros de gravação e leitura de modelos.
+ *
+ * Variantes:
+ *   Io - falha ao acessar o arquivo (a mensagem inclui o caminho)
+ *   Json - documento JSON inválido ou fora do formato esperado
+ *   MsgPackEncode / MsgPackDecode - falha ao codificar ou decodificar
+ *                                   MessagePack (ex: arquivo truncado)
+ *   UnsupportedVersion { found, supported } - versão de formato
+ *                                             inválida (None quando
+ *                                             não é um inteiro)
+ *   VersionTooNew { found, supported } - arquivo gravado por uma
+ *                                        versão mais nova da biblioteca
+ *   BadMagic - arquivo binário sem o cabeçalho "CEPT"
+ *   Length { expected, actual } - arquivo binário com tamanho
+ *                                 diferente do indicado no cabeçalho
+ *                                 (ex: arquivo truncado)
+ *   UnknownActivation { code } - código de ativação desconhecido
+ *   UnregisteredActivation { name } - o modelo usa uma ativação
+ *                      nomeada que não foi registrada com
+ *                      register_activation antes da leitura
+ *   UnserializableActivation { layer, neuron } - ativação Custom
+ *                      (ponteiro de função), que não pode ser gravada;
+ *                      indica o neurônio com o problema (um Neuron
+ *                      isolado é a camada 0, neurônio 0). Ver
+ *                      Neuron::with_named_activation
+ *   UnsupportedActivation { name, format } - ativação sem equivalente
+ *                      no formato gerado (ONNX, Rust ou C só conhecem
+ *                      identity e sigmoid)
+ *   Precision { bits } - precisão dos números diferente de 32 ou 64
+ *   InvalidParams(msg) - pesos ou bias inválidos (ex: NaN)
+ *   InvalidIdentifier(name) - nome que não é um identificador válido
+ *                             para o código gerado
+ *   Csv(ParamCsvError) - CSV de parâmetros fora do formato esperado
+ *   Npy(NpyError) - arquivo .npy inválido ou não suportado
+ *   GzipDisabled - arquivo comprimido com gzip (ou gravação com
+ *                  compressão) sem a feature "gzip"
+ *
+ * Um arquivo gzip corrompido gera Io, como as demais falhas de
+ * leitura.
+ */
+#[derive(Debug)]
+pub enum ModelIoError {
+    Io(io::Error),
+    #[cfg(feature = "serde")]
+    Json(serde_json::Error),
+    #[cfg(feature = "serde")]
+    MsgPackEncode(rmp_serde::encode::Error),
+    #[cfg(feature = "serde")]
+    MsgPackDecode(rmp_serde::decode::Error),
+    UnsupportedVersion { found: Option<u64>, supported: u64 },
+    VersionTooNew { found: u64, supported: u64 },
+    BadMagic,
+    Length { expected: usize, actual: usize },
+    UnknownActivation { code: u8 },
+    UnregisteredActivation { name: String },
+    UnserializableActivation { layer: usize, neuron: usize },
+    UnsupportedActivation { name: String, format: &'static str },
+    Precision { bits: u8 },
+    InvalidParams(String),
+    InvalidIdentifier(String),
+    Csv(ParamCsvError),
+    Npy(NpyError),
+    GzipDisabled,
+}
+
+/*
+ * Erros de leitura do CSV de parâmetros (ver Neuron::import_csv).
+ * As linhas são numeradas a partir de 1, contando o cabeçalho.
+ *
+ * Variantes:
+ *   Header - a primeira linha não é "name,index,value"
+ *   Row { line, content } - linha sem 3 campos ou com nome
+ *                           diferente de activation, weight e bias
+ *   Index { line, value } - índice de peso inválido ou repetido, ou
+ *                           índice preenchido em activation/bias
+ *   Value { line, value } - valor que não é um número
+ *   Activation { line, name } - ativação desconhecida
+ *   Duplicate { line, name } - activation ou bias repetido
+ *   Missing { name, index } - falta a linha de activation ou do peso
+ *                             index (os índices vão de 0 a n - 1)
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParamCsvError {
+    Header,
+    Row { line: usize, content: String },
+    Index { line: usize, value: String },
+    Value { line: usize, value: String },
+    Activation { line: usize, name: String },
+    Duplicate { line: usize, name: String },
+    Missing { name: &'static str, index: Option<usize> },
+}
+
+impl fmt::Display for ParamCsvError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParamCsvError::Header => write!(f, "o cabeçalho deve ser \"name,index,value\""),
+            ParamCsvError::Row { line, content } => write!(f, "linha {}: linha inválida \"{}\"", line, content),
+            ParamCsvError::Index { line, value } => write!(f, "linha {}: índice inválido \"{}\"", line, value),
+            ParamCsvError::Value { line, value } => write!(f, "linha {}: valor inválido \"{}\"", line, value),
+            ParamCsvError::Activation { line, name } => {
+                write!(f, "linha {}: ativação desconhecida \"{}\"", line, name)
+            }
+            ParamCsvError::Duplicate { line, name } => write!(f, "linha {}: {} repetido", line, name),
+            ParamCsvError::Missing { name, index: Some(index) } => write!(f, "falta o {} {}", name, index),
+            ParamCsvError::Missing { name, index: None } => write!(f, "falta a linha {}", name),
+        }
+    }
+}
+
+impl std::error::Error for ParamCsvError {}
+
+impl From<ParamCsvError> for ModelIoError {
+    fn from(err: ParamCsvError) -> Self {
+        ModelIoError::Csv(err)
+    }
+}
+
+/*
+ * Erros de leitura de um arquivo .npy (ver Neuron::load_npy).
+ *
+ * Variantes:
+ *   BadMagic - o arquivo não começa com "\x93NUMPY"
+ *   Version { major } - versão do formato diferente de 1
+ *   Header - cabeçalho truncado ou sem descr, fortran_order e shape
+ *   Dtype { descr } - tipo diferente de float32/float64 little-endian
+ *   FortranOrder - array gravado em ordem de colunas (Fortran)
+ *   Shape { shape } - formato incompatível com o modelo
+ *   Length { expected, actual } - número de bytes de dados diferente
+ *                                 do indicado pelo cabeçalho
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub enum NpyError {
+    BadMagic,
+    Version { major: u8 },
+    Header,
+    Dtype { descr: String },
+    FortranOrder,
+    Shape { shape: Vec<usize> },
+    Length { expected: usize, actual: usize },
+}
+
+impl fmt::Display for NpyError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            NpyError::BadMagic => write!(f, "o arquivo não está no formato .npy"),
+            NpyError::Version { major } => write!(f, "versão {} do formato .npy não suportada", major),
+            NpyError::Header => write!(f, "cabeçalho inválido"),
+            NpyError::Dtype { descr } => write!(f, "tipo {} não suportado (use <f4 ou <f8)", descr),
+            NpyError::FortranOrder => write!(f, "arrays com fortran_order=True não são suportados"),
+            NpyError::Shape { shape } => write!(f, "formato {:?} incompatível com o modelo", shape),
+            NpyError::Length { expected, actual } => {
+                write!(f, "{} bytes de dados, mas o cabeçalho indica {}", actual, expected)
+            }
+        }
+    }
+}
+
+impl std::error::Error for NpyError {}
+
+impl From<NpyError> for ModelIoError {
+    fn from(err: NpyError) -> Self {
+        ModelIoError::Npy(err)
+    }
+}
+
+/*
+ * Lê um array do formato .npy versão 1: magic "\x93NUMPY", versão
+ * (2 bytes), tamanho do cabeçalho (u16 little-endian), cabeçalho em
+ * texto (um dicionário Python) e os dados.
+ *
+ * Parâmetros:
+ *   bytes - conteúdo do arquivo
+ *
+ * Retorno:
+ *   (shape, valores em ordem de linhas), ou NpyError
+ */
+fn parse_npy(bytes: &[u8]) -> Result<(Vec<usize>, Vec<f64>), NpyError> {
+    const NPY_MAGIC: &[u8; 6] = b"\x93NUMPY";
+    if !bytes.starts_with(NPY_MAGIC) {
+        return Err(NpyError::BadMagic);
+    }
+    if bytes.len() < 10 {
+        return Err(NpyError::Header);
+    }
+    if bytes[6] != 1 {
+        return Err(NpyError::Version { major: bytes[6] });
+    }
+    let header_len = usize::from(u16::from_le_bytes([bytes[8], bytes[9]]));
+    let header = bytes
+        .get(10..10 + header_len)
+        .and_then(|header| std::str::from_utf8(header).ok())
+        .ok_or(NpyError::Header)?;
+
+    let descr = npy_header_value(header, "descr").ok_or(NpyError::Header)?;
+    let descr = descr.trim_matches(|c| c == '\'' || c == '"');
+    let width = match descr {
+        "<f4" => 4,
+        "<f8" => 8,
+        _ => return Err(NpyError::Dtype { descr: descr.to_string() }),
+    };
+    match npy_header_value(header, "fortran_order") {
+        Some("False") => {}
+        Some("True") => return Err(NpyError::FortranOrder),
+        _ => return Err(NpyError::Header),
+    }
+    let shape = npy_header_value(header, "shape")
+        .and_then(|shape| shape.strip_prefix('('))
+        .and_then(|shape| shape.strip_suffix(')'))
+        .ok_or(NpyError::Header)?
+        .split(',')
+        .map(str::trim)
+        .filter(|dim| !dim.is_empty())
+        .map(|dim| dim.parse::<usize>().map_err(|_| NpyError::Header))
+        .collect::<Result<Vec<usize>, _>>()?;
+
+    let data = &bytes[10 + header_len..];
+    let expected = shape
+        .iter()
+        .try_fold(width, |acc: usize, &dim| acc.checked_mul(dim))
+        .unwrap_or(usize::MAX);
+    if data.len() != expected {
+        return Err(NpyError::Length { expected, actual: data.len() });
+    }
+
+    let values = data
+        .chunks_exact(width)
+        .map(|chunk| match *chunk {
+            [a, b, c, d] => f64::from(f32::from_le_bytes([a, b, c, d])),
+            [a, b, c, d, e, f, g, h] => f64::from_le_bytes([a, b, c, d, e, f, g, h]),
+            _ => unreachable!("chunks_exact com largura 4 ou 8"),
+        })
+        .collect();
+    Ok((shape, values))
+}
+
+/*
+ * Extrai o valor (como texto) de uma chave do dicionário do
+ * cabeçalho .npy, ex: "'<f4'" para descr ou "(3,)" para shape.
+ */
+fn npy_header_value<'a>(header: &'a str, key: &str) -> Option<&'a str> {
+    let start = header.find(&format!("'{}'", key))? + key.len() + 2;
+    let rest = header[start..].trim_start().strip_prefix(':')?.trim_start();
+    let end = if rest.starts_with('(') {
+        rest.find(')')? + 1
+    } else {
+        rest.find([',', '}'])?
+    };
+    Some(rest[..end].trim())
+}
+
+impl fmt::Display for ModelIoError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ModelIoError::Io(err) => write!(f, "erro de E/S: {}", err),
+            #[cfg(feature = "serde")]
+            ModelIoError::Json(err) => write!(f, "JSON inválido: {}", err),
+            #[cfg(feature = "serde")]
+            ModelIoError::MsgPackEncode(err) => write!(f, "falha ao codificar MessagePack: {}", err),
+            #[cfg(feature = "serde")]
+            ModelIoError::MsgPackDecode(err) => write!(f, "MessagePack inválido: {}", err),
+            ModelIoError::UnsupportedVersion { found: Some(found), supported } => write!(
+                f,
+                "versão de formato não suportada: {} (suportada: {})",
+                found, supported
+            ),
+            ModelIoError::UnsupportedVersion { found: None, .. } => {
+                write!(f, "versão de formato (format_version) inválida")
+            }
+            ModelIoError::VersionTooNew { found, supported } => write!(
+                f,
+                "o arquivo usa a versão de formato {}, mais nova que a suportada ({})",
+                found, supported
+            ),
+            ModelIoError::BadMagic => write!(f, "o arquivo não é um modelo binário (cabeçalho CEPT)"),
+            ModelIoError::Length { expected, actual } => write!(
+                f,
+                "o modelo binário deveria ter {} bytes, mas tem {}",
+                expected, actual
+            ),
+            ModelIoError::UnknownActivation { code } => write!(f, "código de ativação desconhecido: {}", code),
+            ModelIoError::UnregisteredActivation { name } => write!(
+                f,
+                "a ativação \"{}\" não está registrada (ver register_activation)",
+                name
+            ),
+            ModelIoError::UnserializableActivation { layer, neuron } => write!(
+                f,
+                "a ativação do neurônio {} da camada {} é definida pelo usuário e não pode ser gravada",
+                neuron, layer
+            ),
+            ModelIoError::UnsupportedActivation { name, format } => {
+                write!(f, "a ativação \"{}\" não tem equivalente em {}", name, format)
+            }
+            ModelIoError::Precision { bits } => write!(f, "precisão não suportada: {} bits", bits),
+            ModelIoError::InvalidParams(msg) => write!(f, "parâmetros inválidos: {}", msg),
+            ModelIoError::InvalidIdentifier(name) => write!(f, "identificador inválido: \"{}\"", name),
+            ModelIoError::Csv(err) => write!(f, "CSV de parâmetros inválido: {}", err),
+            ModelIoError::Npy(err) => write!(f, "arquivo .npy inválido: {}", err),
+            ModelIoError::GzipDisabled => write!(f, "compressão gzip requer a feature \"gzip\""),
+        }
+    }
+}
+
+impl std::error::Error for ModelIoError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ModelIoError::Io(err) => Some(err),
+            #[cfg(feature = "serde")]
+            ModelIoError::Json(err) => Some(err),
+            #[cfg(feature = "serde")]
+            ModelIoError::MsgPackEncode(err) => Some(err),
+            #[cfg(feature = "serde")]
+            ModelIoError::MsgPackDecode(err) => Some(err),
+            ModelIoError::Csv(err) => Some(err),
+            ModelIoError::Npy(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for ModelIoError {
+    fn from(err: io::Error) -> Self {
+        ModelIoError::Io(err)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<serde_json::Error> for ModelIoError {
+    fn from(err: serde_json::Error) -> Self {
+        ModelIoError::Json(err)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<rmp_serde::encode::Error> for ModelIoError {
+    fn from(err: rmp_serde::encode::Error) -> Self {
+        ModelIoError::MsgPackEncode(err)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<rmp_serde::decode::Error> for ModelIoError {
+    fn from(err: rmp_serde::decode::Error) -> Self {
+        ModelIoError::MsgPackDecode(err)
+    }
+}
+
+/*
+ * Representação serializável de um neurônio.
+ *
+ * Campos:
+ *   activation - nome da função de ativação
+ *   n_connections - número de conexões (deve ser igual a weights.len())
+ *   weights - pesos de cada conexão
+ *   bias - valor do bias
+ *   use_bias - false para neurônios sem bias (ausente = true)
+ */
+#[cfg(feature = "serde")]
+#[derive(Serialize, Deserialize)]
+struct NeuronRepr<T> {
+    activation: String,
+    n_connections: usize,
+    weights: Vec<T>,
+    bias: T,
+    #[serde(default = "default_use_bias")]
+    use_bias: bool,
+}
+
+#[cfg(feature = "serde")]
+fn default_use_bias() -> bool {
+    true
+}
+
+/*
+ * Activation é serializada pelo nome ("identity", "sigmoid"); uma
+ * ativação Custom não pode ser serializada.
+ */
+#[cfg(feature = "serde")]
+impl<T: Scalar> Serialize for Activation<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self.name() {
+            Some(name) => serializer.serialize_str(name),
+            None => Err(serde::ser::Error::custom(
+                "ativação definida pelo usuário não pode ser serializada",
+            )),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T: Scalar> Deserialize<'de> for Activation<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        Activation::from_name(&name)
+            .ok_or_else(|| serde::de::Error::custom(format!("ativação desconhecida: {}", name)))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T: Scalar + Serialize> Serialize for Neuron<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let activation = match self.activation().name() {
+            Some(name) => name.to_string(),
+            None => {
+                return Err(serde::ser::Error::custom(
+                    "ativação definida pelo usuário não pode ser serializada",
+                ));
+            }
+        };
+
+        NeuronRepr {
+            activation,
+            n_connections: self.n_connections(),
+            weights: self.weights().to_vec(),
+            bias: self.bias(),
+            use_bias: self.use_bias(),
+        }
+        .serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T: Scalar + Deserialize<'de>> Deserialize<'de> for Neuron<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let repr = NeuronRepr::<T>::deserialize(deserializer)?;
+
+        let activation = match Activation::<T>::from_name(&repr.activation) {
+            Some(activation) => activation,
+            None => {
+                return Err(serde::de::Error::custom(format!(
+                    "ativação desconhecida: {}",
+                    repr.activation
+                )));
+            }
+        };
+        if repr.weights.len() != repr.n_connections {
+            return Err(serde::de::Error::custom(format!(
+                "{} pesos para um neurônio com {} conexões",
+                repr.weights.len(),
+                repr.n_connections
+            )));
+        }
+
+        let neuron = Neuron::from_weights(activation, repr.weights, repr.bias)
+            .map_err(serde::de::Error::custom)?;
+        Ok(if repr.use_bias { neuron } else { neuron.without_bias() })
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T: Scalar + Serialize + for<'de> Deserialize<'de>> Neuron<T> {
+
+    /*
+     * Grava o neurônio em um arquivo JSON.
+     *
+     * Parâmetros:
+     *   path - caminho do arquivo (sobrescrito se existir)
+     *
+     * Retorno:
+     *   Ok(()), ou CeptronError::Io com ModelIoError:
+     *   UnserializableActivation se a ativação for definida pelo
+     *   usuário (conferido antes de gravar), Io se o arquivo não puder
+     *   ser gravado
+     */
+    pub fn save_json(&self, path: impl AsRef<Path>) -> Result<(), CeptronError> {
+        self.activation().name().ok_or_else(unserializable_activation)?;
+        let json = serde_json::to_string_pretty(self).map_err(ModelIoError::from)?;
+        write_file(path.as_ref(), json).map_err(ModelIoError::from)?;
+        Ok(())
+    }
+
+    /*
+     * Lê um neurônio de um arquivo JSON gravado por save_json.
+     *
+     * Parâmetros:
+     *   path - caminho do arquivo
+     *
+     * Retorno:
+     *   O neurônio lido, ou CeptronError::Io (ver ModelIoError) se o
+     *   arquivo não existir, se a ativação for desconhecida ou se o
+     *   número de pesos não corresponder a n_connections
+     */
+    pub fn load_json(path: impl AsRef<Path>) -> Result<Self, CeptronError> {
+        let bytes = read_model_bytes(path.as_ref())?;
+        Ok(serde_json::from_slice(&bytes).map_err(ModelIoError::from)?)
+    }
+
+    /*
+     * Grava o neurônio em um arquivo JSON versionado (ver ModelFile):
+     *   {"format_version": 2, "kind": "neuron", "model": {..}}
+     *
+     * Parâmetros:
+     *   path - caminho do arquivo (sobrescrito se existir)
+     *
+     * Retorno:
+     *   Ok(()), ou CeptronError::Io com ModelIoError:
+     *   UnserializableActivation se a ativação for definida pelo
+     *   usuário (conferido antes de gravar), Io se o arquivo não puder
+     *   ser gravado
+     */
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), CeptronError> {
+        self.save_with(path, false)
+    }
+
+    /*
+     * Como save, comprimindo o arquivo com gzip se compress for true
+     * (load reconhece os dois casos).
+     *
+     * Retorno:
+     *   Ok(()), ou CeptronError::Io (com ModelIoError::GzipDisabled se
+     *   compress for true sem a feature "gzip")
+     */
+    pub fn save_with(&self, path: impl AsRef<Path>, compress: bool) -> Result<(), CeptronError> {
+        let json = ModelFile::to_json_string(self)?;
+        write_file(path.as_ref(), encode_model_bytes(json.into_bytes(), compress)?).map_err(ModelIoError::from)?;
+        Ok(())
+    }
+
+    /*
+     * Lê um neurônio de um arquivo de modelo (ver ModelFile::load):
+     * JSON de qualquer versão já gravada, migrado para a atual, ou o
+     * formato binário.
+     *
+     * Parâmetros:
+     *   path - caminho do arquivo
+     *
+     * Retorno:
+     *   O neurônio lido, ou CeptronError::Io com ModelIoError: Json se
+     *   o arquivo estiver corrompido, se a ativação for desconhecida ou
+     *   se o número de pesos não corresponder a n_connections;
+     *   VersionTooNew se o arquivo for de uma versão mais nova do
+     *   formato
+     */
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, CeptronError> {
+        match ModelFile::load(path)? {
+            ModelFile::Neuron(neuron) => Ok(neuron),
+        }
+    }
+
+    /*
+     * Grava o neurônio em MessagePack, com o mesmo envelope
+     * versionado de save (os dois formatos são intercambiáveis).
+     *
+     * Parâmetros:
+     *   path - caminho do arquivo (sobrescrito se existir)
+     */
+    pub fn save_msgpack(&self, path: impl AsRef<Path>) -> Result<(), CeptronError> {
+        self.save_msgpack_with(path, false)
+    }
+
+    /*
+     * Como save_msgpack, comprimindo o arquivo com gzip se compress
+     * for true.
+     */
+    pub fn save_msgpack_with(&self, path: impl AsRef<Path>, compress: bool) -> Result<(), CeptronError> {
+        let bytes = encode_model_bytes(ModelFile::to_msgpack(self)?, compress)?;
+        write_file(path.as_ref(), bytes).map_err(ModelIoError::from)?;
+        Ok(())
+    }
+
+    /*
+     * Lê um neurônio gravado por save_msgpack.
+     *
+     * Retorno:
+     *   O neurônio lido, ou CeptronError::Io (com
+     *   ModelIoError::MsgPackDecode se o arquivo estiver truncado ou
+     *   corrompido)
+     */
+    pub fn load_msgpack(path: impl AsRef<Path>) -> Result<Self, CeptronError> {
+        match ModelFile::from_msgpack_slice(&read_model_bytes(path.as_ref())?)? {
+            ModelFile::Neuron(neuron) => Ok(neuron),
+        }
+    }
+}
+
+/*
+ * Versão do formato binário gravado por Neuron::save_bin. A versão 2
+ * acrescentou as ativações nomeadas (código 2, ver to_bytes); os
+ * arquivos da versão 1 continuam sendo lidos.
+ */
+pub const BINARY_VERSION: u8 = 2;
+
+const MAGIC: &[u8; 4] = b"CEPT";
+
+/*
+ * Rejeita versões binárias desconhecidas: mais novas que
+ * BINARY_VERSION (VersionTooNew) ou 0 (UnsupportedVersion).
+ */
+fn check_binary_version(version: u8) -> Result<(), ModelIoError> {
+    let supported = u64::from(BINARY_VERSION);
+    match u64::from(version) {
+        found if found > supported => Err(ModelIoError::VersionTooNew { found, supported }),
+        0 => Err(ModelIoError::UnsupportedVersion { found: Some(0), supported }),
+        _ => Ok(()),
+    }
+}
+
+const GZIP_MAGIC: &[u8; 2] = &[0x1f, 0x8b];
+
+/*
+ * Acrescenta o caminho do arquivo à mensagem de um erro de E/S (ex:
+ * "modelo.cept: No such file or directory"), mantendo o seu tipo
+ * (io::ErrorKind), para que o erro diga qual arquivo falhou.
+ */
+pub(crate) fn io_error_at(path: &Path, err: io::Error) -> io::Error {
+    io::Error::new(err.kind(), format!("{}: {}", path.display(), err))
+}
+
+/*
+ * fs::read, fs::read_to_string, fs::write e fs::rename com o caminho
+ * na mensagem de erro (ver io_error_at).
+ */
+pub(crate) fn read_file(path: &Path) -> io::Result<Vec<u8>> {
+    fs::read(path).map_err(|err| io_error_at(path, err))
+}
+
+pub(crate) fn read_text(path: &Path) -> io::Result<String> {
+    fs::read_to_string(path).map_err(|err| io_error_at(path, err))
+}
+
+pub(crate) fn write_file(path: &Path, contents: impl AsRef<[u8]>) -> io::Result<()> {
+    fs::write(path, contents).map_err(|err| io_error_at(path, err))
+}
+
+pub(crate) fn rename_file(from: &Path, to: &Path) -> io::Result<()> {
+    fs::rename(from, to).map_err(|err| io_error_at(to, err))
+}
+
+/*
+ * Lê um arquivo de modelo ou checkpoint, descomprimindo-o se
+ * começar com o cabeçalho gzip (1f 8b). Nenhum dos formatos começa
+ * com esses bytes, então a detecção não é ambígua.
+ *
+ * Retorno:
+ *   O conteúdo descomprimido, ou ModelIoError: Io se o arquivo não
+ *   puder ser lido ou o gzip estiver corrompido, GzipDisabled para
+ *   um arquivo gzip sem a feature "gzip"
+ */
+fn read_model_bytes(path: &Path) -> Result<Vec<u8>, ModelIoError> {
+    let bytes = read_file(path)?;
+    if !bytes.starts_with(GZIP_MAGIC) {
+        return Ok(bytes);
+    }
+    #[cfg(feature = "gzip")]
+    {
+        use std::io::Read;
+
+        let mut decoded = Vec::new();
+        flate2::read::GzDecoder::new(bytes.as_slice())
+            .read_to_end(&mut decoded)
+            .map_err(|err| io_error_at(path, err))?;
+        Ok(decoded)
+    }
+    #[cfg(not(feature = "gzip"))]
+    Err(ModelIoError::GzipDisabled)
+}
+
+/*
+ * Comprime o conteúdo de um arquivo com gzip se compress for true
+ * (nível padrão de compressão).
+ */
+fn encode_model_bytes(bytes: Vec<u8>, compress: bool) -> Result<Vec<u8>, ModelIoError> {
+    if !compress {
+        return Ok(bytes);
+    }
+    #[cfg(feature = "gzip")]
+    {
+        use std::io::Write;
+
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(&bytes)?;
+        Ok(encoder.finish()?)
+    }
+    #[cfg(not(feature = "gzip"))]
+    Err(ModelIoError::GzipDisabled)
+}
+
+/*
+ * Erro de um Neuron isolado com ativação definida pelo usuário. Os
+ * formatos de gravação conferem a ativação antes de escrever
+ * qualquer coisa, para indicar o neurônio com o problema.
+ */
+fn unserializable_activation() -> ModelIoError {
+    ModelIoError::UnserializableActivation { layer: 0, neuron: 0 }
+}
+
+/*
+ * Erro dos geradores de código (ONNX, Rust, C) para uma ativação
+ * nomeada, que só existe no programa que a registrou.
+ */
+fn unsupported_activation(name: &str, format: &'static str) -> ModelIoError {
+    ModelIoError::UnsupportedActivation { name: name.to_string(), format }
+}
+
+/*
+ * Tamanho do cabeçalho binário: magic (4), versão (1), ativação (1),
+ * flags (1), precisão em bits (1) e número de conexões (u32, 4).
+ */
+const HEADER_LEN: usize = 12;
+
+/*
+ * Código de cada ativação no formato binário. As ativações
+ * registradas (Dyn) usam o código 2, seguido do nome.
+ */
+fn activation_code<T: Scalar>(activation: &Activation<T>) -> Option<u8> {
+    match activation {
+        Activation::Identity => Some(0),
+        Activation::Sigmoid => Some(1),
+        Activation::Dyn(_) => Some(2),
+        Activation::Custom(_) => None,
+    }
+}
+
+impl<T: Scalar> Neuron<T> {
+
+    /*
+     * Codifica o neurônio no formato binário "CEPT":
+     *   "CEPT" | versão | ativação | flags | bits | n_connections (u32)
+     *   | [nome] | pesos | bias
+     * com todos os números em little-endian. Os parâmetros são
+     * gravados com a precisão de T (f32 = 32 bits, f64 = 64 bits).
+     * Uma ActivationFn (código 2) é gravada pelo nome, como um u16
+     * com o tamanho seguido dos bytes UTF-8; a leitura a busca com
+     * Activation::from_name, e por isso ela precisa estar registrada.
+     *
+     * Retorno:
+     *   Os bytes do modelo, ou ModelIoError::UnserializableActivation se a
+     *   ativação for Custom (ou tiver um nome com mais de 65535 bytes)
+     */
+    pub fn to_bytes(&self) -> Result<Vec<u8>, ModelIoError> {
+        let activation = activation_code(self.activation()).ok_or_else(unserializable_activation)?;
+        let name = match self.activation() {
+            Activation::Dyn(act_func) => act_func.name().as_bytes(),
+            _ => &[],
+        };
+        let name_len = u16::try_from(name.len()).map_err(|_| unserializable_activation())?;
+        let bits: u8 = if std::mem::size_of::<T>() == 8 { 64 } else { 32 };
+        let n_connections = self.n_connections();
+
+        let mut bytes = Vec::with_capacity(HEADER_LEN + 2 + name.len() + (n_connections + 1) * usize::from(bits / 8));
+        bytes.extend_from_slice(MAGIC);
+        bytes.push(BINARY_VERSION);
+        bytes.push(activation);
+        bytes.push(u8::from(self.use_bias()));
+        bytes.push(bits);
+        bytes.extend_from_slice(&(n_connections as u32).to_le_bytes());
+        if activation == 2 {
+            bytes.extend_from_slice(&name_len.to_le_bytes());
+            bytes.extend_from_slice(name);
+        }
+
+        for &value in self.weights().iter().chain([&self.bias()]) {
+            let value = value.to_f64().unwrap_or(f64::NAN);
+            if bits == 64 {
+                bytes.extend_from_slice(&value.to_le_bytes());
+            } else {
+                bytes.extend_from_slice(&(value as f32).to_le_bytes());
+            }
+        }
+        Ok(bytes)
+    }
+
+    /*
+     * Decodifica um neurônio gravado por to_bytes.
+     *
+     * Parâmetros:
+     *   bytes - conteúdo do modelo binário
+     *
+     * Retorno:
+     *   O neurônio, ou ModelIoError se o cabeçalho, a versão, a
+     *   ativação ou a precisão forem inválidos, se a ativação nomeada
+     *   não estiver registrada (UnregisteredActivation), ou se o
+     *   tamanho não corresponder ao número de conexões (verificado
+     *   antes de alocar os pesos)
+     */
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ModelIoError> {
+        if !bytes.starts_with(MAGIC) {
+            return Err(ModelIoError::BadMagic);
+        }
+        if bytes.len() < HEADER_LEN {
+            return Err(ModelIoError::Length { expected: HEADER_LEN, actual: bytes.len() });
+        }
+        check_binary_version(bytes[4])?;
+        let mut offset = HEADER_LEN;
+        let activation = match bytes[5] {
+            0 => Activation::Identity,
+            1 => Activation::Sigmoid,
+            // o código 2 só existe a partir da versão 2
+            2 if bytes[4] >= 2 => {
+                let name_len = match bytes.get(offset..offset + 2) {
+                    Some(&[a, b]) => usize::from(u16::from_le_bytes([a, b])),
+                    _ => return Err(ModelIoError::Length { expected: offset + 2, actual: bytes.len() }),
+                };
+                let Some(name) = bytes.get(offset + 2..offset + 2 + name_len) else {
+                    return Err(ModelIoError::Length { expected: offset + 2 + name_len, actual: bytes.len() });
+                };
+                offset += 2 + name_len;
+                let name = String::from_utf8_lossy(name);
+                Activation::from_name(&name)
+                    .ok_or_else(|| ModelIoError::UnregisteredActivation { name: name.into_owned() })?
+            }
+            code => return Err(ModelIoError::UnknownActivation { code }),
+        };
+        let use_bias = bytes[6] != 0;
+        let width = match bytes[7] {
+            32 => 4,
+            64 => 8,
+            bits => return Err(ModelIoError::Precision { bits }),
+        };
+        let n_connections = u32::from_le_bytes([bytes[8], bytes[9], bytes[10], bytes[11]]) as usize;
+
+        let expected = n_connections
+            .checked_add(1)
+            .and_then(|n| n.checked_mul(width))
+            .and_then(|n| n.checked_add(offset))
+            .unwrap_or(usize::MAX);
+        if bytes.len() != expected {
+            return Err(ModelIoError::Length { expected, actual: bytes.len() });
+        }
+
+        let mut values: Vec<T> = bytes[offset..]
+            .chunks_exact(width)
+            .map(|chunk| {
+                let value = match *chunk {
+                    [a, b, c, d] => f64::from(f32::from_le_bytes([a, b, c, d])),
+                    [a, b, c, d, e, f, g, h] => f64::from_le_bytes([a, b, c, d, e, f, g, h]),
+                    _ => unreachable!("chunks_exact com largura 4 ou 8"),
+                };
+                from_f64(value)
+            })
+            .collect();
+        let bias = values.pop().unwrap_or_else(T::zero);
+
+        let neuron = Neuron::from_weights(activation, values, bias)
+            .map_err(|err| ModelIoError::InvalidParams(err.to_string()))?;
+        Ok(if use_bias { neuron } else { neuron.without_bias() })
+    }
+
+    /*
+     * Grava o neurônio no formato binário (ver to_bytes).
+     *
+     * Parâmetros:
+     *   path - caminho do arquivo (sobrescrito se existir)
+     *
+     * Retorno:
+     *   Ok(()), ou CeptronError::Io (ver ModelIoError)
+     */
+    pub fn save_bin(&self, path: impl AsRef<Path>) -> Result<(), CeptronError> {
+        self.save_bin_with(path, false)
+    }
+
+    /*
+     * Como save_bin, comprimindo o arquivo com gzip se compress for
+     * true (load_bin reconhece os dois casos).
+     */
+    pub fn save_bin_with(&self, path: impl AsRef<Path>, compress: bool) -> Result<(), CeptronError> {
+        Ok(self.write_bin(path.as_ref(), compress)?)
+    }
+
+    /*
+     * Lê um neurônio gravado por save_bin (ver from_bytes).
+     *
+     * Parâmetros:
+     *   path - caminho do arquivo
+     *
+     * Retorno:
+     *   O neurônio lido, ou CeptronError::Io (ver ModelIoError)
+     */
+    pub fn load_bin(path: impl AsRef<Path>) -> Result<Self, CeptronError> {
+        Ok(Self::read_bin(path.as_ref())?)
+    }
+
+    /*
+     * Implementação de save_bin_with, com o erro do módulo (usada por
+     * fit para TrainConfig::save_path).
+     */
+    pub(crate) fn write_bin(&self, path: &Path, compress: bool) -> Result<(), ModelIoError> {
+        write_file(path, encode_model_bytes(self.to_bytes()?, compress)?)?;
+        Ok(())
+    }
+
+    /*
+     * Confere, sem gravar nada, se write_bin conseguiria gravar o
+     * neurônio: a ativação precisa ter um código binário (ver
+     * to_bytes) e a compressão requer a feature "gzip". fit chama
+     * antes de treinar, para não falhar só depois de todas as épocas.
+     */
+    pub(crate) fn check_writable(&self, compress: bool) -> Result<(), ModelIoError> {
+        let name_len = self.activation().name().map_or(0, str::len);
+        if activation_code(self.activation()).is_none() || name_len > usize::from(u16::MAX) {
+            return Err(unserializable_activation());
+        }
+        if compress && !cfg!(feature = "gzip") {
+            return Err(ModelIoError::GzipDisabled);
+        }
+        Ok(())
+    }
+
+    /*
+     * Implementação de load_bin, com o erro do módulo (usada por
+     * fit_from).
+     */
+    pub(crate) fn read_bin(path: &Path) -> Result<Self, ModelIoError> {
+        Self::from_bytes(&read_model_bytes(path)?)
+    }
+}
+
+impl<T: Scalar> Neuron<T> {
+
+    /*
+     * Grava os parâmetros em CSV, um por linha:
+     *   name,index,value
+     *   activation,,sigmoid
+     *   weight,0,3.0012
+     *   bias,,4.998
+     *
+     * Os valores são escritos com a menor representação que lê de
+     * volta o mesmo número, sem perda de precisão. Neurônios sem
+     * bias não têm a linha bias.
+     *
+     * Parâmetros:
+     *   path - caminho do arquivo (sobrescrito se existir)
+     *
+     * Retorno:
+     *   Ok(()), ou ModelIoError::UnserializableActivation se a ativação for
+     *   definida pelo usuário
+     */
+    pub fn export_csv(&self, path: impl AsRef<Path>) -> Result<(), ModelIoError> {
+        let activation = self.activation().name().ok_or_else(unserializable_activation)?;
+
+        let mut csv = String::from("name,index,value\n");
+        csv.push_str(&format!("activation,,{}\n", activation));
+        for (index, weight) in self.weights().iter().enumerate() {
+            csv.push_str(&format!("weight,{},{}\n", index, weight));
+        }
+        if self.use_bias() {
+            csv.push_str(&format!("bias,,{}\n", self.bias()));
+        }
+        write_file(path.as_ref(), csv)?;
+        Ok(())
+    }
+}
+
+impl<T: Scalar + FromStr> Neuron<T> {
+
+    /*
+     * Lê um neurônio de um CSV gravado por export_csv (possivelmente
+     * editado à mão). As linhas de peso podem estar em qualquer
+     * ordem, mas cada índice de 0 a n - 1 deve aparecer uma vez.
+     *
+     * Parâmetros:
+     *   path - caminho do arquivo
+     *
+     * Retorno:
+     *   O neurônio lido (sem bias se não houver linha bias), ou
+     *   ModelIoError::Csv indicando a linha do problema
+     */
+    pub fn import_csv(path: impl AsRef<Path>) -> Result<Self, ModelIoError> {
+        let text = read_text(path.as_ref())?;
+        let mut lines = text.lines().enumerate().filter(|(_, raw)| !raw.trim().is_empty());
+        match lines.next() {
+            Some((_, header)) if header.trim() == "name,index,value" => {}
+            _ => return Err(ParamCsvError::Header.into()),
+        }
+
+        let mut activation = None;
+        let mut weights: Vec<Option<T>> = Vec::new();
+        let mut bias = None;
+
+        for (index, raw) in lines {
+            let line = index + 1;
+            let fields: Vec<&str> = raw.split(',').map(str::trim).collect();
+            let &[name, param_index, value] = fields.as_slice() else {
+                return Err(ParamCsvError::Row { line, content: raw.to_string() }.into());
+            };
+            if name != "weight" && !param_index.is_empty() {
+                return Err(ParamCsvError::Index { line, value: param_index.to_string() }.into());
+            }
+            let parse_value = || {
+                value
+                    .parse::<T>()
+                    .map_err(|_| ParamCsvError::Value { line, value: value.to_string() })
+            };
+
+            match name {
+                "activation" => {
+                    if activation.is_some() {
+                        return Err(ParamCsvError::Duplicate { line, name: name.to_string() }.into());
+                    }
+                    let parsed = Activation::from_name(value)
+                        .ok_or(ParamCsvError::Activation { line, name: value.to_string() })?;
+                    activation = Some(parsed);
+                }
+                "weight" => {
+                    let i: usize = param_index
+                        .parse()
+                        .map_err(|_| ParamCsvError::Index { line, value: param_index.to_string() })?;
+                    if weights.len() <= i {
+                        weights.resize(i + 1, None);
+                    }
+                    if weights[i].is_some() {
+                        return Err(ParamCsvError::Index { line, value: param_index.to_string() }.into());
+                    }
+                    weights[i] = Some(parse_value()?);
+                }
+                "bias" => {
+                    if bias.is_some() {
+                        return Err(ParamCsvError::Duplicate { line, name: name.to_string() }.into());
+                    }
+                    bias = Some(parse_value()?);
+                }
+                _ => return Err(ParamCsvError::Row { line, content: raw.to_string() }.into()),
+            }
+        }
+
+        let activation = activation.ok_or(ParamCsvError::Missing { name: "activation", index: None })?;
+        let weights = weights
+            .into_iter()
+            .enumerate()
+            .map(|(index, weight)| weight.ok_or(ParamCsvError::Missing { name: "weight", index: Some(index) }))
+            .collect::<Result<Vec<T>, _>>()?;
+
+        let neuron = Neuron::from_weights(activation, weights, bias.unwrap_or_else(T::zero))
+            .map_err(|err| ModelIoError::InvalidParams(err.to_string()))?;
+        Ok(if bias.is_some() { neuron } else { neuron.without_bias() })
+    }
+}
+
+impl<T: Scalar> Neuron<T> {
+
+    /*
+     * Cria um neurônio com os pesos de um arquivo .npy do NumPy
+     * (ex: np.save("w.npy", modelo.coef_)).
+     *
+     * Parâmetros:
+     *   activation - função de ativação do neurônio
+     *   weights_path - arquivo .npy (versão 1) com os pesos: float32
+     *                  ou float64 little-endian, com formato (n,),
+     *                  (1, n) ou (n, 1)
+     *   bias - valor do bias
+     *
+     * Retorno:
+     *   O neurônio, ou ModelIoError::Npy se o arquivo for inválido ou
+     *   não suportado (ex: fortran_order=True)
+     */
+    pub fn load_npy(activation: Activation<T>, weights_path: impl AsRef<Path>, bias: T) -> Result<Self, ModelIoError> {
+        let (shape, values) = parse_npy(&read_file(weights_path.as_ref())?)?;
+        match shape.as_slice() {
+            [_] | [1, _] | [_, 1] => {}
+            _ => return Err(NpyError::Shape { shape }.into()),
+        }
+
+        let weights = values.into_iter().map(from_f64).collect();
+        Neuron::from_weights(activation, weights, bias).map_err(|err| ModelIoError::InvalidParams(err.to_string()))
+    }
+}
+
+/*
+ * Codificação mínima do formato protobuf usado pelo ONNX: cada
+ * campo é uma chave (número do campo << 3 | tipo) seguida de um
+ * varint (tipo 0) ou de um tamanho e dos bytes (tipo 2).
+ */
+fn pb_varint(out: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        out.push((value as u8 & 0x7f) | 0x80);
+        value >>= 7;
+    }
+    out.push(value as u8);
+}
+
+fn pb_int(out: &mut Vec<u8>, field: u64, value: u64) {
+    pb_varint(out, field << 3);
+    pb_varint(out, value);
+}
+
+fn pb_bytes(out: &mut Vec<u8>, field: u64, bytes: &[u8]) {
+    pb_varint(out, (field << 3) | 2);
+    pb_varint(out, bytes.len() as u64);
+    out.extend_from_slice(bytes);
+}
+
+/*
+ * NodeProto: entradas (1), saídas (2), nome (3) e operador (4).
+ */
+fn onnx_node(op_type: &str, inputs: &[&str], output: &str) -> Vec<u8> {
+    let mut node = Vec::new();
+    for input in inputs {
+        pb_bytes(&mut node, 1, input.as_bytes());
+    }
+    pb_bytes(&mut node, 2, output.as_bytes());
+    pb_bytes(&mut node, 3, output.as_bytes());
+    pb_bytes(&mut node, 4, op_type.as_bytes());
+    node
+}
+
+/*
+ * TensorProto: dimensões (1), tipo (2), nome (8) e dados brutos
+ * little-endian (9).
+ */
+fn onnx_tensor(name: &str, dims: &[u64], elem_type: u64, raw_data: &[u8]) -> Vec<u8> {
+    let mut tensor = Vec::new();
+    for &dim in dims {
+        pb_int(&mut tensor, 1, dim);
+    }
+    pb_int(&mut tensor, 2, elem_type);
+    pb_bytes(&mut tensor, 8, name.as_bytes());
+    pb_bytes(&mut tensor, 9, raw_data);
+    tensor
+}
+
+/*
+ * ValueInfoProto de um tensor [N, width], com N (número de
+ * amostras) simbólico.
+ */
+fn onnx_value_info(name: &str, elem_type: u64, width: u64) -> Vec<u8> {
+    let mut batch = Vec::new();
+    pb_bytes(&mut batch, 2, b"N");
+    let mut columns = Vec::new();
+    pb_int(&mut columns, 1, width);
+    let mut shape = Vec::new();
+    pb_bytes(&mut shape, 1, &batch);
+    pb_bytes(&mut shape, 1, &columns);
+
+    let mut tensor_type = Vec::new();
+    pb_int(&mut tensor_type, 1, elem_type);
+    pb_bytes(&mut tensor_type, 2, &shape);
+    let mut type_proto = Vec::new();
+    pb_bytes(&mut type_proto, 1, &tensor_type);
+
+    let mut value_info = Vec::new();
+    pb_bytes(&mut value_info, 1, name.as_bytes());
+    pb_bytes(&mut value_info, 2, &type_proto);
+    value_info
+}
+
+impl<T: Scalar> Neuron<T> {
+
+    /*
+     * Codifica o neurônio como um modelo ONNX (opset 13):
+     *   Y = activation(MatMul(X, W) + B)
+     * com X de formato [N, n_connections], W [n_connections, 1] e B
+     * [1] gravados como initializers, e Y [N, 1]. Neurônios sem bias
+     * não têm o nó Add. Os tensores são float32 (ou float64 se T for
+     * f64).
+     *
+     * Retorno:
+     *   Os bytes do ModelProto, ou ModelIoError (UnserializableActivation
+     *   para Custom, UnsupportedActivation para uma ActivationFn, que
+     *   não tem operador ONNX)
+     *
+     * Para conferir em Python (fora do cargo):
+     *   import onnxruntime, numpy as np
+     *   s = onnxruntime.InferenceSession("modelo.onnx")
+     *   s.run(None, {"X": np.array([[6.0, 1.0]], dtype=np.float32)})
+     */
+    pub fn to_onnx(&self) -> Result<Vec<u8>, ModelIoError> {
+        let activation_op = match self.activation() {
+            Activation::Identity => "Identity",
+            Activation::Sigmoid => "Sigmoid",
+            Activation::Custom(_) => return Err(unserializable_activation()),
+            Activation::Dyn(act_func) => return Err(unsupported_activation(act_func.name(), "ONNX")),
+        };
+        // TensorProto.DataType: FLOAT = 1, DOUBLE = 11
+        let (elem_type, wide) = if std::mem::size_of::<T>() == 8 { (11, true) } else { (1, false) };
+        let raw = |values: &[T]| -> Vec<u8> {
+            values
+                .iter()
+                .flat_map(|value| {
+                    let value = value.to_f64().unwrap_or(f64::NAN);
+                    if wide { value.to_le_bytes().to_vec() } else { (value as f32).to_le_bytes().to_vec() }
+                })
+                .collect()
+        };
+        let n_connections = self.n_connections() as u64;
+
+        let mut graph = Vec::new();
+        let pre_activation = if self.use_bias() { "Z" } else { "XW" };
+        pb_bytes(&mut graph, 1, &onnx_node("MatMul", &["X", "W"], "XW"));
+        if self.use_bias() {
+            pb_bytes(&mut graph, 1, &onnx_node("Add", &["XW", "B"], "Z"));
+        }
+        pb_bytes(&mut graph, 1, &onnx_node(activation_op, &[pre_activation], "Y"));
+        pb_bytes(&mut graph, 2, b"perceptron");
+        pb_bytes(&mut graph, 5, &onnx_tensor("W", &[n_connections, 1], elem_type, &raw(self.weights())));
+        if self.use_bias() {
+            pb_bytes(&mut graph, 5, &onnx_tensor("B", &[1], elem_type, &raw(&[self.bias()])));
+        }
+        pb_bytes(&mut graph, 11, &onnx_value_info("X", elem_type, n_connections));
+        pb_bytes(&mut graph, 12, &onnx_value_info("Y", elem_type, 1));
+
+        let mut opset = Vec::new();
+        pb_bytes(&mut opset, 1, b"");
+        pb_int(&mut opset, 2, 13);
+
+        let mut model = Vec::new();
+        pb_int(&mut model, 1, 7);
+        pb_bytes(&mut model, 2, b"perceptron");
+        pb_bytes(&mut model, 7, &graph);
+        pb_bytes(&mut model, 8, &opset);
+        Ok(model)
+    }
+
+    /*
+     * Grava o neurônio em um arquivo ONNX (ver to_onnx).
+     *
+     * Parâmetros:
+     *   path - caminho do arquivo (sobrescrito se existir)
+     */
+    pub fn export_onnx(&self, path: impl AsRef<Path>) -> Result<(), ModelIoError> {
+        write_file(path.as_ref(), self.to_onnx()?)?;
+        Ok(())
+    }
+}
+
+const CHECKPOINT_MAGIC: &[u8; 4] = b"CKPT";
+
+/*
+ * Estado de um treinamento interrompido (ver neuralnet::fit_resume).
+ *
+ * Campos:
+ *   epoch - número de épocas já concluídas
+ *   config_hash - TrainConfig::fingerprint da configuração usada
+ *   neuron - parâmetros no espaço de treino (com scale_target, o
+ *            neurônio ainda está na escala padronizada)
+ *
+ * Formato: "CKPT" | versão (u8) | epoch (u64) | config_hash (u64) |
+ * neurônio no formato binário "CEPT", em little-endian.
+ */
+pub struct Checkpoint<T: 'static = Float> {
+    pub epoch: usize,
+    pub config_hash: u64,
+    pub neuron: Neuron<T>,
+}
+
+impl<T: Scalar> Checkpoint<T> {
+
+    /*
+     * Grava um checkpoint de forma atômica: o conteúdo vai para um
+     * arquivo temporário ao lado de path, que depois é renomeado.
+     * Uma interrupção durante a gravação mantém o checkpoint anterior.
+     *
+     * Parâmetros:
+     *   path - arquivo do checkpoint
+     *   epoch - épocas concluídas
+     *   config_hash - resumo da configuração
+     *   neuron - o neurônio em treinamento
+     */
+    pub fn save(path: &Path, epoch: usize, config_hash: u64, neuron: &Neuron<T>) -> Result<(), ModelIoError> {
+        Self::save_with(path, epoch, config_hash, neuron, false)
+    }
+
+    /*
+     * Como save, comprimindo o checkpoint com gzip se compress for
+     * true (load reconhece os dois casos).
+     */
+    pub fn save_with(
+        path: &Path,
+        epoch: usize,
+        config_hash: u64,
+        neuron: &Neuron<T>,
+        compress: bool,
+    ) -> Result<(), ModelIoError> {
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(CHECKPOINT_MAGIC);
+        bytes.push(BINARY_VERSION);
+        bytes.extend_from_slice(&(epoch as u64).to_le_bytes());
+        bytes.extend_from_slice(&config_hash.to_le_bytes());
+        bytes.extend_from_slice(&neuron.to_bytes()?);
+
+        let mut tmp = path.as_os_str().to_owned();
+        tmp.push(".tmp");
+        write_file(Path::new(&tmp), encode_model_bytes(bytes, compress)?)?;
+        rename_file(Path::new(&tmp), path)?;
+        Ok(())
+    }
+
+    /*
+     * Grava um checkpoint em MessagePack, de forma atômica como save:
+     *   {"format_version", "kind": "checkpoint", "epoch",
+     *    "config_hash", "model"}
+     * com o neurônio no mesmo formato de Neuron::save_msgpack.
+     */
+    #[cfg(feature = "serde")]
+    pub fn save_msgpack(path: &Path, epoch: usize, config_hash: u64, neuron: &Neuron<T>) -> Result<(), ModelIoError>
+    where
+        T: Serialize,
+    {
+        neuron.activation().name().ok_or_else(unserializable_activation)?;
+        let document = serde_json::json!({
+            "format_version": FORMAT_VERSION,
+            "kind": "checkpoint",
+            "epoch": epoch,
+            "config_hash": config_hash,
+            "model": serde_json::to_value(neuron)?,
+        });
+
+        let mut tmp = path.as_os_str().to_owned();
+        tmp.push(".tmp");
+        write_file(Path::new(&tmp), rmp_serde::to_vec(&document)?)?;
+        rename_file(Path::new(&tmp), path)?;
+        Ok(())
+    }
+
+    /*
+     * Lê um checkpoint gravado por save_msgpack.
+     *
+     * Retorno:
+     *   O checkpoint, ou ModelIoError (MsgPackDecode se o arquivo
+     *   estiver truncado, VersionTooNew se for de uma versão mais
+     *   nova ou não for um checkpoint)
+     */
+    #[cfg(feature = "serde")]
+    pub fn load_msgpack(path: impl AsRef<Path>) -> Result<Self, ModelIoError> {
+        Self::from_msgpack_slice(&read_model_bytes(path.as_ref())?)
+    }
+
+    /*
+     * Decodifica um checkpoint em MessagePack. O neurônio é lido em
+     * f64 e convertido para T, para que load (e fit_resume) aceitem
+     * os dois formatos sem exigir T: Deserialize; os valores de um
+     * checkpoint f32 voltam exatamente.
+     */
+    #[cfg(feature = "serde")]
+    fn from_msgpack_slice(bytes: &[u8]) -> Result<Self, ModelIoError> {
+        let mut document: serde_json::Value = rmp_serde::from_slice(bytes)?;
+        let version = document.get("format_version").and_then(serde_json::Value::as_u64);
+        match version {
+            Some(found) if found > FORMAT_VERSION => {
+                return Err(ModelIoError::VersionTooNew { found, supported: FORMAT_VERSION });
+            }
+            Some(_) => {}
+            None => return Err(ModelIoError::UnsupportedVersion { found: None, supported: FORMAT_VERSION }),
+        }
+        let field = |name: &str| document.get(name).and_then(serde_json::Value::as_u64);
+        let (Some("checkpoint"), Some(epoch), Some(config_hash)) = (
+            document.get("kind").and_then(serde_json::Value::as_str),
+            field("epoch"),
+            field("config_hash"),
+        ) else {
+            return Err(ModelIoError::MsgPackDecode(serde::de::Error::custom("o arquivo não é um checkpoint")));
+        };
+        let model = document.get_mut("model").map(serde_json::Value::take).unwrap_or_default();
+        let wide: Neuron<f64> = serde_json::from_value(model)?;
+        let name = wide.activation().name().ok_or_else(unserializable_activation)?;
+        let activation = Activation::from_name(name)
+            .ok_or_else(|| ModelIoError::UnregisteredActivation { name: name.to_string() })?;
+        let neuron = Neuron::from_weights(
+            activation,
+            wide.weights().iter().map(|&weight| from_f64(weight)).collect(),
+            from_f64(wide.bias()),
+        )
+        .map_err(|err| ModelIoError::InvalidParams(err.to_string()))?;
+        Ok(Self {
+            epoch: epoch as usize,
+            config_hash,
+            neuron: if wide.use_bias() { neuron } else { neuron.without_bias() },
+        })
+    }
+
+    /*
+     * Lê um checkpoint gravado por save ou, com a feature "serde",
+     * por save_msgpack (reconhecido pelo primeiro byte, que em
+     * MessagePack marca um mapa).
+     *
+     * Retorno:
+     *   O checkpoint, ou ModelIoError se o arquivo não for um
+     *   checkpoint, tiver outra versão ou estiver truncado
+     */
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, ModelIoError> {
+        const HEADER: usize = 21;
+        let bytes = read_model_bytes(path.as_ref())?;
+        #[cfg(feature = "serde")]
+        if matches!(bytes.first(), Some(0x80..=0x8f | 0xde | 0xdf)) {
+            return Self::from_msgpack_slice(&bytes);
+        }
+        if !bytes.starts_with(CHECKPOINT_MAGIC) {
+            return Err(ModelIoError::BadMagic);
+        }
+        if bytes.len() < HEADER {
+            return Err(ModelIoError::Length { expected: HEADER, actual: bytes.len() });
+        }
+        check_binary_version(bytes[4])?;
+
+        let read_u64 = |at: usize| {
+            let mut word = [0u8; 8];
+            word.copy_from_slice(&bytes[at..at + 8]);
+            u64::from_le_bytes(word)
+        };
+        Ok(Self {
+            epoch: read_u64(5) as usize,
+            config_hash: read_u64(13),
+            neuron: Neuron::from_bytes(&bytes[HEADER..])?,
+        })
+    }
+}
+
+/*
+ * Verifica se name pode ser usado como nome de função no código
+ * gerado: letras ASCII, dígitos e '_', sem começar com dígito.
+ */
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+        && name != "_"
+}
+
+impl<T: Scalar> Neuron<T> {
+
+    /*
+     * Gera uma função Rust independente (sem dependências) que
+     * calcula a saída do neurônio, com os pesos como literais:
+     *   pub fn fn_name(x: &[f32]) -> f32
+     *
+     * A função gerada soma os termos na mesma ordem de compute_out,
+     * e portanto dá o mesmo resultado; entra em pânico se x não
+     * tiver um valor por conexão. Usa f64 se T for f64.
+     *
+     * Parâmetros:
+     *   fn_name - nome da função gerada
+     *
+     * Retorno:
+     *   O código-fonte, ou ModelIoError se fn_name não for um
+     *   identificador válido ou se a ativação não for identity nem
+     *   sigmoid (UnsupportedActivation para uma ActivationFn)
+     */
+    pub fn to_rust_source(&self, fn_name: &str) -> Result<String, ModelIoError> {
+        if !is_identifier(fn_name) {
+            return Err(ModelIoError::InvalidIdentifier(fn_name.to_string()));
+        }
+        let wide = std::mem::size_of::<T>() == 8;
+        let ty = if wide { "f64" } else { "f32" };
+        let output = match self.activation() {
+            Activation::Identity => "z".to_string(),
+            Activation::Sigmoid => "1.0 / (1.0 + (-z).exp())".to_string(),
+            Activation::Custom(_) => return Err(unserializable_activation()),
+            Activation::Dyn(act_func) => return Err(unsupported_activation(act_func.name(), "Rust")),
+        };
+        // {:?} escreve o menor literal que lê de volta o mesmo valor,
+        // sempre com ponto decimal (ex: 1.0)
+        let literal = |value: T| {
+            let value = value.to_f64().unwrap_or(f64::NAN);
+            if wide { format!("{:?}", value) } else { format!("{:?}", value as f32) }
+        };
+        let weights: Vec<String> = self.weights().iter().map(|&weight| literal(weight)).collect();
+
+        let mut source = String::new();
+        source.push_str(&format!(
+            "/// Gerado a partir de um neurônio treinado ({} entradas, ativação {}).\n",
+            self.n_connections(),
+            self.activation().name().unwrap_or("custom")
+        ));
+        source.push_str(&format!("pub fn {}(x: &[{}]) -> {} {{\n", fn_name, ty, ty));
+        source.push_str(&format!(
+            "    const WEIGHTS: [{}; {}] = [{}];\n",
+            ty,
+            weights.len(),
+            weights.join(", ")
+        ));
+        source.push_str("    assert_eq!(x.len(), WEIGHTS.len(), \"número de entradas\");\n");
+        source.push_str(&format!("    let mut z: {} = 0.0;\n", ty));
+        source.push_str("    for (input, weight) in x.iter().zip(WEIGHTS.iter()) {\n");
+        source.push_str("        z += input * weight;\n");
+        source.push_str("    }\n");
+        if self.use_bias() {
+            source.push_str(&format!("    z += {};\n", literal(self.bias())));
+        }
+        source.push_str(&format!("    {}\n", output));
+        source.push_str("}\n");
+        Ok(source)
+    }
+}
+
+impl<T: Scalar> Neuron<T> {
+
+    /*
+     * Gera um header C com os parâmetros e uma função de predição:
+     *   #define PREFIX_N_INPUTS 2
+     *   static const float prefix_weights[PREFIX_N_INPUTS] = {...};
+     *   static const float prefix_bias = ...;
+     *   static inline float prefix_predict(const float* x)
+     *
+     * Os literais têm dígitos suficientes para reproduzir os valores
+     * exatos. Usa double se T for f64. A ativação sigmoid usa
+     * math.h (expf/exp).
+     *
+     * Parâmetros:
+     *   prefix - prefixo dos nomes (identificador C); os #define usam
+     *            o prefixo em maiúsculas
+     *
+     * Retorno:
+     *   O texto do header, ou ModelIoError se prefix não for um
+     *   identificador válido ou se a ativação não for identity nem
+     *   sigmoid (UnsupportedActivation para uma ActivationFn)
+     */
+    pub fn to_c_header(&self, prefix: &str) -> Result<String, ModelIoError> {
+        if !is_identifier(prefix) {
+            return Err(ModelIoError::InvalidIdentifier(prefix.to_string()));
+        }
+        let wide = std::mem::size_of::<T>() == 8;
+        let (ty, suffix, exp) = if wide { ("double", "", "exp") } else { ("float", "f", "expf") };
+        let output = match self.activation() {
+            Activation::Identity => "z".to_string(),
+            Activation::Sigmoid => format!("1.0{s} / (1.0{s} + {}(-z))", exp, s = suffix),
+            Activation::Custom(_) => return Err(unserializable_activation()),
+            Activation::Dyn(act_func) => return Err(unsupported_activation(act_func.name(), "C")),
+        };
+        let literal = |value: T| {
+            let value = value.to_f64().unwrap_or(f64::NAN);
+            if wide { format!("{:?}", value) } else { format!("{:?}f", value as f32) }
+        };
+        let weights: Vec<String> = self.weights().iter().map(|&weight| literal(weight)).collect();
+        let upper = prefix.to_ascii_uppercase();
+        let guard = format!("{}_MODEL_H", upper);
+        let bias = if self.use_bias() { literal(self.bias()) } else { format!("0.0{}", suffix) };
+
+        let mut header = String::new();
+        header.push_str(&format!(
+            "/* Gerado a partir de um neurônio treinado (ativação {}). */\n",
+            self.activation().name().unwrap_or("custom")
+        ));
+        header.push_str(&format!("#ifndef {}\n#define {}\n\n", guard, guard));
+        header.push_str("#include <math.h>\n\n");
+        header.push_str(&format!("#define {}_N_INPUTS {}\n\n", upper, self.n_connections()));
+        header.push_str(&format!(
+            "static const {} {}_weights[{}_N_INPUTS] = {{{}}};\n",
+            ty,
+            prefix,
+            upper,
+            weights.join(", ")
+        ));
+        header.push_str(&format!("static const {} {}_bias = {};\n\n", ty, prefix, bias));
+        header.push_str(&format!("static inline {} {}_predict(const {}* x) {{\n", ty, prefix, ty));
+        header.push_str(&format!("    {} z = 0.0{};\n", ty, suffix));
+        header.push_str(&format!("    for (int i = 0; i < {}_N_INPUTS; i++) {{\n", upper));
+        header.push_str(&format!("        z += x[i] * {}_weights[i];\n", prefix));
+        header.push_str("    }\n");
+        header.push_str(&format!("    z += {}_bias;\n", prefix));
+        header.push_str(&format!("    return {};\n", output));
+        header.push_str("}\n\n");
+        header.push_str(&format!("#endif /* {} */\n", guard));
+        Ok(header)
+    }
+
+    /*
+     * Grava o header C gerado por to_c_header.
+     *
+     * Parâmetros:
+     *   path - caminho do arquivo (ex: "modelo.h")
+     *   prefix - prefixo dos nomes
+     */
+    pub fn export_c_header(&self, path: impl AsRef<Path>, prefix: &str) -> Result<(), ModelIoError> {
+        write_file(path.as_ref(), self.to_c_header(prefix)?)?;
+        Ok(())
+    }
+}
+
+impl<T: Scalar> Neuron<T> {
+
+    /*
+     * Relatório em texto do neurônio (ver report_with), sem métricas
+     * e com as entradas chamadas x1, x2, ...
+     */
+    pub fn report(&self) -> String {
+        self.report_with(None, None)
+    }
+
+    /*
+     * Relatório em texto do neurônio: entradas, ativação, parâmetros,
+     * norma dos pesos e a equação aprendida, ex:
+     *   y = 3.0010*x1 + 1.9980*x2 + 5.0020
+     *
+     * Parâmetros:
+     *   data - se informado, o relatório inclui o custo (mse) nessas
+     *          amostras
+     *   feature_names - nomes das entradas, usados na equação no
+     *                   lugar de x1, x2, ...; None usa os nomes de
+     *                   data (ex: o cabeçalho do CSV), se houver.
+     *                   Ignorados se não houver um nome por entrada
+     *
+     * Retorno:
+     *   O relatório, terminado em quebra de linha
+     */
+    pub fn report_with(&self, data: Option<&Dataset<T>>, feature_names: Option<&[&str]>) -> String {
+        let names: Vec<String> = match (feature_names, data.and_then(Dataset::feature_names)) {
+            (Some(names), _) => names.iter().map(|name| name.to_string()).collect(),
+            (None, Some(names)) => names.to_vec(),
+            (None, None) => Vec::new(),
+        };
+        let equation = self.wrapped_terms(&names, 4);
+
+        let weights: Vec<String> = self.weights().iter().map(|weight| format!("{:.4}", weight)).collect();
+        let mut report = String::new();
+        report.push_str(&format!("Entradas            : {}\n", self.n_connections()));
+        report.push_str(&format!("Ativação            : {}\n", self.activation().name().unwrap_or("definida pelo usuário")));
+        report.push_str(&format!("Pesos               : [{}]\n", weights.join(", ")));
+        if self.use_bias() {
+            report.push_str(&format!("Bias                : {:.4}\n", self.bias()));
+        } else {
+            report.push_str("Bias                : (sem bias)\n");
+        }
+        report.push_str(&format!("Norma dos pesos     : {:.4}\n", self.weight_norm()));
+        report.push_str(&format!("Equação             : y = {}\n", equation));
+        report.push_str(&format!("Fingerprint         : {:016x}\n", self.fingerprint()));
+        if let Some(data) = data {
+            #[allow(deprecated)]
+            let cost = compute_cost_on(self, data, mse);
+            report.push_str(&format!("Custo (mse)         : {} ({} amostras)\n", cost, data.len()));
+        }
+        report
+    }
+
+    /*
+     * Grava o relatório (ver report_with) em um arquivo de texto.
+     *
+     * Parâmetros:
+     *   path - caminho do arquivo (sobrescrito se existir)
+     *   data - amostras para as métricas (e os nomes das entradas), ou None
+     */
+    pub fn save_report(&self, path: impl AsRef<Path>, data: Option<&Dataset<T>>) -> Result<(), ModelIoError> {
+        write_file(path.as_ref(), self.report_with(data, None))?;
+        Ok(())
+    }
+
+    /*
+     * A equação aprendida em texto, ex:
+     *   y = 3.001*area + 1.998*rooms + 5.002
+     *   y = sigmoid(1.5*x1 - 0.25*x2)
+     *
+     * Coeficientes negativos aparecem como "- 1.5*x", nunca como
+     * "+ -1.5*x"; coeficientes nulos são mantidos, para que cada
+     * entrada apareça na equação.
+     *
+     * Parâmetros:
+     *   feature_names - nomes das entradas; None (ou um nome por
+     *                   entrada a menos/mais) usa x1, x2, ...
+     *   precision - casas decimais dos coeficientes
+     *
+     * Retorno:
+     *   A equação, ou NotLinear se a ativação for definida pelo
+     *   usuário (não há fórmula conhecida para ela)
+     */
+    pub fn to_equation(&self, feature_names: Option<&[String]>, precision: usize) -> Result<String, NotLinear> {
+        self.activation().name().ok_or(NotLinear)?;
+        Ok(format!("y = {}", self.wrapped_terms(feature_names.unwrap_or_default(), precision)))
+    }
+
+    /*
+     * A mesma informação de to_equation em um XML mínimo no estilo
+     * PMML (RegressionModel): nomes das entradas, coeficientes,
+     * intercepto e a função de ligação (normalizationMethod "none"
+     * para identity, "logit" para sigmoid). Os valores são gravados
+     * com precisão completa.
+     *
+     * Parâmetros:
+     *   feature_names - nomes das entradas, como em to_equation
+     *
+     * Retorno:
+     *   O documento XML, ou NotLinear se a ativação for definida pelo
+     *   usuário
+     */
+    pub fn to_pmml(&self, feature_names: Option<&[String]>) -> Result<String, NotLinear> {
+        let link = match self.activation() {
+            Activation::Identity => "none",
+            Activation::Sigmoid => "logit",
+            Activation::Custom(_) | Activation::Dyn(_) => return Err(NotLinear),
+        };
+        let names = self.feature_names(feature_names.unwrap_or_default());
+        let intercept = if self.use_bias() { self.bias() } else { T::zero() };
+
+        let mut xml = String::new();
+        xml.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        xml.push_str("<PMML version=\"4.4\" xmlns=\"http://www.dmg.org/PMML-4_4\">\n");
+        xml.push_str("  <DataDictionary>\n");
+        for name in &names {
+            xml.push_str(&format!("    <DataField name=\"{}\" optype=\"continuous\" dataType=\"double\"/>\n", xml_escape(name)));
+        }
+        xml.push_str("    <DataField name=\"y\" optype=\"continuous\" dataType=\"double\"/>\n");
+        xml.push_str("  </DataDictionary>\n");
+        xml.push_str(&format!("  <RegressionModel functionName=\"regression\" normalizationMethod=\"{}\">\n", link));
+        xml.push_str("    <MiningSchema>\n");
+        for name in &names {
+            xml.push_str(&format!("      <MiningField name=\"{}\"/>\n", xml_escape(name)));
+        }
+        xml.push_str("      <MiningField name=\"y\" usageType=\"target\"/>\n");
+        xml.push_str("    </MiningSchema>\n");
+        xml.push_str(&format!("    <RegressionTable intercept=\"{}\">\n", intercept));
+        for (name, weight) in names.iter().zip(self.weights()) {
+            xml.push_str(&format!(
+                "      <NumericPredictor name=\"{}\" exponent=\"1\" coefficient=\"{}\"/>\n",
+                xml_escape(name),
+                weight
+            ));
+        }
+        xml.push_str("    </RegressionTable>\n");
+        xml.push_str("  </RegressionModel>\n");
+        xml.push_str("</PMML>\n");
+        Ok(xml)
+    }
+
+    /*
+     * Grava o XML gerado por to_pmml.
+     *
+     * Parâmetros:
+     *   path - caminho do arquivo (ex: "modelo.pmml")
+     *   feature_names - nomes das entradas, ou None
+     */
+    pub fn export_pmml(&self, path: impl AsRef<Path>, feature_names: Option<&[String]>) -> Result<(), ModelIoError> {
+        let xml = self.to_pmml(feature_names).map_err(|_| unserializable_activation())?;
+        write_file(path.as_ref(), xml)?;
+        Ok(())
+    }
+
+    /*
+     * Nomes das entradas: feature_names se houver um por entrada,
+     * senão x1, x2, ...
+     */
+    fn feature_names(&self, feature_names: &[String]) -> Vec<String> {
+        if feature_names.len() == self.n_connections() {
+            feature_names.to_vec()
+        } else {
+            (1..=self.n_connections()).map(|i| format!("x{}", i)).collect()
+        }
+    }
+
+    /*
+     * Lado direito da equação, dentro da ativação quando ela não é
+     * identity (ex: "sigmoid(1.5*x1 - 0.25)").
+     */
+    fn wrapped_terms(&self, feature_names: &[String], precision: usize) -> String {
+        let mut equation = String::new();
+        for (i, (weight, name)) in self.weights().iter().zip(self.feature_names(feature_names)).enumerate() {
+            let negative = *weight < T::zero();
+            if i == 0 {
+                equation.push_str(&format!("{}{:.*}*{}", if negative { "-" } else { "" }, precision, weight.abs(), name));
+            } else {
+                equation.push_str(&format!(" {} {:.*}*{}", if negative { "-" } else { "+" }, precision, weight.abs(), name));
+            }
+        }
+        if self.use_bias() {
+            let sign = if self.bias() < T::zero() { "-" } else { "+" };
+            if equation.is_empty() {
+                equation.push_str(&format!("{}{:.*}", if sign == "-" { "-" } else { "" }, precision, self.bias().abs()));
+            } else {
+                equation.push_str(&format!(" {} {:.*}", sign, precision, self.bias().abs()));
+            }
+        }
+        match self.activation() {
+            Activation::Identity => equation,
+            activation => format!("{}({})", activation.name().unwrap_or("f"), equation),
+        }
+    }
+}
+
+/*
+ * Erro de Neuron::to_equation e Neuron::to_pmml: a ativação é
+ * definida pelo usuário, então o modelo não tem uma fórmula conhecida.
+ */
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NotLinear;
+
+impl fmt::Display for NotLinear {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "a ativação é definida pelo usuário e não tem uma equação conhecida")
+    }
+}
+
+impl std::error::Error for NotLinear {}
+
+/*
+ * Escapa um texto para um valor de atributo XML.
+ */
+fn xml_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+/*
+ * Opções de Neuron::to_dot.
+ *
+ * Campos:
+ *   precision - casas decimais dos pesos e do bias nos rótulos
+ *   weight_labels - se true, cada aresta mostra o seu peso (desative
+ *                   para modelos com muitas entradas)
+ *   color_by_sign - se true, arestas de pesos positivos são azuis e
+ *                   de pesos negativos vermelhas, com espessura
+ *                   proporcional ao módulo do peso
+ */
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DotOptions {
+    pub precision: usize,
+    pub weight_labels: bool,
+    pub color_by_sign: bool,
+}
+
+impl Default for DotOptions {
+    fn default() -> Self {
+        Self {
+            precision: 4,
+            weight_labels: true,
+            color_by_sign: true,
+        }
+    }
+}
+
+impl<T: Scalar> Neuron<T> {
+
+    /*
+     * Gera um grafo no formato DOT do Graphviz: um nó por entrada, um
+     * nó para o neurônio (com a ativação e o bias) e uma aresta por
+     * peso. Para gerar a imagem:
+     *   dot -Tpng neuronio.dot -o neuronio.png
+     *
+     * Parâmetros:
+     *   options - precisão e estilo dos rótulos (ver DotOptions)
+     *
+     * Retorno:
+     *   O texto do grafo
+     */
+    pub fn to_dot(&self, options: DotOptions) -> String {
+        let precision = options.precision;
+        let max_abs = self.weights().iter().fold(T::zero(), |max, weight| max.max(weight.abs()));
+
+        let mut dot = String::from("digraph neuron {\n    rankdir=LR;\n");
+        for i in 1..=self.n_connections() {
+            dot.push_str(&format!("    x{} [shape=circle, label=\"x{}\"];\n", i, i));
+        }
+        let activation = self.activation().name().unwrap_or("custom");
+        let label = if self.use_bias() {
+            format!("{}\\nb = {:.*}", activation, precision, self.bias())
+        } else {
+            activation.to_string()
+        };
+        dot.push_str(&format!("    out [shape=doublecircle, label=\"{}\"];\n", label));
+
+        for (i, &weight) in self.weights().iter().enumerate() {
+            let mut attrs = Vec::new();
+            if options.weight_labels {
+                attrs.push(format!("label=\"{:.*}\"", precision, weight));
+            }
+            if options.color_by_sign {
+                let color = if weight < T::zero() { "red" } else { "blue" };
+                let scale = if max_abs > T::zero() {
+                    (weight.abs() / max_abs).to_f64().unwrap_or(0.0)
+                } else {
+                    0.0
+                };
+                attrs.push(format!("color={}", color));
+                attrs.push(format!("penwidth={:.2}", 1.0 + 2.0 * scale));
+            }
+            if attrs.is_empty() {
+                dot.push_str(&format!("    x{} -> out;\n", i + 1));
+            } else {
+                dot.push_str(&format!("    x{} -> out [{}];\n", i + 1, attrs.join(", ")));
+            }
+        }
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+/*
+ * Versão atual do formato JSON gravado por Neuron::save.
+ *
+ * Histórico:
+ *   0 - save_json: o neurônio direto, sem format_version
+ *   1 - o neurônio direto, com "format_version": 1
+ *   2 - envelope {"format_version", "kind", "model"}, para que outros
+ *       tipos de modelo possam ser gravados no mesmo formato
+ */
+pub const FORMAT_VERSION: u64 = 2;
+
+/*
+ * Envelope de um arquivo de modelo: o tipo do modelo gravado.
+ *
+ * Variantes:
+ *   Neuron(Neuron) - um neurônio
+ *
+ * load reconhece o formato binário pelo cabeçalho "CEPT" e, com a
+ * feature "serde", o JSON de qualquer versão até FORMAT_VERSION,
+ * migrando-o para a versão atual antes de ler o modelo.
+ */
+pub enum ModelFile<T: 'static = Float> {
+    Neuron(Neuron<T>),
+}
+
+impl<T: Scalar> ModelFile<T> {
+
+    /*
+     * Lê um arquivo de modelo (binário, JSON ou MessagePack).
+     *
+     * Parâmetros:
+     *   path - caminho do arquivo
+     *
+     * Retorno:
+     *   O modelo, ou ModelIoError (VersionTooNew para arquivos de uma
+     *   versão mais nova do formato)
+     */
+    #[cfg(feature = "serde")]
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, ModelIoError>
+    where
+        T: for<'de> Deserialize<'de>,
+    {
+        let bytes = read_model_bytes(path.as_ref())?;
+        if bytes.starts_with(MAGIC) {
+            return Self::from_bytes(&bytes);
+        }
+        // Um documento MessagePack começa com um mapa (0x80-0x8f,
+        // 0xde ou 0xdf); JSON começa com texto
+        if let Some(0x80..=0x8f | 0xde | 0xdf) = bytes.first() {
+            return Self::from_msgpack_slice(&bytes);
+        }
+        Self::from_json_slice(&bytes)
+    }
+
+    /*
+     * Lê um arquivo de modelo binário (sem a feature "serde", JSON
+     * não é suportado).
+     */
+    #[cfg(not(feature = "serde"))]
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, ModelIoError> {
+        Self::from_bytes(&read_model_bytes(path.as_ref())?)
+    }
+
+    /*
+     * Decodifica um modelo no formato binário (ver Neuron::to_bytes).
+     */
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ModelIoError> {
+        Ok(ModelFile::Neuron(Neuron::from_bytes(bytes)?))
+    }
+
+    /*
+     * Decodifica um modelo em JSON, migrando versões antigas.
+     *
+     * Parâmetros:
+     *   json - conteúdo do arquivo
+     *
+     * Retorno:
+     *   O modelo, ou ModelIoError: Json se o documento for inválido,
+     *   UnsupportedVersion se format_version não for um inteiro, ou
+     *   VersionTooNew se for maior que FORMAT_VERSION
+     */
+    #[cfg(feature = "serde")]
+    pub fn from_json_slice(json: &[u8]) -> Result<Self, ModelIoError>
+    where
+        T: for<'de> Deserialize<'de>,
+    {
+        Self::from_document(serde_json::from_slice(json)?)
+    }
+
+    /*
+     * Decodifica um modelo em MessagePack (ver from_json_slice).
+     */
+    #[cfg(feature = "serde")]
+    pub fn from_msgpack_slice(bytes: &[u8]) -> Result<Self, ModelIoError>
+    where
+        T: for<'de> Deserialize<'de>,
+    {
+        Self::from_document(rmp_serde::from_slice(bytes)?)
+    }
+
+    /*
+     * Lê o modelo de um documento já decodificado (JSON ou
+     * MessagePack), migrando versões antigas.
+     */
+    #[cfg(feature = "serde")]
+    fn from_document(mut document: serde_json::Value) -> Result<Self, ModelIoError>
+    where
+        T: for<'de> Deserialize<'de>,
+    {
+        let version = match document.get("format_version") {
+            None => 0,
+            Some(version) => version
+                .as_u64()
+                .ok_or(ModelIoError::UnsupportedVersion { found: None, supported: FORMAT_VERSION })?,
+        };
+        if version > FORMAT_VERSION {
+            return Err(ModelIoError::VersionTooNew { found: version, supported: FORMAT_VERSION });
+        }
+
+        // Versões 0 e 1 gravavam o neurônio direto no documento
+        if version < 2 {
+            if let serde_json::Value::Object(fields) = &mut document {
+                fields.remove("format_version");
+            }
+            document = serde_json::json!({
+                "format_version": FORMAT_VERSION,
+                "kind": "neuron",
+                "model": document,
+            });
+        }
+
+        let kind = document
+            .get("kind")
+            .and_then(serde_json::Value::as_str)
+            .unwrap_or("")
+            .to_string();
+        match kind.as_str() {
+            "neuron" => {
+                let model = document
+                    .get_mut("model")
+                    .map(serde_json::Value::take)
+                    .unwrap_or_default();
+                Ok(ModelFile::Neuron(serde_json::from_value(model)?))
+            }
+            _ => Err(ModelIoError::Json(serde::de::Error::custom(format!(
+                "tipo de modelo desconhecido: \"{}\"",
+                kind
+            )))),
+        }
+    }
+
+    /*
+     * Lê os metadados gravados ao lado de um arquivo de modelo (ver
+     * ModelMetadata).
+     *
+     * Parâmetros:
+     *   path - caminho do arquivo do modelo (não dos metadados)
+     *
+     * Retorno:
+     *   Some(metadados), None se o modelo não tiver metadados, ou
+     *   ModelIoError se o arquivo de metadados for inválido
+     */
+    #[cfg(feature = "serde")]
+    pub fn metadata(path: impl AsRef<Path>) -> Result<Option<ModelMetadata>, ModelIoError> {
+        ModelMetadata::load(path)
+    }
+
+    /*
+     * Codifica um neurônio como JSON na versão atual do formato.
+     * Falha com UnserializableActivation se a ativação for definida
+     * pelo usuário.
+     */
+    #[cfg(feature = "serde")]
+    pub fn to_json_string(neuron: &Neuron<T>) -> Result<String, ModelIoError>
+    where
+        T: Serialize,
+    {
+        Ok(serde_json::to_string_pretty(&Self::document(neuron)?)?)
+    }
+
+    /*
+     * Codifica um neurônio em MessagePack, com o mesmo documento de
+     * to_json_string.
+     */
+    #[cfg(feature = "serde")]
+    pub fn to_msgpack(neuron: &Neuron<T>) -> Result<Vec<u8>, ModelIoError>
+    where
+        T: Serialize,
+    {
+        Ok(rmp_serde::to_vec(&Self::document(neuron)?)?)
+    }
+
+    /*
+     * Envelope da versão atual do formato para um neurônio.
+     */
+    #[cfg(feature = "serde")]
+    fn document(neuron: &Neuron<T>) -> Result<serde_json::Value, ModelIoError>
+    where
+        T: Serialize,
+    {
+        neuron.activation().name().ok_or_else(unserializable_activation)?;
+        Ok(serde_json::json!({
+            "format_version": FORMAT_VERSION,
+            "kind": "neuron",
+            "model": serde_json::to_value(neuron)?,
+        }))
+    }
+}
+
+/*
+ * Como um modelo foi treinado, gravado por fit ao lado do modelo
+ * (ver TrainConfig::save_path) para que o experimento possa ser
+ * reproduzido.
+ *
+ * Campos:
+ *   library_version - versão da biblioteca que treinou o modelo
+ *   model_fingerprint - Neuron::fingerprint do modelo gravado
+ *   config - a configuração de treinamento (inclusive a semente)
+ *   dataset - identificação dos dados de treino
+ *   history - (época, custo após a época), possivelmente reduzido
+ *             (ver TrainConfig::history_points)
+ *
+ * Os números são gravados em f64, qualquer que seja a precisão do
+ * modelo.
+ */
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ModelMetadata {
+    pub library_version: String,
+    pub model_fingerprint: u64,
+    pub config: TrainConfig<f64>,
+    pub dataset: DatasetFingerprint,
+    pub history: Vec<(usize, f64)>,
+}
+
+#[cfg(feature = "serde")]
+impl ModelMetadata {
+
+    /*
+     * Grava os metadados em JSON ao lado do modelo (ver
+     * metadata_path).
+     *
+     * Parâmetros:
+     *   model_path - caminho do arquivo do modelo
+     */
+    pub fn save(&self, model_path: impl AsRef<Path>) -> Result<(), ModelIoError> {
+        write_file(&metadata_path(model_path.as_ref()), serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /*
+     * Lê os metadados de um modelo.
+     *
+     * Parâmetros:
+     *   model_path - caminho do arquivo do modelo
+     *
+     * Retorno:
+     *   Some(metadados), None se o arquivo de metadados não existir,
+     *   ou ModelIoError se ele não puder ser lido
+     */
+    pub fn load(model_path: impl AsRef<Path>) -> Result<Option<Self>, ModelIoError> {
+        match read_file(&metadata_path(model_path.as_ref())) {
+            Ok(bytes) => Ok(Some(serde_json::from_slice(&bytes)?)),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(err.into()),
+        }
+    }
+}
+
+/*
+ * Caminho dos metadados de um modelo: a extensão do arquivo do
+ * modelo trocada por ".meta.json" (ex: modelo.cept ->
+ * modelo.meta.json).
+ */
+pub fn metadata_path(model_path: &Path) -> PathBuf {
+    model_path.with_extension("meta.json")
+}
+
+/*
+ * Reduz um histórico de custo a no máximo max_points pontos
+ * igualmente espaçados, sempre mantendo o primeiro e o último.
+ *
+ * Parâmetros:
+ *   history - (época, custo) de cada época
+ *   max_points - número máximo de pontos (valores menores que 2
+ *                mantêm só o primeiro e o último)
+ *
+ * Retorno:
+ *   Os pontos escolhidos, na ordem original
+ */
+pub fn downsample_history<T: Copy>(history: &[(usize, T)], max_points: usize) -> Vec<(usize, T)> {
+    let max_points = max_points.max(2);
+    if history.len() <= max_points {
+        return history.to_vec();
+    }
+    let last = history.len() - 1;
+    (0..max_points)
+        .map(|k| history[k * last / (max_points - 1)])
+        .collect()
+}
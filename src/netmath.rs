@@ -6,9 +6,68 @@
  * Este módulo implementa:
  *   - Funções de ativação (identidade, sigmoid)
  *   - Funções de custo (MSE - Mean Squared Error)
+ *   - O enum Activation, que identifica a função de ativação pelo nome
+ *   - O trait ActivationFn, para ativações definidas fora do crate,
+ *     com parâmetros próprios, derivada e nome; registradas com
+ *     register_activation, elas podem ser gravadas e lidas pelo nome
+ *   - O trait Scalar, que permite usar f32 ou f64 em todo o projeto
+ *   - O tipo Float, precisão padrão dos tipos genéricos (f32, ou f64
+ *     com a feature "f64")
  */
 
+#[cfg(feature = "std")]
+use core::any::Any;
+use core::fmt;
+#[cfg(feature = "std")]
+use std::sync::Mutex;
+
+#[cfg(not(feature = "std"))]
+use alloc::boxed::Box;
+
 use num::pow;
+use num::traits::NumAssign;
+use rand::distributions::uniform::SampleUniform;
+
+/*
+ * Tipo numérico de ponto flutuante usado por neurônios, custos e
+ * treinamento.
+ *
+ * Implementado automaticamente para f32 e f64 (qualquer tipo que
+ * seja num::Float, aceite operadores compostos como += e que o
+ * rand saiba sortear).
+ */
+pub trait Scalar: num::Float + NumAssign + SampleUniform + fmt::Debug + fmt::Display + 'static {}
+
+impl<T> Scalar for T where T: num::Float + NumAssign + SampleUniform + fmt::Debug + fmt::Display + 'static {}
+
+/*
+ * Precisão padrão do crate: o tipo usado por Neuron, Dataset,
+ * TrainConfig e os demais tipos genéricos quando T não é informado.
+ *
+ * É f32; com a feature "f64", passa a ser f64, para quem precisa de
+ * precisão dupla sem escrever Neuron<f64> em todo lugar. Neuron32 e
+ * Neuron64 continuam fixos.
+ */
+#[cfg(not(feature = "f64"))]
+pub type Float = f32;
+#[cfg(feature = "f64")]
+pub type Float = f64;
+
+/*
+ * Converte uma constante f64 para o tipo numérico escolhido.
+ *
+ * Parâmetros:
+ *   x - constante a ser convertida
+ *
+ * Retorno:
+ *   O valor de x no tipo T (arredondado se T for f32)
+ */
+pub fn from_f64<T: Scalar>(x: f64) -> T {
+    match T::from(x) {
+        Some(value) => value,
+        None => panic!("from_f64: {} não pode ser representado", x),
+    }
+}
 
 /*
  * Função de ativação Identidade.
@@ -23,8 +82,7 @@ use num::pow;
  * Retorno:
  *   O próprio valor x (f(x) = x)
  */
-#[allow(dead_code)]
-pub fn ident(x: f32) -> f32 {
+pub fn ident<T: Scalar>(x: T) -> T {
     x
 }
 
@@ -47,9 +105,283 @@ pub fn ident(x: f32) -> f32 {
  * Retorno:
  *   Valor entre 0 e 1 representando a probabilidade de ativação
  */
-#[allow(dead_code)]
-pub fn sigmoid(x: f32) -> f32 {
-    1.0 / (1.0 + (-x).exp())
+pub fn sigmoid<T: Scalar>(x: T) -> T {
+    T::one() / (T::one() + (-x).exp())
+}
+
+/*
+ * Cópia de uma ActivationFn em uma nova Box, para que
+ * Box<dyn ActivationFn<T>> (e, portanto, Activation e Neuron) possa
+ * ser clonada.
+ *
+ * Implementado automaticamente para todo tipo ActivationFn que seja
+ * Clone; basta `#[derive(Clone)]` na ativação.
+ */
+pub trait CloneBoxed<T> {
+    fn clone_boxed(&self) -> Box<dyn ActivationFn<T>>;
+}
+
+impl<T, F> CloneBoxed<T> for F
+where
+    F: ActivationFn<T> + Clone + 'static,
+{
+    fn clone_boxed(&self) -> Box<dyn ActivationFn<T>> {
+        Box::new(self.clone())
+    }
+}
+
+impl<T> Clone for Box<dyn ActivationFn<T>> {
+    fn clone(&self) -> Self {
+        self.clone_boxed()
+    }
+}
+
+/*
+ * Função de ativação definida pelo usuário, com parâmetros próprios.
+ *
+ * Um tipo que implementa ActivationFn é usado por um neurônio pela
+ * variante Activation::Dyn, que é dona da ativação (uma
+ * Box<dyn ActivationFn<T>>): parâmetros decididos em tempo de
+ * execução não precisam de um `static`.
+ *
+ * Métodos:
+ *   apply - f(x)
+ *   derivative - f'(x)
+ *   name - nome usado na gravação do modelo; para que o modelo possa
+ *          ser lido de volta, a ativação deve ser registrada com
+ *          register_activation
+ */
+pub trait ActivationFn<T = Float>: CloneBoxed<T> + Send + Sync {
+    fn apply(&self, x: T) -> T;
+    fn derivative(&self, x: T) -> T;
+    fn name(&self) -> &str;
+}
+
+/*
+ * Função de ativação de um neurônio.
+ *
+ * Variantes:
+ *   Identity - f(x) = x (ver ident)
+ *   Sigmoid - σ(x) = 1/(1 + e⁻ˣ) (ver sigmoid)
+ *   Custom(fn) - função definida pelo usuário; funciona normalmente
+ *                no cálculo da saída, mas não pode ser serializada
+ *                (não tem nome)
+ *   Dyn(f) - ativação definida pelo usuário com o trait ActivationFn;
+ *            gravada pelo nome (ver register_activation)
+ *
+ * Ao contrário de um ponteiro de função, as variantes nomeadas
+ * podem ser comparadas, impressas e gravadas em arquivo.
+ */
+#[derive(Clone)]
+pub enum Activation<T: 'static = Float> {
+    Identity,
+    Sigmoid,
+    Custom(fn(T) -> T),
+    Dyn(Box<dyn ActivationFn<T>>),
+}
+
+impl<T: Scalar> Activation<T> {
+
+    /*
+     * Aplica a função de ativação.
+     *
+     * Parâmetros:
+     *   x - valor de entrada (soma ponderada + bias)
+     *
+     * Retorno:
+     *   O valor ativado
+     */
+    pub fn apply(&self, x: T) -> T {
+        match self {
+            Activation::Identity => ident(x),
+            Activation::Sigmoid => sigmoid(x),
+            Activation::Custom(act_func) => act_func(x),
+            Activation::Dyn(act_func) => act_func.apply(x),
+        }
+    }
+
+    /*
+     * Calcula a derivada da função de ativação em x. Para Custom, que
+     * não tem derivada conhecida, usa diferenças finitas.
+     */
+    pub fn derivative(&self, x: T) -> T {
+        match self {
+            Activation::Identity => T::one(),
+            Activation::Sigmoid => {
+                let s = sigmoid(x);
+                s * (T::one() - s)
+            }
+            Activation::Custom(act_func) => {
+                let eps: T = from_f64(0.0001);
+                (act_func(x + eps) - act_func(x)) / eps
+            }
+            Activation::Dyn(act_func) => act_func.derivative(x),
+        }
+    }
+
+    /*
+     * Cria uma Activation::Dyn com a ativação fornecida.
+     *
+     * Parâmetros:
+     *   act_func - a ativação (ex: LeakyRelu { slope: 0.25 })
+     */
+    pub fn from_fn(act_func: impl ActivationFn<T> + 'static) -> Self {
+        Activation::Dyn(Box::new(act_func))
+    }
+
+    /*
+     * Retorna o nome da ativação ("identity", "sigmoid" ou o nome de
+     * uma ActivationFn), ou None para Custom.
+     */
+    pub fn name(&self) -> Option<&str> {
+        match self {
+            Activation::Identity => Some("identity"),
+            Activation::Sigmoid => Some("sigmoid"),
+            Activation::Custom(_) => None,
+            Activation::Dyn(act_func) => Some(act_func.name()),
+        }
+    }
+
+    /*
+     * Busca uma ativação nomeada.
+     *
+     * Parâmetros:
+     *   name - nome da ativação ("identity", "sigmoid" ou o de uma
+     *          ativação registrada com register_activation)
+     *
+     * Retorno:
+     *   A ativação correspondente, ou None se o nome for desconhecido
+     */
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "identity" => Some(Activation::Identity),
+            "sigmoid" => Some(Activation::Sigmoid),
+            _ => registered_activation(name).map(Activation::Dyn),
+        }
+    }
+}
+
+/*
+ * As ativações embutidas também implementam ActivationFn, para que
+ * código genérico trate todas da mesma forma. Custom se chama
+ * "custom".
+ */
+impl<T: Scalar> ActivationFn<T> for Activation<T> {
+    fn apply(&self, x: T) -> T {
+        Activation::apply(self, x)
+    }
+
+    fn derivative(&self, x: T) -> T {
+        Activation::derivative(self, x)
+    }
+
+    fn name(&self) -> &str {
+        Activation::name(self).unwrap_or("custom")
+    }
+}
+
+/*
+ * Ativações registradas com register_activation. Cada elemento é uma
+ * Box<dyn ActivationFn<T>> (f32 ou f64) guardada como Any, pois um
+ * static não pode depender de T; from_name devolve uma cópia. Requer
+ * a feature "std" (Mutex); sem ela não há registro e from_name só
+ * conhece as ativações do crate.
+ */
+#[cfg(feature = "std")]
+static REGISTRY: Mutex<Vec<Box<dyn Any + Send>>> = Mutex::new(Vec::new());
+
+/*
+ * Registra uma ActivationFn para que Activation::from_name (e,
+ * portanto, a leitura de modelos gravados) a encontre pelo nome.
+ * Registrar de novo o mesmo nome substitui a ativação anterior;
+ * os nomes "identity" e "sigmoid" são reservados e ignorados.
+ *
+ * Parâmetros:
+ *   act_func - a ativação; o registro guarda uma cópia (ver
+ *              CloneBoxed)
+ */
+#[cfg(feature = "std")]
+pub fn register_activation<T: Scalar>(act_func: &dyn ActivationFn<T>) {
+    let mut registry = REGISTRY.lock().unwrap_or_else(|err| err.into_inner());
+    registry.retain(|entry| {
+        entry
+            .downcast_ref::<Box<dyn ActivationFn<T>>>()
+            .is_none_or(|registered| registered.name() != act_func.name())
+    });
+    registry.push(Box::new(act_func.clone_boxed()));
+}
+
+/*
+ * Busca no registro uma ativação pelo nome.
+ */
+#[cfg(feature = "std")]
+fn registered_activation<T: Scalar>(name: &str) -> Option<Box<dyn ActivationFn<T>>> {
+    let registry = REGISTRY.lock().unwrap_or_else(|err| err.into_inner());
+    registry
+        .iter()
+        .filter_map(|entry| entry.downcast_ref::<Box<dyn ActivationFn<T>>>())
+        .find(|act_func| act_func.name() == name)
+        .cloned()
+}
+
+#[cfg(not(feature = "std"))]
+fn registered_activation<T: Scalar>(_name: &str) -> Option<Box<dyn ActivationFn<T>>> {
+    None
+}
+
+impl<T> fmt::Debug for Activation<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Activation::Identity => write!(f, "Identity"),
+            Activation::Sigmoid => write!(f, "Sigmoid"),
+            Activation::Custom(act_func) => f.debug_tuple("Custom").field(act_func).finish(),
+            Activation::Dyn(act_func) => f.debug_tuple("Dyn").field(&act_func.name()).finish(),
+        }
+    }
+}
+
+/*
+ * Compatibilidade com o antigo campo fn(f32) -> f32: os ponteiros
+ * para ident e sigmoid viram as variantes nomeadas, e qualquer
+ * outra função vira Custom.
+ *
+ * A conversão compara endereços de função, o que é só uma
+ * aproximação: o compilador pode dar endereços diferentes à mesma
+ * função (em unidades de código distintas) ou o mesmo endereço a
+ * funções diferentes com código idêntico. Se ident ou sigmoid não
+ * forem reconhecidos, o resultado é Custom, que calcula a mesma
+ * saída mas não pode ser gravado. Nenhuma gravação depende dessa
+ * comparação: os formatos usam só o nome da variante. Código novo
+ * deve usar Activation::Identity e Activation::Sigmoid diretamente.
+ */
+impl<T: Scalar> From<fn(T) -> T> for Activation<T> {
+    fn from(act_func: fn(T) -> T) -> Self {
+        if core::ptr::fn_addr_eq(act_func, ident::<T> as fn(T) -> T) {
+            Activation::Identity
+        } else if core::ptr::fn_addr_eq(act_func, sigmoid::<T> as fn(T) -> T) {
+            Activation::Sigmoid
+        } else {
+            Activation::Custom(act_func)
+        }
+    }
+}
+
+/*
+ * Duas Dyn são iguais se tiverem o mesmo nome, que é o que identifica
+ * a ativação na gravação (ver register_activation). Duas Custom são
+ * comparadas pelo endereço da função, com as mesmas ressalvas de
+ * From<fn(T) -> T>: o resultado é aproximado.
+ */
+impl<T> PartialEq for Activation<T> {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Activation::Identity, Activation::Identity) => true,
+            (Activation::Sigmoid, Activation::Sigmoid) => true,
+            (Activation::Custom(a), Activation::Custom(b)) => core::ptr::fn_addr_eq(*a, *b),
+            (Activation::Dyn(a), Activation::Dyn(b)) => a.name() == b.name(),
+            _ => false,
+        }
+    }
 }
 
 /*
@@ -64,15 +396,15 @@ pub fn sigmoid(x: f32) -> f32 {
  *   O erro quadrático médio entre os valores esperados e preditos
  */
 
-pub fn mse(
-    out_true: &[f32], 
-    out_pred: &[f32], 
+pub fn mse<T: Scalar>(
+    out_true: &[T], 
+    out_pred: &[T], 
     sample_size: usize
-) -> f32 {
-    let mut sum_squared_errors = 0.0;
+) -> T {
+    let mut sum_squared_errors = T::zero();
 
     for i in 0..sample_size {
         sum_squared_errors += pow(out_pred[i] - out_true[i], 2);
     }
-    sum_squared_errors / sample_size as f32
+    sum_squared_errors / from_f64(sample_size as f64)
 }
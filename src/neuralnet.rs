@@ -5,11 +5,45 @@
  *
  * Este módulo implementa:
  *   - Cálculo de custo total do neurônio
- *   - Cálculo de gradientes por diferenças finitas
+ *   - Variantes com prefixo try_ (try_compute_cost, try_train, ...)
+ *     que conferem as amostras antes de calcular e retornam
+ *     CeptronError em vez de entrar em pânico
+ *   - Variantes que recebem um Dataset (compute_cost_on, train_on,
+ *     fit_on), dispensando o parâmetro sample_size
+ *   - Cálculo de gradientes por diferenças finitas, de qualquer
+ *     parâmetro (compute_gradient), para laços de treinamento
+ *     próprios
  *   - Algoritmo de treinamento por gradiente descendente
+ *   - Treinamento online, uma amostra por vez (partial_fit)
+ *   - Detecção de divergência (NaN/infinito) durante o treinamento
+ *   - Registro do treinamento pela fachada log (feature "log"):
+ *     resumos de época em info ("epoch=120 cost=0.0123 lr=0.001"),
+ *     início e fim de fit em debug, gradientes em trace e
+ *     divergência em warn; o usuário escolhe o logger e o nível
+ *   - O trait Model, que unifica fit/predict de Neuron e Pipeline
+ *   - Validação cruzada k-fold, simples ou estratificada, para
+ *     qualquer Model
+ *   - Classificação multiclasse um-contra-todos (one-vs-rest)
  */
 
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use rand::seq::SliceRandom;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::data::{find_nan, group_by_label, quote_csv_field, Column, DataError, DataIssue, Dataset};
+#[cfg(feature = "serde")]
+use crate::data::DatasetFingerprint;
+use crate::error::CeptronError;
+use crate::modelio::{write_file, Checkpoint, ModelIoError};
+use crate::modelio::downsample_history;
+#[cfg(feature = "serde")]
+use crate::modelio::ModelMetadata;
 use crate::neuron::*;
+use crate::netmath::{from_f64, mse, Activation, Float, Scalar};
+use crate::utils::{default_rng, derive_seed, rng_from, seeded_rng};
 
 /*
  * Calcula o custo total do neurônio para um conjunto de amostras.
@@ -24,34 +58,206 @@ use crate::neuron::*;
  * Retorno:
  *   O custo calculado pela função de custo fornecida
  */
+#[deprecated(note = "use try_compute_cost, que retorna CeptronError em vez de entrar em pânico")]
+pub fn compute_cost<T: Scalar>(
+    neuron: &Neuron<T>, 
+    x: &[Vec<T>], 
+    y: &[T], 
+    cost: fn(&[T], &[T], usize) -> T, 
+    sample_size: usize
+) -> T {
+    let out_pred = match neuron.predict_batch(&x[..sample_size]) {
+        Ok(out_pred) => out_pred,
+        Err(err) => panic!("compute_cost: {}", err),
+    };
+    cost(y, &out_pred, sample_size)
+}
+
+/*
+ * Confere as amostras recebidas pelas variantes try_: sample_size
+ * amostras em x e em y, uma entrada por conexão em cada linha e
+ * todos os valores finitos.
+ *
+ * Retorno:
+ *   Ok(()), ou CeptronError: Data(DataError::Empty) se sample_size
+ *   for 0, InvalidConfig se x ou y tiverem menos de sample_size
+ *   amostras, DimensionMismatch (com a linha) para uma linha de
+ *   tamanho errado, InvalidData para NaN ou infinito
+ */
+fn check_samples<T: Scalar>(neuron: &Neuron<T>, x: &[Vec<T>], y: &[T], sample_size: usize) -> Result<(), CeptronError> {
+    if sample_size == 0 {
+        return Err(DataError::Empty.into());
+    }
+    if x.len() < sample_size || y.len() < sample_size {
+        return Err(CeptronError::InvalidConfig(format!(
+            "sample_size = {}, mas há {} entradas e {} saídas esperadas",
+            sample_size,
+            x.len(),
+            y.len()
+        )));
+    }
+    let expected = neuron.n_connections();
+    if let Some((row, sample)) = x[..sample_size].iter().enumerate().find(|(_, sample)| sample.len() != expected) {
+        return Err(CeptronError::DimensionMismatch { expected, actual: sample.len(), row: Some(row) });
+    }
+    for (row, (sample, target)) in x[..sample_size].iter().zip(y).enumerate() {
+        let column = match sample.iter().position(|value| !value.is_finite()) {
+            Some(j) => Column::Input(j),
+            None if !target.is_finite() => Column::Target,
+            None => continue,
+        };
+        let value = match column {
+            Column::Input(j) => sample[j],
+            Column::Target => *target,
+        };
+        return Err(CeptronError::InvalidData(if value.is_nan() {
+            DataIssue::NaN { row, column }
+        } else {
+            DataIssue::Infinite { row, column }
+        }));
+    }
+    Ok(())
+}
 
-pub fn compute_cost(
-    neuron: &Neuron, 
-    x: &Vec<Vec<f32>>, 
-    y: &[f32], 
-    cost: fn(&[f32], &[f32], usize) -> f32, 
+/*
+ * Calcula o custo total do neurônio (ver compute_cost), conferindo
+ * as amostras antes (ver check_samples).
+ *
+ * Retorno:
+ *   O custo, ou CeptronError se as amostras forem inválidas
+ */
+pub fn try_compute_cost<T: Scalar>(
+    neuron: &Neuron<T>,
+    x: &[Vec<T>],
+    y: &[T],
+    cost: fn(&[T], &[T], usize) -> T,
     sample_size: usize
-) -> f32 {
-    let mut out_pred: Vec<f32> = Vec::new();
+) -> Result<T, CeptronError> {
+    check_samples(neuron, x, y, sample_size)?;
+    #[allow(deprecated)]
+    let total = compute_cost(neuron, x, y, cost, sample_size);
+    Ok(total)
+}
 
+/*
+ * Calcula o custo total do neurônio para um Dataset.
+ *
+ * Parâmetros:
+ *   neuron - referência ao neurônio a ser avaliado
+ *   data - amostras de entrada e saídas esperadas
+ *   cost - função de custo a ser utilizada (ex: mse)
+ *
+ * Retorno:
+ *   O custo calculado sobre todas as amostras de data
+ */
+#[deprecated(note = "use try_compute_cost_on, que retorna CeptronError em vez de entrar em pânico")]
+#[allow(deprecated)]
+pub fn compute_cost_on<T: Scalar>(
+    neuron: &Neuron<T>,
+    data: &Dataset<T>,
+    cost: fn(&[T], &[T], usize) -> T
+) -> T {
+    compute_cost(neuron, data.inputs(), data.targets(), cost, data.len())
+}
+
+/*
+ * Variante de compute_cost_on que retorna CeptronError (ex:
+ * DimensionMismatch se data tiver outro número de entradas).
+ */
+pub fn try_compute_cost_on<T: Scalar>(
+    neuron: &Neuron<T>,
+    data: &Dataset<T>,
+    cost: fn(&[T], &[T], usize) -> T
+) -> Result<T, CeptronError> {
+    try_compute_cost(neuron, data.inputs(), data.targets(), cost, data.len())
+}
+
+/*
+ * Calcula o custo do neurônio com um peso por amostra: a média
+ * ponderada do custo de cada amostra,
+ *   Σ(weights[i] * cost([y[i]], [pred[i]], 1)) / Σ(weights[i])
+ *
+ * Parâmetros:
+ *   neuron - referência ao neurônio a ser avaliado
+ *   x - vetor de vetores contendo as entradas de cada amostra
+ *   y - vetor com os valores esperados (gabarito)
+ *   weights - peso de cada amostra (ex: data::balanced_weights)
+ *   cost - função de custo por amostra (ex: mse)
+ *   sample_size - número de amostras
+ *
+ * Retorno:
+ *   O custo ponderado; com todos os pesos iguais, o mesmo que
+ *   compute_cost
+ */
+#[deprecated(note = "use try_compute_weighted_cost, que retorna CeptronError em vez de entrar em pânico")]
+pub fn compute_weighted_cost<T: Scalar>(
+    neuron: &Neuron<T>,
+    x: &[Vec<T>],
+    y: &[T],
+    weights: &[T],
+    cost: fn(&[T], &[T], usize) -> T,
+    sample_size: usize
+) -> T {
+    let out_pred = match neuron.predict_batch(&x[..sample_size]) {
+        Ok(out_pred) => out_pred,
+        Err(err) => panic!("compute_weighted_cost: {}", err),
+    };
+
+    let mut total = T::zero();
+    let mut total_weight = T::zero();
     for i in 0..sample_size {
-        out_pred.push(neuron.compute_out(&x[i]));
+        total += weights[i] * cost(&y[i..=i], &out_pred[i..=i], 1);
+        total_weight += weights[i];
     }
-    cost(y, &out_pred, sample_size)
+    total / total_weight
 }
 
 /*
- * Enum que representa o tipo de parâmetro a ser ajustado.
+ * Variante de compute_weighted_cost que confere as amostras e os
+ * pesos antes de calcular.
  *
- * Variantes:
- *   Weight(usize) - representa um peso específico pelo seu índice
- *   Bias - representa o bias do neurônio
+ * Retorno:
+ *   O custo ponderado, ou CeptronError: os erros de check_samples,
+ *   InvalidConfig se weights não tiver um peso finito e não negativo
+ *   por amostra ou se todos os pesos forem 0
  */
+pub fn try_compute_weighted_cost<T: Scalar>(
+    neuron: &Neuron<T>,
+    x: &[Vec<T>],
+    y: &[T],
+    weights: &[T],
+    cost: fn(&[T], &[T], usize) -> T,
+    sample_size: usize
+) -> Result<T, CeptronError> {
+    check_samples(neuron, x, y, sample_size)?;
+    check_weights(weights, sample_size)?;
+    #[allow(deprecated)]
+    let total = compute_weighted_cost(neuron, x, y, weights, cost, sample_size);
+    Ok(total)
+}
 
-enum ParamType {
-    Weight(usize),
-    Bias,
+/*
+ * Confere os pesos das amostras de try_compute_weighted_cost e
+ * try_train_weighted.
+ */
+fn check_weights<T: Scalar>(weights: &[T], sample_size: usize) -> Result<(), CeptronError> {
+    if weights.len() < sample_size {
+        return Err(CeptronError::InvalidConfig(format!(
+            "{} pesos para {} amostras",
+            weights.len(),
+            sample_size
+        )));
+    }
+    let weights = &weights[..sample_size];
+    if let Some(i) = weights.iter().position(|&weight| !weight.is_finite() || weight < T::zero()) {
+        return Err(CeptronError::InvalidConfig(format!("o peso da amostra {} deve ser finito e não negativo", i)));
+    }
+    if weights.iter().all(|&weight| weight == T::zero()) {
+        return Err(CeptronError::InvalidConfig("todos os pesos das amostras são 0".to_string()));
+    }
+    Ok(())
 }
+
 /*
  * Calcula o gradiente de um parâmetro usando diferenças finitas.
  *
@@ -63,39 +269,77 @@ enum ParamType {
  *   cost - função de custo a ser utilizada (ex: mse)
  *   x - vetor de vetores contendo as entradas de cada amostra
  *   y - vetor com os valores esperados (gabarito)
+ *   weights - peso de cada amostra (None = custo sem pesos,
+ *             Some = compute_weighted_cost)
  *   param - tipo do parâmetro a ser derivado (Weight ou Bias)
  *   sample_size - número de amostras
  *
  * Retorno:
  *   O gradiente (derivada parcial) do parâmetro em relação ao custo
+ *
+ * O neurônio volta aos parâmetros originais ao final. Entra em
+ * pânico se param não existir no neurônio (ver Neuron::params).
  */
-fn compute_gradient(
-    neuron: &mut Neuron, 
-    cost: fn(&[f32], &[f32], usize) -> f32,
-    x: &Vec<Vec<f32>>,
-    y: &[f32],
+#[deprecated(note = "use try_compute_gradient, que retorna CeptronError em vez de entrar em pânico")]
+#[allow(deprecated)]
+pub fn compute_gradient<T: Scalar>(
+    neuron: &mut Neuron<T>, 
+    cost: fn(&[T], &[T], usize) -> T,
+    x: &[Vec<T>],
+    y: &[T],
+    weights: Option<&[T]>,
     param: ParamType,
     sample_size: usize
-) -> f32 {
-    let eps = 0.0001;
+) -> T {
+    let objective = |neuron: &Neuron<T>| match weights {
+        Some(weights) => compute_weighted_cost(neuron, x, y, weights, cost, sample_size),
+        None => compute_cost(neuron, x, y, cost, sample_size),
+    };
+    let eps: T = from_f64(0.0001);
     
     // Modifica o parâmetro diretamente no neurônio
-    match param {
-        ParamType::Weight(i) => neuron.weights[i] += eps,
-        ParamType::Bias => neuron.bias += eps,
+    if let Err(err) = neuron.apply_delta(param, eps) {
+        panic!("compute_gradient: {}", err);
     }
-    let variation_cost = compute_cost(neuron, x, y, cost, sample_size);
+    let variation_cost = objective(neuron);
     
     // Restaura o parâmetro
-    match param {
-        ParamType::Weight(i) => neuron.weights[i] -= eps,
-        ParamType::Bias => neuron.bias -= eps,
+    if let Err(err) = neuron.apply_delta(param, -eps) {
+        panic!("compute_gradient: {}", err);
     }
-    let normal_cost = compute_cost(neuron, x, y, cost, sample_size);
+    let normal_cost = objective(neuron);
 
     (variation_cost - normal_cost) / eps
 }
 
+/*
+ * Variante de compute_gradient que confere as amostras (e os pesos,
+ * se houver) antes de calcular.
+ *
+ * Retorno:
+ *   O gradiente, ou CeptronError: os erros de check_samples e de
+ *   try_compute_weighted_cost, ou InvalidConfig se param não existir
+ *   no neurônio (ver Neuron::params)
+ */
+pub fn try_compute_gradient<T: Scalar>(
+    neuron: &mut Neuron<T>,
+    cost: fn(&[T], &[T], usize) -> T,
+    x: &[Vec<T>],
+    y: &[T],
+    weights: Option<&[T]>,
+    param: ParamType,
+    sample_size: usize
+) -> Result<T, CeptronError> {
+    check_samples(neuron, x, y, sample_size)?;
+    if let Some(weights) = weights {
+        check_weights(weights, sample_size)?;
+    }
+    neuron.get_param(param).map_err(|err| CeptronError::InvalidConfig(err.to_string()))?;
+    #[allow(deprecated)]
+    let gradient = compute_gradient(neuron, cost, x, y, weights, param, sample_size);
+    Ok(gradient)
+}
+
 /*
  * Treina o neurônio ajustando seus pesos e bias.
  *
@@ -112,24 +356,1508 @@ fn compute_gradient(
  * Retorno:
  *   Nenhum (modifica o neurônio in-place)
  */
+#[deprecated(note = "use try_train, que retorna CeptronError em vez de entrar em pânico")]
+pub fn train<T: Scalar>(
+    neuron: &mut Neuron<T>, 
+    cost: fn(&[T], &[T], usize) -> T, 
+    x: &[Vec<T>], 
+    y: &[T], 
+    sample_size: usize
+) {
+    if let Err(err) = train_step(neuron, cost, x, y, None, sample_size, from_f64(0.001)) {
+        panic!("train: {}", err);
+    }
+}
 
-pub fn train(
-    neuron: &mut Neuron, 
-    cost: fn(&[f32], &[f32], usize) -> f32, 
-    x: &Vec<Vec<f32>>, 
-    y: &[f32], 
+/*
+ * Variante de train que confere as amostras antes do passo (ver
+ * check_samples) e retorna um erro se o treinamento divergir.
+ *
+ * Retorno:
+ *   Ok(()), ou CeptronError: os erros de check_samples (o neurônio
+ *   não é alterado), ou Diverged (época 0) se algum gradiente não
+ *   for finito
+ */
+pub fn try_train<T: Scalar>(
+    neuron: &mut Neuron<T>,
+    cost: fn(&[T], &[T], usize) -> T,
+    x: &[Vec<T>],
+    y: &[T],
+    sample_size: usize
+) -> Result<(), CeptronError> {
+    check_samples(neuron, x, y, sample_size)?;
+    try_step(neuron, cost, x, y, None, sample_size)
+}
+
+/*
+ * Passo de try_train e try_train_weighted: converte o erro de
+ * train_step em CeptronError::Diverged.
+ */
+fn try_step<T: Scalar>(
+    neuron: &mut Neuron<T>,
+    cost: fn(&[T], &[T], usize) -> T,
+    x: &[Vec<T>],
+    y: &[T],
+    weights: Option<&[T]>,
     sample_size: usize
+) -> Result<(), CeptronError> {
+    match train_step(neuron, cost, x, y, weights, sample_size, from_f64(0.001)) {
+        Ok(()) => Ok(()),
+        Err(NeuronError::NonFiniteDelta { param, .. }) => Err(DivergenceError { epoch: 0, param }.into()),
+        Err(err) => panic!("try_train: {}", err),
+    }
+}
+
+/*
+ * Treina o neurônio por um passo minimizando o custo ponderado
+ * (ver compute_weighted_cost e train).
+ *
+ * Parâmetros:
+ *   neuron - referência ao neurônio a ser treinado
+ *   cost - função de custo por amostra (ex: mse)
+ *   x - vetor de vetores contendo as entradas de cada amostra
+ *   y - vetor com os valores esperados (gabarito)
+ *   weights - peso de cada amostra (ex: data::balanced_weights)
+ *   sample_size - número de amostras
+ *
+ * Retorno:
+ *   Nenhum (modifica o neurônio in-place)
+ */
+#[deprecated(note = "use try_train_weighted, que retorna CeptronError em vez de entrar em pânico")]
+pub fn train_weighted<T: Scalar>(
+    neuron: &mut Neuron<T>,
+    cost: fn(&[T], &[T], usize) -> T,
+    x: &[Vec<T>],
+    y: &[T],
+    weights: &[T],
+    sample_size: usize
+) {
+    if let Err(err) = train_step(neuron, cost, x, y, Some(weights), sample_size, from_f64(0.001)) {
+        panic!("train_weighted: {}", err);
+    }
+}
+
+/*
+ * Variante de train_weighted que confere as amostras e os pesos
+ * antes do passo (ver try_train e try_compute_weighted_cost).
+ */
+pub fn try_train_weighted<T: Scalar>(
+    neuron: &mut Neuron<T>,
+    cost: fn(&[T], &[T], usize) -> T,
+    x: &[Vec<T>],
+    y: &[T],
+    weights: &[T],
+    sample_size: usize
+) -> Result<(), CeptronError> {
+    check_samples(neuron, x, y, sample_size)?;
+    check_weights(weights, sample_size)?;
+    try_step(neuron, cost, x, y, Some(weights), sample_size)
+}
+
+/*
+ * Treina o neurônio por um passo usando todas as amostras de um
+ * Dataset (ver train).
+ *
+ * Parâmetros:
+ *   neuron - referência ao neurônio a ser treinado
+ *   cost - função de custo a ser minimizada (ex: mse)
+ *   data - amostras de entrada e saídas esperadas
+ */
+#[deprecated(note = "use try_train_on, que retorna CeptronError em vez de entrar em pânico")]
+#[allow(deprecated)]
+pub fn train_on<T: Scalar>(
+    neuron: &mut Neuron<T>,
+    cost: fn(&[T], &[T], usize) -> T,
+    data: &Dataset<T>
 ) {
-    let mut gradient;
+    train(neuron, cost, data.inputs(), data.targets(), data.len());
+}
+
+/*
+ * Variante de train_on que retorna CeptronError (ver try_train).
+ */
+pub fn try_train_on<T: Scalar>(
+    neuron: &mut Neuron<T>,
+    cost: fn(&[T], &[T], usize) -> T,
+    data: &Dataset<T>
+) -> Result<(), CeptronError> {
+    try_train(neuron, cost, data.inputs(), data.targets(), data.len())
+}
+
+/*
+ * Executa um passo de gradiente descendente com uma taxa de
+ * aprendizado explícita.
+ *
+ * Parâmetros:
+ *   neuron - referência ao neurônio a ser treinado
+ *   cost - função de custo a ser minimizada (ex: mse)
+ *   x - vetor de vetores contendo as entradas de cada amostra
+ *   y - vetor com os valores esperados (gabarito)
+ *   weights - peso de cada amostra, ou None
+ *   sample_size - número de amostras
+ *   learning_rate - tamanho do passo na direção oposta ao gradiente
+ *
+ * Retorno:
+ *   Ok(()), ou NeuronError::NonFiniteDelta se algum gradiente não
+ *   for finito, o que indica que o treinamento divergiu (os
+ *   parâmetros anteriores ao erro já foram atualizados)
+ */
+fn train_step<T: Scalar>(
+    neuron: &mut Neuron<T>,
+    cost: fn(&[T], &[T], usize) -> T,
+    x: &[Vec<T>],
+    y: &[T],
+    weights: Option<&[T]>,
+    sample_size: usize,
+    learning_rate: T
+) -> Result<(), NeuronError<T>> {
+    // Um neurônio sem bias mantém o bias fixo em 0.0 (params não
+    // inclui Bias)
+    for param in neuron.params() {
+        #[allow(deprecated)]
+        let gradient = compute_gradient(neuron, cost, x, y, weights, param, sample_size);
+        #[cfg(feature = "log")]
+        log::trace!("param={:?} gradient={}", param, gradient);
+        neuron.apply_delta(param, -learning_rate * gradient)?;
+    }
+
+    Ok(())
+}
+
+/*
+ * Treina o neurônio com uma única amostra (aprendizado online).
+ *
+ * Executa um passo de gradiente descendente usando apenas a
+ * amostra (x, y), sem precisar do histórico de dados. Útil quando
+ * as amostras chegam uma a uma (streaming).
+ *
+ * Parâmetros:
+ *   neuron - referência ao neurônio a ser treinado
+ *   x - vetor de entrada da amostra
+ *   y - valor esperado da amostra
+ *   learning_rate - tamanho do passo na direção oposta ao gradiente
+ *   cost - função de custo a ser minimizada (ex: mse)
+ *
+ * Retorno:
+ *   Ok(()), ou CeptronError (o neurônio não é alterado):
+ *   DimensionMismatch se x não tiver um valor por conexão, ou
+ *   Diverged (época 0) se algum gradiente não for finito (ex: amostra
+ *   finita, mas muito grande)
+ *
+ * O passo é o mesmo de train (os parâmetros são atualizados na ordem
+ * de params()); se algum gradiente não for finito, os parâmetros
+ * já atualizados voltam aos valores anteriores ao passo.
+ */
+pub fn partial_fit<T: Scalar>(
+    neuron: &mut Neuron<T>,
+    x: &[T],
+    y: T,
+    learning_rate: T,
+    cost: fn(&[T], &[T], usize) -> T
+) -> Result<(), CeptronError> {
+    let expected = neuron.n_connections();
+    if x.len() != expected {
+        return Err(CeptronError::DimensionMismatch { expected, actual: x.len(), row: None });
+    }
+
+    let (weights, bias) = (neuron.weights().to_vec(), neuron.bias());
+    let sample = [x.to_vec()];
+    match train_step(neuron, cost, &sample, &[y], None, 1, learning_rate) {
+        Ok(()) => Ok(()),
+        Err(NeuronError::NonFiniteDelta { param, .. }) => {
+            neuron.set_params(&weights, bias).expect("parâmetros anteriores ao passo");
+            Err(DivergenceError { epoch: 0, param }.into())
+        }
+        // train_step só altera parâmetros listados por params()
+        Err(err) => unreachable!("partial_fit: {}", err),
+    }
+}
+
+/*
+ * Configuração de um treinamento completo.
+ *
+ * Campos:
+ *   epochs - número de passos de gradiente descendente
+ *   learning_rate - taxa de aprendizado de cada passo
+ *   max_norm - se definido, após cada passo a norma L2 dos pesos
+ *              é limitada a esse valor (restrição max-norm)
+ *   debug_checks - se true, fit também verifica após cada passo se
+ *                  todos os parâmetros continuam finitos (um gradiente
+ *                  não finito é sempre detectado, com ou sem essa
+ *                  opção, e gera FitError::Diverged)
+ *   seed - semente usada para embaralhar as amostras (ex: na
+ *          divisão em folds de cross_validate); None usa um
+ *          gerador aleatório. Outras etapas do experimento podem
+ *          derivar dela as suas sementes (ver derived_seed)
+ *   validate_data - se true (padrão), fit procura NaN nos dados
+ *                   antes de treinar e retorna FitError::InvalidData
+ *                   (verificação barata; desative para dados já
+ *                   auditados com Dataset::validate)
+ *   scale_target - se true, fit padroniza as saídas esperadas
+ *                  (z-score) antes de treinar e converte os pesos e
+ *                  o bias aprendidos de volta à unidade original; só
+ *                  é exato para a ativação Identity (max_norm passa a
+ *                  valer no espaço padronizado)
+ *   sample_weights - peso de cada amostra (ex: data::balanced_weights
+ *                    para classes desbalanceadas); fit passa a
+ *                    minimizar o custo ponderado (ver
+ *                    compute_weighted_cost). None = pesos iguais
+ *   checkpoint_every - se definido, fit grava um checkpoint em
+ *                      checkpoint_path a cada N épocas (ver
+ *                      fit_resume)
+ *   checkpoint_path - arquivo do checkpoint (substituído a cada
+ *                     gravação, de forma atômica); como em
+ *                     save_path, um neurônio que não pode ser gravado
+ *                     falha antes da primeira época
+ *   save_path - se definido, fit grava o neurônio treinado nesse
+ *               arquivo (formato binário, ver Neuron::save_bin) e,
+ *               com a feature "serde", os metadados do treinamento
+ *               ao lado dele (ver ModelMetadata); uma ativação que
+ *               não pode ser gravada falha antes da primeira época
+ *   history_points - número máximo de pontos do histórico de custo
+ *                    gravado nos metadados (ver downsample_history);
+ *                    None grava o custo de todas as épocas
+ *   compress - se true, os checkpoints e o arquivo de save_path são
+ *              comprimidos com gzip (requer a feature "gzip")
+ *   log_every - com a feature "log", fit registra em info o custo a
+ *               cada N épocas; None não registra resumos de época
+ *
+ * O valor padrão reproduz o treinamento do exemplo em main.rs
+ * (50.000 iterações com taxa 0.001).
+ */
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TrainConfig<T = Float> {
+    pub epochs: usize,
+    pub learning_rate: T,
+    pub max_norm: Option<T>,
+    pub debug_checks: bool,
+    pub seed: Option<u64>,
+    pub validate_data: bool,
+    pub scale_target: bool,
+    pub sample_weights: Option<Vec<T>>,
+    pub checkpoint_every: Option<usize>,
+    pub checkpoint_path: Option<PathBuf>,
+    pub save_path: Option<PathBuf>,
+    pub history_points: Option<usize>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub compress: bool,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub log_every: Option<usize>,
+}
+
+impl<T: Scalar> Default for TrainConfig<T> {
+    fn default() -> Self {
+        Self {
+            epochs: 50000,
+            learning_rate: from_f64(0.001),
+            max_norm: None,
+            debug_checks: false,
+            seed: None,
+            validate_data: true,
+            scale_target: false,
+            sample_weights: None,
+            checkpoint_every: None,
+            checkpoint_path: None,
+            save_path: None,
+            history_points: None,
+            compress: false,
+            log_every: None,
+        }
+    }
+}
+
+impl<T: Scalar> TrainConfig<T> {
+
+    /*
+     * Sub-semente da etapa stream (ex: 0 para a inicialização, 1
+     * para a divisão treino/teste), derivada de seed com
+     * utils::derive_seed; None se seed não estiver definido.
+     */
+    pub fn derived_seed(&self, stream: u64) -> Option<u64> {
+        self.seed.map(|seed| derive_seed(seed, stream))
+    }
+
+    /*
+     * Resumo (hash FNV-1a) das opções que determinam o resultado do
+     * treinamento: épocas, taxa de aprendizado, max_norm,
+     * scale_target e sample_weights. Gravado nos checkpoints para
+     * que fit_resume recuse continuar com outra configuração.
+     */
+    pub fn fingerprint(&self) -> u64 {
+        let mut hash: u64 = 0xcbf29ce484222325;
+        let mut feed = |value: u64| {
+            for byte in value.to_le_bytes() {
+                hash ^= u64::from(byte);
+                hash = hash.wrapping_mul(0x100000001b3);
+            }
+        };
+        let bits = |value: T| value.to_f64().unwrap_or(f64::NAN).to_bits();
+
+        feed(self.epochs as u64);
+        feed(bits(self.learning_rate));
+        feed(self.max_norm.map_or(u64::MAX, bits));
+        feed(u64::from(self.scale_target));
+        match &self.sample_weights {
+            Some(weights) => {
+                feed(weights.len() as u64);
+                weights.iter().for_each(|&weight| feed(bits(weight)));
+            }
+            None => feed(u64::MAX),
+        }
+        hash
+    }
+}
+
+/*
+ * Erro retornado por fit quando o treinamento diverge.
+ *
+ * Campos:
+ *   epoch - época (a partir de 0) em que o problema foi detectado
+ *   param - primeiro parâmetro que deixou de ser finito (ou cujo
+ *           gradiente não é finito)
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub struct DivergenceError {
+    pub epoch: usize,
+    pub param: ParamType,
+}
+
+impl fmt::Display for DivergenceError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "o treinamento divergiu na época {}: o parâmetro {:?} não é finito",
+            self.epoch, self.param
+        )
+    }
+}
+
+impl std::error::Error for DivergenceError {}
+
+/*
+ * Erros de fit.
+ *
+ * Variantes:
+ *   InvalidData(DataIssue) - os dados têm NaN (ver
+ *                            TrainConfig::validate_data); o
+ *                            neurônio não é alterado
+ *   Diverged(DivergenceError) - o treinamento divergiu: um gradiente
+ *                               ou (com TrainConfig::debug_checks)
+ *                               um parâmetro deixou de ser finito
+ *   ScaleTargetActivation - TrainConfig::scale_target com uma
+ *                           ativação diferente de Identity
+ *   WeightCount { samples, weights } - TrainConfig::sample_weights
+ *                                      sem um peso por amostra
+ *   Checkpoint(ModelIoError) - falha ao gravar ou ler um checkpoint
+ *   CheckpointConfig { expected, found } - o checkpoint foi gravado
+ *                                          com outra configuração
+ *                                          (ver TrainConfig::fingerprint)
+ *   Save(ModelIoError) - falha ao gravar o modelo ou os metadados em
+ *                        TrainConfig::save_path
+ *   Load(ModelIoError) - falha ao ler o modelo de fit_from
+ *   FeatureWidth { expected, actual } - os dados (ou os de fit_from)
+ *                                       não têm uma entrada por
+ *                                       conexão do neurônio
+ *   SampleCount { sample_size, inputs, targets } - x ou y têm menos
+ *                                       de sample_size amostras
+ *   Hyperparameter { name, value } - taxa de aprendizado ou max_norm
+ *                                    não positivo ou não finito
+ */
+#[derive(Debug)]
+pub enum FitError {
+    InvalidData(DataIssue),
+    Diverged(DivergenceError),
+    ScaleTargetActivation,
+    WeightCount { samples: usize, weights: usize },
+    Checkpoint(ModelIoError),
+    CheckpointConfig { expected: u64, found: u64 },
+    Save(ModelIoError),
+    Load(ModelIoError),
+    FeatureWidth { expected: usize, actual: usize },
+    SampleCount { sample_size: usize, inputs: usize, targets: usize },
+    Hyperparameter { name: &'static str, value: f64 },
+}
+
+impl fmt::Display for FitError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FitError::InvalidData(issue) => write!(f, "dados inválidos: {}", issue),
+            FitError::Diverged(err) => write!(f, "{}", err),
+            FitError::ScaleTargetActivation => {
+                write!(f, "scale_target só pode ser usado com a ativação Identity")
+            }
+            FitError::WeightCount { samples, weights } => {
+                write!(f, "{} pesos para {} amostras", weights, samples)
+            }
+            FitError::Checkpoint(err) => write!(f, "checkpoint: {}", err),
+            FitError::CheckpointConfig { expected, found } => write!(
+                f,
+                "o checkpoint foi gravado com outra configuração ({:016x}, esperada {:016x})",
+                found, expected
+            ),
+            FitError::Save(err) => write!(f, "falha ao gravar o modelo treinado: {}", err),
+            FitError::Load(err) => write!(f, "falha ao ler o modelo: {}", err),
+            FitError::FeatureWidth { expected, actual } => write!(
+                f,
+                "os dados têm {} entradas, mas o modelo tem {} conexões",
+                actual, expected
+            ),
+            FitError::SampleCount { sample_size, inputs, targets } => write!(
+                f,
+                "sample_size = {}, mas há {} entradas e {} saídas esperadas",
+                sample_size, inputs, targets
+            ),
+            FitError::Hyperparameter { name, value } => {
+                write!(f, "{} deve ser positivo e finito (recebido {})", name, value)
+            }
+        }
+    }
+}
+
+impl std::error::Error for FitError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            FitError::InvalidData(issue) => Some(issue),
+            FitError::Diverged(err) => Some(err),
+            FitError::Checkpoint(err) | FitError::Save(err) | FitError::Load(err) => Some(err),
+            FitError::ScaleTargetActivation
+            | FitError::WeightCount { .. }
+            | FitError::FeatureWidth { .. }
+            | FitError::SampleCount { .. }
+            | FitError::Hyperparameter { .. }
+            | FitError::CheckpointConfig { .. } => None,
+        }
+    }
+}
+
+impl From<DivergenceError> for FitError {
+    fn from(err: DivergenceError) -> Self {
+        FitError::Diverged(err)
+    }
+}
+
+impl From<ModelIoError> for FitError {
+    fn from(err: ModelIoError) -> Self {
+        FitError::Checkpoint(err)
+    }
+}
+
+/*
+ * Treina o neurônio pelo número de épocas definido na configuração.
+ *
+ * Parâmetros:
+ *   neuron - referência ao neurônio a ser treinado
+ *   cost - função de custo a ser minimizada (ex: mse)
+ *   x - vetor de vetores contendo as entradas de cada amostra
+ *   y - vetor com os valores esperados (gabarito)
+ *   sample_size - número de amostras
+ *   config - número de épocas, taxa de aprendizado e restrições
+ *
+ * Retorno:
+ *   Ok(()) (modifica o neurônio in-place), ou o FitError do problema
+ *   convertido para CeptronError (ver From<FitError>):
+ *   InvalidConfig se x ou y tiverem menos de sample_size amostras
+ *   (SampleCount), se config.learning_rate ou config.max_norm não
+ *   forem positivos e finitos (Hyperparameter), se
+ *   config.sample_weights não tiver um peso por amostra (WeightCount)
+ *   ou se config.scale_target for usado com uma ativação não linear
+ *   (ScaleTargetActivation); DimensionMismatch se uma amostra não
+ *   tiver uma entrada por conexão; InvalidData se
+ *   config.validate_data estiver ativo e houver NaN nas amostras;
+ *   Fit(FitError::Checkpoint) se um checkpoint não puder ser gravado;
+ *   Fit(FitError::Save) se o modelo não puder ser gravado em
+ *   config.save_path; ou Diverged se algum gradiente não for finito
+ *   (ou, com config.debug_checks, se algum parâmetro deixar de ser
+ *   finito).
+ *
+ * Com config.scale_target, o neurônio retornado já prevê na unidade
+ * original das saídas esperadas.
+ */
+pub fn fit<T: Scalar>(
+    neuron: &mut Neuron<T>,
+    cost: fn(&[T], &[T], usize) -> T,
+    x: &[Vec<T>],
+    y: &[T],
+    sample_size: usize,
+    config: &TrainConfig<T>
+) -> Result<(), CeptronError> {
+    Ok(fit_impl(neuron, cost, x, y, sample_size, config, None)?)
+}
+
+/*
+ * Retoma um treinamento a partir do checkpoint gravado por fit
+ * (ver TrainConfig::checkpoint_every). Com os mesmos dados e a
+ * mesma configuração, o resultado é idêntico ao de um fit sem
+ * interrupção.
+ *
+ * Parâmetros:
+ *   path - arquivo do checkpoint (binário, ou MessagePack gravado
+ *          por Checkpoint::save_msgpack com a feature "serde")
+ *   cost - função de custo a ser minimizada (ex: mse)
+ *   x - vetor de vetores contendo as entradas de cada amostra
+ *   y - vetor com os valores esperados (gabarito)
+ *   sample_size - número de amostras
+ *   config - a mesma configuração do treinamento interrompido
+ *            (checkpoints continuam sendo gravados se definidos)
+ *
+ * Retorno:
+ *   O neurônio treinado, CeptronError::Fit com
+ *   FitError::CheckpointConfig se o checkpoint tiver sido gravado com
+ *   outra configuração (ou FitError::Checkpoint se não puder ser
+ *   lido), ou os mesmos erros de fit
+ */
+pub fn fit_resume<T: Scalar>(
+    path: impl AsRef<Path>,
+    cost: fn(&[T], &[T], usize) -> T,
+    x: &[Vec<T>],
+    y: &[T],
+    sample_size: usize,
+    config: &TrainConfig<T>
+) -> Result<Neuron<T>, CeptronError> {
+    let checkpoint = Checkpoint::<T>::load(path).map_err(FitError::from)?;
+    let expected = config.fingerprint();
+    if checkpoint.config_hash != expected {
+        return Err(FitError::CheckpointConfig { expected, found: checkpoint.config_hash }.into());
+    }
+
+    let mut neuron = checkpoint.neuron;
+    fit_impl(&mut neuron, cost, x, y, sample_size, config, Some(checkpoint.epoch))?;
+    Ok(neuron)
+}
+
+/*
+ * Ponto de partida de fit_from: um neurônio já treinado ou o
+ * arquivo em que ele foi gravado.
+ *
+ * Variantes:
+ *   Model(neuron) - o próprio neurônio
+ *   Path(path) - arquivo no formato binário (ver Neuron::save_bin e
+ *                TrainConfig::save_path)
+ */
+pub enum WarmStart<T: 'static = Float> {
+    Model(Neuron<T>),
+    Path(PathBuf),
+}
+
+impl<T> From<Neuron<T>> for WarmStart<T> {
+    fn from(neuron: Neuron<T>) -> Self {
+        WarmStart::Model(neuron)
+    }
+}
+
+impl<T> From<PathBuf> for WarmStart<T> {
+    fn from(path: PathBuf) -> Self {
+        WarmStart::Path(path)
+    }
+}
 
-    for i in 0..neuron.n_connections as usize {
-        let param = ParamType::Weight(i);
-        gradient = compute_gradient(neuron, cost, x, y, param, sample_size);
-        neuron.weights[i] -= 0.001 * gradient;
+impl<T> From<&Path> for WarmStart<T> {
+    fn from(path: &Path) -> Self {
+        WarmStart::Path(path.to_path_buf())
     }
+}
+
+/*
+ * Continua o treinamento de um modelo já treinado em novos dados
+ * (warm start), em vez de começar de pesos aleatórios.
+ *
+ * O número de entradas do modelo é conferido antes de qualquer
+ * alteração nos parâmetros. O gradiente descendente não guarda
+ * estado entre épocas além dos próprios parâmetros, então não há
+ * nada mais a restaurar.
+ *
+ * Parâmetros:
+ *   start - o neurônio treinado ou o arquivo em que ele foi gravado
+ *   cost - função de custo a ser minimizada (ex: mse)
+ *   data - novos dados de treino
+ *   config - configuração do treinamento (config.epochs épocas a
+ *            mais)
+ *
+ * Retorno:
+ *   O neurônio treinado, CeptronError::Fit com FitError::Load se o
+ *   arquivo não puder ser lido, CeptronError::DimensionMismatch se
+ *   data não tiver uma entrada por conexão do neurônio, ou os mesmos
+ *   erros de fit
+ */
+pub fn fit_from<T: Scalar>(
+    start: impl Into<WarmStart<T>>,
+    cost: fn(&[T], &[T], usize) -> T,
+    data: &Dataset<T>,
+    config: &TrainConfig<T>
+) -> Result<Neuron<T>, CeptronError> {
+    let mut neuron = match start.into() {
+        WarmStart::Model(neuron) => neuron,
+        WarmStart::Path(path) => Neuron::read_bin(&path).map_err(FitError::Load)?,
+    };
+    if data.n_features() != neuron.n_connections() {
+        return Err(FitError::FeatureWidth {
+            expected: neuron.n_connections(),
+            actual: data.n_features(),
+        }
+        .into());
+    }
+    fit_on(&mut neuron, cost, data, config)?;
+    Ok(neuron)
+}
+
+/*
+ * Implementação de fit e fit_resume. Com resume_epoch, o neurônio
+ * vem de um checkpoint: já está no espaço de treino (padronizado,
+ * com scale_target) e o treino continua dessa época.
+ */
+fn fit_impl<T: Scalar>(
+    neuron: &mut Neuron<T>,
+    cost: fn(&[T], &[T], usize) -> T,
+    x: &[Vec<T>],
+    y: &[T],
+    sample_size: usize,
+    config: &TrainConfig<T>,
+    resume_epoch: Option<usize>
+) -> Result<(), FitError> {
+    if x.len() < sample_size || y.len() < sample_size {
+        return Err(FitError::SampleCount { sample_size, inputs: x.len(), targets: y.len() });
+    }
+    let expected = neuron.n_connections();
+    if let Some(sample) = x[..sample_size].iter().find(|sample| sample.len() != expected) {
+        return Err(FitError::FeatureWidth { expected, actual: sample.len() });
+    }
+    let hyperparameters = [("learning_rate", Some(config.learning_rate)), ("max_norm", config.max_norm)];
+    for (name, value) in hyperparameters {
+        if let Some(value) = value
+            && !(value.is_finite() && value > T::zero())
+        {
+            return Err(FitError::Hyperparameter { name, value: value.to_f64().unwrap_or(f64::NAN) });
+        }
+    }
+    if config.validate_data
+        && let Some(issue) = find_nan(&x[..sample_size], &y[..sample_size])
+    {
+        return Err(FitError::InvalidData(issue));
+    }
+    if let Some(weights) = &config.sample_weights
+        && weights.len() != sample_size
+    {
+        return Err(FitError::WeightCount { samples: sample_size, weights: weights.len() });
+    }
+    // o modelo só é gravado depois das épocas: um neurônio que não
+    // pode ser gravado falha antes de qualquer alteração
+    if config.save_path.is_some() {
+        neuron.check_writable(config.compress).map_err(FitError::Save)?;
+    }
+    if checkpoint_target(config).is_some() {
+        neuron.check_writable(config.compress).map_err(FitError::Checkpoint)?;
+    }
+    let start = resume_epoch.unwrap_or(0);
+    let history = if !config.scale_target {
+        run_epochs(neuron, cost, x, y, sample_size, config, start)?
+    } else {
+        if *neuron.activation() != Activation::Identity {
+            return Err(FitError::ScaleTargetActivation);
+        }
+
+        // y = mean + std * y_scaled: com ativação identidade, basta
+        // converter os parâmetros para o espaço padronizado antes do
+        // treino e de volta depois dele
+        let (mean, std) = target_mean_std(&y[..sample_size]);
+        let center = if neuron.use_bias() { mean } else { T::zero() };
+        let y_scaled: Vec<T> = y[..sample_size].iter().map(|&target| (target - center) / std).collect();
+
+        if resume_epoch.is_none() {
+            rescale_params(neuron, T::one() / std, -center / std);
+        }
+        let result = run_epochs(neuron, cost, x, &y_scaled, sample_size, config, start);
+        rescale_params(neuron, std, center);
+        result?
+    };
+
+    if let Some(path) = &config.save_path {
+        let history = match config.history_points {
+            Some(max_points) => downsample_history(&history, max_points),
+            None => history,
+        };
+        neuron.write_bin(path, config.compress).map_err(FitError::Save)?;
+        save_metadata(path, neuron, config, &x[..sample_size], &y[..sample_size], history)
+            .map_err(FitError::Save)?;
+    }
+    Ok(())
+}
+
+/*
+ * Grava os metadados do treinamento ao lado do modelo em path (ver
+ * ModelMetadata).
+ */
+#[cfg(feature = "serde")]
+fn save_metadata<T: Scalar>(
+    path: &Path,
+    neuron: &Neuron<T>,
+    config: &TrainConfig<T>,
+    x: &[Vec<T>],
+    y: &[T],
+    history: Vec<(usize, T)>
+) -> Result<(), ModelIoError> {
+    let wide = |value: T| value.to_f64().unwrap_or(f64::NAN);
+    let config = TrainConfig {
+        epochs: config.epochs,
+        learning_rate: wide(config.learning_rate),
+        max_norm: config.max_norm.map(wide),
+        debug_checks: config.debug_checks,
+        seed: config.seed,
+        validate_data: config.validate_data,
+        scale_target: config.scale_target,
+        sample_weights: config.sample_weights.as_ref().map(|weights| weights.iter().map(|&w| wide(w)).collect()),
+        checkpoint_every: config.checkpoint_every,
+        checkpoint_path: config.checkpoint_path.clone(),
+        save_path: config.save_path.clone(),
+        history_points: config.history_points,
+        compress: config.compress,
+        log_every: config.log_every,
+    };
+    ModelMetadata {
+        library_version: env!("CARGO_PKG_VERSION").to_string(),
+        model_fingerprint: neuron.fingerprint(),
+        config,
+        dataset: DatasetFingerprint::of(x, y),
+        history: history.into_iter().map(|(epoch, cost)| (epoch, wide(cost))).collect(),
+    }
+    .save(path)
+}
+
+/*
+ * Sem a feature "serde", os metadados não são gravados.
+ */
+#[cfg(not(feature = "serde"))]
+fn save_metadata<T: Scalar>(
+    _path: &Path,
+    _neuron: &Neuron<T>,
+    _config: &TrainConfig<T>,
+    _x: &[Vec<T>],
+    _y: &[T],
+    _history: Vec<(usize, T)>
+) -> Result<(), ModelIoError> {
+    Ok(())
+}
+
+/*
+ * Laço de treinamento de fit (sem validação nem padronização), da
+ * época start até config.epochs, gravando os checkpoints.
+ *
+ * Retorno:
+ *   O histórico (época, custo após a época) quando o modelo será
+ *   gravado com metadados (TrainConfig::save_path com a feature
+ *   "serde"); vazio caso contrário. Com scale_target, o custo é o do
+ *   alvo padronizado.
+ */
+fn run_epochs<T: Scalar>(
+    neuron: &mut Neuron<T>,
+    cost: fn(&[T], &[T], usize) -> T,
+    x: &[Vec<T>],
+    y: &[T],
+    sample_size: usize,
+    config: &TrainConfig<T>,
+    start: usize
+) -> Result<Vec<(usize, T)>, FitError> {
+    let checkpoint = checkpoint_target(config);
+    let config_hash = config.fingerprint();
+    let record = cfg!(feature = "serde") && config.save_path.is_some();
+    let mut history = Vec::new();
+    #[allow(deprecated)]
+    let epoch_cost = |neuron: &Neuron<T>| match &config.sample_weights {
+        Some(weights) => compute_weighted_cost(neuron, x, y, weights, cost, sample_size),
+        None => compute_cost(neuron, x, y, cost, sample_size),
+    };
+    #[cfg(feature = "log")]
+    log::debug!(
+        "fit start epoch={} epochs={} samples={} lr={}",
+        start, config.epochs, sample_size, config.learning_rate
+    );
+
+    for epoch in start..config.epochs {
+        match train_step(neuron, cost, x, y, config.sample_weights.as_deref(), sample_size, config.learning_rate) {
+            Ok(()) => {}
+            Err(NeuronError::NonFiniteDelta { param, .. }) => return Err(diverged(epoch, param)),
+            // train_step só altera parâmetros listados por params()
+            Err(err) => unreachable!("fit: {}", err),
+        }
+        if let Some(max_norm) = config.max_norm {
+            neuron.clip_weight_norm(max_norm);
+        }
+        if config.debug_checks
+            && let Some(param) = neuron.first_non_finite()
+        {
+            return Err(diverged(epoch, param));
+        }
+        #[cfg(feature = "log")]
+        if let Some(every) = config.log_every
+            && every > 0
+            && (epoch + 1).is_multiple_of(every)
+            && log::log_enabled!(log::Level::Info)
+        {
+            log::info!("epoch={} cost={} lr={}", epoch + 1, epoch_cost(neuron), config.learning_rate);
+        }
+        if let Some((every, path)) = checkpoint
+            && (epoch + 1).is_multiple_of(every)
+        {
+            Checkpoint::save_with(path, epoch + 1, config_hash, neuron, config.compress)?;
+        }
+        if record {
+            history.push((epoch + 1, epoch_cost(neuron)));
+        }
+    }
+    #[cfg(feature = "log")]
+    log::debug!("fit end epochs={} cost={}", config.epochs, epoch_cost(neuron));
+    Ok(history)
+}
+
+/*
+ * Intervalo e arquivo dos checkpoints, se config pedir checkpoints
+ * (checkpoint_every maior que zero e checkpoint_path definido).
+ */
+fn checkpoint_target<T>(config: &TrainConfig<T>) -> Option<(usize, &PathBuf)> {
+    match (config.checkpoint_every, &config.checkpoint_path) {
+        (Some(every), Some(path)) if every > 0 => Some((every, path)),
+        _ => None,
+    }
+}
+
+/*
+ * Erro de divergência de run_epochs, registrado em warn com a
+ * feature "log".
+ */
+fn diverged(epoch: usize, param: ParamType) -> FitError {
+    #[cfg(feature = "log")]
+    log::warn!("epoch={} diverged param={:?}", epoch, param);
+    DivergenceError { epoch, param }.into()
+}
+
+/*
+ * Média e desvio padrão (populacional) das saídas esperadas. Um
+ * desvio padrão zero é trocado por 1.0 para não dividir por zero.
+ */
+fn target_mean_std<T: Scalar>(y: &[T]) -> (T, T) {
+    let n: T = from_f64(y.len() as f64);
+    let mean = y.iter().fold(T::zero(), |acc, &target| acc + target) / n;
+    let variance = y.iter().fold(T::zero(), |acc, &target| acc + (target - mean) * (target - mean)) / n;
+    let std = variance.sqrt();
+    (mean, if std == T::zero() { T::one() } else { std })
+}
+
+/*
+ * Multiplica todos os parâmetros por scale e soma shift ao bias
+ * (o bias só é alterado se o neurônio tiver bias).
+ */
+fn rescale_params<T: Scalar>(neuron: &mut Neuron<T>, scale: T, shift: T) {
+    for i in 0..neuron.n_connections() {
+        let weight = neuron.weights()[i];
+        neuron.set_weight(i, weight * scale);
+    }
+    if neuron.use_bias() {
+        let bias = neuron.bias();
+        neuron.set_bias(bias * scale + shift);
+    }
+}
+
+/*
+ * Treina o neurônio com todas as amostras de um Dataset pelo
+ * número de épocas da configuração (ver fit).
+ *
+ * Parâmetros:
+ *   neuron - referência ao neurônio a ser treinado
+ *   cost - função de custo a ser minimizada (ex: mse)
+ *   data - amostras de entrada e saídas esperadas
+ *   config - número de épocas, taxa de aprendizado e restrições
+ *
+ * Retorno:
+ *   O mesmo que fit
+ */
+pub fn fit_on<T: Scalar>(
+    neuron: &mut Neuron<T>,
+    cost: fn(&[T], &[T], usize) -> T,
+    data: &Dataset<T>,
+    config: &TrainConfig<T>
+) -> Result<(), CeptronError> {
+    fit(neuron, cost, data.inputs(), data.targets(), data.len(), config)
+}
+
+/*
+ * Resultado de Model::fit.
+ *
+ * Campos:
+ *   epochs - número de épocas executadas
+ *   cost - custo (mse) do modelo treinado nos dados de treino
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub struct FitResult<T = Float> {
+    pub epochs: usize,
+    pub cost: T,
+}
+
+/*
+ * Modelo treinável, para que utilitários como cross_validate não
+ * dependam do tipo concreto (Neuron ou Pipeline).
+ *
+ * Métodos:
+ *   predict - calcula a saída para uma amostra, ou
+ *             CeptronError::DimensionMismatch se ela não tiver um
+ *             valor por entrada
+ *   fit - treina o modelo com mse nos dados (ver fit_on); os erros
+ *         de treinamento são convertidos para CeptronError
+ *   num_parameters - número de parâmetros treináveis
+ */
+pub trait Model<T: Scalar = Float> {
+    type Output;
+
+    fn predict(&self, x: &[T]) -> Result<Self::Output, CeptronError>;
+    fn fit(&mut self, data: &Dataset<T>, config: &TrainConfig<T>) -> Result<FitResult<T>, CeptronError>;
+    fn num_parameters(&self) -> usize;
+}
+
+/*
+ * Um Neuron é treinado com fit_on e mse; seus parâmetros são os
+ * pesos e o bias (se houver).
+ */
+impl<T: Scalar> Model<T> for Neuron<T> {
+    type Output = T;
+
+    fn predict(&self, x: &[T]) -> Result<T, CeptronError> {
+        Ok(self.try_compute_out(x)?)
+    }
+
+    fn fit(&mut self, data: &Dataset<T>, config: &TrainConfig<T>) -> Result<FitResult<T>, CeptronError> {
+        fit_on(self, mse, data, config)?;
+        #[allow(deprecated)]
+        let cost = compute_cost_on(self, data, mse);
+        Ok(FitResult { epochs: config.epochs, cost })
+    }
+
+    fn num_parameters(&self) -> usize {
+        self.param_count()
+    }
+}
+
+/*
+ * Erros de cross_validate.
+ *
+ * Variantes:
+ *   InvalidK { k, len } - k < 2 ou k maior que o número de amostras
+ *   LabelCount { samples, labels } - número de rótulos diferente do
+ *                                    número de amostras (estratificado)
+ *   Fit { fold, source } - o treinamento ou a predição do fold
+ *                          `fold` falhou (source é o erro de Model)
+ */
+#[derive(Debug)]
+pub enum CvError {
+    InvalidK { k: usize, len: usize },
+    LabelCount { samples: usize, labels: usize },
+    Fit { fold: usize, source: Box<CeptronError> },
+}
+
+impl fmt::Display for CvError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CvError::InvalidK { k, len } => write!(
+                f,
+                "k = {} inválido para {} amostras (é preciso 2 <= k <= amostras)",
+                k, len
+            ),
+            CvError::LabelCount { samples, labels } => write!(
+                f,
+                "{} rótulos para {} amostras",
+                labels, samples
+            ),
+            CvError::Fit { fold, source } => write!(f, "fold {}: {}", fold, source),
+        }
+    }
+}
+
+impl std::error::Error for CvError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            CvError::Fit { source, .. } => Some(source.as_ref()),
+            CvError::InvalidK { .. } | CvError::LabelCount { .. } => None,
+        }
+    }
+}
+
+/*
+ * Resultado de uma validação cruzada.
+ *
+ * Campos:
+ *   fold_costs - custo de validação (mse) de cada fold, na ordem
+ *   mean - média de fold_costs
+ *   std - desvio padrão populacional de fold_costs
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub struct CvReport<T = Float> {
+    pub fold_costs: Vec<T>,
+    pub mean: T,
+    pub std: T,
+}
+
+/*
+ * Validação cruzada k-fold.
+ *
+ * Embaralha as amostras (com config.seed), divide-as em k folds e,
+ * para cada fold, treina um modelo novo nos outros k - 1 folds
+ * com Model::fit e mede o custo (mse) no fold separado.
+ *
+ * Parâmetros:
+ *   k - número de folds (2 <= k <= dataset.len(); k == len equivale
+ *       a leave-one-out)
+ *   dataset - amostras de entrada e saídas esperadas
+ *   config - configuração de treinamento de cada fold
+ *   build_model - cria o modelo de cada fold (ex: um Neuron ou um
+ *                 Pipeline), deixando a inicialização (e a semente)
+ *                 a cargo do chamador
+ *
+ * Retorno:
+ *   Um CvReport, ou CvError se k for inválido ou algum treinamento
+ *   falhar
+ *
+ * Quando len % k != 0, os primeiros len % k folds recebem uma
+ * amostra a mais, de modo que os tamanhos diferem no máximo em 1.
+ */
+pub fn cross_validate<T: Scalar, M: Model<T, Output = T>>(
+    k: usize,
+    dataset: &Dataset<T>,
+    config: &TrainConfig<T>,
+    build_model: impl Fn() -> M
+) -> Result<CvReport<T>, CvError> {
+    let len = dataset.len();
+    if k < 2 || k > len {
+        return Err(CvError::InvalidK { k, len });
+    }
+
+    let mut indices: Vec<usize> = (0..len).collect();
+    shuffle_indices(&mut indices, config.seed);
+
+    let mut folds = Vec::with_capacity(k);
+    let mut start = 0;
+    for fold in 0..k {
+        let size = len / k + if fold < len % k { 1 } else { 0 };
+        folds.push(indices[start..start + size].to_vec());
+        start += size;
+    }
+
+    run_folds(&folds, dataset, config, build_model)
+}
+
+/*
+ * Validação cruzada k-fold estratificada, para classificação.
+ *
+ * Como cross_validate, mas os folds preservam a proporção de cada
+ * classe: as amostras de cada classe são embaralhadas e
+ * distribuídas alternadamente entre os folds, de modo que o número
+ * de amostras de uma classe em dois folds difere no máximo em 1
+ * (e o tamanho total dos folds também).
+ *
+ * Parâmetros:
+ *   k - número de folds (2 <= k <= dataset.len())
+ *   dataset - amostras de entrada e saídas esperadas
+ *   labels - classe de cada amostra (usada só para estratificar)
+ *   config - configuração de treinamento de cada fold
+ *   build_model - cria o modelo de cada fold
+ *
+ * Retorno:
+ *   Um CvReport, ou CvError se k for inválido, se labels tiver
+ *   tamanho diferente de dataset ou se algum treinamento falhar
+ */
+pub fn cross_validate_stratified<T: Scalar, M: Model<T, Output = T>>(
+    k: usize,
+    dataset: &Dataset<T>,
+    labels: &[usize],
+    config: &TrainConfig<T>,
+    build_model: impl Fn() -> M
+) -> Result<CvReport<T>, CvError> {
+    let len = dataset.len();
+    if labels.len() != len {
+        return Err(CvError::LabelCount { samples: len, labels: labels.len() });
+    }
+    if k < 2 || k > len {
+        return Err(CvError::InvalidK { k, len });
+    }
+
+    let mut rng = rng_from(config.seed);
+    let mut folds = vec![Vec::new(); k];
+    let mut position = 0;
+    for (_, mut class) in group_by_label(labels) {
+        class.shuffle(&mut rng);
+        for &index in class.iter() {
+            folds[position % k].push(index);
+            position += 1;
+        }
+    }
+
+    run_folds(&folds, dataset, config, build_model)
+}
+
+/*
+ * Embaralha índices com a semente fornecida (None usa um gerador
+ * aleatório).
+ */
+fn shuffle_indices(indices: &mut [usize], seed: Option<u64>) {
+    match seed {
+        Some(seed) => indices.shuffle(&mut seeded_rng(seed)),
+        None => indices.shuffle(&mut default_rng()),
+    }
+}
+
+/*
+ * Treina e avalia um modelo por fold e monta o CvReport.
+ *
+ * Parâmetros:
+ *   folds - índices das amostras de validação de cada fold
+ *   (demais parâmetros como em cross_validate)
+ */
+fn run_folds<T: Scalar, M: Model<T, Output = T>>(
+    folds: &[Vec<usize>],
+    dataset: &Dataset<T>,
+    config: &TrainConfig<T>,
+    build_model: impl Fn() -> M
+) -> Result<CvReport<T>, CvError> {
+    let mut fold_costs = Vec::with_capacity(folds.len());
+
+    for (fold, valid_indices) in folds.iter().enumerate() {
+        let train_indices: Vec<usize> = folds
+            .iter()
+            .enumerate()
+            .filter(|&(other, _)| other != fold)
+            .flat_map(|(_, indices)| indices.iter().copied())
+            .collect();
+        let train_data = dataset.subset(&train_indices);
+        let valid_data = dataset.subset(valid_indices);
+
+        // Os pesos das amostras acompanham as amostras de treino do fold
+        let mut fold_config = config.clone();
+        if let Some(weights) = &config.sample_weights
+            && weights.len() == dataset.len()
+        {
+            fold_config.sample_weights = Some(train_indices.iter().map(|&i| weights[i]).collect());
+        }
+
+        let mut model = build_model();
+        let fold_error = |source| CvError::Fit { fold, source: Box::new(source) };
+        model.fit(&train_data, &fold_config).map_err(fold_error)?;
+        let out_pred = valid_data
+            .inputs()
+            .iter()
+            .map(|sample| model.predict(sample))
+            .collect::<Result<Vec<T>, CeptronError>>()
+            .map_err(fold_error)?;
+        fold_costs.push(mse(valid_data.targets(), &out_pred, valid_data.len()));
+    }
+
+    let n: T = from_f64(folds.len() as f64);
+    let mean = fold_costs.iter().fold(T::zero(), |sum, &cost| sum + cost) / n;
+    let variance = fold_costs
+        .iter()
+        .fold(T::zero(), |sum, &cost| sum + (cost - mean) * (cost - mean))
+        / n;
+
+    Ok(CvReport { fold_costs, mean, std: variance.sqrt() })
+}
+
+/*
+ * Erro retornado quando um rótulo de classe está fora do
+ * intervalo 0..n_classes.
+ *
+ * Campos:
+ *   index - posição da amostra com o rótulo inválido
+ *   label - o rótulo encontrado
+ *   n_classes - número de classes esperado
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClassLabelError {
+    pub index: usize,
+    pub label: usize,
+    pub n_classes: usize,
+}
+
+impl fmt::Display for ClassLabelError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "rótulo {} da amostra {} fora do intervalo 0..{}",
+            self.label, self.index, self.n_classes
+        )
+    }
+}
+
+impl std::error::Error for ClassLabelError {}
+
+/*
+ * Erros de OneVsRest::fit.
+ *
+ * Variantes:
+ *   ClassLabel(ClassLabelError) - rótulo fora do intervalo 0..n_classes
+ *   LabelCount { samples, labels } - número de rótulos diferente do
+ *                                    número de amostras
+ *   Empty - nenhuma amostra, ou amostras sem entradas
+ *   Fit { class, source } - o treinamento do neurônio da classe class
+ *                           falhou
+ */
+#[derive(Debug)]
+pub enum OneVsRestError {
+    ClassLabel(ClassLabelError),
+    LabelCount { samples: usize, labels: usize },
+    Empty,
+    Fit { class: usize, source: FitError },
+}
+
+impl fmt::Display for OneVsRestError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            OneVsRestError::ClassLabel(err) => write!(f, "{}", err),
+            OneVsRestError::LabelCount { samples, labels } => write!(
+                f,
+                "{} rótulos para {} amostras",
+                labels, samples
+            ),
+            OneVsRestError::Empty => write!(f, "não há amostras com entradas para treinar"),
+            OneVsRestError::Fit { class, source } => write!(f, "classe {}: {}", class, source),
+        }
+    }
+}
 
-    let param = ParamType::Bias;
-    gradient = compute_gradient(neuron, cost, x, y, param, sample_size);
-    neuron.bias -= 0.001 * gradient;
+impl std::error::Error for OneVsRestError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            OneVsRestError::ClassLabel(err) => Some(err),
+            OneVsRestError::Fit { source, .. } => Some(source),
+            OneVsRestError::LabelCount { .. } | OneVsRestError::Empty => None,
+        }
+    }
+}
+
+impl From<ClassLabelError> for OneVsRestError {
+    fn from(err: ClassLabelError) -> Self {
+        OneVsRestError::ClassLabel(err)
+    }
+}
+
+/*
+ * Classificador multiclasse um-contra-todos (one-vs-rest).
+ *
+ * Treina um neurônio sigmoid por classe, cada um aprendendo a
+ * separar sua classe (alvo 1.0) de todas as outras (alvo 0.0).
+ * A classe prevista é a do neurônio com a maior saída.
+ *
+ * Campos:
+ *   classifiers - um neurônio por classe, na ordem dos rótulos
+ */
+pub struct OneVsRest<T: 'static = Float> {
+    pub classifiers: Vec<Neuron<T>>,
+}
 
-}
\ No newline at end of file
+impl<T: Scalar> OneVsRest<T> {
+
+    /*
+     * Treina um classificador um-contra-todos.
+     *
+     * Parâmetros:
+     *   n_classes - número de classes (rótulos válidos: 0..n_classes)
+     *   x - vetor de vetores contendo as entradas de cada amostra
+     *   y_labels - rótulo de classe de cada amostra
+     *   config - configuração de treinamento de cada neurônio
+     *
+     * Retorno:
+     *   O classificador treinado, ou OneVsRestError se x e y_labels
+     *   tiverem tamanhos diferentes, se não houver amostras (ou elas
+     *   não tiverem entradas), se algum rótulo for inválido ou se o
+     *   treinamento de alguma classe falhar
+     *
+     * Com config.seed definido, os pesos iniciais do neurônio de cada
+     * classe vêm de config.derived_seed(classe), e o resultado é
+     * reproduzível.
+     */
+    pub fn fit(
+        n_classes: usize,
+        x: &[Vec<T>],
+        y_labels: &[usize],
+        config: &TrainConfig<T>
+    ) -> Result<Self, OneVsRestError> {
+        if x.len() != y_labels.len() {
+            return Err(OneVsRestError::LabelCount { samples: x.len(), labels: y_labels.len() });
+        }
+        let n_connections = x.first().map_or(0, |row| row.len());
+        if n_connections == 0 {
+            return Err(OneVsRestError::Empty);
+        }
+        if let Some((index, &label)) = y_labels
+            .iter()
+            .enumerate()
+            .find(|&(_, &label)| label >= n_classes)
+        {
+            return Err(ClassLabelError { index, label, n_classes }.into());
+        }
+
+        let sample_size = y_labels.len();
+        let mut classifiers = Vec::with_capacity(n_classes);
+
+        for class in 0..n_classes {
+            let targets: Vec<T> = y_labels
+                .iter()
+                .map(|&label| if label == class { T::one() } else { T::zero() })
+                .collect();
+
+            let mut builder = NeuronBuilder::new(n_connections).activation(Activation::Sigmoid);
+            if let Some(seed) = config.derived_seed(class as u64) {
+                builder = builder.seed(seed);
+            }
+            let mut neuron = builder.build().expect("n_connections > 0: a inicialização padrão é válida");
+            fit_impl(&mut neuron, mse, x, &targets, sample_size, config, None)
+                .map_err(|source| OneVsRestError::Fit { class, source })?;
+            classifiers.push(neuron);
+        }
+
+        Ok(Self { classifiers })
+    }
+
+    /*
+     * Calcula a saída de cada neurônio para uma amostra.
+     *
+     * Parâmetros:
+     *   x - vetor de entrada
+     *
+     * Retorno:
+     *   Um escore por classe, na ordem dos rótulos
+     */
+    pub fn predict_proba(&self, x: &[T]) -> Vec<T> {
+        self.classifiers
+            .iter()
+            .map(|neuron| neuron.compute_out(x))
+            .collect()
+    }
+
+    /*
+     * Prevê a classe de uma amostra.
+     *
+     * Parâmetros:
+     *   x - vetor de entrada
+     *
+     * Retorno:
+     *   O rótulo cujo neurônio produziu a maior saída
+     *   (em caso de empate, o menor rótulo)
+     */
+    pub fn predict(&self, x: &[T]) -> usize {
+        let scores = self.predict_proba(x);
+        let mut best = 0;
+
+        for class in 1..scores.len() {
+            if scores[class] > scores[best] {
+                best = class;
+            }
+        }
+        best
+    }
+}
+
+/*
+ * Erros de export_predictions.
+ *
+ * Variantes:
+ *   Io - falha ao gravar o arquivo
+ *   Input(InputDimError) - amostra com número de entradas diferente
+ *                          do número de conexões do neurônio
+ *   HeaderNames { expected, actual } - header_names sem um nome por
+ *                                      entrada
+ */
+#[derive(Debug)]
+pub enum ExportError {
+    Io(std::io::Error),
+    Input(InputDimError),
+    HeaderNames { expected: usize, actual: usize },
+}
+
+impl fmt::Display for ExportError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ExportError::Io(err) => write!(f, "falha ao gravar as predições: {}", err),
+            ExportError::Input(err) => write!(f, "{}", err),
+            ExportError::HeaderNames { expected, actual } => write!(
+                f,
+                "{} nomes de coluna para {} entradas",
+                actual, expected
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ExportError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ExportError::Io(err) => Some(err),
+            ExportError::Input(err) => Some(err),
+            ExportError::HeaderNames { .. } => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for ExportError {
+    fn from(err: std::io::Error) -> Self {
+        ExportError::Io(err)
+    }
+}
+
+impl From<InputDimError> for ExportError {
+    fn from(err: InputDimError) -> Self {
+        ExportError::Input(err)
+    }
+}
+
+/*
+ * Grava as predições do neurônio para cada amostra em um arquivo CSV,
+ * para análise em outras ferramentas:
+ *   x1,x2,y_true,y_pred,residual
+ *   6,1,1,0.9812,0.0188
+ *
+ * residual = y_true - y_pred. Os valores são escritos com a menor
+ * representação que lê de volta o mesmo número, e nomes com vírgula,
+ * aspas ou quebra de linha vão entre aspas (como Dataset::from_csv
+ * os lê).
+ *
+ * Parâmetros:
+ *   neuron - neurônio treinado
+ *   dataset - amostras a serem avaliadas
+ *   path - caminho do arquivo (sobrescrito se existir)
+ *   header_names - nome de cada entrada, ou None para usar os nomes
+ *                  do dataset (ex: o cabeçalho do CSV) ou, se ele
+ *                  não tiver nomes, x1, x2, ...
+ *
+ * Retorno:
+ *   Ok(()), ou ExportError se header_names não tiver um nome por
+ *   entrada, se as amostras não tiverem uma entrada por conexão ou
+ *   se o arquivo não puder ser gravado
+ */
+pub fn export_predictions<T: Scalar>(
+    neuron: &Neuron<T>,
+    dataset: &Dataset<T>,
+    path: &Path,
+    header_names: Option<&[String]>,
+) -> Result<(), ExportError> {
+    let n_inputs = neuron.n_connections();
+    if dataset.n_features() != n_inputs {
+        return Err(InputDimError { expected: n_inputs, actual: dataset.n_features() }.into());
+    }
+    let mut columns: Vec<String> = match header_names.or(dataset.feature_names()) {
+        Some(names) if names.len() != n_inputs => {
+            return Err(ExportError::HeaderNames { expected: n_inputs, actual: names.len() });
+        }
+        Some(names) => names.to_vec(),
+        None => (1..=n_inputs).map(|i| format!("x{}", i)).collect(),
+    };
+    columns.extend(["y_true", "y_pred", "residual"].map(String::from));
+
+    let header: Vec<String> = columns.iter().map(|name| quote_csv_field(name, ',')).collect();
+    let mut csv = header.join(",");
+    csv.push('\n');
+    for (x, &y_true) in dataset.inputs().iter().zip(dataset.targets()) {
+        let y_pred = neuron.try_compute_out(x)?;
+        let mut row: Vec<String> = x.iter().map(|value| value.to_string()).collect();
+        row.extend([y_true, y_pred, y_true - y_pred].map(|value| value.to_string()));
+        csv.push_str(&row.join(","));
+        csv.push('\n');
+    }
+    write_file(path, csv)?;
+    Ok(())
+}
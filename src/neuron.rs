@@ -6,31 +6,320 @@
  * Este módulo implementa:
  *   - Estrutura Neuron (perceptron)
  *   - Inicialização de neurônios com pesos aleatórios
- *   - Computação da saída do neurônio (forward pass)
+ *     (uniforme, Xavier/Glorot ou He)
+ *   - Computação da saída do neurônio (forward pass), com entradas
+ *     densas, iteradores ou esparsas
+ *   - Precisão genérica (f32 ou f64) via netmath::Scalar
+ *   - Construtor fluente NeuronBuilder
+ *   - Predição sobre matrizes do ndarray (feature "ndarray") e do
+ *     nalgebra (feature "nalgebra")
  */
 
-use crate::utils::randomize;
+use core::any::Any;
+use core::fmt;
+
+#[cfg(not(feature = "std"))]
+use alloc::{boxed::Box, string::{String, ToString}, vec, vec::Vec};
+
+#[cfg(feature = "nalgebra")]
+use nalgebra::{DMatrix, DVector};
+#[cfg(feature = "ndarray")]
+use ndarray::{Array1, ArrayView2};
+
+use rand::Rng;
+
+use crate::netmath::{from_f64, Activation, Float, Scalar};
+use crate::utils::{default_rng, randn_with, randomize_with, seeded_rng};
+
+/*
+ * Erro retornado quando o vetor de entrada não tem exatamente
+ * um valor por conexão do neurônio.
+ *
+ * Campos:
+ *   expected - número de conexões do neurônio
+ *   actual - tamanho do vetor de entrada recebido
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub struct InputDimError {
+    pub expected: usize,
+    pub actual: usize,
+}
+
+impl fmt::Display for InputDimError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "entrada com {} valores, mas o neurônio espera {}",
+            self.actual, self.expected
+        )
+    }
+}
+
+impl core::error::Error for InputDimError {}
+
+/*
+ * Erro retornado quando uma linha de um lote de amostras tem
+ * tamanho diferente do número de conexões do neurônio.
+ *
+ * Campos:
+ *   index - índice da primeira linha inválida
+ *   source - os tamanhos esperado e recebido dessa linha
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub struct BatchInputError {
+    pub index: usize,
+    pub source: InputDimError,
+}
+
+impl fmt::Display for BatchInputError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "amostra {}: {}", self.index, self.source)
+    }
+}
+
+impl core::error::Error for BatchInputError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        Some(&self.source)
+    }
+}
+
+/*
+ * Erro retornado quando uma entrada esparsa é inválida.
+ *
+ * Variantes:
+ *   LengthMismatch { indices, values } - indices e values com
+ *                                        tamanhos diferentes
+ *   IndexOutOfRange { position, index, n_connections } - índice
+ *                                        sem peso correspondente
+ *   NotIncreasing { position, index } - índice repetido ou fora de
+ *                                       ordem (os índices devem ser
+ *                                       estritamente crescentes)
+ *   Batch { sample, source } - erro na amostra `sample` de um lote
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub enum SparseInputError {
+    LengthMismatch { indices: usize, values: usize },
+    IndexOutOfRange { position: usize, index: usize, n_connections: usize },
+    NotIncreasing { position: usize, index: usize },
+    Batch { sample: usize, source: Box<SparseInputError> },
+}
+
+impl fmt::Display for SparseInputError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SparseInputError::LengthMismatch { indices, values } => write!(
+                f,
+                "entrada esparsa com {} índices e {} valores",
+                indices, values
+            ),
+            SparseInputError::IndexOutOfRange { position, index, n_connections } => write!(
+                f,
+                "índice {} (posição {}) não existe em um neurônio com {} conexões",
+                index, position, n_connections
+            ),
+            SparseInputError::NotIncreasing { position, index } => write!(
+                f,
+                "índice {} (posição {}) repetido ou fora de ordem",
+                index, position
+            ),
+            SparseInputError::Batch { sample, source } => {
+                write!(f, "amostra {}: {}", sample, source)
+            }
+        }
+    }
+}
+
+impl core::error::Error for SparseInputError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            SparseInputError::Batch { source, .. } => Some(source.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+/*
+ * Erro retornado por checked_compute_out.
+ *
+ * Variantes:
+ *   Input(InputDimError) - vetor de entrada com tamanho inválido
+ *   NonFinite { value } - a saída calculada é NaN ou infinita
+ *                         (parâmetros ou entradas divergentes)
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub enum OutputError<T = Float> {
+    Input(InputDimError),
+    NonFinite { value: T },
+}
+
+impl<T: fmt::Display> fmt::Display for OutputError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            OutputError::Input(err) => write!(f, "{}", err),
+            OutputError::NonFinite { value } => write!(f, "saída do neurônio não é finita ({})", value),
+        }
+    }
+}
+
+impl<T: fmt::Debug + fmt::Display + 'static> core::error::Error for OutputError<T> {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            OutputError::Input(err) => Some(err),
+            OutputError::NonFinite { .. } => None,
+        }
+    }
+}
+
+impl<T> From<InputDimError> for OutputError<T> {
+    fn from(err: InputDimError) -> Self {
+        OutputError::Input(err)
+    }
+}
+
+/*
+ * Erros de construção e validação de um neurônio.
+ *
+ * Variantes:
+ *   InvalidRange { min, max } - intervalo de inicialização vazio
+ *                               ou com limites não finitos
+ *   EmptyWeights - vetor de pesos sem nenhuma conexão
+ *   NonFiniteWeight { index, value } - peso NaN ou infinito
+ *   NonFiniteBias { value } - bias NaN ou infinito
+ *   NonFiniteDelta { param, delta } - incremento NaN ou infinito
+ *   WeightIndexOutOfRange { index, n_connections } - peso inexistente
+ *   WeightCountMismatch { expected, actual } - número de pesos diferente
+ *                                              do número de conexões
+ *   NoBias - tentativa de alterar o bias de um neurônio sem bias
+ *   UnknownActivation { name } - nome que não é de uma ativação
+ *                                conhecida (ver Activation::from_name)
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub enum NeuronError<T = Float> {
+    InvalidRange { min: T, max: T },
+    EmptyWeights,
+    NonFiniteWeight { index: usize, value: T },
+    NonFiniteBias { value: T },
+    NonFiniteDelta { param: ParamType, delta: T },
+    WeightIndexOutOfRange { index: usize, n_connections: usize },
+    WeightCountMismatch { expected: usize, actual: usize },
+    NoBias,
+    UnknownActivation { name: String },
+}
+
+impl<T: fmt::Display> fmt::Display for NeuronError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            NeuronError::InvalidRange { min, max } => write!(
+                f,
+                "intervalo de inicialização inválido [{}, {}): os limites devem ser finitos e min < max",
+                min, max
+            ),
+            NeuronError::EmptyWeights => {
+                write!(f, "o neurônio precisa de pelo menos um peso")
+            }
+            NeuronError::NonFiniteWeight { index, value } => {
+                write!(f, "peso {} não é finito ({})", index, value)
+            }
+            NeuronError::NonFiniteBias { value } => {
+                write!(f, "bias não é finito ({})", value)
+            }
+            NeuronError::NonFiniteDelta { param, delta } => {
+                write!(f, "incremento não finito ({}) para o parâmetro {:?}", delta, param)
+            }
+            NeuronError::WeightIndexOutOfRange { index, n_connections } => write!(
+                f,
+                "peso {} não existe em um neurônio com {} conexões",
+                index, n_connections
+            ),
+            NeuronError::WeightCountMismatch { expected, actual } => write!(
+                f,
+                "{} pesos fornecidos para um neurônio com {} conexões",
+                actual, expected
+            ),
+            NeuronError::NoBias => write!(f, "o neurônio não tem bias"),
+            NeuronError::UnknownActivation { name } => write!(f, "ativação desconhecida: \"{}\"", name),
+        }
+    }
+}
+
+impl<T: fmt::Debug + fmt::Display> core::error::Error for NeuronError<T> {}
+
+/*
+ * Enum que representa o tipo de parâmetro a ser ajustado.
+ *
+ * Variantes:
+ *   Weight(usize) - representa um peso específico pelo seu índice
+ *   Bias - representa o bias do neurônio
+ */
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ParamType {
+    Weight(usize),
+    Bias,
+}
 
 /*
  * Estrutura que representa um neurônio (Perceptron).
  *
  * Campos:
  *   weights - vetor de pesos para cada conexão de entrada
- *   n_connections - número de conexões (entradas) do neurônio
  *   bias - termo de viés (deslocamento) adicionado à soma ponderada
- *   act_func - função de ativação aplicada à saída
+ *   activation - função de ativação aplicada à saída
+ *   init - esquema usado para sortear os parâmetros iniciais
+ *          (None se os parâmetros foram fornecidos pelo usuário)
+ *   generation - contador incrementado a cada reset, para que
+ *                estados externos (ex: otimizadores) saibam que os
+ *                parâmetros foram sorteados novamente
+ *   use_bias - false para um neurônio sem bias (modelo homogêneo):
+ *              o bias fica fixo em 0.0 e não é treinado
  *
  * A saída do neurônio é calculada como:
- *   output = act_func(sum(x[i] * weights[i]) + bias)
+ *   output = activation(sum(x[i] * weights[i]) + bias)
+ *
+ * Os campos são privados: o número de conexões é sempre
+ * weights.len(), e só pode ser definido na construção.
+ *
+ * O tipo T (Float por padrão: f32, ou f64 com a feature "f64") é
+ * usado em pesos, bias, entradas e saídas; ver netmath::Scalar.
+ */
+pub struct Neuron<T: 'static = Float> {
+    weights: Vec<T>,            // Pesos das conexões
+    bias: T,                    // Termo de viés
+    activation: Activation<T>,  // Função de ativação
+    init: Option<InitScheme<T>>, // Esquema de inicialização
+    generation: u64,            // Número de resets
+    use_bias: bool              // Se o bias participa da saída
+}
+
+/*
+ * Neurônios de precisão simples e dupla.
+ *
+ * Neuron sem parâmetro de tipo é o mesmo que Neuron<Float>: Neuron32,
+ * ou Neuron64 com a feature "f64".
+ */
+pub type Neuron32 = Neuron<f32>;
+pub type Neuron64 = Neuron<f64>;
+
+/*
+ * Esquema de inicialização dos parâmetros de um neurônio.
+ *
+ * Variantes:
+ *   Uniform { min, max } - pesos e bias em Uniform(min, max)
+ *   Xavier { fan_out } - pesos em Uniform(±sqrt(6 / (fan_in + fan_out))),
+ *                        bias igual a 0.0
+ *   He - pesos em Normal(0, sqrt(2 / fan_in)), bias igual a 0.0
+ *        (indicado para neurônios ReLU)
+ *   Constant { weight, bias } - todos os pesos iguais a weight e o
+ *                               bias igual a bias, sem sorteio
  */
-pub struct Neuron {
-    pub weights: Vec<f32>,          // Pesos das conexões
-    pub n_connections: u32,         // Número de entradas
-    pub bias: f32,                  // Termo de viés
-    pub act_func: fn(f32) -> f32    // Função de ativação
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum InitScheme<T = Float> {
+    Uniform { min: T, max: T },
+    Xavier { fan_out: usize },
+    He,
+    Constant { weight: T, bias: T },
 }
 
-impl Neuron {
+impl<T: Scalar> Neuron<T> {
     
     /*
      * Computa o valor de saída do neurônio.
@@ -41,42 +330,1314 @@ impl Neuron {
      *
      * Retorno:
      *   Valor de saída do neurônio
+     *
+     * Entra em pânico se x não tiver exatamente n_connections
+     * valores (ver try_compute_out).
      */
     
-    pub fn compute_out(&self, x: &Vec<f32>) -> f32 {
-        let mut weighted_sum = 0.0;
-        
-        for i in 0..self.n_connections {
-            weighted_sum += x[i as usize] * self.weights[i as usize];
+    pub fn compute_out(&self, x: &[T]) -> T {
+        match self.try_compute_out(x) {
+            Ok(out) => out,
+            Err(err) => panic!("compute_out: {}", err),
+        }
+    }
+
+    /*
+     * Computa o valor de saída do neurônio validando a entrada.
+     *
+     * Parâmetros:
+     *   x - vetor de entrada (um valor por conexão)
+     *
+     * Retorno:
+     *   Valor de saída do neurônio, ou InputDimError se o tamanho
+     *   de x for diferente de n_connections (maior ou menor)
+     */
+    pub fn try_compute_out(&self, x: &[T]) -> Result<T, InputDimError> {
+        self.try_weighted_sum(x).map(|sum| self.activation.apply(sum))
+    }
+
+    /*
+     * Computa o valor de saída do neurônio validando a entrada e
+     * o resultado.
+     *
+     * Parâmetros:
+     *   x - vetor de entrada (um valor por conexão)
+     *
+     * Retorno:
+     *   Valor de saída do neurônio, OutputError::Input se o tamanho
+     *   de x for inválido, ou OutputError::NonFinite se a saída for
+     *   NaN ou infinita
+     */
+    pub fn checked_compute_out(&self, x: &[T]) -> Result<T, OutputError<T>> {
+        let value = self.try_compute_out(x)?;
+        if !value.is_finite() {
+            return Err(OutputError::NonFinite { value });
+        }
+        Ok(value)
+    }
+
+    /*
+     * Computa a saída do neurônio para uma entrada esparsa, sem
+     * montar o vetor denso: Σ(values[k] * weights[indices[k]]) + bias.
+     * As entradas ausentes valem 0.0.
+     *
+     * Parâmetros:
+     *   indices - posições das entradas não nulas, estritamente
+     *             crescentes (o que também rejeita duplicatas)
+     *   values - valor de cada entrada não nula
+     *
+     * Retorno:
+     *   Valor de saída do neurônio, ou SparseInputError se os
+     *   tamanhos forem diferentes ou algum índice for inválido
+     */
+    pub fn compute_out_sparse(&self, indices: &[usize], values: &[T]) -> Result<T, SparseInputError> {
+        if indices.len() != values.len() {
+            return Err(SparseInputError::LengthMismatch {
+                indices: indices.len(),
+                values: values.len(),
+            });
+        }
+
+        let n_connections = self.n_connections();
+        let mut weighted_sum = T::zero();
+        let mut previous: Option<usize> = None;
+
+        for (position, (&index, &value)) in indices.iter().zip(values).enumerate() {
+            if index >= n_connections {
+                return Err(SparseInputError::IndexOutOfRange { position, index, n_connections });
+            }
+            if previous.is_some_and(|previous| index <= previous) {
+                return Err(SparseInputError::NotIncreasing { position, index });
+            }
+            previous = Some(index);
+            weighted_sum += value * self.weights[index];
+        }
+        if self.use_bias {
+            weighted_sum += self.bias;
+        }
+        Ok(self.activation.apply(weighted_sum))
+    }
+
+    /*
+     * Computa a saída do neurônio para várias amostras esparsas.
+     *
+     * Parâmetros:
+     *   x - uma amostra por item, no formato (indices, values)
+     *       de compute_out_sparse
+     *
+     * Retorno:
+     *   Uma saída por amostra, ou SparseInputError::Batch indicando
+     *   a primeira amostra inválida
+     */
+    pub fn predict_batch_sparse(&self, x: &[(Vec<usize>, Vec<T>)]) -> Result<Vec<T>, SparseInputError> {
+        x.iter()
+            .enumerate()
+            .map(|(sample, (indices, values))| {
+                self.compute_out_sparse(indices, values)
+                    .map_err(|source| SparseInputError::Batch { sample, source: Box::new(source) })
+            })
+            .collect()
+    }
+
+    /*
+     * Computa a saída do neurônio para várias amostras.
+     *
+     * Parâmetros:
+     *   x - vetor de vetores contendo as entradas de cada amostra
+     *
+     * Retorno:
+     *   Uma saída por amostra, ou BatchInputError indicando a
+     *   primeira amostra com tamanho inválido
+     */
+    pub fn predict_batch(&self, x: &[Vec<T>]) -> Result<Vec<T>, BatchInputError> {
+        let mut out = Vec::with_capacity(x.len());
+        self.predict_batch_into(x, &mut out)?;
+        Ok(out)
+    }
+
+    /*
+     * Computa a saída do neurônio para várias amostras, reutilizando
+     * um vetor do chamador (evita alocação em laços de treinamento).
+     *
+     * Parâmetros:
+     *   x - vetor de vetores contendo as entradas de cada amostra
+     *   out - vetor de saída; seu conteúdo anterior é descartado e,
+     *         ao final, contém uma saída por amostra
+     *
+     * Retorno:
+     *   Ok(()), ou BatchInputError indicando a primeira amostra com
+     *   tamanho inválido (nesse caso out fica vazio)
+     */
+    pub fn predict_batch_into(&self, x: &[Vec<T>], out: &mut Vec<T>) -> Result<(), BatchInputError> {
+        out.clear();
+
+        for (index, sample) in x.iter().enumerate() {
+            match self.try_compute_out(sample) {
+                Ok(value) => out.push(value),
+                Err(source) => {
+                    out.clear();
+                    return Err(BatchInputError { index, source });
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /*
+     * Valor de decisão do classificador: o mesmo que compute_out,
+     * com um nome que deixa o código de classificação mais claro.
+     */
+    pub fn decision_value(&self, x: &[T]) -> T {
+        self.compute_out(x)
+    }
+
+    /*
+     * Classifica uma amostra comparando a saída com um limiar.
+     * Pensado para neurônios com ativação sigmoid.
+     *
+     * Parâmetros:
+     *   x - vetor de entrada
+     *   threshold - limiar de decisão
+     *
+     * Retorno:
+     *   true se compute_out(x) >= threshold (a saída exatamente no
+     *   limiar conta como positiva), false caso contrário
+     */
+    pub fn predict_label(&self, x: &[T], threshold: T) -> bool {
+        self.compute_out(x) >= threshold
+    }
+
+    /*
+     * Classifica uma amostra com o limiar padrão 0.5.
+     */
+    pub fn predict_label_default(&self, x: &[T]) -> bool {
+        self.predict_label(x, from_f64(0.5))
+    }
+
+    /*
+     * Classifica várias amostras comparando cada saída com um limiar.
+     *
+     * Parâmetros:
+     *   x - vetor de vetores contendo as entradas de cada amostra
+     *   threshold - limiar de decisão (>= é positivo)
+     *
+     * Retorno:
+     *   Um rótulo por amostra
+     *
+     * Entra em pânico se alguma amostra tiver tamanho inválido.
+     */
+    pub fn predict_labels(&self, x: &[Vec<T>], threshold: T) -> Vec<bool> {
+        match self.predict_batch(x) {
+            Ok(out) => out.into_iter().map(|value| value >= threshold).collect(),
+            Err(err) => panic!("predict_labels: {}", err),
+        }
+    }
+
+    /*
+     * Computa a soma ponderada das entradas mais o bias, antes
+     * da função de ativação: Σ(x[i] * weights[i]) + bias.
+     *
+     * Parâmetros:
+     *   x - vetor de entrada
+     *
+     * Retorno:
+     *   A pré-ativação do neurônio (compute_out = activation(weighted_sum))
+     *
+     * Entra em pânico se x não tiver exatamente n_connections valores.
+     */
+    pub fn weighted_sum(&self, x: &[T]) -> T {
+        match self.try_weighted_sum(x) {
+            Ok(sum) => sum,
+            Err(err) => panic!("weighted_sum: {}", err),
+        }
+    }
+
+    /*
+     * Computa a soma ponderada validando a entrada.
+     *
+     * Parâmetros:
+     *   x - vetor de entrada (um valor por conexão)
+     *
+     * Retorno:
+     *   A pré-ativação do neurônio, ou InputDimError se o tamanho
+     *   de x for diferente de n_connections
+     */
+    pub fn try_weighted_sum(&self, x: &[T]) -> Result<T, InputDimError> {
+        self.weighted_sum_iter(x.iter().copied())
+    }
+
+    /*
+     * Computa a saída do neurônio consumindo as entradas de um
+     * iterador, sem exigir um Vec por amostra.
+     *
+     * Parâmetros:
+     *   x - qualquer fonte de valores (iterador, map sobre um
+     *       parser, etc.) com um valor por conexão
+     *
+     * Retorno:
+     *   Valor de saída do neurônio, ou InputDimError se x produzir
+     *   menos ou mais valores do que n_connections. Valores a mais
+     *   são um erro, e não ignorados: para informar o tamanho
+     *   recebido, o restante do iterador é contado (portanto x
+     *   precisa ser finito).
+     */
+    pub fn compute_out_iter<I: IntoIterator<Item = T>>(&self, x: I) -> Result<T, InputDimError> {
+        self.weighted_sum_iter(x).map(|sum| self.activation.apply(sum))
+    }
+
+    /*
+     * Implementação única da soma ponderada: as versões com slice
+     * delegam para cá.
+     */
+    fn weighted_sum_iter<I: IntoIterator<Item = T>>(&self, x: I) -> Result<T, InputDimError> {
+        let expected = self.n_connections();
+        let mut inputs = x.into_iter();
+        let mut weighted_sum = T::zero();
+
+        for (actual, weight) in self.weights.iter().enumerate() {
+            match inputs.next() {
+                Some(input) => weighted_sum += input * *weight,
+                None => return Err(InputDimError { expected, actual }),
+            }
+        }
+        if inputs.next().is_some() {
+            let actual = expected + 1 + inputs.count();
+            return Err(InputDimError { expected, actual });
+        }
+
+        if self.use_bias {
+            weighted_sum += self.bias;
         }
-        weighted_sum += self.bias;
-        (self.act_func)(weighted_sum)
+        Ok(weighted_sum)
     }
     
     /*
      * Cria um neurônio e inicializa seus pesos e bias.
      *
      * Parâmetros:
-     *   act_func - a função de ativação do neurônio
+     *   activation - a função de ativação do neurônio
      *   n_connections - número de conexões do neurônio
      *
      * Retorno:
      *   O neurônio criado.
+     *
+     * Exige a feature "std-rand" (sem ela, use new_seeded).
      */
-    pub fn new(act_func: fn(f32) -> f32, n_connections: u32) -> Self {
-        let mut weights: Vec<f32> = Vec::new();
-    
-        for _i in 0..n_connections {
-            weights.push(randomize(-1.0, 1.0));
+    #[cfg(feature = "std-rand")]
+    pub fn new(activation: Activation<T>, n_connections: usize) -> Self {
+        let init = InitScheme::Uniform { min: -T::one(), max: T::one() };
+        Self::with_init(activation, n_connections, init, &mut default_rng())
+    }
+
+    /*
+     * Como new, mas sorteando os pesos e o bias com o gerador
+     * fornecido (ex: um utils::CeptronRng compartilhado pelo
+     * experimento).
+     *
+     * Parâmetros:
+     *   activation - a função de ativação do neurônio
+     *   n_connections - número de conexões do neurônio
+     *   rng - gerador dos sorteios
+     */
+    pub fn new_with_rng(activation: Activation<T>, n_connections: usize, rng: &mut impl Rng) -> Self {
+        let init = InitScheme::Uniform { min: -T::one(), max: T::one() };
+        Self::with_init(activation, n_connections, init, rng)
+    }
+
+    /*
+     * Cria um neurônio a partir de um ponteiro de função de ativação,
+     * como na API antiga (Neuron::new(sigmoid, n)).
+     *
+     * ident e sigmoid são convertidos para Activation::Identity e
+     * Activation::Sigmoid; outras funções viram Activation::Custom.
+     * A conversão compara endereços de função e pode não reconhecê-los
+     * (ver From<fn(T) -> T> para Activation); nesse caso o neurônio
+     * funciona, mas não pode ser gravado.
+     */
+    #[deprecated(note = "use Neuron::new com um Activation")]
+    #[cfg(feature = "std-rand")]
+    pub fn new_with_fn(act_func: fn(T) -> T, n_connections: usize) -> Self {
+        Self::new(Activation::from(act_func), n_connections)
+    }
+
+    /*
+     * Cria um neurônio com pesos e bias sorteados em [min, max).
+     *
+     * Parâmetros:
+     *   activation - a função de ativação do neurônio
+     *   n_connections - número de conexões do neurônio
+     *   min - limite inferior do intervalo (inclusivo)
+     *   max - limite superior do intervalo (exclusivo)
+     *
+     * Retorno:
+     *   O neurônio criado, NeuronError::EmptyWeights se
+     *   n_connections for 0, ou NeuronError::InvalidRange se os
+     *   limites não forem finitos ou se min >= max
+     *
+     * Exige a feature "std-rand" (sem ela, use new_with_range_seeded).
+     */
+    #[cfg(feature = "std-rand")]
+    pub fn new_with_range(
+        activation: Activation<T>,
+        n_connections: usize,
+        min: T,
+        max: T
+    ) -> Result<Self, NeuronError<T>> {
+        if n_connections == 0 {
+            return Err(NeuronError::EmptyWeights);
         }
-        
-        Self {
-            act_func,
-            n_connections,
-            weights,
-            bias: randomize(-1.0, 1.0)
+        check_range(min, max)?;
+        let init = InitScheme::Uniform { min, max };
+        Ok(Self::with_init(activation, n_connections, init, &mut default_rng()))
+    }
+
+    /*
+     * Como new_with_range, mas sorteando a partir de uma semente
+     * (mesma semente => mesmos parâmetros).
+     */
+    pub fn new_with_range_seeded(
+        activation: Activation<T>,
+        n_connections: usize,
+        min: T,
+        max: T,
+        seed: u64
+    ) -> Result<Self, NeuronError<T>> {
+        if n_connections == 0 {
+            return Err(NeuronError::EmptyWeights);
         }
+        check_range(min, max)?;
+        let init = InitScheme::Uniform { min, max };
+        Ok(Self::with_init(activation, n_connections, init, &mut seeded_rng(seed)))
+    }
+
+    /*
+     * Cria um neurônio com inicialização Xavier/Glorot.
+     *
+     * Os pesos são sorteados em Uniform(-l, l), com
+     * l = sqrt(6 / (fan_in + fan_out)), e o bias começa em 0.0.
+     * Isso evita saturar neurônios sigmoid com muitas entradas.
+     *
+     * Parâmetros:
+     *   activation - a função de ativação do neurônio
+     *   n_connections - número de conexões do neurônio (fan_in)
+     *   fan_out - número de saídas (1 para um neurônio isolado,
+     *             ou o tamanho da camada seguinte em uma rede)
+     *
+     * Retorno:
+     *   O neurônio criado.
+     *
+     * Exige a feature "std-rand" (sem ela, use new_xavier_seeded).
+     */
+    #[cfg(feature = "std-rand")]
+    pub fn new_xavier(activation: Activation<T>, n_connections: usize, fan_out: usize) -> Self {
+        let init = InitScheme::Xavier { fan_out };
+        Self::with_init(activation, n_connections, init, &mut default_rng())
     }
-}
 
+    /*
+     * Como new_xavier, mas sorteando a partir de uma semente.
+     */
+    pub fn new_xavier_seeded(activation: Activation<T>, n_connections: usize, fan_out: usize, seed: u64) -> Self {
+        let init = InitScheme::Xavier { fan_out };
+        Self::with_init(activation, n_connections, init, &mut seeded_rng(seed))
+    }
 
+    /*
+     * Cria um neurônio com inicialização He (Kaiming) normal.
+     *
+     * Os pesos são sorteados em Normal(0, sqrt(2 / fan_in)) e o
+     * bias começa em 0.0. Indicado para ativações ReLU.
+     *
+     * Parâmetros:
+     *   activation - a função de ativação do neurônio
+     *   n_connections - número de conexões do neurônio (fan_in)
+     *
+     * Retorno:
+     *   O neurônio criado.
+     *
+     * Exige a feature "std-rand" (sem ela, use new_he_seeded).
+     */
+    #[cfg(feature = "std-rand")]
+    pub fn new_he(activation: Activation<T>, n_connections: usize) -> Self {
+        Self::with_init(activation, n_connections, InitScheme::He, &mut default_rng())
+    }
+
+    /*
+     * Como new_he, mas sorteando a partir de uma semente.
+     */
+    pub fn new_he_seeded(activation: Activation<T>, n_connections: usize, seed: u64) -> Self {
+        Self::with_init(activation, n_connections, InitScheme::He, &mut seeded_rng(seed))
+    }
+
+    /*
+     * Cria um neurônio com a inicialização padrão (Uniform(-1, 1))
+     * a partir de uma semente, de forma reproduzível.
+     *
+     * Parâmetros:
+     *   activation - a função de ativação do neurônio
+     *   n_connections - número de conexões do neurônio
+     *   seed - semente do gerador (ver utils::SeededRng)
+     *
+     * Retorno:
+     *   O neurônio criado (mesma semente => mesmos parâmetros)
+     */
+    pub fn new_seeded(activation: Activation<T>, n_connections: usize, seed: u64) -> Self {
+        let init = InitScheme::Uniform { min: -T::one(), max: T::one() };
+        Self::with_init(activation, n_connections, init, &mut seeded_rng(seed))
+    }
+
+    /*
+     * Cria um neurônio com todos os pesos e o bias iguais a 0.0.
+     *
+     * Parâmetros:
+     *   activation - a função de ativação do neurônio
+     *   n_connections - número de conexões do neurônio
+     *
+     * Retorno:
+     *   O neurônio criado (determinístico, sem sorteio)
+     */
+    pub fn zeros(activation: Activation<T>, n_connections: usize) -> Self {
+        Self::constant(activation, n_connections, T::zero(), T::zero())
+    }
+
+    /*
+     * Cria um neurônio com parâmetros constantes.
+     *
+     * Parâmetros:
+     *   activation - a função de ativação do neurônio
+     *   n_connections - número de conexões do neurônio
+     *   weight - valor de todos os pesos
+     *   bias - valor do bias
+     *
+     * Retorno:
+     *   O neurônio criado (determinístico, sem sorteio)
+     */
+    pub fn constant(activation: Activation<T>, n_connections: usize, weight: T, bias: T) -> Self {
+        if n_connections == 0 {
+            panic!("Neuron: {}", NeuronError::<T>::EmptyWeights);
+        }
+
+        Self {
+            activation,
+            init: Some(InitScheme::Constant { weight, bias }),
+            generation: 0,
+            use_bias: true,
+            weights: vec![weight; n_connections],
+            bias
+        }
+    }
+
+    /*
+     * Cria um neurônio a partir de pesos e bias conhecidos.
+     *
+     * O número de conexões é inferido de weights.len().
+     *
+     * Parâmetros:
+     *   activation - a função de ativação do neurônio
+     *   weights - pesos de cada conexão (o vetor é consumido)
+     *   bias - valor do bias
+     *
+     * Retorno:
+     *   O neurônio criado, ou NeuronError se weights estiver vazio
+     *   ou se algum parâmetro não for finito
+     */
+    pub fn from_weights(
+        activation: Activation<T>,
+        weights: Vec<T>,
+        bias: T
+    ) -> Result<Self, NeuronError<T>> {
+        if weights.is_empty() {
+            return Err(NeuronError::EmptyWeights);
+        }
+        if let Some((index, &value)) = weights.iter().enumerate().find(|(_, w)| !w.is_finite()) {
+            return Err(NeuronError::NonFiniteWeight { index, value });
+        }
+        if !bias.is_finite() {
+            return Err(NeuronError::NonFiniteBias { value: bias });
+        }
+
+        Ok(Self {
+            activation,
+            init: None,
+            generation: 0,
+            use_bias: true,
+            weights,
+            bias
+        })
+    }
+
+    /*
+     * Cria um neurônio sorteando pesos e bias conforme o esquema
+     * de inicialização escolhido, usando o gerador fornecido.
+     *
+     * Assim como from_weights, recusa n_connections == 0: um
+     * neurônio sem pesos não poderia ser carregado de volta por
+     * load/load_bin. Os construtores infalíveis entram em pânico
+     * com a mensagem de NeuronError::EmptyWeights.
+     */
+    fn with_init(
+        activation: Activation<T>,
+        n_connections: usize,
+        init: InitScheme<T>,
+        rng: &mut impl Rng
+    ) -> Self {
+        if n_connections == 0 {
+            panic!("Neuron: {}", NeuronError::<T>::EmptyWeights);
+        }
+
+        let mut draw = || match init {
+            InitScheme::Uniform { min, max } => randomize_with(rng, min, max),
+            InitScheme::Xavier { fan_out } => {
+                let limit = (from_f64::<T>(6.0) / from_f64((n_connections + fan_out) as f64)).sqrt();
+                randomize_with(rng, -limit, limit)
+            }
+            InitScheme::He => {
+                let std = (from_f64::<T>(2.0) / from_f64(n_connections as f64)).sqrt();
+                randn_with(rng, T::zero(), std)
+            }
+            InitScheme::Constant { weight, .. } => weight,
+        };
+
+        let mut weights: Vec<T> = Vec::new();
+
+        for _i in 0..n_connections {
+            weights.push(draw());
+        }
+
+        let bias = match init {
+            InitScheme::Uniform { .. } => draw(),
+            InitScheme::Xavier { .. } | InitScheme::He => T::zero(),
+            InitScheme::Constant { bias, .. } => bias,
+        };
+
+        Self {
+            activation,
+            init: Some(init),
+            generation: 0,
+            use_bias: true,
+            weights,
+            bias
+        }
+    }
+
+    /*
+     * Retorna os pesos das conexões, na ordem das entradas.
+     */
+    pub fn weights(&self) -> &[T] {
+        &self.weights
+    }
+
+    /*
+     * Retorna o bias do neurônio.
+     */
+    pub fn bias(&self) -> T {
+        self.bias
+    }
+
+    /*
+     * Retorna a norma L2 dos pesos (o bias não entra no cálculo).
+     */
+    pub fn weight_norm(&self) -> T {
+        let mut sum_squares = T::zero();
+        for weight in &self.weights {
+            sum_squares += *weight * *weight;
+        }
+        sum_squares.sqrt()
+    }
+
+    /*
+     * Aplica a restrição max-norm: se a norma L2 dos pesos passar
+     * de max_norm, o vetor de pesos é reescalado para ter norma
+     * exatamente max_norm (a direção e o bias não mudam).
+     *
+     * Parâmetros:
+     *   max_norm - norma máxima permitida
+     *
+     * Retorno:
+     *   true se os pesos foram reescalados
+     */
+    pub fn clip_weight_norm(&mut self, max_norm: T) -> bool {
+        let norm = self.weight_norm();
+        if norm <= max_norm {
+            return false;
+        }
+
+        let scale = max_norm / norm;
+        for weight in &mut self.weights {
+            *weight *= scale;
+        }
+        true
+    }
+
+    /*
+     * Verifica se todos os pesos e o bias são finitos (nem NaN
+     * nem infinitos).
+     */
+    pub fn is_finite(&self) -> bool {
+        self.first_non_finite().is_none()
+    }
+
+    /*
+     * Retorna o primeiro parâmetro NaN ou infinito (pesos na ordem
+     * das entradas, depois o bias), ou None se todos forem finitos.
+     */
+    pub fn first_non_finite(&self) -> Option<ParamType> {
+        if let Some(index) = self.weights.iter().position(|w| !w.is_finite()) {
+            return Some(ParamType::Weight(index));
+        }
+        if !self.bias.is_finite() {
+            return Some(ParamType::Bias);
+        }
+        None
+    }
+
+    /*
+     * Compara dois neurônios com tolerância.
+     *
+     * Parâmetros:
+     *   other - neurônio a ser comparado
+     *   tol - diferença absoluta máxima aceita em cada peso e no bias
+     *
+     * Retorno:
+     *   true se os dois têm o mesmo número de conexões, a mesma
+     *   função de ativação, o mesmo uso de bias e todos os
+     *   parâmetros diferem no máximo tol
+     */
+    pub fn approx_eq(&self, other: &Neuron<T>, tol: T) -> bool {
+        self.n_connections() == other.n_connections()
+            && self.activation == other.activation
+            && self.use_bias == other.use_bias
+            && (self.bias - other.bias).abs() <= tol
+            && self
+                .weights
+                .iter()
+                .zip(&other.weights)
+                .all(|(a, b)| (*a - *b).abs() <= tol)
+    }
+
+    /*
+     * Resumo estável do modelo, para conferir se dois treinamentos
+     * produziram o mesmo neurônio.
+     *
+     * Hash FNV-1a (64 bits, base 0xcbf29ce484222325, primo
+     * 0x100000001b3) de, em ordem: precisão de T em bits, número de
+     * conexões, nome da ativação ("custom" para Custom), uso de bias,
+     * e o padrão de bits nativo de T (4 bytes para f32, 8 para f64)
+     * de cada peso e do bias. Inteiros em little-endian. Por usar os
+     * bits, -0.0 e 0.0 (e NaNs com payloads diferentes) dão resumos
+     * diferentes; os bits não passam por f64, que silenciaria os NaNs
+     * sinalizadores de f32. O algoritmo não depende da versão do
+     * Rust, ao contrário de DefaultHasher.
+     */
+    pub fn fingerprint(&self) -> u64 {
+        let mut hash: u64 = 0xcbf29ce484222325;
+        let mut feed = |bytes: &[u8]| {
+            for &byte in bytes {
+                hash ^= u64::from(byte);
+                hash = hash.wrapping_mul(0x100000001b3);
+            }
+        };
+        // (bytes, largura): f32 e f64 usam os próprios bits; outro
+        // Scalar passa por f64
+        let bits = |value: T| -> ([u8; 8], usize) {
+            let native: &dyn Any = &value;
+            if let Some(single) = native.downcast_ref::<f32>() {
+                let [a, b, c, d] = single.to_bits().to_le_bytes();
+                ([a, b, c, d, 0, 0, 0, 0], 4)
+            } else if let Some(double) = native.downcast_ref::<f64>() {
+                (double.to_bits().to_le_bytes(), 8)
+            } else {
+                (value.to_f64().unwrap_or(f64::NAN).to_bits().to_le_bytes(), 8)
+            }
+        };
+
+        feed(&(8 * core::mem::size_of::<T>() as u64).to_le_bytes());
+        feed(&(self.n_connections() as u64).to_le_bytes());
+        feed(self.activation.name().unwrap_or("custom").as_bytes());
+        feed(&[u8::from(self.use_bias)]);
+        for &value in self.weights.iter().chain([&self.bias]) {
+            let (bytes, width) = bits(value);
+            feed(&bytes[..width]);
+        }
+        hash
+    }
+
+    /*
+     * Retorna o número de conexões (entradas) do neurônio.
+     */
+    pub fn n_connections(&self) -> usize {
+        self.weights.len()
+    }
+
+    /*
+     * Retorna a função de ativação do neurônio.
+     */
+    pub fn activation(&self) -> &Activation<T> {
+        &self.activation
+    }
+
+    /*
+     * Retorna o esquema (e o intervalo, se uniforme) usado para
+     * inicializar o neurônio, ou None se ele foi criado a partir
+     * de pesos explícitos.
+     */
+    pub fn init(&self) -> Option<InitScheme<T>> {
+        self.init
+    }
+
+    /*
+     * Retorna quantas vezes o neurônio foi reinicializado por reset.
+     */
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /*
+     * Sorteia novamente os pesos e o bias, no próprio neurônio.
+     *
+     * Usa o esquema de inicialização original (ou Uniform(-1, 1)
+     * se o neurônio foi criado com from_weights). O número de
+     * conexões e a função de ativação não mudam, e o contador
+     * generation é incrementado.
+     *
+     * Parâmetros:
+     *   seed - semente do sorteio (Some) ou gerador do sistema (None)
+     *
+     * Retorno:
+     *   Nenhum (modifica o neurônio in-place)
+     */
+    pub fn reset(&mut self, seed: Option<u64>) {
+        let init = self.init.unwrap_or(InitScheme::Uniform { min: -T::one(), max: T::one() });
+        let n_connections = self.n_connections();
+
+        let fresh = match seed {
+            Some(seed) => Self::with_init(self.activation.clone(), n_connections, init, &mut seeded_rng(seed)),
+            None => Self::with_init(self.activation.clone(), n_connections, init, &mut default_rng()),
+        };
+
+        self.weights = fresh.weights;
+        if self.use_bias {
+            self.bias = fresh.bias;
+        }
+        self.generation += 1;
+    }
+
+    /*
+     * Altera o peso de uma conexão.
+     *
+     * Parâmetros:
+     *   i - índice da conexão (entra em pânico se i >= n_connections)
+     *   value - novo valor do peso
+     */
+    pub fn set_weight(&mut self, i: usize, value: T) {
+        self.weights[i] = value;
+    }
+
+    /*
+     * Altera o bias do neurônio.
+     *
+     * Parâmetros:
+     *   value - novo valor do bias
+     *
+     * Entra em pânico se o neurônio não tiver bias (use_bias false).
+     */
+    pub fn set_bias(&mut self, value: T) {
+        if !self.use_bias {
+            panic!("set_bias: {}", NeuronError::<T>::NoBias);
+        }
+        self.bias = value;
+    }
+
+    /*
+     * Indica se o neurônio usa o termo de bias.
+     */
+    pub fn use_bias(&self) -> bool {
+        self.use_bias
+    }
+
+    /*
+     * Remove o bias do neurônio: o bias passa a ser 0.0 e deixa de
+     * ser somado à saída e ajustado pelo treinamento.
+     *
+     * Retorno:
+     *   O próprio neurônio, sem bias
+     */
+    pub fn without_bias(mut self) -> Self {
+        self.use_bias = false;
+        self.bias = T::zero();
+        self
+    }
+
+    /*
+     * Troca a função de ativação por uma ativação nomeada, mantendo
+     * os pesos e o bias. Útil para substituir uma Activation::Custom
+     * equivalente antes de gravar o modelo.
+     *
+     * Parâmetros:
+     *   name - nome da ativação ("identity" ou "sigmoid")
+     *
+     * Retorno:
+     *   O próprio neurônio com a nova ativação, ou
+     *   NeuronError::UnknownActivation se o nome for desconhecido
+     */
+    pub fn with_named_activation(mut self, name: &str) -> Result<Self, NeuronError<T>> {
+        self.activation = Activation::from_name(name)
+            .ok_or_else(|| NeuronError::UnknownActivation { name: name.to_string() })?;
+        Ok(self)
+    }
+
+    /*
+     * Soma um incremento a um parâmetro do neurônio.
+     *
+     * Parâmetros:
+     *   param - parâmetro a ser alterado (Weight(i) ou Bias)
+     *   delta - valor a ser somado
+     *
+     * Retorno:
+     *   Ok(()), ou NeuronError se delta não for finito, se o índice
+     *   do peso não existir ou se o parâmetro for Bias em um neurônio
+     *   sem bias (o neurônio não é alterado)
+     */
+    pub fn apply_delta(&mut self, param: ParamType, delta: T) -> Result<(), NeuronError<T>> {
+        if !delta.is_finite() {
+            return Err(NeuronError::NonFiniteDelta { param, delta });
+        }
+
+        match param {
+            ParamType::Weight(index) => {
+                let n_connections = self.n_connections();
+                match self.weights.get_mut(index) {
+                    Some(weight) => *weight += delta,
+                    None => return Err(NeuronError::WeightIndexOutOfRange { index, n_connections }),
+                }
+            }
+            ParamType::Bias if !self.use_bias => return Err(NeuronError::NoBias),
+            ParamType::Bias => self.bias += delta,
+        }
+        Ok(())
+    }
+
+    /*
+     * Número de parâmetros treináveis: os pesos e, se houver, o bias.
+     */
+    pub fn param_count(&self) -> usize {
+        self.n_connections() + usize::from(self.use_bias)
+    }
+
+    /*
+     * Todos os parâmetros treináveis, na ordem em que o treinamento
+     * os atualiza: Weight(0), ..., Weight(n - 1) e, se houver, Bias.
+     *
+     * O iterador não mantém o neurônio emprestado, então o neurônio
+     * pode ser alterado (ex: set_param) durante a iteração.
+     */
+    pub fn params(&self) -> impl Iterator<Item = ParamType> + use<T> {
+        (0..self.n_connections())
+            .map(ParamType::Weight)
+            .chain(self.use_bias.then_some(ParamType::Bias))
+    }
+
+    /*
+     * Lê um parâmetro do neurônio.
+     *
+     * Parâmetros:
+     *   param - Weight(i) ou Bias
+     *
+     * Retorno:
+     *   O valor do parâmetro, ou NeuronError::WeightIndexOutOfRange /
+     *   NoBias se o parâmetro não existir
+     */
+    pub fn get_param(&self, param: ParamType) -> Result<T, NeuronError<T>> {
+        match param {
+            ParamType::Weight(index) => self.weights.get(index).copied().ok_or(NeuronError::WeightIndexOutOfRange {
+                index,
+                n_connections: self.n_connections(),
+            }),
+            ParamType::Bias if !self.use_bias => Err(NeuronError::NoBias),
+            ParamType::Bias => Ok(self.bias),
+        }
+    }
+
+    /*
+     * Altera um parâmetro do neurônio.
+     *
+     * Parâmetros:
+     *   param - Weight(i) ou Bias
+     *   value - novo valor
+     *
+     * Retorno:
+     *   Ok(()), ou NeuronError se value não for finito ou se o
+     *   parâmetro não existir (o neurônio não é alterado)
+     */
+    pub fn set_param(&mut self, param: ParamType, value: T) -> Result<(), NeuronError<T>> {
+        match param {
+            ParamType::Weight(index) => {
+                let n_connections = self.n_connections();
+                let weight = self
+                    .weights
+                    .get_mut(index)
+                    .ok_or(NeuronError::WeightIndexOutOfRange { index, n_connections })?;
+                if !value.is_finite() {
+                    return Err(NeuronError::NonFiniteWeight { index, value });
+                }
+                *weight = value;
+            }
+            ParamType::Bias if !self.use_bias => return Err(NeuronError::NoBias),
+            ParamType::Bias if !value.is_finite() => return Err(NeuronError::NonFiniteBias { value }),
+            ParamType::Bias => self.bias = value,
+        }
+        Ok(())
+    }
+
+    /*
+     * Substitui todos os parâmetros do neurônio.
+     *
+     * Parâmetros:
+     *   weights - novos pesos (um por conexão)
+     *   bias - novo bias
+     *
+     * Retorno:
+     *   Ok(()), ou NeuronError se o número de pesos for diferente
+     *   de n_connections, se algum valor não for finito ou se bias
+     *   for diferente de 0.0 em um neurônio sem bias
+     *   (o neurônio não é alterado)
+     */
+    pub fn set_params(&mut self, weights: &[T], bias: T) -> Result<(), NeuronError<T>> {
+        let expected = self.n_connections();
+        if weights.len() != expected {
+            return Err(NeuronError::WeightCountMismatch { expected, actual: weights.len() });
+        }
+        if let Some((index, &value)) = weights.iter().enumerate().find(|(_, w)| !w.is_finite()) {
+            return Err(NeuronError::NonFiniteWeight { index, value });
+        }
+        if !bias.is_finite() {
+            return Err(NeuronError::NonFiniteBias { value: bias });
+        }
+        if !self.use_bias && bias != T::zero() {
+            return Err(NeuronError::NoBias);
+        }
+
+        self.weights.copy_from_slice(weights);
+        self.bias = bias;
+        Ok(())
+    }
+}
+
+
+
+/*
+ * Valida um intervalo de inicialização [min, max).
+ *
+ * Retorno:
+ *   Ok(()), ou NeuronError::InvalidRange se algum limite não for
+ *   finito ou se min >= max
+ */
+fn check_range<T: Scalar>(min: T, max: T) -> Result<(), NeuronError<T>> {
+    if !(min.is_finite() && max.is_finite() && min < max) {
+        return Err(NeuronError::InvalidRange { min, max });
+    }
+    Ok(())
+}
+
+/*
+ * Formatação de depuração: mostra todos os campos, com o nome da
+ * função de ativação quando ela é conhecida.
+ */
+impl<T: Scalar> fmt::Debug for Neuron<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Neuron")
+            .field("weights", &self.weights)
+            .field("bias", &self.bias)
+            .field("use_bias", &self.use_bias)
+            .field("n_connections", &self.n_connections())
+            .field("activation", &self.activation.name().unwrap_or("custom"))
+            .field("init", &self.init)
+            .finish()
+    }
+}
+
+/*
+ * Formatação legível, por exemplo:
+ *   Neuron(2 inputs, w=[3.00, 2.00], b=5.00, act=identity)
+ *
+ * A precisão dos valores segue o formato ({:.4}, etc.),
+ * com 2 casas decimais por padrão. Um neurônio sem bias mostra
+ * "b=none".
+ */
+impl<T: Scalar> fmt::Display for Neuron<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let precision = f.precision().unwrap_or(2);
+
+        write!(f, "Neuron({} inputs, w=[", self.n_connections())?;
+        for (i, weight) in self.weights.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{:.*}", precision, weight)?;
+        }
+        write!(f, "], b=")?;
+        if self.use_bias {
+            write!(f, "{:.*}", precision, self.bias)?;
+        } else {
+            write!(f, "none")?;
+        }
+        write!(f, ", act={})", self.activation.name().unwrap_or("custom"))
+    }
+}
+
+/*
+ * Erros de construção pelo NeuronBuilder.
+ *
+ * Variantes:
+ *   Conflict { option, other } - duas opções que não podem ser
+ *                                usadas juntas (ex: weights e init)
+ *   Invalid(NeuronError) - parâmetros inválidos (intervalo vazio,
+ *                          número de pesos errado, valores não finitos)
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub enum BuildError<T = Float> {
+    Conflict { option: &'static str, other: &'static str },
+    Invalid(NeuronError<T>),
+}
+
+impl<T: fmt::Display> fmt::Display for BuildError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BuildError::Conflict { option, other } => {
+                write!(f, "as opções {} e {} não podem ser usadas juntas", option, other)
+            }
+            BuildError::Invalid(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl<T: fmt::Debug + fmt::Display> core::error::Error for BuildError<T> {}
+
+impl<T> From<NeuronError<T>> for BuildError<T> {
+    fn from(err: NeuronError<T>) -> Self {
+        BuildError::Invalid(err)
+    }
+}
+
+/*
+ * Construtor fluente de neurônios.
+ *
+ * Exemplo:
+ *   NeuronBuilder::new(2)
+ *       .activation(Activation::Sigmoid)
+ *       .init(InitScheme::Xavier { fan_out: 1 })
+ *       .seed(42)
+ *       .build()
+ *
+ * Sem nenhuma opção, build() equivale a
+ * Neuron::new(Activation::Identity, n_inputs):
+ * pesos e bias em Uniform(-1, 1) sorteados pelo gerador do sistema.
+ *
+ * Campos:
+ *   n_inputs - número de conexões do neurônio
+ *   activation - função de ativação (padrão: identidade)
+ *   init - esquema de inicialização (padrão: Uniform(-1, 1))
+ *   seed - semente do sorteio (padrão: gerador do sistema)
+ *   weights - pesos e bias explícitos (não combinam com init/seed)
+ *   use_bias - false para construir um neurônio sem bias
+ */
+pub struct NeuronBuilder<T: 'static = Float> {
+    n_inputs: usize,
+    activation: Activation<T>,
+    init: Option<InitScheme<T>>,
+    seed: Option<u64>,
+    weights: Option<(Vec<T>, T)>,
+    use_bias: bool,
+}
+
+impl<T: Scalar> NeuronBuilder<T> {
+
+    /*
+     * Inicia a construção de um neurônio com n_inputs conexões.
+     */
+    pub fn new(n_inputs: usize) -> Self {
+        Self {
+            n_inputs,
+            activation: Activation::Identity,
+            init: None,
+            seed: None,
+            weights: None,
+            use_bias: true,
+        }
+    }
+
+    /*
+     * Define a função de ativação.
+     */
+    pub fn activation(mut self, activation: Activation<T>) -> Self {
+        self.activation = activation;
+        self
+    }
+
+    /*
+     * Define o esquema de inicialização dos parâmetros.
+     */
+    pub fn init(mut self, init: InitScheme<T>) -> Self {
+        self.init = Some(init);
+        self
+    }
+
+    /*
+     * Define a semente do sorteio, para resultados reproduzíveis.
+     */
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /*
+     * Define pesos e bias explícitos em vez de sorteá-los.
+     */
+    pub fn weights(mut self, weights: Vec<T>, bias: T) -> Self {
+        self.weights = Some((weights, bias));
+        self
+    }
+
+    /*
+     * Define se o neurônio terá bias (padrão: true).
+     */
+    pub fn bias(mut self, use_bias: bool) -> Self {
+        self.use_bias = use_bias;
+        self
+    }
+
+    /*
+     * Constrói o neurônio.
+     *
+     * Retorno:
+     *   O neurônio, ou BuildError se opções conflitantes foram
+     *   usadas (weights com init ou seed) ou se algum parâmetro
+     *   for inválido (incluindo n_inputs == 0)
+     */
+    pub fn build(self) -> Result<Neuron<T>, BuildError<T>> {
+        if let Some((weights, bias)) = self.weights {
+            if self.init.is_some() {
+                return Err(BuildError::Conflict { option: "weights", other: "init" });
+            }
+            if self.seed.is_some() {
+                return Err(BuildError::Conflict { option: "weights", other: "seed" });
+            }
+            if weights.len() != self.n_inputs {
+                return Err(BuildError::Invalid(NeuronError::WeightCountMismatch {
+                    expected: self.n_inputs,
+                    actual: weights.len(),
+                }));
+            }
+            if !self.use_bias && bias != T::zero() {
+                return Err(BuildError::Conflict { option: "weights", other: "bias(false)" });
+            }
+            let neuron = Neuron::from_weights(self.activation, weights, bias)?;
+            return Ok(if self.use_bias { neuron } else { neuron.without_bias() });
+        }
+
+        if self.n_inputs == 0 {
+            return Err(BuildError::Invalid(NeuronError::EmptyWeights));
+        }
+        let init = self.init.unwrap_or(InitScheme::Uniform { min: -T::one(), max: T::one() });
+        if let InitScheme::Uniform { min, max } = init {
+            check_range(min, max)?;
+        }
+
+        let neuron = match self.seed {
+            Some(seed) => Neuron::with_init(self.activation, self.n_inputs, init, &mut seeded_rng(seed)),
+            None => Neuron::with_init(self.activation, self.n_inputs, init, &mut default_rng()),
+        };
+        Ok(if self.use_bias { neuron } else { neuron.without_bias() })
+    }
+}
+
+#[cfg(feature = "ndarray")]
+impl<T: Scalar> Neuron<T> {
+
+    /*
+     * Calcula a saída do neurônio para cada linha de uma matriz do
+     * ndarray, sem convertê-la em Vec<Vec<T>>. Aceita views não
+     * contíguas (ex: fatias de colunas) e produz os mesmos valores
+     * que compute_out em cada linha.
+     *
+     * Parâmetros:
+     *   x - uma amostra por linha
+     *
+     * Retorno:
+     *   Uma saída por linha, ou InputDimError se x não tiver uma
+     *   coluna por conexão
+     */
+    pub fn predict_array(&self, x: ArrayView2<T>) -> Result<Array1<T>, InputDimError> {
+        if x.ncols() != self.n_connections() {
+            return Err(InputDimError { expected: self.n_connections(), actual: x.ncols() });
+        }
+        x.rows()
+            .into_iter()
+            .map(|row| self.compute_out_iter(row.iter().copied()))
+            .collect()
+    }
+}
+
+/*
+ * Pesos e bias do neurônio como (Array1, bias).
+ */
+#[cfg(feature = "ndarray")]
+impl<T: Scalar> From<&Neuron<T>> for (Array1<T>, T) {
+    fn from(neuron: &Neuron<T>) -> Self {
+        (Array1::from(neuron.weights.clone()), neuron.bias)
+    }
+}
+
+#[cfg(feature = "nalgebra")]
+impl<T: Scalar + nalgebra::Scalar> Neuron<T> {
+
+    /*
+     * Cria um neurônio a partir de um DVector de pesos (ver
+     * from_weights).
+     *
+     * Parâmetros:
+     *   weights - pesos de cada conexão
+     *   bias - valor do bias
+     *   activation - a função de ativação do neurônio
+     *
+     * Retorno:
+     *   O neurônio criado, ou NeuronError se weights estiver vazio
+     *   ou se algum parâmetro não for finito
+     */
+    pub fn from_dvector(
+        weights: &DVector<T>,
+        bias: T,
+        activation: Activation<T>
+    ) -> Result<Self, NeuronError<T>> {
+        Self::from_weights(activation, weights.iter().copied().collect(), bias)
+    }
+
+    /*
+     * Retorna os pesos como um DVector.
+     */
+    pub fn weights_dvector(&self) -> DVector<T> {
+        DVector::from_column_slice(&self.weights)
+    }
+
+    /*
+     * Calcula a saída do neurônio para cada linha de uma DMatrix
+     * (uma amostra por linha), com os mesmos valores de compute_out.
+     *
+     * Parâmetros:
+     *   x - uma amostra por linha
+     *
+     * Retorno:
+     *   Uma saída por linha, ou InputDimError se x não tiver uma
+     *   coluna por conexão
+     */
+    pub fn predict_matrix(&self, x: &DMatrix<T>) -> Result<DVector<T>, InputDimError> {
+        if x.ncols() != self.n_connections() {
+            return Err(InputDimError { expected: self.n_connections(), actual: x.ncols() });
+        }
+        let out = x
+            .row_iter()
+            .map(|row| self.compute_out_iter(row.iter().copied()))
+            .collect::<Result<Vec<T>, InputDimError>>()?;
+        Ok(DVector::from_vec(out))
+    }
+}
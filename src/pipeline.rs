@@ -0,0 +1,227 @@
+/*
+ * pipeline.rs
+ *
+ * Módulo que agrupa a normalização dos dados e o neurônio treinado.
+ *
+ * Este módulo implementa:
+ *   - Enum Scaler, que unifica MinMaxScaler e StandardScaler
+ *   - Estrutura Pipeline: normaliza as entradas, calcula a saída do
+ *     neurônio e devolve a predição na escala original do alvo
+ *   - O trait Model para Pipeline (ex: para cross_validate)
+ *
+ * Com a feature "serde", o Pipeline inteiro (normalizadores e
+ * neurônio) pode ser serializado.
+ */
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::data::{Dataset, MinMaxScaler, StandardScaler};
+use crate::error::CeptronError;
+use crate::netmath::{mse, Float, Scalar};
+use crate::neuralnet::{fit_on, FitResult, Model, TrainConfig};
+use crate::neuron::{InputDimError, Neuron};
+
+/*
+ * Tipo de normalização das entradas de um Pipeline.
+ *
+ * Variantes:
+ *   MinMax - MinMaxScaler (entradas de treino em [0, 1])
+ *   Standard - StandardScaler (média 0 e desvio padrão 1)
+ */
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ScalerKind {
+    MinMax,
+    Standard,
+}
+
+/*
+ * Normalizador de entradas já ajustado.
+ */
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Scaler<T = Float> {
+    MinMax(MinMaxScaler<T>),
+    Standard(StandardScaler<T>),
+}
+
+impl<T: Scalar> Scaler<T> {
+
+    /*
+     * Ajusta um normalizador do tipo escolhido às entradas de data.
+     */
+    pub fn fit(kind: ScalerKind, data: &Dataset<T>) -> Self {
+        match kind {
+            ScalerKind::MinMax => Scaler::MinMax(MinMaxScaler::fit(data)),
+            ScalerKind::Standard => Scaler::Standard(StandardScaler::fit(data)),
+        }
+    }
+
+    /*
+     * Normaliza as entradas de um conjunto de dados.
+     */
+    pub fn transform(&self, data: &Dataset<T>) -> Result<Dataset<T>, InputDimError> {
+        match self {
+            Scaler::MinMax(scaler) => scaler.transform(data),
+            Scaler::Standard(scaler) => scaler.transform(data),
+        }
+    }
+
+    /*
+     * Normaliza uma amostra.
+     */
+    pub fn transform_sample(&self, x: &[T]) -> Result<Vec<T>, InputDimError> {
+        match self {
+            Scaler::MinMax(scaler) => scaler.transform_sample(x),
+            Scaler::Standard(scaler) => scaler.transform_sample(x),
+        }
+    }
+}
+
+/*
+ * Normalização + neurônio.
+ *
+ * fit ajusta os normalizadores aos dados de treino e treina o
+ * neurônio nos dados transformados; predict recebe entradas na
+ * escala original, aplica a mesma transformação e devolve a saída
+ * na unidade original do alvo. Assim não é preciso lembrar de
+ * normalizar cada nova entrada nem de desfazer a normalização da
+ * predição.
+ *
+ * Campos:
+ *   input_scaling - normalização das entradas (None = nenhuma)
+ *   scale_target - se true, o alvo é padronizado (z-score) no
+ *                  treino e a predição é levada de volta à escala
+ *                  original
+ *   input_scaler - normalizador das entradas ajustado por fit
+ *   target_scaler - padronizador do alvo ajustado por fit
+ *   neuron - o neurônio (treinado por fit)
+ */
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(
+        serialize = "T: Scalar + Serialize",
+        deserialize = "T: Scalar + Deserialize<'de>"
+    ))
+)]
+pub struct Pipeline<T: 'static = Float> {
+    pub input_scaling: Option<ScalerKind>,
+    pub scale_target: bool,
+    pub input_scaler: Option<Scaler<T>>,
+    pub target_scaler: Option<StandardScaler<T>>,
+    pub neuron: Neuron<T>,
+}
+
+impl<T: Scalar> Pipeline<T> {
+
+    /*
+     * Cria um pipeline ainda não ajustado.
+     *
+     * Parâmetros:
+     *   neuron - neurônio a ser treinado (já inicializado)
+     *   input_scaling - normalização das entradas, ou None
+     *   scale_target - se o alvo deve ser padronizado
+     */
+    pub fn new(neuron: Neuron<T>, input_scaling: Option<ScalerKind>, scale_target: bool) -> Self {
+        Self {
+            input_scaling,
+            scale_target,
+            input_scaler: None,
+            target_scaler: None,
+            neuron,
+        }
+    }
+
+    /*
+     * Ajusta os normalizadores e treina o neurônio (mse) nos dados
+     * transformados.
+     *
+     * Parâmetros:
+     *   data - conjunto de treino, na escala original
+     *   config - configuração de treinamento
+     *
+     * Retorno:
+     *   Ok(()), ou o CeptronError de fit
+     */
+    pub fn fit(&mut self, data: &Dataset<T>, config: &TrainConfig<T>) -> Result<(), CeptronError> {
+        self.input_scaler = self.input_scaling.map(|kind| Scaler::fit(kind, data));
+        let mut scaled = match &self.input_scaler {
+            Some(scaler) => scaler.transform(data).expect("o normalizador foi ajustado a data"),
+            None => data.clone(),
+        };
+
+        self.target_scaler = None;
+        if self.scale_target {
+            let mut target_scaler = StandardScaler::fit(data);
+            target_scaler.fit_target(data);
+            let targets = scaled.targets().iter().map(|&y| target_scaler.transform_target(y)).collect();
+            scaled = Dataset::new(scaled.inputs().to_vec(), targets)
+                .expect("a padronização mantém os invariantes do Dataset");
+            self.target_scaler = Some(target_scaler);
+        }
+
+        fit_on(&mut self.neuron, mse, &scaled, config)
+    }
+
+    /*
+     * Calcula a predição para uma entrada na escala original.
+     *
+     * Parâmetros:
+     *   x_raw - vetor de entrada, sem normalização
+     *
+     * Retorno:
+     *   A predição na unidade original do alvo, ou InputDimError se
+     *   x_raw não tiver um valor por entrada
+     */
+    pub fn try_predict(&self, x_raw: &[T]) -> Result<T, InputDimError> {
+        let out = match &self.input_scaler {
+            Some(scaler) => self.neuron.try_compute_out(&scaler.transform_sample(x_raw)?)?,
+            None => self.neuron.try_compute_out(x_raw)?,
+        };
+        Ok(match &self.target_scaler {
+            Some(scaler) => scaler.inverse_transform_target(out),
+            None => out,
+        })
+    }
+
+    /*
+     * Calcula a predição para uma entrada na escala original.
+     *
+     * Entra em pânico se x_raw não tiver um valor por entrada
+     * (ver try_predict).
+     */
+    pub fn predict(&self, x_raw: &[T]) -> T {
+        match self.try_predict(x_raw) {
+            Ok(out) => out,
+            Err(err) => panic!("predict: {}", err),
+        }
+    }
+}
+
+/*
+ * Um Pipeline se comporta como o seu neurônio, com predições na
+ * escala original; o custo de FitResult também é medido nela.
+ */
+impl<T: Scalar> Model<T> for Pipeline<T> {
+    type Output = T;
+
+    fn predict(&self, x: &[T]) -> Result<T, CeptronError> {
+        Ok(self.try_predict(x)?)
+    }
+
+    fn fit(&mut self, data: &Dataset<T>, config: &TrainConfig<T>) -> Result<FitResult<T>, CeptronError> {
+        Pipeline::fit(self, data, config)?;
+        let out_pred: Vec<T> = data
+            .inputs()
+            .iter()
+            .map(|sample| self.try_predict(sample).expect("fit confere o número de entradas"))
+            .collect();
+        Ok(FitResult { epochs: config.epochs, cost: mse(data.targets(), &out_pred, data.len()) })
+    }
+
+    fn num_parameters(&self) -> usize {
+        self.neuron.param_count()
+    }
+}
@@ -0,0 +1,33 @@
+/*
+ * prelude.rs
+ *
+ * Reexporta os tipos e funções de uso mais comum, para que um único
+ *   use perceptron::prelude::*;
+ * baste para criar, treinar, avaliar e gravar um neurônio.
+ *
+ * Itens mais específicos (leitura de CSV, exportações, checkpoints,
+ * normalizadores) continuam nos seus módulos. Sem a feature "std",
+ * apenas os itens de inferência (neurônio e ativações) são
+ * reexportados.
+ */
+
+#[cfg(feature = "std")]
+pub use crate::data::{train_test_split, train_test_split_with, Dataset};
+#[cfg(feature = "std")]
+pub use crate::error::CeptronError;
+#[cfg(feature = "std")]
+pub use crate::modelio::ModelIoError;
+pub use crate::netmath::{ident, mse, sigmoid, Activation, ActivationFn, Float, Scalar};
+#[cfg(feature = "std")]
+pub use crate::netmath::register_activation;
+#[cfg(feature = "std")]
+#[allow(deprecated)]
+pub use crate::neuralnet::{
+    compute_cost, compute_cost_on, compute_gradient, cross_validate, fit, fit_on, train, train_on,
+    try_compute_cost, try_compute_cost_on, try_compute_gradient, try_train, try_train_on, FitError, FitResult,
+    Model, TrainConfig,
+};
+pub use crate::neuron::{InputDimError, Neuron, Neuron32, Neuron64, NeuronBuilder, ParamType};
+#[cfg(feature = "std")]
+pub use crate::pipeline::{Pipeline, ScalerKind};
+pub use crate::utils::CeptronRng;
@@ -0,0 +1,35 @@
+/*
+ * testutil.rs
+ *
+ * Módulo de utilitários para testes com neurônios treinados.
+ *
+ * Este módulo implementa:
+ *   - A macro assert_neuron_approx_eq!, que compara dois neurônios
+ *     com tolerância (ver Neuron::approx_eq) e, em caso de falha,
+ *     mostra os parâmetros de ambos
+ */
+
+/*
+ * Verifica se dois neurônios são aproximadamente iguais.
+ *
+ * Uso:
+ *   assert_neuron_approx_eq!(treinado, esperado, 1e-3);
+ *
+ * Entra em pânico, mostrando os dois neurônios, se eles tiverem
+ * tamanhos ou ativações diferentes ou se algum parâmetro diferir
+ * mais do que a tolerância.
+ */
+#[macro_export]
+macro_rules! assert_neuron_approx_eq {
+    ($left:expr, $right:expr, $tol:expr $(,)?) => {{
+        let left = &$left;
+        let right = &$right;
+        let tol = $tol;
+        if !left.approx_eq(right, tol) {
+            panic!(
+                "neurônios diferentes (tolerância {}):\n  esquerda: {:.6}\n   direita: {:.6}",
+                tol, left, right
+            );
+        }
+    }};
+}
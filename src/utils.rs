@@ -5,9 +5,28 @@
  *
  * Este módulo implementa:
  *   - Geração de números aleatórios para inicialização de pesos
+ *     (distribuições uniforme e normal)
+ *   - Variantes com gerador injetável, para resultados reproduzíveis
+ *   - Gerador SplitMix64, usado sem a feature "std-rand"
+ *   - Escolha do gerador conforme a feature "std-rand" (seeded_rng,
+ *     rng_from e default_rng)
+ *   - CeptronRng, gerador injetável com semente que pode ser
+ *     repassado a inicialização, embaralhamento e geradores de dados,
+ *     e derive_seed, que deriva sub-sementes de uma semente principal
+ *
+ * Com a feature "std-rand" (padrão), os sorteios sem semente usam
+ * o gerador do sistema operacional. Sem ela, o crate não depende de
+ * aleatoriedade do sistema (ex: wasm32-unknown-unknown): todo
+ * sorteio vem de uma semente, e as funções que aceitam semente
+ * opcional usam FALLBACK_SEED quando ela não é informada.
  */
 
-use rand::Rng;
+#[cfg(feature = "std-rand")]
+use rand::rngs::StdRng;
+use rand::distributions::uniform::SampleUniform;
+use rand::{Rng, RngCore, SeedableRng};
+
+use crate::netmath::{from_f64, Scalar};
 
 /*
  * Gera um valor aleatório em um intervalo especificado.
@@ -20,6 +39,246 @@ use rand::Rng;
  *   Um valor aleatório entre min e max
  */
 
-pub fn randomize(min: f32, max: f32) -> f32 {
-    rand::thread_rng().gen_range(min..max)
-}
\ No newline at end of file
+#[cfg(feature = "std-rand")]
+pub fn randomize<T: SampleUniform + PartialOrd>(min: T, max: T) -> T {
+    randomize_with(&mut rand::thread_rng(), min, max)
+}
+
+/*
+ * Gera um valor aleatório em um intervalo usando um gerador
+ * fornecido pelo chamador (ex: StdRng com semente fixa).
+ *
+ * Parâmetros:
+ *   rng - gerador de números aleatórios
+ *   min - limite inferior do intervalo (inclusivo)
+ *   max - limite superior do intervalo (exclusivo)
+ *
+ * Retorno:
+ *   Um valor aleatório entre min e max
+ */
+
+pub fn randomize_with<T: SampleUniform + PartialOrd>(rng: &mut impl Rng, min: T, max: T) -> T {
+    rng.gen_range(min..max)
+}
+
+/*
+ * Gera um valor aleatório com distribuição normal.
+ *
+ * Usa a transformação de Box–Muller sobre dois valores uniformes:
+ *   z = sqrt(-2 ln u1) * cos(2π u2)
+ *
+ * Parâmetros:
+ *   mean - média da distribuição
+ *   std - desvio padrão da distribuição
+ *
+ * Retorno:
+ *   Um valor sorteado de Normal(mean, std)
+ */
+
+#[cfg(feature = "std-rand")]
+pub fn randn<T: Scalar>(mean: T, std: T) -> T {
+    randn_with(&mut rand::thread_rng(), mean, std)
+}
+
+/*
+ * Gera um valor com distribuição normal usando um gerador
+ * fornecido pelo chamador.
+ *
+ * Parâmetros:
+ *   rng - gerador de números aleatórios
+ *   mean - média da distribuição
+ *   std - desvio padrão da distribuição
+ *
+ * Retorno:
+ *   Um valor sorteado de Normal(mean, std)
+ */
+
+pub fn randn_with<T: Scalar>(rng: &mut impl Rng, mean: T, std: T) -> T {
+    // u1 em (0, 1] para que ln(u1) seja finito
+    let u1: T = T::one() - rng.gen_range(T::zero()..T::one());
+    let u2: T = rng.gen_range(T::zero()..T::one());
+    let two: T = from_f64(2.0);
+    let z = (-two * u1.ln()).sqrt() * (two * from_f64(core::f64::consts::PI) * u2).cos();
+    mean + std * z
+}
+
+/*
+ * Gerador pseudoaleatório SplitMix64 (Steele, Lea e Flood, 2014):
+ * pequeno, rápido e sem dependências, mas não criptográfico.
+ *
+ * A cada número, o estado avança 0x9e3779b97f4a7c15 e o resultado é
+ * o estado embaralhado por duas multiplicações e três xor-shifts.
+ */
+#[derive(Debug, Clone)]
+pub struct SplitMix64 {
+    state: u64,
+}
+
+impl RngCore for SplitMix64 {
+    fn next_u32(&mut self) -> u32 {
+        (self.next_u64() >> 32) as u32
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        for chunk in dest.chunks_mut(8) {
+            let bytes = self.next_u64().to_le_bytes();
+            chunk.copy_from_slice(&bytes[..chunk.len()]);
+        }
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+impl SeedableRng for SplitMix64 {
+    type Seed = [u8; 8];
+
+    fn from_seed(seed: Self::Seed) -> Self {
+        Self { state: u64::from_le_bytes(seed) }
+    }
+
+    fn seed_from_u64(state: u64) -> Self {
+        Self { state }
+    }
+}
+
+/*
+ * Gerador usado pelos sorteios com semente: StdRng com a feature
+ * "std-rand", SplitMix64 sem ela. A mesma semente dá resultados
+ * diferentes nos dois modos.
+ */
+#[cfg(feature = "std-rand")]
+pub type SeededRng = StdRng;
+#[cfg(not(feature = "std-rand"))]
+pub type SeededRng = SplitMix64;
+
+/*
+ * Semente usada sem a feature "std-rand" quando nenhuma é informada.
+ */
+pub const FALLBACK_SEED: u64 = 0;
+
+/*
+ * Cria o gerador com semente fixa (ver SeededRng).
+ */
+pub fn seeded_rng(seed: u64) -> SeededRng {
+    SeededRng::seed_from_u64(seed)
+}
+
+/*
+ * Cria o gerador a partir de uma semente opcional: sem semente, usa
+ * a entropia do sistema ("std-rand") ou FALLBACK_SEED.
+ */
+pub fn rng_from(seed: Option<u64>) -> SeededRng {
+    match seed {
+        Some(seed) => seeded_rng(seed),
+        #[cfg(feature = "std-rand")]
+        None => StdRng::from_entropy(),
+        #[cfg(not(feature = "std-rand"))]
+        None => seeded_rng(FALLBACK_SEED),
+    }
+}
+
+/*
+ * Gerador dos sorteios sem semente: o gerador da thread
+ * (rand::thread_rng) com a feature "std-rand", ou SplitMix64 com
+ * FALLBACK_SEED sem ela.
+ */
+#[cfg(feature = "std-rand")]
+pub fn default_rng() -> impl Rng {
+    rand::thread_rng()
+}
+
+#[cfg(not(feature = "std-rand"))]
+pub fn default_rng() -> impl Rng {
+    SplitMix64::seed_from_u64(FALLBACK_SEED)
+}
+
+/*
+ * Gerador injetável do crate: um SeededRng com semente, clonável,
+ * que pode ser passado a qualquer função que aceite `&mut impl Rng`
+ * (Neuron::new_with_rng, Dataset::shuffle_with,
+ * train_test_split_with, ...). Usar o mesmo CeptronRng em todas as
+ * etapas de um experimento o torna reproduzível de ponta a ponta.
+ *
+ * fork cria um gerador independente para uma sub-etapa, sem que o
+ * número de sorteios dela altere a sequência do gerador principal.
+ */
+#[derive(Debug, Clone)]
+pub struct CeptronRng {
+    inner: SeededRng,
+}
+
+impl CeptronRng {
+
+    /*
+     * Cria o gerador com uma semente fixa.
+     */
+    pub fn seeded(seed: u64) -> Self {
+        Self { inner: seeded_rng(seed) }
+    }
+
+    /*
+     * Cria o gerador a partir de uma semente opcional (ver rng_from).
+     */
+    pub fn from_seed_option(seed: Option<u64>) -> Self {
+        Self { inner: rng_from(seed) }
+    }
+
+    /*
+     * Cria o gerador com a entropia do sistema. Exige a feature
+     * "std-rand".
+     */
+    #[cfg(feature = "std-rand")]
+    pub fn from_entropy() -> Self {
+        Self { inner: StdRng::from_entropy() }
+    }
+
+    /*
+     * Cria um gerador independente, com semente sorteada deste
+     * gerador.
+     */
+    pub fn fork(&mut self) -> Self {
+        Self::seeded(self.inner.next_u64())
+    }
+}
+
+impl RngCore for CeptronRng {
+    fn next_u32(&mut self) -> u32 {
+        self.inner.next_u32()
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.inner.next_u64()
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.inner.fill_bytes(dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+        self.inner.try_fill_bytes(dest)
+    }
+}
+
+/*
+ * Deriva uma sub-semente de uma semente principal (um passo do
+ * SplitMix64), de modo que cada etapa (stream = 0, 1, 2, ...) tenha
+ * a sua semente e todas dependam só da principal.
+ *
+ * Parâmetros:
+ *   seed - semente principal
+ *   stream - identificador da etapa
+ */
+pub fn derive_seed(seed: u64, stream: u64) -> u64 {
+    SplitMix64::seed_from_u64(seed ^ stream.wrapping_mul(0x9e3779b97f4a7c15)).next_u64()
+}
@@ -0,0 +1,185 @@
+/*
+ * activation_fn.rs
+ *
+ * Teste de uma ativação definida fora do crate com o trait
+ * ActivationFn: uma leaky ReLU com inclinação própria, usada de ponta
+ * a ponta (treino, predição e gravação pelo nome, no formato binário
+ * e, com a feature "serde", em JSON).
+ */
+
+#![cfg(feature = "std")]
+
+use perceptron::data::Dataset;
+use perceptron::modelio::{Checkpoint, ModelIoError};
+use perceptron::netmath::{mse, register_activation, Activation, ActivationFn, Float};
+use perceptron::neuralnet::{fit_on, try_compute_cost_on, TrainConfig};
+use perceptron::neuron::Neuron;
+
+/*
+ * Leaky ReLU: x para x >= 0, slope * x para x < 0. A inclinação é
+ * decidida em tempo de execução e faz parte do nome.
+ */
+#[derive(Clone)]
+struct LeakyRelu {
+    slope: Float,
+    name: String,
+}
+
+fn leaky(slope: Float) -> LeakyRelu {
+    LeakyRelu { slope, name: format!("leaky_relu_{}", slope) }
+}
+
+impl ActivationFn for LeakyRelu {
+    fn apply(&self, x: Float) -> Float {
+        if x >= 0.0 { x } else { self.slope * x }
+    }
+
+    fn derivative(&self, x: Float) -> Float {
+        if x >= 0.0 { 1.0 } else { self.slope }
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+/*
+ * Amostras de y = leaky(2x1 - x2 + 0.5), com saídas dos dois lados
+ * do joelho.
+ */
+fn leaky_data() -> Dataset {
+    let mut x = Vec::new();
+    for i in 0..6 {
+        for j in 0..6 {
+            x.push(vec![i as Float * 0.4 - 1.0, j as Float * 0.4 - 1.0]);
+        }
+    }
+    let act_func = leaky(0.25);
+    let y = x.iter().map(|sample| act_func.apply(2.0 * sample[0] - sample[1] + 0.5)).collect();
+    Dataset::new(x, y).expect("dados válidos")
+}
+
+#[test]
+fn builtin_activations_implement_the_trait() {
+    let sigmoid: &dyn ActivationFn = &Activation::<Float>::Sigmoid;
+
+    assert_eq!(sigmoid.apply(0.0), 0.5);
+    assert_eq!(sigmoid.derivative(0.0), 0.25);
+    assert_eq!(sigmoid.name(), "sigmoid");
+    assert_eq!(ActivationFn::name(&Activation::<Float>::Identity), "identity");
+}
+
+#[test]
+fn external_activation_is_used_for_outputs() {
+    let neuron = Neuron::from_weights(Activation::from_fn(leaky(0.25)), vec![1.0, 1.0], 0.0).expect("parâmetros finitos");
+
+    assert_eq!(neuron.compute_out(&[2.0, 1.0]), 3.0);
+    assert_eq!(neuron.compute_out(&[-2.0, -1.0]), -0.75);
+    assert_eq!(neuron.activation().name(), Some("leaky_relu_0.25"));
+    assert_eq!(neuron.activation().derivative(-1.0), 0.25);
+}
+
+#[test]
+fn runtime_parameters_are_owned_and_cloned() {
+    let neurons: Vec<Neuron> = [0.1, 0.5]
+        .into_iter()
+        .map(|slope| Neuron::from_weights(Activation::from_fn(leaky(slope)), vec![1.0], 0.0).expect("parâmetros finitos"))
+        .collect();
+    let copy = neurons[1].activation().clone();
+    drop(neurons);
+
+    assert_eq!(copy.apply(-2.0), -1.0);
+    assert_eq!(copy.name(), Some("leaky_relu_0.5"));
+    assert_eq!(copy, Activation::from_fn(leaky(0.5)));
+    assert_ne!(copy, Activation::from_fn(leaky(0.1)));
+}
+
+#[test]
+fn external_activation_trains_end_to_end() {
+    let data = leaky_data();
+    let mut neuron = Neuron::new_seeded(Activation::from_fn(leaky(0.25)), 2, 5);
+    let initial = try_compute_cost_on(&neuron, &data, mse).expect("dados válidos");
+    let config = TrainConfig { epochs: 5000, learning_rate: 0.1, ..TrainConfig::default() };
+    fit_on(&mut neuron, mse, &data, &config).expect("treinamento válido");
+    let cost = try_compute_cost_on(&neuron, &data, mse).expect("dados válidos");
+
+    assert!(cost < initial / 100.0 && cost < 1e-3, "custo inicial {}, final {}", initial, cost);
+    for (&weight, &expected) in neuron.weights().iter().zip(&[2.0, -1.0]) {
+        assert!((weight - expected).abs() < 0.05, "{:?}", neuron.weights());
+    }
+}
+
+#[test]
+fn registered_activation_is_found_by_name() {
+    register_activation::<Float>(&leaky(0.25));
+
+    let found = Activation::<Float>::from_name("leaky_relu_0.25").expect("ativação registrada");
+    assert_eq!(found.apply(-5.0), -1.25);
+    assert!(Activation::<Float>::from_name("leaky_relu_0.5").is_none());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn registered_activation_survives_a_json_round_trip() {
+    register_activation::<Float>(&leaky(0.25));
+    let neuron = Neuron::from_weights(Activation::from_fn(leaky(0.25)), vec![2.0, -1.0], 0.5).expect("parâmetros finitos");
+    let path = std::env::temp_dir().join(format!("ceptron-{}-leaky.json", std::process::id()));
+    neuron.save(&path).expect("gravação");
+    let loaded: Result<Neuron, _> = Neuron::load(&path);
+    std::fs::remove_file(&path).ok();
+    let loaded = loaded.expect("leitura");
+
+    assert_eq!(loaded.activation().name(), Some("leaky_relu_0.25"));
+    for sample in [[1.0, 0.0], [-1.0, 1.0]] {
+        assert_eq!(loaded.compute_out(&sample), neuron.compute_out(&sample));
+    }
+}
+
+#[test]
+fn registered_activation_survives_a_binary_round_trip() {
+    register_activation::<Float>(&leaky(0.25));
+    let neuron = Neuron::from_weights(Activation::from_fn(leaky(0.25)), vec![2.0, -1.0], 0.5).expect("parâmetros finitos");
+    let loaded: Neuron = Neuron::from_bytes(&neuron.to_bytes().expect("ativação nomeada")).expect("leitura");
+
+    assert_eq!(loaded.activation().name(), Some("leaky_relu_0.25"));
+    assert_eq!(loaded.compute_out(&[-1.0, 1.0]), neuron.compute_out(&[-1.0, 1.0]));
+
+    let path = std::env::temp_dir().join(format!("ceptron-{}-leaky.ckpt", std::process::id()));
+    Checkpoint::save(&path, 3, 7, &neuron).expect("gravação");
+    let checkpoint: Result<Checkpoint, _> = Checkpoint::load(&path);
+    std::fs::remove_file(&path).ok();
+    let checkpoint = checkpoint.expect("leitura");
+
+    assert_eq!(checkpoint.neuron.activation().name(), Some("leaky_relu_0.25"));
+    assert_eq!(checkpoint.neuron.weights(), neuron.weights());
+}
+
+#[test]
+fn unregistered_activation_is_named_when_reading() {
+    let neuron = Neuron::from_weights(Activation::from_fn(leaky(0.75)), vec![1.0], 0.0).expect("parâmetros finitos");
+    let result = Neuron::<Float>::from_bytes(&neuron.to_bytes().expect("ativação nomeada"));
+
+    match result {
+        Err(ModelIoError::UnregisteredActivation { name }) => assert_eq!(name, "leaky_relu_0.75"),
+        other => panic!("esperado UnregisteredActivation, obtido {:?}", other.map(|_| ())),
+    }
+}
+
+#[test]
+fn code_generators_reject_named_activations() {
+    let neuron = Neuron::from_weights(Activation::from_fn(leaky(0.25)), vec![1.0], 0.0).expect("parâmetros finitos");
+
+    let results = [
+        neuron.to_onnx().map(|_| ()),
+        neuron.to_rust_source("model").map(|_| ()),
+        neuron.to_c_header("model").map(|_| ()),
+    ];
+    for (result, expected) in results.into_iter().zip(["ONNX", "Rust", "C"]) {
+        match result {
+            Err(ModelIoError::UnsupportedActivation { name, format }) => {
+                assert_eq!((name.as_str(), format), ("leaky_relu_0.25", expected));
+            }
+            other => panic!("esperado UnsupportedActivation, obtido {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,188 @@
+/*
+ * checkpoint.rs
+ *
+ * Testes dos checkpoints de fit e da retomada com fit_resume.
+ */
+
+#![cfg(feature = "std")]
+
+use std::path::PathBuf;
+
+use perceptron::data::{make_linear, Dataset};
+use perceptron::error::CeptronError;
+use perceptron::modelio::{Checkpoint, ModelIoError};
+use perceptron::netmath::{mse, Activation, Float};
+use perceptron::neuralnet::{fit_on, fit_resume, FitError, TrainConfig};
+use perceptron::neuron::Neuron;
+
+/*
+ * Caminho de um arquivo temporário exclusivo deste teste.
+ */
+fn temp_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("ceptron-{}-{}", std::process::id(), name))
+}
+
+/*
+ * Amostras com ruído de y = 3x1 + 2x2 + 5.
+ */
+fn data() -> Dataset {
+    make_linear(30, &[3.0, 2.0], 5.0, 0.1, (0.0, 1.0), 3)
+        .expect("parâmetros válidos")
+        .data
+}
+
+fn config(epochs: usize) -> TrainConfig {
+    TrainConfig { epochs, learning_rate: 0.05, seed: Some(3), ..TrainConfig::default() }
+}
+
+/*
+ * Treino sem interrupção, do neurônio sorteado com a semente 3.
+ */
+fn uninterrupted(config: &TrainConfig) -> Neuron {
+    let mut neuron = Neuron::new_seeded(Activation::Identity, 2, 3);
+    fit_on(&mut neuron, mse, &data(), config).expect("treinamento válido");
+    neuron
+}
+
+/*
+ * Confere que dois neurônios têm exatamente os mesmos parâmetros.
+ */
+fn assert_bit_identical(a: &Neuron, b: &Neuron) {
+    let bits = |neuron: &Neuron| -> Vec<_> {
+        neuron.weights().iter().chain([&neuron.bias()]).map(|value| value.to_bits()).collect()
+    };
+    assert_eq!(bits(a), bits(b));
+}
+
+/*
+ * Retoma o treino do checkpoint em path até config.epochs.
+ */
+fn resume(path: &PathBuf, config: &TrainConfig) -> Result<Neuron, CeptronError> {
+    let data = data();
+    fit_resume(path, mse, data.inputs(), data.targets(), data.len(), config)
+}
+
+#[test]
+fn killed_run_resumes_bit_for_bit() {
+    let full = config(300);
+    let expected = uninterrupted(&full);
+
+    // o processo "morre" na época 137: o checkpoint tem o estado
+    // dessa época, gravado com o resumo da configuração completa
+    let killed = uninterrupted(&config(137));
+    let path = temp_path("killed.ckpt");
+    Checkpoint::save(&path, 137, full.fingerprint(), &killed).expect("gravação");
+    let resumed = resume(&path, &full);
+    std::fs::remove_file(&path).ok();
+
+    assert_bit_identical(&resumed.expect("retomada válida"), &expected);
+}
+
+#[test]
+fn checkpoint_written_by_fit_resumes_bit_for_bit() {
+    let path = temp_path("fit.ckpt");
+    let full = TrainConfig { checkpoint_every: Some(70), checkpoint_path: Some(path.clone()), ..config(300) };
+    let expected = uninterrupted(&full);
+
+    let checkpoint: Checkpoint = Checkpoint::load(&path).expect("checkpoint gravado por fit");
+    assert_eq!(checkpoint.epoch, 280);
+    assert_eq!(checkpoint.config_hash, full.fingerprint());
+
+    // retoma do checkpoint real da época 280, como se o treino
+    // tivesse morrido antes de terminar as últimas 20 épocas
+    let resumed = resume(&path, &full);
+    let tmp_left = path.with_extension("ckpt.tmp").exists();
+    std::fs::remove_file(&path).ok();
+
+    assert_bit_identical(&resumed.expect("retomada válida"), &expected);
+    assert!(!tmp_left, "o arquivo temporário deve ser renomeado");
+}
+
+#[test]
+fn scale_target_run_resumes_bit_for_bit() {
+    let path = temp_path("scaled.ckpt");
+    let full = TrainConfig {
+        scale_target: true,
+        checkpoint_every: Some(100),
+        checkpoint_path: Some(path.clone()),
+        ..config(250)
+    };
+    let expected = uninterrupted(&full);
+
+    // o checkpoint da época 200 ficou no espaço padronizado
+    let resumed = resume(&path, &full);
+    std::fs::remove_file(&path).ok();
+
+    assert_bit_identical(&resumed.expect("retomada válida"), &expected);
+}
+
+#[test]
+fn mismatched_config_is_rejected() {
+    let path = temp_path("mismatch.ckpt");
+    let original = config(300);
+    Checkpoint::save(&path, 100, original.fingerprint(), &uninterrupted(&config(100))).expect("gravação");
+
+    let other = TrainConfig { learning_rate: 0.01, ..config(300) };
+    let result = resume(&path, &other);
+    std::fs::remove_file(&path).ok();
+
+    match result {
+        Err(CeptronError::Fit(FitError::CheckpointConfig { expected, found })) => {
+            assert_eq!(expected, other.fingerprint());
+            assert_eq!(found, original.fingerprint());
+        }
+        other => panic!("esperado CheckpointConfig, obtido {:?}", other.map(|_| ())),
+    }
+}
+
+#[test]
+fn checkpoint_of_another_width_is_rejected() {
+    let path = temp_path("width.ckpt");
+    let full = config(300);
+    let wide: Neuron = Neuron::new_seeded(Activation::Identity, 3, 3);
+    Checkpoint::save(&path, 100, full.fingerprint(), &wide).expect("gravação");
+
+    let result = resume(&path, &full);
+    std::fs::remove_file(&path).ok();
+    assert!(
+        matches!(result, Err(CeptronError::DimensionMismatch { expected: 3, actual: 2, .. })),
+        "{:?}",
+        result.map(|_| ())
+    );
+}
+
+#[test]
+fn unsavable_checkpoint_fails_before_training() {
+    fn relu(x: Float) -> Float {
+        x.max(0.0)
+    }
+    let path = temp_path("custom.ckpt");
+    let checkpointed = TrainConfig { checkpoint_every: Some(10), checkpoint_path: Some(path.clone()), ..config(100) };
+    let mut neuron = Neuron::from_weights(Activation::Custom(relu), vec![0.5, 0.5], 0.0).expect("parâmetros finitos");
+
+    let result = fit_on(&mut neuron, mse, &data(), &checkpointed);
+    assert!(
+        matches!(result, Err(CeptronError::Fit(FitError::Checkpoint(ModelIoError::UnserializableActivation { .. })))),
+        "{:?}",
+        result
+    );
+    assert_eq!((neuron.weights(), neuron.bias()), (&[0.5, 0.5][..], 0.0));
+    assert!(!path.exists());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn msgpack_checkpoint_resumes_bit_for_bit() {
+    let full = config(300);
+    let expected = uninterrupted(&full);
+
+    let path = temp_path("killed.msgpack");
+    let killed = uninterrupted(&config(137));
+    Checkpoint::save_msgpack(&path, 137, full.fingerprint(), &killed).expect("gravação");
+    let loaded: Result<Checkpoint, _> = Checkpoint::load(&path);
+    let resumed = resume(&path, &full);
+    std::fs::remove_file(&path).ok();
+
+    assert_eq!(loaded.expect("MessagePack reconhecido").epoch, 137);
+    assert_bit_identical(&resumed.expect("retomada válida"), &expected);
+}
@@ -0,0 +1,175 @@
+/*
+ * codegen.rs
+ *
+ * Testes da geração de código com o modelo embutido
+ * (Neuron::to_rust_source e Neuron::to_c_header).
+ */
+
+#![cfg(feature = "std")]
+
+use perceptron::modelio::ModelIoError;
+use perceptron::netmath::{Activation, Float};
+use perceptron::neuron::{Neuron, Neuron32, Neuron64};
+use perceptron::utils::{randomize_with, seeded_rng};
+
+// código gerado por sigmoid_neuron().to_rust_source("sigmoid_model"),
+// compilado junto com este teste
+include!("fixtures/sigmoid_model.rs");
+
+/*
+ * O neurônio cujo código está em fixtures/sigmoid_model.rs.
+ */
+fn sigmoid_neuron() -> Neuron32 {
+    Neuron32::from_weights(Activation::Sigmoid, vec![3.0012, -1.5, 0.1], 4.998).expect("parâmetros finitos")
+}
+
+/*
+ * Avalia o código gerado sem compilá-lo: lê os literais de WEIGHTS
+ * e do bias e refaz a soma na mesma ordem (ativação identidade).
+ */
+fn interpret(source: &str) -> impl Fn(&[Float]) -> Float + use<> {
+    let array = source
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("const WEIGHTS: "))
+        .and_then(|line| line.split_once("= ["))
+        .and_then(|(_, rest)| rest.strip_suffix("];"))
+        .expect("literal de WEIGHTS");
+    let weights: Vec<Float> = array.split(", ").map(|w| w.parse().expect("literal numérico")).collect();
+    let bias: Float = source
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("z += ")?.strip_suffix(';')?.parse().ok())
+        .unwrap_or(0.0);
+
+    move |x: &[Float]| {
+        let mut z: Float = 0.0;
+        for (input, weight) in x.iter().zip(&weights) {
+            z += input * weight;
+        }
+        z + bias
+    }
+}
+
+#[test]
+fn generated_source_matches_compiled_fixture() {
+    let source = sigmoid_neuron().to_rust_source("sigmoid_model").expect("ativação conhecida");
+    assert_eq!(source, include_str!("fixtures/sigmoid_model.rs"));
+}
+
+#[test]
+fn compiled_function_matches_compute_out() {
+    let neuron = sigmoid_neuron();
+    let mut rng = seeded_rng(4);
+    for _i in 0..100 {
+        let x: Vec<f32> = (0..3).map(|_| randomize_with(&mut rng, -5.0, 5.0)).collect();
+        assert_eq!(sigmoid_model(&x).to_bits(), neuron.compute_out(&x).to_bits());
+    }
+}
+
+#[test]
+fn interpreted_literals_match_compute_out() {
+    let mut rng = seeded_rng(8);
+    for seed in 0..10 {
+        let neuron: Neuron = Neuron::new_seeded(Activation::Identity, 4, seed);
+        let generated = interpret(&neuron.to_rust_source("model").expect("ativação conhecida"));
+        for _i in 0..10 {
+            let x: Vec<Float> = (0..4).map(|_| randomize_with(&mut rng, -10.0, 10.0)).collect();
+            assert_eq!(generated(&x).to_bits(), neuron.compute_out(&x).to_bits());
+        }
+    }
+}
+
+#[test]
+fn bias_free_source_has_no_bias_term() {
+    let neuron = sigmoid_neuron().without_bias();
+    let source = neuron.to_rust_source("model").expect("ativação conhecida");
+    assert!(!source.contains("z += 4.998"), "{}", source);
+}
+
+#[test]
+#[should_panic(expected = "número de entradas")]
+fn compiled_function_checks_input_width() {
+    sigmoid_model(&[1.0, 2.0]);
+}
+
+#[test]
+fn invalid_names_and_custom_activations_are_rejected() {
+    fn relu(x: Float) -> Float {
+        x.max(0.0)
+    }
+    let neuron = sigmoid_neuron();
+    for name in ["", "1model", "my-model", "_"] {
+        assert!(matches!(neuron.to_rust_source(name), Err(ModelIoError::InvalidIdentifier(_))), "{:?}", name);
+    }
+
+    let custom = Neuron::from_weights(Activation::Custom(relu), vec![1.0], 0.0).expect("parâmetros finitos");
+    assert!(matches!(custom.to_rust_source("model"), Err(ModelIoError::UnserializableActivation { .. })));
+}
+
+/*
+ * Literais do array prefix_weights de um header gerado.
+ */
+fn c_weight_literals<'a>(header: &'a str, prefix: &str) -> Vec<&'a str> {
+    let start = format!("{}_weights[", prefix);
+    header
+        .lines()
+        .find(|line| line.contains(&start))
+        .and_then(|line| line.split_once("= {"))
+        .and_then(|(_, rest)| rest.strip_suffix("};"))
+        .expect("array de pesos")
+        .split(", ")
+        .collect()
+}
+
+#[test]
+fn c_header_matches_golden_snapshot() {
+    let header = sigmoid_neuron().to_c_header("sensor").expect("ativação conhecida");
+    assert_eq!(header, include_str!("fixtures/sigmoid_model.h"));
+}
+
+#[test]
+fn c_header_arrays_have_one_literal_per_input() {
+    let neuron: Neuron32 = Neuron::new_seeded(Activation::Identity, 7, 2);
+    let header = neuron.to_c_header("model").expect("ativação conhecida");
+
+    assert!(header.contains("#define MODEL_N_INPUTS 7\n"), "{}", header);
+    assert!(header.contains("static const float model_weights[MODEL_N_INPUTS] = {"), "{}", header);
+    assert_eq!(c_weight_literals(&header, "model").len(), 7);
+    assert!(!header.contains("expf"), "{}", header);
+}
+
+#[test]
+fn c_header_float_literals_round_trip() {
+    for seed in 0..20 {
+        let neuron: Neuron32 = Neuron::new_seeded(Activation::Sigmoid, 5, seed);
+        let header = neuron.to_c_header("model").expect("ativação conhecida");
+
+        let weights: Vec<f32> = c_weight_literals(&header, "model")
+            .into_iter()
+            .map(|literal| literal.strip_suffix('f').expect("sufixo f").parse().expect("literal numérico"))
+            .collect();
+        assert_eq!(weights, neuron.weights());
+        let bias = format!("static const float model_bias = {:?}f;", neuron.bias());
+        assert!(header.contains(&bias), "{}", header);
+    }
+}
+
+#[test]
+fn c_header_uses_double_for_f64() {
+    let neuron = Neuron64::from_weights(Activation::Sigmoid, vec![0.1, 0.2], 0.3).expect("parâmetros finitos");
+    let header = neuron.to_c_header("wide").expect("ativação conhecida");
+
+    assert!(header.contains("static const double wide_weights[WIDE_N_INPUTS] = {0.1, 0.2};"), "{}", header);
+    assert!(header.contains("return 1.0 / (1.0 + exp(-z));"), "{}", header);
+}
+
+#[test]
+fn bias_free_c_header_uses_zero_bias() {
+    let header = sigmoid_neuron().without_bias().to_c_header("sensor").expect("ativação conhecida");
+    assert!(header.contains("static const float sensor_bias = 0.0f;"), "{}", header);
+}
+
+#[test]
+fn c_header_rejects_invalid_prefix() {
+    let result = sigmoid_neuron().to_c_header("my model");
+    assert!(matches!(result, Err(ModelIoError::InvalidIdentifier(ref name)) if name == "my model"), "{:?}", result);
+}
@@ -0,0 +1,258 @@
+/*
+ * cross_validation.rs
+ *
+ * Testes da validação cruzada k-fold (neuralnet::cross_validate).
+ */
+
+#![cfg(feature = "std")]
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use perceptron::data::Dataset;
+use perceptron::error::CeptronError;
+use perceptron::netmath::{mse, Activation, Float};
+use perceptron::neuralnet::{
+    cross_validate, cross_validate_stratified, try_compute_cost_on, CvError, FitResult, Model, TrainConfig,
+};
+use perceptron::neuron::Neuron;
+use perceptron::pipeline::{Pipeline, ScalerKind};
+
+/*
+ * Modelo que prevê a média das saídas de treino e registra quantas
+ * amostras recebeu em cada fit.
+ */
+struct MeanModel {
+    mean: Float,
+    train_sizes: Rc<RefCell<Vec<usize>>>,
+}
+
+impl Model for MeanModel {
+    type Output = Float;
+
+    fn predict(&self, _x: &[Float]) -> Result<Float, CeptronError> {
+        Ok(self.mean)
+    }
+
+    fn fit(&mut self, data: &Dataset, config: &TrainConfig) -> Result<FitResult, CeptronError> {
+        self.train_sizes.borrow_mut().push(data.len());
+        self.mean = data.targets().iter().sum::<Float>() / data.len() as Float;
+        let out_pred = vec![self.mean; data.len()];
+        Ok(FitResult { epochs: config.epochs, cost: mse(data.targets(), &out_pred, data.len()) })
+    }
+
+    fn num_parameters(&self) -> usize {
+        1
+    }
+}
+
+/*
+ * Conjunto com x = [i] e y = i * i.
+ */
+fn squares(n: usize) -> Dataset {
+    let x = (0..n).map(|i| vec![i as Float]).collect();
+    let y = (0..n).map(|i| (i * i) as Float).collect();
+    Dataset::new(x, y).expect("dados válidos")
+}
+
+fn config() -> TrainConfig {
+    TrainConfig { epochs: 200, learning_rate: 0.01, seed: Some(2), ..TrainConfig::default() }
+}
+
+#[test]
+fn fold_sizes_differ_by_at_most_one() {
+    let train_sizes = Rc::new(RefCell::new(Vec::new()));
+    let report = cross_validate(3, &squares(10), &config(), || MeanModel { mean: 0.0, train_sizes: train_sizes.clone() })
+        .expect("k válido");
+
+    assert_eq!(report.fold_costs.len(), 3);
+    let fold_sizes: Vec<usize> = train_sizes.borrow().iter().map(|&n| 10 - n).collect();
+    assert_eq!(fold_sizes, [4, 3, 3]);
+}
+
+#[test]
+fn k_equal_to_len_is_leave_one_out() {
+    let data = squares(6);
+    let train_sizes = Rc::new(RefCell::new(Vec::new()));
+    let report = cross_validate(6, &data, &config(), || MeanModel { mean: 0.0, train_sizes: train_sizes.clone() })
+        .expect("k válido");
+
+    assert_eq!(*train_sizes.borrow(), [5; 6]);
+
+    // Cada fold deixa uma amostra de fora; seu custo é o erro
+    // quadrático da média das outras cinco
+    let total: Float = data.targets().iter().sum();
+    let mut expected: Vec<Float> = data
+        .targets()
+        .iter()
+        .map(|&y| {
+            let mean = (total - y) / 5.0;
+            (y - mean) * (y - mean)
+        })
+        .collect();
+    let mut actual = report.fold_costs.clone();
+    expected.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    actual.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    for (a, e) in actual.iter().zip(&expected) {
+        assert!((a - e).abs() <= 1e-4 * e.max(1.0), "{} != {}", a, e);
+    }
+}
+
+#[test]
+fn mean_and_std_summarize_fold_costs() {
+    let report = cross_validate(4, &squares(12), &config(), || Neuron::new_seeded(Activation::Identity, 1, 2))
+        .expect("k válido");
+
+    let n = report.fold_costs.len() as Float;
+    let mean = report.fold_costs.iter().sum::<Float>() / n;
+    let variance = report.fold_costs.iter().map(|c| (c - mean) * (c - mean)).sum::<Float>() / n;
+    assert!((report.mean - mean).abs() <= 1e-5 * mean.abs().max(1.0));
+    assert!((report.std - variance.sqrt()).abs() <= 1e-5 * mean.abs().max(1.0));
+}
+
+#[test]
+fn rejects_invalid_k() {
+    let data = squares(5);
+    for k in [0, 1, 6] {
+        match cross_validate(k, &data, &config(), || Neuron::new_seeded(Activation::Identity, 1, 2)) {
+            Err(CvError::InvalidK { k: reported, len: 5 }) => assert_eq!(reported, k),
+            other => panic!("esperado InvalidK para k = {}, obtido {:?}", k, other.map(|r| r.mean)),
+        }
+    }
+}
+
+/*
+ * Modelo que registra os rótulos (y = classe) de cada fold de
+ * treino.
+ */
+struct RecordingModel {
+    train_targets: Rc<RefCell<Vec<Vec<Float>>>>,
+}
+
+impl Model for RecordingModel {
+    type Output = Float;
+
+    fn predict(&self, _x: &[Float]) -> Result<Float, CeptronError> {
+        Ok(0.0)
+    }
+
+    fn fit(&mut self, data: &Dataset, config: &TrainConfig) -> Result<FitResult, CeptronError> {
+        self.train_targets.borrow_mut().push(data.targets().to_vec());
+        Ok(FitResult { epochs: config.epochs, cost: 0.0 })
+    }
+
+    fn num_parameters(&self) -> usize {
+        0
+    }
+}
+
+#[test]
+fn stratified_folds_keep_every_class_in_training() {
+    // 16 amostras da classe 0 e 4 da classe 1
+    let labels: Vec<usize> = (0..20).map(|i| usize::from(i % 5 == 0)).collect();
+    let x = (0..20).map(|i| vec![i as Float]).collect();
+    let y = labels.iter().map(|&l| l as Float).collect();
+    let data = Dataset::new(x, y).expect("dados válidos");
+
+    let train_targets = Rc::new(RefCell::new(Vec::new()));
+    cross_validate_stratified(4, &data, &labels, &config(), || RecordingModel { train_targets: train_targets.clone() })
+        .expect("k válido");
+
+    for targets in train_targets.borrow().iter() {
+        assert_eq!(targets.len(), 15);
+        assert_eq!(targets.iter().filter(|&&y| y == 1.0).count(), 3);
+    }
+    assert!(matches!(
+        cross_validate_stratified(4, &data, &labels[..10], &config(), || Neuron::new_seeded(Activation::Identity, 1, 2)),
+        Err(CvError::LabelCount { samples: 20, labels: 10 })
+    ));
+}
+
+#[test]
+fn stratified_folds_group_sparse_labels() {
+    // 16 amostras do rótulo 1_000_000_000 e 4 de usize::MAX
+    let labels: Vec<usize> = (0..20).map(|i| if i % 5 == 0 { usize::MAX } else { 1_000_000_000 }).collect();
+    let x = (0..20).map(|i| vec![i as Float]).collect();
+    let y = labels.iter().map(|&l| Float::from(u8::from(l == usize::MAX))).collect();
+    let data = Dataset::new(x, y).expect("dados válidos");
+
+    let train_targets = Rc::new(RefCell::new(Vec::new()));
+    cross_validate_stratified(4, &data, &labels, &config(), || RecordingModel { train_targets: train_targets.clone() })
+        .expect("k válido");
+
+    assert_eq!(train_targets.borrow().len(), 4);
+    for targets in train_targets.borrow().iter() {
+        assert_eq!(targets.len(), 15);
+        assert_eq!(targets.iter().filter(|&&y| y == 1.0).count(), 3);
+    }
+}
+
+#[test]
+fn neuron_fit_reports_its_training_cost() {
+    let data = squares(8);
+    let mut neuron = Neuron::new_seeded(Activation::Identity, 1, 2);
+    let result = Model::fit(&mut neuron, &data, &config()).expect("treinamento válido");
+
+    assert_eq!(result.epochs, 200);
+    assert_eq!(result.cost.to_bits(), try_compute_cost_on(&neuron, &data, mse).expect("dados válidos").to_bits());
+    assert_eq!(Model::predict(&neuron, &[3.0]).expect("uma entrada"), neuron.compute_out(&[3.0]));
+    assert!(matches!(
+        Model::predict(&neuron, &[3.0, 1.0]),
+        Err(CeptronError::DimensionMismatch { expected: 1, actual: 2, row: None })
+    ));
+}
+
+#[test]
+fn num_parameters_counts_the_bias_only_when_used() {
+    let neuron = || -> Neuron { Neuron::new_seeded(Activation::Identity, 3, 2) };
+
+    assert_eq!(neuron().num_parameters(), 4);
+    assert_eq!(neuron().without_bias().num_parameters(), 3);
+    assert_eq!(Pipeline::new(neuron(), None, true).num_parameters(), 4);
+    assert_eq!(Pipeline::new(neuron().without_bias(), Some(ScalerKind::Standard), false).num_parameters(), 3);
+}
+
+#[test]
+fn pipeline_fit_reports_cost_in_original_units() {
+    let data = squares(8);
+    let mut pipeline = Pipeline::new(Neuron::new_seeded(Activation::Identity, 1, 2), Some(ScalerKind::Standard), true);
+    let result = Model::fit(&mut pipeline, &data, &config()).expect("treinamento válido");
+
+    let out_pred: Vec<Float> = data.inputs().iter().map(|sample| pipeline.predict(sample)).collect();
+    assert_eq!(result.cost.to_bits(), mse(data.targets(), &out_pred, data.len()).to_bits());
+    assert!(matches!(Model::predict(&pipeline, &[]), Err(CeptronError::DimensionMismatch { .. })));
+}
+
+#[test]
+fn cross_validate_runs_neurons_and_pipelines() {
+    let data = squares(12);
+    let neuron = || Neuron::new_seeded(Activation::Identity, 1, 2);
+    let plain = cross_validate(3, &data, &config(), neuron).expect("k válido");
+    let scaled = cross_validate(3, &data, &config(), || Pipeline::new(neuron(), Some(ScalerKind::Standard), true))
+        .expect("k válido");
+
+    assert_eq!(plain.fold_costs.len(), 3);
+    assert_eq!(scaled.fold_costs.len(), 3);
+    assert!(plain.fold_costs.iter().chain(&scaled.fold_costs).all(|cost| cost.is_finite()));
+    let again = cross_validate(3, &data, &config(), || Pipeline::new(neuron(), Some(ScalerKind::Standard), true))
+        .expect("k válido");
+    assert_eq!(again.fold_costs, scaled.fold_costs);
+    assert_ne!(scaled.fold_costs, plain.fold_costs);
+}
+
+#[test]
+fn fold_failures_carry_the_model_error() {
+    let data = squares(6);
+    let bad = TrainConfig { learning_rate: -1.0, ..config() };
+    let err = cross_validate(3, &data, &bad, || Neuron::new_seeded(Activation::Identity, 1, 2)).expect_err("taxa negativa");
+
+    match &err {
+        CvError::Fit { fold: 0, source } => assert!(matches!(**source, CeptronError::InvalidConfig(_)), "{:?}", source),
+        other => panic!("esperado Fit no fold 0, obtido {:?}", other),
+    }
+    assert!(std::error::Error::source(&err).is_some());
+
+    // Modelo com o número errado de entradas falha na predição
+    let err = cross_validate(3, &data, &config(), || Neuron::new_seeded(Activation::Identity, 2, 2)).expect_err("largura errada");
+    assert!(matches!(err, CvError::Fit { fold: 0, .. }), "{:?}", err);
+}
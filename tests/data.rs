@@ -0,0 +1,908 @@
+/*
+ * data.rs
+ *
+ * Testes do conjunto de dados (data.rs): construção, leitura de
+ * arquivos e divisão em treino e teste.
+ */
+
+#![cfg(feature = "std")]
+
+use std::fs;
+use std::path::PathBuf;
+
+use perceptron::assert_neuron_approx_eq;
+use perceptron::data::{
+    make_blobs, make_linear, sliding_window, stratified_split, train_test_split, BatchSizeError, BlobError, Column, CsvError, CsvOptions,
+    DataError, DataIssue, Dataset, LibsvmError, SplitError, TryCollectDataset, WindowError,
+};
+use perceptron::error::CeptronError;
+use perceptron::netmath::{mse, Activation, Float};
+use perceptron::neuralnet::{fit, fit_on, try_compute_cost, try_compute_cost_on, TrainConfig};
+use perceptron::neuron::Neuron;
+
+/*
+ * Amostras de y = 3x1 + 2x2 + 5 com entradas inteiras.
+ */
+fn linear_rows() -> (Vec<Vec<Float>>, Vec<Float>) {
+    let x: Vec<Vec<Float>> = vec![vec![6.0, 1.0], vec![5.0, 0.0], vec![4.0, 1.0], vec![1.0, 4.0], vec![1.0, 2.0]];
+    let y = x.iter().map(|s| 3.0 * s[0] + 2.0 * s[1] + 5.0).collect();
+    (x, y)
+}
+
+/*
+ * Grava `contents` em um arquivo temporário exclusivo deste teste.
+ */
+fn temp_file(name: &str, contents: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("ceptron-{}-{}", std::process::id(), name));
+    fs::write(&path, contents).expect("diretório temporário gravável");
+    path
+}
+
+/*
+ * O DataError de uma construção que falhou (None se deu certo).
+ */
+fn data_error<T: std::fmt::Debug>(result: Result<T, CeptronError>) -> Option<DataError> {
+    match result {
+        Ok(_) => None,
+        Err(CeptronError::Data(err)) => Some(err),
+        Err(other) => panic!("esperado CeptronError::Data, obtido {:?}", other),
+    }
+}
+
+fn config() -> TrainConfig {
+    TrainConfig { epochs: 2000, learning_rate: 0.01, ..TrainConfig::default() }
+}
+
+#[test]
+fn new_exposes_the_samples() {
+    let (x, y) = linear_rows();
+    let data = Dataset::new(x.clone(), y.clone()).expect("dados válidos");
+
+    assert_eq!(data.len(), 5);
+    assert_eq!(data.n_features(), 2);
+    assert_eq!(data.inputs(), &x[..]);
+    assert_eq!(data.targets(), &y[..]);
+    assert_eq!(data.sample(3), (&[1.0, 4.0][..], 16.0));
+}
+
+#[test]
+fn new_rejects_empty_data() {
+    assert_eq!(data_error(Dataset::<Float>::new(Vec::new(), Vec::new())), Some(DataError::Empty));
+}
+
+#[test]
+fn new_rejects_length_mismatch() {
+    let (x, mut y) = linear_rows();
+    y.pop();
+    assert_eq!(data_error(Dataset::new(x, y)), Some(DataError::LengthMismatch { inputs: 5, targets: 4 }));
+}
+
+#[test]
+fn new_rejects_ragged_rows() {
+    let (mut x, y) = linear_rows();
+    x[2].push(1.0);
+    assert_eq!(data_error(Dataset::new(x, y)), Some(DataError::RowWidth { row: 2, expected: 2, actual: 3 }));
+}
+
+#[test]
+fn new_rejects_nan() {
+    let (mut x, y) = linear_rows();
+    x[1][1] = Float::NAN;
+    assert_eq!(data_error(Dataset::new(x, y)), Some(DataError::NaN { row: 1, column: Some(1) }));
+
+    let (x, mut y) = linear_rows();
+    y[4] = Float::NAN;
+    assert_eq!(data_error(Dataset::new(x, y)), Some(DataError::NaN { row: 4, column: None }));
+}
+
+#[test]
+fn dataset_training_matches_raw_slices() {
+    let (x, y) = linear_rows();
+    let data = Dataset::new(x.clone(), y.clone()).expect("dados válidos");
+
+    let mut raw = Neuron::new_seeded(Activation::Identity, 2, 3);
+    let mut wrapped = Neuron::new_seeded(Activation::Identity, 2, 3);
+    assert_eq!(try_compute_cost(&raw, &x, &y, mse, x.len()).unwrap(), try_compute_cost_on(&wrapped, &data, mse).unwrap());
+
+    fit(&mut raw, mse, &x, &y, x.len(), &config()).expect("treinamento válido");
+    fit_on(&mut wrapped, mse, &data, &config()).expect("treinamento válido");
+
+    assert_eq!(raw.weights(), wrapped.weights());
+    assert_eq!(raw.bias(), wrapped.bias());
+    assert_eq!(try_compute_cost(&raw, &x, &y, mse, x.len()).unwrap(), try_compute_cost_on(&wrapped, &data, mse).unwrap());
+}
+
+#[test]
+fn csv_round_trip_trains_the_linear_example() {
+    let mut contents = String::from("x1,\"x2\",y\n");
+    for i in 0..20 {
+        let (x1, x2) = ((i % 5) as Float * 0.25, (i / 5) as Float * 0.25);
+        contents += &format!("{},\"{}\",{}\n", x1, x2, 3.0 * x1 + 2.0 * x2 + 5.0);
+    }
+    contents += "\n\n";
+    let path = temp_file("linear.csv", &contents);
+
+    let data = Dataset::<Float>::from_csv(&path, 2, true);
+    fs::remove_file(&path).ok();
+    let data = data.expect("CSV válido");
+    assert_eq!(data.len(), 20);
+    assert_eq!(data.n_features(), 2);
+
+    let mut neuron = Neuron::zeros(Activation::Identity, 2);
+    let config = TrainConfig { epochs: 5000, learning_rate: 0.1, ..TrainConfig::default() };
+    fit_on(&mut neuron, mse, &data, &config).expect("treinamento válido");
+
+    let expected = Neuron::from_weights(Activation::Identity, vec![3.0, 2.0], 5.0).expect("parâmetros finitos");
+    assert_neuron_approx_eq!(neuron, expected, 1e-3);
+}
+
+#[test]
+fn csv_reports_malformed_fields() {
+    let path = temp_file("malformed.csv", "x1,x2,y\n1,2,12\n3,abc,18\n");
+    let result = Dataset::<Float>::from_csv(&path, 2, true);
+    fs::remove_file(&path).ok();
+    match result {
+        Err(CeptronError::Csv(CsvError::Parse { line: 3, column: 2, value })) => assert_eq!(value, "abc"),
+        other => panic!("esperado Parse na linha 3, coluna 2, obtido {:?}", other),
+    }
+
+    let path = temp_file("ragged.csv", "1,2,12\n3,4\n");
+    let result = Dataset::<Float>::from_csv(&path, 2, false);
+    fs::remove_file(&path).ok();
+    assert!(matches!(result, Err(CeptronError::Csv(CsvError::ColumnCount { line: 2, expected: 3, actual: 2 }))), "{:?}", result);
+
+    let path = temp_file("quote.csv", "1,\"2,12\n");
+    let result = Dataset::<Float>::from_csv(&path, 2, false);
+    fs::remove_file(&path).ok();
+    assert!(matches!(result, Err(CeptronError::Csv(CsvError::UnterminatedQuote { line: 1 }))), "{:?}", result);
+}
+
+/*
+ * Conjunto em que cada amostra é identificada pelo próprio valor:
+ * x = [i], y = i.
+ */
+fn tagged(n: usize) -> Dataset {
+    let x = (0..n).map(|i| vec![i as Float]).collect();
+    let y = (0..n).map(|i| i as Float).collect();
+    Dataset::new(x, y).expect("dados válidos")
+}
+
+#[test]
+fn split_sizes_add_up() {
+    let data = tagged(10);
+    let (train, test) = train_test_split(&data, 0.3, Some(1)).expect("fração válida");
+    assert_eq!((train.len(), test.len()), (7, 3));
+}
+
+#[test]
+fn split_is_deterministic_under_a_seed() {
+    let data = tagged(50);
+    let (train_a, test_a) = train_test_split(&data, 0.2, Some(9)).expect("fração válida");
+    let (train_b, test_b) = train_test_split(&data, 0.2, Some(9)).expect("fração válida");
+    assert_eq!(train_a.targets(), train_b.targets());
+    assert_eq!(test_a.targets(), test_b.targets());
+}
+
+#[test]
+fn split_rejects_invalid_fractions() {
+    let data = tagged(10);
+    for fraction in [0.0, 1.0, -0.5, 1.5, f32::NAN] {
+        assert!(matches!(
+            train_test_split(&data, fraction, Some(1)),
+            Err(SplitError::InvalidFraction { .. })
+        ));
+    }
+    assert_eq!(
+        train_test_split(&data, 0.01, Some(1)).err(),
+        Some(SplitError::EmptySplit { n_train: 10, n_test: 0 })
+    );
+}
+
+#[test]
+fn split_halves_are_disjoint_and_cover_the_data() {
+    let data = tagged(40);
+    let (train, test) = train_test_split(&data, 0.25, Some(4)).expect("fração válida");
+
+    for (x, &y) in train.inputs().iter().chain(test.inputs()).zip(train.targets().iter().chain(test.targets())) {
+        assert_eq!(x[0], y, "entrada e saída de uma amostra ficaram separadas");
+    }
+    let mut tags: Vec<Float> = train.targets().iter().chain(test.targets()).copied().collect();
+    tags.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    assert_eq!(tags, data.targets());
+}
+
+/*
+ * Linhas (entradas e saída) ordenadas, para comparar conjuntos
+ * como multiconjuntos de amostras.
+ */
+fn sorted_rows(data: &Dataset) -> Vec<Vec<Float>> {
+    let mut rows: Vec<Vec<Float>> = data
+        .inputs()
+        .iter()
+        .zip(data.targets())
+        .map(|(x, &y)| x.iter().copied().chain([y]).collect())
+        .collect();
+    rows.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    rows
+}
+
+#[test]
+fn shuffle_keeps_inputs_with_their_targets() {
+    let (x, y) = linear_rows();
+    let data = Dataset::new(x, y).expect("dados válidos");
+    let shuffled = data.shuffled(Some(5));
+
+    assert_ne!(shuffled.targets(), data.targets());
+    assert_eq!(sorted_rows(&shuffled), sorted_rows(&data));
+}
+
+#[test]
+fn shuffle_is_reproducible_under_a_seed() {
+    let data = tagged(30);
+    let mut in_place = tagged(30);
+    in_place.shuffle(Some(12));
+
+    assert_eq!(data.shuffled(Some(12)), in_place);
+    assert_ne!(data.shuffled(Some(13)), in_place);
+}
+
+#[test]
+fn shuffling_a_single_sample_is_a_no_op() {
+    let data = tagged(1);
+    assert_eq!(data.shuffled(Some(3)), data);
+    assert_eq!(data.shuffled(None), data);
+}
+
+#[test]
+fn batches_cover_every_sample_once() {
+    let data = tagged(10);
+
+    let sizes: Vec<usize> = data.batches(4, false).expect("lote válido").map(|batch| batch.len()).collect();
+    assert_eq!(sizes, [4, 4, 2]);
+    let seen: Vec<Float> = data.batches(4, false).unwrap().flat_map(|batch| batch.iter().map(|(_, y)| y).collect::<Vec<_>>()).collect();
+    assert_eq!(seen, data.targets());
+
+    let sizes: Vec<usize> = data.batches(4, true).expect("lote válido").map(|batch| batch.len()).collect();
+    assert_eq!(sizes, [4, 4]);
+}
+
+#[test]
+fn shuffled_batches_are_a_seeded_permutation() {
+    let data = tagged(10);
+    let order = |seed| -> Vec<Float> {
+        data.batches_shuffled(3, false, Some(seed))
+            .expect("lote válido")
+            .flat_map(|batch| batch.to_dataset().targets().to_vec())
+            .collect()
+    };
+
+    let mut tags = order(6);
+    assert_eq!(tags, order(6));
+    assert_ne!(tags, data.targets());
+    tags.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    assert_eq!(tags, data.targets());
+    assert_eq!(data.batches_shuffled(3, true, Some(6)).unwrap().map(|batch| batch.len()).sum::<usize>(), 9);
+}
+
+#[test]
+fn batch_views_keep_the_feature_width() {
+    let (x, y) = linear_rows();
+    let data = Dataset::new(x, y).expect("dados válidos");
+
+    for batch in data.batches(2, false).expect("lote válido") {
+        let (inputs, targets) = batch.as_slices().expect("lote não embaralhado");
+        assert_eq!(inputs.len(), targets.len());
+        assert!(inputs.iter().all(|sample| sample.len() == data.n_features()));
+        assert_eq!(batch.to_dataset().n_features(), data.n_features());
+    }
+}
+
+#[test]
+fn batches_reject_zero_size() {
+    let data = tagged(3);
+    assert_eq!(data.batches(0, false).err(), Some(BatchSizeError));
+    assert_eq!(data.batches_shuffled(0, true, Some(1)).err(), Some(BatchSizeError));
+}
+
+#[test]
+fn make_linear_without_noise_is_recovered_by_training() {
+    let generated = make_linear(60, &[1.5, -2.0, 0.5], 3.0, 0.0, (-1.0, 1.0), 21).expect("parâmetros válidos");
+    assert_eq!((generated.data.len(), generated.data.n_features()), (60, 3));
+    assert!(generated.data.inputs().iter().flatten().all(|v| (-1.0..=1.0).contains(v)));
+
+    let mut neuron = Neuron::zeros(Activation::Identity, 3);
+    let config = TrainConfig { epochs: 3000, learning_rate: 0.1, ..TrainConfig::default() };
+    fit_on(&mut neuron, mse, &generated.data, &config).expect("treinamento válido");
+
+    let expected = Neuron::from_weights(Activation::Identity, generated.weights.clone(), generated.bias).expect("parâmetros finitos");
+    assert_neuron_approx_eq!(neuron, expected, 1e-2);
+}
+
+#[test]
+fn make_linear_is_deterministic_under_a_seed() {
+    let generate = |seed| make_linear(20, &[3.0, 2.0], 5.0, 0.5, (0.0, 10.0), seed).expect("parâmetros válidos");
+    assert_eq!(generate(4), generate(4));
+    assert_ne!(generate(4).data, generate(5).data);
+    assert_eq!(make_linear(0, &[1.0], 0.0, 0.0, (0.0, 1.0), 4).err(), Some(DataError::Empty));
+}
+
+fn distance(a: &[Float], b: &[Float]) -> Float {
+    a.iter().zip(b).map(|(p, q)| (p - q) * (p - q)).sum::<Float>().sqrt()
+}
+
+#[test]
+fn make_blobs_balances_labels() {
+    let centers = vec![vec![0.0, 0.0], vec![5.0, 0.0], vec![0.0, 5.0]];
+    let (x, labels) = make_blobs(100, &centers, 0.5, 8).expect("centros válidos");
+
+    assert_eq!(x.len(), 100);
+    let counts: Vec<usize> = (0..3).map(|class| labels.iter().filter(|&&l| l == class).count()).collect();
+    assert_eq!(counts, [34, 33, 33]);
+}
+
+#[test]
+fn make_blobs_points_are_closest_to_their_center() {
+    let centers = vec![vec![0.0, 0.0, 0.0], vec![4.0, 4.0, 0.0], vec![0.0, 4.0, 4.0]];
+    let (x, labels) = make_blobs(90, &centers, 0.3, 2).expect("centros válidos");
+
+    for (class, center) in centers.iter().enumerate() {
+        let members: Vec<&Vec<Float>> = x.iter().zip(&labels).filter(|&(_, &l)| l == class).map(|(p, _)| p).collect();
+        let mean_distance = |c: &[Float]| members.iter().map(|p| distance(p, c)).sum::<Float>() / members.len() as Float;
+        let own = mean_distance(center);
+        for (other, other_center) in centers.iter().enumerate().filter(|&(other, _)| other != class) {
+            assert!(own < mean_distance(other_center), "classe {} mais perto do centro {}", class, other);
+        }
+    }
+}
+
+#[test]
+fn make_blobs_is_reproducible_and_validates_centers() {
+    let centers = vec![vec![1.0, 1.0], vec![4.0, 4.0]];
+    assert_eq!(make_blobs(20, &centers, 0.8, 3), make_blobs(20, &centers, 0.8, 3));
+
+    assert_eq!(make_blobs::<Float>(10, &[], 1.0, 3), Err(BlobError::NoCenters));
+    assert_eq!(
+        make_blobs(10, &[vec![0.0, 0.0], vec![1.0]], 1.0, 3),
+        Err(BlobError::CenterWidth { center: 1, expected: 2, actual: 1 })
+    );
+}
+
+/*
+ * 95 amostras da classe 0 e 5 da classe 1; x = [i] identifica a
+ * amostra.
+ */
+fn imbalanced() -> (Vec<Vec<Float>>, Vec<usize>) {
+    let x = (0..100).map(|i| vec![i as Float]).collect();
+    let labels = (0..100).map(|i| usize::from(i % 20 == 0)).collect();
+    (x, labels)
+}
+
+#[test]
+fn stratified_split_preserves_class_ratios() {
+    let (x, labels) = imbalanced();
+    let ((train_x, train_labels), (test_x, test_labels)) = stratified_split(&x, &labels, 0.2, Some(3)).expect("fração válida");
+
+    assert_eq!(train_x.len() + test_x.len(), 100);
+    for (class, total) in [(0, 95), (1, 5)] {
+        let in_test = test_labels.iter().filter(|&&l| l == class).count();
+        let in_train = train_labels.iter().filter(|&&l| l == class).count();
+        assert_eq!(in_test + in_train, total);
+        assert!((in_test as f64 - total as f64 * 0.2).abs() <= 1.0, "classe {}: {} no teste", class, in_test);
+    }
+    for (sample, &label) in test_x.iter().zip(&test_labels) {
+        assert_eq!(labels[sample[0] as usize], label);
+    }
+}
+
+#[test]
+fn stratified_split_is_deterministic_under_a_seed() {
+    let (x, labels) = imbalanced();
+    assert_eq!(stratified_split(&x, &labels, 0.3, Some(8)), stratified_split(&x, &labels, 0.3, Some(8)));
+}
+
+#[test]
+fn stratified_split_groups_sparse_labels() {
+    let x: Vec<Vec<Float>> = (0..20).map(|i| vec![i as Float]).collect();
+    let labels: Vec<usize> = (0..20).map(|i| if i % 4 == 0 { usize::MAX } else { 1_000_000_000 }).collect();
+    let ((_, train_labels), (test_x, test_labels)) = stratified_split(&x, &labels, 0.2, Some(5)).expect("fração válida");
+
+    for (class, in_test) in [(1_000_000_000, 3), (usize::MAX, 1)] {
+        assert_eq!(test_labels.iter().filter(|&&l| l == class).count(), in_test);
+        assert!(train_labels.contains(&class));
+    }
+    for (sample, &label) in test_x.iter().zip(&test_labels) {
+        assert_eq!(labels[sample[0] as usize], label);
+    }
+}
+
+#[test]
+fn stratified_split_rejects_singleton_classes() {
+    let x: Vec<Vec<Float>> = (0..5).map(|i| vec![i as Float]).collect();
+    assert_eq!(
+        stratified_split(&x, &[0, 0, 1, 0, 0], 0.4, Some(1)),
+        Err(SplitError::TooFewSamples { class: 1, count: 1 })
+    );
+    assert_eq!(
+        stratified_split(&x, &[0, 0, 1], 0.4, Some(1)),
+        Err(SplitError::LabelCount { samples: 5, labels: 3 })
+    );
+}
+
+#[test]
+fn csv_blank_cells_load_as_missing() {
+    let path = temp_file("blanks.csv", "x1,x2,y\n1,,3\n,2,4\n5,6,7\n");
+    let result = Dataset::<Float>::from_csv_with_missing(&path, 2, true);
+    let strict = Dataset::<Float>::from_csv(&path, 2, true);
+    fs::remove_file(&path).ok();
+
+    let data = result.expect("células vazias viram NaN");
+    assert!(data.has_missing());
+    assert!(data.inputs()[0][1].is_nan() && data.inputs()[1][0].is_nan());
+    assert_eq!(data.targets(), [3.0, 4.0, 7.0]);
+    assert!(matches!(strict, Err(CeptronError::Csv(CsvError::Parse { line: 2, column: 2, .. }))), "{:?}", strict);
+}
+
+#[test]
+fn collecting_pairs_reports_the_first_bad_row() {
+    let rows = vec![(vec![1.0, 2.0], 3.0), (vec![4.0, 5.0], 6.0), (vec![7.0], 8.0), (vec![9.0], 10.0)];
+    assert_eq!(
+        data_error(rows.into_iter().try_collect_dataset()),
+        Some(DataError::RowWidth { row: 2, expected: 2, actual: 1 })
+    );
+
+    let rows = vec![(vec![1.0], 3.0), (vec![4.0], Float::NAN)];
+    assert_eq!(data_error(Dataset::from_pairs(rows)), Some(DataError::NaN { row: 1, column: None }));
+    assert_eq!(data_error(std::iter::empty::<(Vec<Float>, Float)>().try_collect_dataset()), Some(DataError::Empty));
+}
+
+#[test]
+fn collected_dataset_trains_like_new() {
+    let (x, y) = linear_rows();
+    let built = Dataset::new(x.clone(), y.clone()).expect("dados válidos");
+    let collected = x.into_iter().zip(y).try_collect_dataset().expect("pares válidos");
+    assert_eq!(collected, built);
+
+    let mut pushed = Dataset::new(vec![built.inputs()[0].clone()], vec![built.targets()[0]]).expect("dados válidos");
+    for (sample, &target) in built.inputs().iter().zip(built.targets()).skip(1) {
+        pushed.push(sample.clone(), target).expect("amostra válida");
+    }
+    assert_eq!(data_error(pushed.push(vec![1.0], 0.0)), Some(DataError::RowWidth { row: 5, expected: 2, actual: 1 }));
+
+    let mut a = Neuron::new_seeded(Activation::Identity, 2, 3);
+    let mut b = Neuron::new_seeded(Activation::Identity, 2, 3);
+    let mut c = Neuron::new_seeded(Activation::Identity, 2, 3);
+    fit_on(&mut a, mse, &built, &config()).expect("treinamento válido");
+    fit_on(&mut b, mse, &collected, &config()).expect("treinamento válido");
+    fit_on(&mut c, mse, &pushed, &config()).expect("treinamento válido");
+    assert_eq!((a.weights(), a.bias()), (b.weights(), b.bias()));
+    assert_eq!((a.weights(), a.bias()), (c.weights(), c.bias()));
+}
+
+#[test]
+fn describe_matches_hand_computed_statistics() {
+    let x: Vec<Vec<Float>> = vec![vec![1.0, 5.0], vec![2.0, 5.0], vec![3.0, 5.0], vec![6.0, 5.0]];
+    let data = Dataset::new(x, vec![0.0, 2.0, 4.0, 10.0]).expect("dados válidos");
+    let summary = data.describe();
+
+    assert_eq!(summary.columns.len(), 3);
+    let first = &summary.columns[0];
+    assert_eq!((first.column, first.count, first.min, first.max, first.mean), (Column::Input(0), 4, 1.0, 6.0, 3.0));
+    // variância populacional: (4 + 1 + 0 + 9) / 4 = 3.5
+    assert!((first.std - 3.5_f64.sqrt() as Float).abs() < 1e-6);
+
+    let constant = &summary.columns[1];
+    assert_eq!((constant.min, constant.max, constant.mean, constant.std), (5.0, 5.0, 5.0, 0.0));
+
+    let target = &summary.columns[2];
+    assert_eq!((target.column, target.mean), (Column::Target, 4.0));
+    assert!((target.std - 14.0_f64.sqrt() as Float).abs() < 1e-5);
+
+    let table = summary.to_string();
+    assert_eq!(table.lines().count(), 4);
+    assert!(table.lines().next().unwrap().starts_with("coluna"));
+    let names: Vec<&str> = table.lines().skip(1).filter_map(|line| line.split_whitespace().next()).collect();
+    assert_eq!(names, ["x1", "x2", "y"]);
+}
+
+#[test]
+fn columns_are_named_from_one_like_the_report() {
+    assert_eq!(Column::Input(0).to_string(), "x1");
+    assert_eq!(Column::Target.to_string(), "y");
+    assert_eq!(DataIssue::ConstantColumn { column: 1 }.to_string(), "a entrada x2 é constante");
+    assert_eq!(
+        DataIssue::NaN { row: 3, column: Column::Input(2) }.to_string(),
+        "NaN na amostra 3, coluna x3"
+    );
+}
+
+#[test]
+fn describe_is_stable_for_large_offsets() {
+    let x: Vec<Vec<Float>> = (0..4).map(|i| vec![1.0e6 + i as Float]).collect();
+    let data = Dataset::new(x, vec![0.0; 4]).expect("dados válidos");
+
+    let stats = &data.describe().columns[0];
+    assert!((stats.std - 1.25_f64.sqrt() as Float).abs() < 1e-3, "{}", stats.std);
+    assert_eq!(data.describe().columns[1].std, 0.0);
+}
+
+#[test]
+fn describe_needs_samples() {
+    // Não existe Dataset vazio: a construção já falha
+    assert_eq!(data_error(Dataset::<Float>::from_pairs(Vec::new())), Some(DataError::Empty));
+}
+
+#[test]
+fn libsvm_sample_matches_hand_built_dataset() {
+    let text = "# amostras de y = 3x1 + 2x3 + 5\n\
+                8 1:1 # x2 ausente vale 0\n\
+                \n\
+                12.5 1:1.5 3:1\n\
+                5 \n";
+    let expected = Dataset::new(vec![vec![1.0, 0.0, 0.0], vec![1.5, 0.0, 1.0], vec![0.0, 0.0, 0.0]], vec![8.0, 12.5, 5.0])
+        .expect("dados válidos");
+
+    assert_eq!(Dataset::from_libsvm_str(text, None).expect("arquivo válido"), expected);
+
+    let wide = Dataset::<Float>::from_libsvm_str(text, Some(5)).expect("arquivo válido");
+    assert_eq!(wide.n_features(), 5);
+    assert_eq!(wide.inputs()[1], [1.5, 0.0, 1.0, 0.0, 0.0]);
+}
+
+#[test]
+fn libsvm_reports_each_malformed_line() {
+    let parse = |text: &str, n_features| Dataset::<Float>::from_libsvm_str(text, n_features);
+
+    assert!(matches!(parse("1 1:1\nabc 1:2\n", None), Err(CeptronError::Libsvm(LibsvmError::Label { line: 2, .. }))));
+    assert!(matches!(parse("1 1:1\n2 12\n", None), Err(CeptronError::Libsvm(LibsvmError::Pair { line: 2, ref token })) if token == "12"));
+    assert!(matches!(parse("1 0:1\n", None), Err(CeptronError::Libsvm(LibsvmError::Index { line: 1, .. }))));
+    assert!(matches!(parse("1 x:1\n", None), Err(CeptronError::Libsvm(LibsvmError::Index { line: 1, .. }))));
+    assert!(matches!(parse("1 1:abc\n", None), Err(CeptronError::Libsvm(LibsvmError::Value { line: 1, ref token })) if token == "1:abc"));
+    assert!(matches!(parse("1 2:1 2:3\n", None), Err(CeptronError::Libsvm(LibsvmError::NotIncreasing { line: 1, index: 2 }))));
+    assert!(matches!(parse("1 3:1 1:3\n", None), Err(CeptronError::Libsvm(LibsvmError::NotIncreasing { line: 1, index: 1 }))));
+    assert!(matches!(
+        parse("# cabeçalho\n1 4:1\n", Some(3)),
+        Err(CeptronError::Libsvm(LibsvmError::IndexTooLarge { line: 2, index: 4, n_features: 3 }))
+    ));
+    assert!(matches!(parse("# só comentários\n", None), Err(CeptronError::Libsvm(LibsvmError::Data(DataError::Empty)))));
+}
+
+#[cfg(feature = "serde")]
+mod json {
+    use super::*;
+    use perceptron::data::{JsonDataError, JsonLayout};
+
+    /*
+     * Conjunto com valores "quebrados", que só sobrevivem a uma ida
+     * e volta se o JSON preservar todos os dígitos.
+     */
+    fn irregular() -> Dataset {
+        make_linear(12, &[0.3, -1.7], 0.1, 0.05, (-1.0, 1.0), 9).expect("parâmetros válidos").data
+    }
+
+    #[test]
+    fn both_layouts_round_trip_exactly() {
+        let data = irregular();
+        for (layout, name) in [(JsonLayout::Records, "records.json"), (JsonLayout::Columns, "columns.json")] {
+            let path = temp_file(name, "");
+            data.to_json(&path, layout).expect("arquivo gravável");
+            let loaded = Dataset::from_json(&path);
+            fs::remove_file(&path).ok();
+            assert_eq!(loaded.expect("JSON válido"), data);
+        }
+    }
+
+    #[test]
+    fn loaded_dataset_trains_identically() {
+        let data = irregular();
+        let loaded = Dataset::from_json_str(&data.to_json_string(JsonLayout::Records).unwrap()).expect("JSON válido");
+
+        let mut a = Neuron::new_seeded(Activation::Identity, 2, 1);
+        let mut b = Neuron::new_seeded(Activation::Identity, 2, 1);
+        fit_on(&mut a, mse, &data, &config()).expect("treinamento válido");
+        fit_on(&mut b, mse, &loaded, &config()).expect("treinamento válido");
+        assert_eq!((a.weights(), a.bias()), (b.weights(), b.bias()));
+    }
+
+    #[test]
+    fn non_finite_values_round_trip() {
+        let x = vec![vec![1.0, Float::NAN], vec![Float::INFINITY, Float::NEG_INFINITY]];
+        let data = Dataset::new_with_missing(x, vec![2.0, Float::INFINITY]).expect("NaN só nas entradas");
+
+        for layout in [JsonLayout::Records, JsonLayout::Columns] {
+            let text = data.to_json_string(layout).expect("valores codificáveis");
+            assert!(text.contains("\"NaN\"") && text.contains("\"-Infinity\""), "{}", text);
+            let loaded = Dataset::<Float>::from_json_str(&text).expect("JSON válido");
+            assert!(loaded.inputs()[0][1].is_nan());
+            assert_eq!(loaded.inputs()[1], [Float::INFINITY, Float::NEG_INFINITY]);
+            assert_eq!(loaded.targets(), [2.0, Float::INFINITY]);
+        }
+    }
+
+    #[test]
+    fn schema_errors_report_the_json_path() {
+        let path_of = |text: &str| match Dataset::<Float>::from_json_str(text) {
+            Err(CeptronError::Json(JsonDataError::Schema { path, .. })) => path,
+            other => panic!("esperado Schema, obtido {:?}", other),
+        };
+
+        assert_eq!(path_of(r#"[{"features": [1, 2], "target": 3}, {"features": [1, "a"], "target": 3}]"#), "$[1].features[1]");
+        assert_eq!(path_of(r#"[{"features": [1, 2]}]"#), "$[0].target");
+        assert_eq!(path_of(r#"{"x": [[1], [2]], "y": [1, null]}"#), "$.y[1]");
+        assert_eq!(path_of(r#"{"y": [1]}"#), "$.x");
+        assert_eq!(path_of("3"), "$");
+        assert!(matches!(
+            Dataset::<Float>::from_json_str(r#"{"x": [[1], [2, 3]], "y": [1, 2]}"#),
+            Err(CeptronError::Json(JsonDataError::Data(DataError::RowWidth { row: 1, .. })))
+        ));
+    }
+}
+
+#[test]
+fn validate_reports_every_issue_with_coordinates() {
+    let x = vec![
+        vec![1.0, 7.0, 0.0],
+        vec![2.0, 7.0, Float::NAN],
+        vec![1.0, 7.0, 0.0],
+        vec![Float::INFINITY, 7.0, 5.0],
+    ];
+    let data = Dataset::new_with_missing(x, vec![4.0, 4.0, 4.0, 4.0]).expect("NaN só nas entradas");
+
+    assert_eq!(
+        data.validate(),
+        Err(vec![
+            DataIssue::NaN { row: 1, column: Column::Input(2) },
+            DataIssue::Infinite { row: 3, column: Column::Input(0) },
+            DataIssue::ConstantColumn { column: 1 },
+            DataIssue::DuplicateRow { row: 2, first: 0 },
+            DataIssue::ConstantTarget,
+        ])
+    );
+
+    let (x, y) = linear_rows();
+    assert_eq!(Dataset::new(x, y).unwrap().validate(), Ok(()));
+}
+
+#[test]
+fn validate_reports_infinite_targets() {
+    let data = Dataset::new(vec![vec![1.0], vec![2.0]], vec![1.0, Float::NEG_INFINITY]).expect("sem NaN");
+    assert_eq!(data.validate(), Err(vec![DataIssue::Infinite { row: 1, column: Column::Target }]));
+}
+
+#[test]
+fn drop_invalid_rows_counts_removed_samples() {
+    let x = vec![vec![1.0, 2.0], vec![Float::NAN, 1.0], vec![3.0, 4.0], vec![5.0, Float::INFINITY]];
+    let mut data = Dataset::new_with_missing(x, vec![1.0, 2.0, 3.0, 4.0]).expect("NaN só nas entradas");
+
+    assert_eq!(data.drop_invalid_rows().expect("restam amostras válidas"), 2);
+    assert_eq!(data.inputs(), [vec![1.0, 2.0], vec![3.0, 4.0]]);
+    assert_eq!(data.targets(), [1.0, 3.0]);
+    assert_eq!(data.drop_invalid_rows().expect("restam amostras válidas"), 0);
+}
+
+#[test]
+fn drop_invalid_rows_rejects_all_invalid_samples() {
+    let x = vec![vec![Float::NAN], vec![1.0]];
+    let mut data = Dataset::new_with_missing(x, vec![1.0, Float::INFINITY]).expect("NaN só nas entradas");
+
+    assert_eq!(data_error(data.drop_invalid_rows()), Some(DataError::Empty));
+    assert_eq!(data.len(), 2);
+    assert_eq!(data.targets()[0], 1.0);
+}
+
+#[test]
+fn fit_rejects_nan_unless_validation_is_disabled() {
+    let (mut x, y) = linear_rows();
+    x[3][0] = Float::NAN;
+    let mut neuron = Neuron::new_seeded(Activation::Identity, 2, 3);
+    let initial = neuron.weights().to_vec();
+
+    match fit(&mut neuron, mse, &x, &y, x.len(), &config()) {
+        Err(CeptronError::InvalidData(DataIssue::NaN { row: 3, column: Column::Input(0) })) => {}
+        other => panic!("esperado InvalidData, obtido {:?}", other),
+    }
+    assert_eq!(neuron.weights(), initial);
+
+    let unchecked = TrainConfig { validate_data: false, ..config() };
+    assert!(matches!(fit(&mut neuron, mse, &x, &y, x.len(), &unchecked), Err(CeptronError::Diverged(_))));
+}
+
+#[test]
+fn augmentation_appends_jittered_copies() {
+    let (x, y) = linear_rows();
+    let data = Dataset::new(x, y).expect("dados válidos");
+
+    let augmented = data.augment_gaussian(3, &[0.1, 0.0], None, 5).expect("um desvio por entrada");
+    assert_eq!(augmented.len(), data.len() * 4);
+    assert_eq!(&augmented.inputs()[..data.len()], data.inputs());
+    for (copy, original) in augmented.inputs()[data.len()..].iter().zip(data.inputs().iter().cycle()) {
+        assert_ne!(copy[0], original[0]);
+        assert_eq!(copy[1], original[1], "desvio 0.0 mantém a entrada");
+    }
+    assert!(augmented.targets().chunks(data.len()).all(|chunk| chunk == data.targets()));
+
+    assert_eq!(augmented, data.augment_gaussian(3, &[0.1, 0.0], None, 5).unwrap());
+    assert_ne!(augmented, data.augment_gaussian(3, &[0.1, 0.0], None, 6).unwrap());
+    assert!(data.augment_gaussian(1, &[0.1], None, 5).is_err());
+}
+
+#[test]
+fn zero_noise_augmentation_duplicates_samples() {
+    let (x, y) = linear_rows();
+    let data = Dataset::new(x, y).expect("dados válidos");
+
+    let augmented = data.augment_gaussian(2, &[0.0, 0.0], Some(0.0), 1).expect("um desvio por entrada");
+    let tripled = Dataset::concat(&Dataset::concat(&data, &data).unwrap(), &data).unwrap();
+    assert_eq!(augmented, tripled);
+}
+
+#[test]
+fn training_on_augmented_linear_example_recovers_parameters() {
+    let (x, y) = linear_rows();
+    let data = Dataset::new(x, y).expect("dados válidos");
+    let augmented = data.augment_gaussian(8, &[0.02, 0.02], None, 3).expect("um desvio por entrada");
+
+    let mut neuron = Neuron::zeros(Activation::Identity, 2);
+    let config = TrainConfig { epochs: 20000, learning_rate: 0.02, ..TrainConfig::default() };
+    fit_on(&mut neuron, mse, &augmented, &config).expect("treinamento válido");
+
+    let expected = Neuron::from_weights(Activation::Identity, vec![3.0, 2.0], 5.0).expect("parâmetros finitos");
+    assert_neuron_approx_eq!(neuron, expected, 0.1);
+}
+
+#[test]
+fn sliding_window_builds_lagged_rows() {
+    let series: Vec<Float> = (1..=10).map(|v| v as Float).collect();
+
+    let data = sliding_window(&series, 3, 1).expect("série longa o bastante");
+    assert_eq!(data.len(), 10 - 3 - 1 + 1);
+    assert_eq!(data.sample(0), (&[1.0, 2.0, 3.0][..], 4.0));
+    assert_eq!(data.sample(6), (&[7.0, 8.0, 9.0][..], 10.0));
+
+    let ahead = sliding_window(&series, 2, 3).expect("série longa o bastante");
+    assert_eq!(ahead.len(), 10 - 2 - 3 + 1);
+    assert_eq!(ahead.sample(0), (&[1.0, 2.0][..], 5.0));
+    assert_eq!(ahead.sample(5), (&[6.0, 7.0][..], 10.0));
+}
+
+#[test]
+fn sliding_window_rejects_short_series_and_zero_sizes() {
+    let series: Vec<Float> = vec![1.0, 2.0, 3.0];
+    assert_eq!(sliding_window(&series, 3, 1).err(), Some(WindowError::TooShort { len: 3, needed: 4 }));
+    assert_eq!(sliding_window(&series, 2, 1).map(|data| data.len()), Ok(1));
+    assert_eq!(sliding_window(&series, 0, 1).err(), Some(WindowError::ZeroWindow));
+    assert_eq!(sliding_window(&series, 1, 0).err(), Some(WindowError::ZeroHorizon));
+    assert_eq!(
+        sliding_window(&[1.0, Float::NAN, 3.0], 1, 1).err(),
+        Some(WindowError::Data(DataError::NaN { row: 0, column: None }))
+    );
+}
+
+#[test]
+fn concat_keeps_a_then_b() {
+    let a = tagged(3);
+    let b = Dataset::new(vec![vec![10.0], vec![11.0]], vec![10.0, 11.0]).expect("dados válidos");
+
+    let joined = Dataset::concat(&a, &b).expect("mesma largura");
+    assert_eq!(joined.len(), a.len() + b.len());
+    assert_eq!(joined.targets(), [0.0, 1.0, 2.0, 10.0, 11.0]);
+
+    let mut extended = a.clone();
+    extended.extend(&b).expect("mesma largura");
+    assert_eq!(extended, joined);
+}
+
+#[test]
+fn concat_rejects_feature_width_mismatch() {
+    let (x, y) = linear_rows();
+    let wide = Dataset::new(x, y).expect("dados válidos");
+    let narrow = tagged(2);
+
+    assert_eq!(data_error(Dataset::concat(&wide, &narrow)), Some(DataError::FeatureWidth { expected: 2, actual: 1 }));
+    let mut target = narrow.clone();
+    assert_eq!(data_error(target.extend(&wide)), Some(DataError::FeatureWidth { expected: 1, actual: 2 }));
+    assert_eq!(target, narrow);
+}
+
+#[test]
+fn training_on_shards_matches_a_single_file() {
+    let (x, y) = linear_rows();
+    let rows: Vec<String> = x.iter().zip(&y).map(|(s, t)| format!("{},{},{}\n", s[0], s[1], t)).collect();
+    let first = temp_file("shard-a.csv", &rows[..2].concat());
+    let second = temp_file("shard-b.csv", &rows[2..].concat());
+    let whole = temp_file("shard-all.csv", &rows.concat());
+
+    let load = |path: &PathBuf| Dataset::<Float>::from_csv(path, 2, false);
+    let (a, b, single) = (load(&first), load(&second), load(&whole));
+    for path in [&first, &second, &whole] {
+        fs::remove_file(path).ok();
+    }
+    let joined = Dataset::concat(&a.expect("CSV válido"), &b.expect("CSV válido")).expect("mesma largura");
+    let single = single.expect("CSV válido");
+
+    let config = TrainConfig { seed: Some(4), ..config() };
+    let mut from_shards = Neuron::new_seeded(Activation::Identity, 2, 4);
+    let mut from_single = Neuron::new_seeded(Activation::Identity, 2, 4);
+    fit_on(&mut from_shards, mse, &joined, &config).expect("treinamento válido");
+    fit_on(&mut from_single, mse, &single, &config).expect("treinamento válido");
+    assert_eq!((from_shards.weights(), from_shards.bias()), (from_single.weights(), from_single.bias()));
+}
+
+/*
+ * Lê um CSV gravado em um arquivo temporário com as opções dadas.
+ */
+fn load_csv(name: &str, contents: &str, target_column: usize, options: &CsvOptions) -> Result<Dataset, CeptronError> {
+    let path = temp_file(name, contents);
+    let result = Dataset::from_csv_with_options(&path, target_column, options);
+    fs::remove_file(&path).ok();
+    result
+}
+
+#[test]
+fn csv_header_names_the_inputs() {
+    let options = CsvOptions { has_header: true, ..CsvOptions::default() };
+    let data = load_csv("named.csv", "area,price,rooms\n1,12,2\n2,17,3\n", 1, &options).expect("CSV válido");
+    assert_eq!(data.feature_names(), Some(&["area".to_string(), "rooms".to_string()][..]));
+
+    let (train, _) = train_test_split(&data, 0.5, Some(1)).expect("divisão válida");
+    assert_eq!(train.feature_names(), data.feature_names());
+
+    let headless = load_csv("headless.csv", "1,12,2\n2,17,3\n", 1, &CsvOptions::default()).expect("CSV válido");
+    assert_eq!(headless.feature_names(), None);
+}
+
+#[test]
+fn with_feature_names_checks_the_count() {
+    let (x, y) = linear_rows();
+    let data = Dataset::new(x, y).expect("dados válidos");
+
+    assert_eq!(
+        data_error(data.clone().with_feature_names(vec!["a".to_string()])),
+        Some(DataError::FeatureNames { expected: 2, actual: 1 })
+    );
+    let named = data.with_feature_names(vec!["a".to_string(), "b".to_string()]).expect("um nome por entrada");
+    assert_eq!(named.feature_names().map(<[String]>::len), Some(2));
+}
+
+#[test]
+fn csv_reads_semicolons_and_decimal_commas() {
+    let options = CsvOptions { delimiter: b';', decimal_comma: true, has_header: true, ..CsvOptions::default() };
+    let data = load_csv("european.csv", "x1;x2;y\n1,5;2;12,5\n0,25;1;7,75\n", 2, &options).expect("CSV válido");
+
+    assert_eq!(data.inputs(), [vec![1.5, 2.0], vec![0.25, 1.0]]);
+    assert_eq!(data.targets(), [12.5, 7.75]);
+}
+
+#[test]
+fn csv_reads_tab_delimited_files() {
+    let options = CsvOptions { delimiter: b'\t', ..CsvOptions::default() };
+    let data = load_csv("tabs.tsv", "12\t1\t2\n7\t0\t1\n", 0, &options).expect("CSV válido");
+
+    assert_eq!(data.inputs(), [vec![1.0, 2.0], vec![0.0, 1.0]]);
+    assert_eq!(data.targets(), [12.0, 7.0]);
+}
+
+#[test]
+fn csv_skips_preamble_rows() {
+    let options = CsvOptions { skip_rows: 2, has_header: true, ..CsvOptions::default() };
+    let contents = "exportado em 2024-01-01\nunidade: metros\nx1,x2,y\n1,2,12\n3,x,18\n";
+
+    match load_csv("preamble.csv", contents, 2, &options) {
+        Err(CeptronError::Csv(CsvError::Parse { line: 5, column: 2, value })) => assert_eq!(value, "x"),
+        other => panic!("esperado Parse na linha 5, coluna 2, obtido {:?}", other),
+    }
+    let data = load_csv("preamble.csv", &contents.replace("3,x,18", "3,4,22"), 2, &options).expect("CSV válido");
+    assert_eq!(data.len(), 2);
+}
+
+#[test]
+fn csv_na_tokens_load_as_missing() {
+    let options = CsvOptions { na_strings: vec!["NA".to_string()], ..CsvOptions::default() };
+    let data = load_csv("na.csv", "1,NA,3\nNA,2,4\n", 2, &options).expect("NA nas entradas");
+
+    assert!(data.has_missing());
+    assert!(data.inputs()[0][1].is_nan() && data.inputs()[1][0].is_nan());
+    assert!(matches!(
+        load_csv("na-target.csv", "1,2,NA\n", 2, &options),
+        Err(CeptronError::Csv(CsvError::Parse { line: 1, column: 3, .. }))
+    ));
+}
@@ -0,0 +1,55 @@
+/*
+ * demo.rs
+ *
+ * Teste do exemplo de main.rs: o mesmo treinamento, feito só com a
+ * API pública (prelude), converge e é reproduzido por fit_on.
+ */
+
+#![cfg(feature = "std")]
+
+use perceptron::prelude::*;
+
+/*
+ * As amostras de main.rs, separadas em treino e teste com semente.
+ */
+fn demo_split() -> (Dataset, Dataset) {
+    let x = vec![
+        vec![6.0, 1.0], vec![5.0, 0.0],
+        vec![4.0, 1.0], vec![1.0, 4.0],
+        vec![1.0, 2.0], vec![2.0, 3.0]
+    ];
+    let out_true = vec![1.0, 1.0, 1.0, 0.0, 0.0, 0.0];
+    let data = Dataset::new(x, out_true).expect("dados válidos");
+    train_test_split(&data, 1.0 / 3.0, Some(42)).expect("divisão válida")
+}
+
+fn demo_neuron() -> Neuron {
+    Neuron::new_seeded(Activation::Sigmoid, 2, 42)
+}
+
+#[test]
+#[allow(deprecated)]
+fn demo_training_converges_and_matches_fit_on() {
+    let (train_data, test_data) = demo_split();
+
+    // O laço de main.rs
+    let mut demo = demo_neuron();
+    let initial_cost = compute_cost_on(&demo, &train_data, mse);
+    for _i in 0..50000 {
+        train_on(&mut demo, mse, &train_data);
+    }
+
+    // O mesmo treino pela API de configuração
+    let mut fitted = demo_neuron();
+    let config = TrainConfig { epochs: 50000, ..TrainConfig::default() };
+    fit_on(&mut fitted, mse, &train_data, &config).expect("treinamento válido");
+
+    let cost = compute_cost_on(&demo, &train_data, mse);
+    assert_eq!(cost.to_bits(), compute_cost_on(&fitted, &train_data, mse).to_bits());
+    assert_eq!(demo.weights(), fitted.weights());
+    assert!(cost < initial_cost / 2.0, "custo inicial {}, final {}", initial_cost, cost);
+
+    for (sample, &target) in test_data.inputs().iter().zip(test_data.targets()) {
+        assert_eq!(demo.compute_out(sample) > 0.5, target == 1.0, "amostra {:?}", sample);
+    }
+}
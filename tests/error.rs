@@ -0,0 +1,118 @@
+/*
+ * error.rs
+ *
+ * Testes de CeptronError: cada variante é produzida pela API que a
+ * gera e a sua mensagem traz os números (ou o arquivo) necessários
+ * para corrigir o problema.
+ */
+
+#![cfg(feature = "std")]
+
+use std::path::PathBuf;
+
+use perceptron::data::Dataset;
+use perceptron::error::CeptronError;
+use perceptron::netmath::{mse, Activation, Float};
+use perceptron::neuralnet::{fit, try_train, TrainConfig};
+use perceptron::neuron::Neuron;
+
+/*
+ * Caminho de um arquivo temporário exclusivo deste teste.
+ */
+fn temp_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("ceptron-{}-{}", std::process::id(), name))
+}
+
+fn neuron(n_connections: usize) -> Neuron {
+    Neuron::zeros(Activation::Identity, n_connections)
+}
+
+#[test]
+fn dimension_mismatch_names_both_widths_and_the_row() {
+    let single = CeptronError::from(neuron(3).try_compute_out(&[1.0; 5]).expect_err("largura errada"));
+    assert!(matches!(single, CeptronError::DimensionMismatch { expected: 3, actual: 5, row: None }), "{:?}", single);
+    let text = single.to_string();
+    assert!(text.contains('3') && text.contains('5'), "{}", text);
+
+    let rows: Vec<Vec<Float>> = vec![vec![1.0; 3], vec![1.0; 3], vec![1.0; 7]];
+    let batch = CeptronError::from(neuron(3).predict_batch(&rows).expect_err("largura errada"));
+    let text = batch.to_string();
+    assert!(text.contains("amostra 2") && text.contains('7') && text.contains('3'), "{}", text);
+}
+
+#[test]
+fn invalid_config_names_the_hyperparameter_and_value() {
+    let x: Vec<Vec<Float>> = vec![vec![1.0], vec![2.0]];
+    let y: Vec<Float> = vec![1.0, 2.0];
+    let config = TrainConfig { learning_rate: -0.5, ..TrainConfig::default() };
+    let err = fit(&mut neuron(1), mse, &x, &y, 2, &config).expect_err("taxa negativa");
+
+    assert!(matches!(err, CeptronError::InvalidConfig(_)), "{:?}", err);
+    let text = err.to_string();
+    assert!(text.contains("learning_rate") && text.contains("-0.5"), "{}", text);
+}
+
+#[test]
+fn data_error_names_both_lengths() {
+    let err = Dataset::new(vec![vec![1.0]; 4], vec![0.0; 6]).expect_err("tamanhos diferentes");
+
+    assert!(matches!(err, CeptronError::Data(_)), "{:?}", err);
+    let text = err.to_string();
+    assert!(text.contains('4') && text.contains('6'), "{}", text);
+}
+
+#[test]
+fn libsvm_error_names_the_line() {
+    let err = Dataset::<Float>::from_libsvm_str("1 1:1\n2 1:abc\n", None).expect_err("valor inválido");
+
+    assert!(matches!(err, CeptronError::Libsvm(_)), "{:?}", err);
+    let text = err.to_string();
+    assert!(text.contains("linha 2") && text.contains("1:abc"), "{}", text);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn json_error_names_the_path() {
+    let err = Dataset::<Float>::from_json_str(r#"{"x": [[1], [2]], "y": [1, null]}"#).expect_err("alvo nulo");
+
+    assert!(matches!(err, CeptronError::Json(_)), "{:?}", err);
+    assert!(err.to_string().contains("$.y[1]"), "{}", err);
+}
+
+#[test]
+fn invalid_data_names_the_sample() {
+    let x: Vec<Vec<Float>> = vec![vec![1.0], vec![2.0], vec![Float::NAN]];
+    let y: Vec<Float> = vec![1.0, 2.0, 3.0];
+    let err = try_train(&mut neuron(1), mse, &x, &y, 3).expect_err("NaN na entrada");
+
+    assert!(matches!(err, CeptronError::InvalidData(_)), "{:?}", err);
+    assert!(err.to_string().contains("amostra 2"), "{}", err);
+}
+
+#[test]
+fn diverged_names_the_epoch() {
+    let x: Vec<Vec<Float>> = vec![vec![1.0], vec![2.0]];
+    let y: Vec<Float> = vec![1.0, Float::INFINITY];
+    let config = TrainConfig { validate_data: false, ..TrainConfig::default() };
+    let err = fit(&mut neuron(1), mse, &x, &y, 2, &config).expect_err("alvo infinito");
+
+    assert!(matches!(err, CeptronError::Diverged(_)), "{:?}", err);
+    assert!(err.to_string().contains("época 0"), "{}", err);
+}
+
+#[test]
+fn io_errors_name_the_file() {
+    let missing = temp_path("does-not-exist.cept");
+    let err = Neuron::<Float>::load_bin(&missing).expect_err("arquivo ausente");
+    assert!(matches!(err, CeptronError::Io(_)), "{:?}", err);
+    assert!(err.to_string().contains(&missing.display().to_string()), "{}", err);
+
+    let csv = temp_path("does-not-exist.csv");
+    let err = Dataset::<Float>::from_csv(&csv, 1, false).expect_err("arquivo ausente");
+    assert!(matches!(err, CeptronError::Csv(_)), "{:?}", err);
+    assert!(err.to_string().contains(&csv.display().to_string()), "{}", err);
+
+    let directory = temp_path("no-such-dir").join("model.cept");
+    let err = neuron(2).save_bin(&directory).expect_err("diretório ausente");
+    assert!(err.to_string().contains(&directory.display().to_string()), "{}", err);
+}
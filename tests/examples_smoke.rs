@@ -0,0 +1,75 @@
+/*
+ * examples_smoke.rs
+ *
+ * Testes de fumaça dos exemplos em examples/: a mesma sequência de
+ * chamadas da biblioteca, com poucas épocas, roda e termina com
+ * custo menor que o inicial.
+ */
+
+#![cfg(feature = "std")]
+
+use perceptron::data::{make_blobs, make_linear, sliding_window};
+use perceptron::prelude::*;
+use perceptron::utils::{randn_with, seeded_rng};
+
+/*
+ * Treina com fit_on e retorna os custos no treino antes e depois.
+ */
+fn fit_costs(neuron: &mut Neuron32, train_data: &Dataset<f32>, config: &TrainConfig<f32>) -> (f32, f32) {
+    let initial = try_compute_cost_on(neuron, train_data, mse).expect("dados válidos");
+    fit_on(neuron, mse, train_data, config).expect("treinamento válido");
+    (initial, try_compute_cost_on(neuron, train_data, mse).expect("dados válidos"))
+}
+
+#[test]
+fn regression_example_improves() {
+    let linear = make_linear(200, &[3.0, 2.0], 5.0, 0.0, (0.0, 1.0), 7).expect("parâmetros válidos");
+    let (train_data, test_data) = train_test_split(&linear.data, 0.25, Some(7)).expect("divisão válida");
+    let mut neuron: Neuron32 = Neuron::new_seeded(Activation::Identity, 2, 7);
+    let config = TrainConfig { epochs: 50, learning_rate: 0.1, seed: Some(7), ..TrainConfig::default() };
+    let initial_test = try_compute_cost_on(&neuron, &test_data, mse).expect("dados válidos");
+
+    let (initial, cost) = fit_costs(&mut neuron, &train_data, &config);
+
+    assert!(cost < initial, "custo inicial {}, final {}", initial, cost);
+    assert!(try_compute_cost_on(&neuron, &test_data, mse).expect("dados válidos") < initial_test);
+    assert!(!neuron.report_with(Some(&train_data), Some(&["x1", "x2"])).is_empty());
+}
+
+#[test]
+fn classification_example_improves() {
+    let centers = vec![vec![1.0, 1.0], vec![4.0, 4.0]];
+    let (x, labels) = make_blobs(200, &centers, 0.8, 11).expect("parâmetros válidos");
+    let targets = labels.iter().map(|&label| label as f32).collect();
+    let data = Dataset::new(x, targets).expect("dados válidos");
+    let (train_data, test_data) = train_test_split(&data, 0.25, Some(11)).expect("divisão válida");
+    let mut neuron: Neuron32 = Neuron::new_seeded(Activation::Sigmoid, 2, 11);
+    let config = TrainConfig { epochs: 50, learning_rate: 0.5, seed: Some(11), ..TrainConfig::default() };
+
+    let (initial, cost) = fit_costs(&mut neuron, &train_data, &config);
+
+    assert!(cost < initial, "custo inicial {}, final {}", initial, cost);
+    let predicted = neuron.predict_labels(test_data.inputs(), 0.5);
+    assert_eq!(predicted.len(), test_data.len());
+}
+
+#[test]
+fn autoregression_example_improves() {
+    let mut rng = seeded_rng(5);
+    let mut series: Vec<f32> = vec![0.0, 0.0];
+    for t in 2..400 {
+        let next = 1.2 * series[t - 1] - 0.5 * series[t - 2] + randn_with(&mut rng, 0.0, 0.1);
+        series.push(next);
+    }
+    let data = sliding_window(&series, 2, 1).expect("série válida");
+    let n_train = data.len() - 80;
+    let (train_x, test_x) = data.inputs().split_at(n_train);
+    let train_data = Dataset::new(train_x.to_vec(), data.targets()[..n_train].to_vec()).expect("dados válidos");
+    let mut neuron: Neuron32 = Neuron::new_seeded(Activation::Identity, 2, 5);
+    let config = TrainConfig { epochs: 50, learning_rate: 0.5, seed: Some(5), ..TrainConfig::default() };
+
+    let (initial, cost) = fit_costs(&mut neuron, &train_data, &config);
+
+    assert!(cost < initial, "custo inicial {}, final {}", initial, cost);
+    assert_eq!(neuron.predict_batch(test_x).expect("janelas de 2 valores").len(), 80);
+}
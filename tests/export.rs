@@ -0,0 +1,258 @@
+/*
+ * export.rs
+ *
+ * Testes das exportações em texto do modelo treinado: relatório,
+ * equação (em texto e em XML no estilo PMML), predições em CSV e
+ * grafo DOT.
+ */
+
+#![cfg(feature = "std")]
+
+use std::path::PathBuf;
+
+use perceptron::data::{make_linear, Dataset};
+use perceptron::modelio::{DotOptions, NotLinear};
+use perceptron::netmath::{Activation, Float};
+use perceptron::neuralnet::{export_predictions, ExportError};
+use perceptron::neuron::Neuron;
+
+/*
+ * Caminho de um arquivo temporário exclusivo deste teste.
+ */
+fn temp_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("ceptron-{}-{}", std::process::id(), name))
+}
+
+/*
+ * Neurônio próximo de y = 3x1 + 2x2 + 5.
+ */
+fn linear() -> Neuron {
+    Neuron::from_weights(Activation::Identity, vec![3.001, 1.998], 5.002).expect("parâmetros finitos")
+}
+
+/*
+ * Lê um CSV com cabeçalho (alvo na última coluna).
+ */
+fn load_named(name: &str, contents: &str) -> Dataset {
+    let path = temp_path(name);
+    std::fs::write(&path, contents).expect("gravação");
+    let data = Dataset::from_csv(&path, 2, true);
+    std::fs::remove_file(&path).ok();
+    data.expect("CSV válido")
+}
+
+/*
+ * Linha do relatório que começa com label.
+ */
+fn report_line<'a>(report: &'a str, label: &str) -> &'a str {
+    report.lines().find(|line| line.starts_with(label)).expect("linha presente")
+}
+
+#[test]
+fn report_prints_the_linear_equation() {
+    let report = linear().report();
+
+    assert_eq!(report_line(&report, "Equação"), "Equação             : y = 3.0010*x1 + 1.9980*x2 + 5.0020");
+    assert_eq!(report_line(&report, "Pesos"), "Pesos               : [3.0010, 1.9980]");
+    assert_eq!(report_line(&report, "Ativação"), "Ativação            : identity");
+    assert!(report.ends_with('\n'));
+}
+
+#[test]
+fn report_uses_csv_header_names() {
+    let data = load_named("named.csv", "area,rooms,price\n1,2,12\n2,1,13\n");
+    let report = linear().report_with(Some(&data), None);
+
+    assert_eq!(report_line(&report, "Equação"), "Equação             : y = 3.0010*area + 1.9980*rooms + 5.0020");
+    assert!(report_line(&report, "Custo").ends_with("(2 amostras)"), "{}", report);
+}
+
+#[test]
+fn explicit_names_take_precedence() {
+    let data = load_named("override.csv", "area,rooms,price\n1,2,12\n");
+    let report = linear().report_with(Some(&data), Some(&["a", "b"]));
+
+    assert_eq!(report_line(&report, "Equação"), "Equação             : y = 3.0010*a + 1.9980*b + 5.0020");
+}
+
+#[test]
+fn save_report_writes_the_report_with_dataset_names() {
+    let data = load_named("saved.csv", "area,rooms,price\n1,2,12\n");
+    let path = temp_path("report.txt");
+    linear().save_report(&path, Some(&data)).expect("gravação");
+    let saved = std::fs::read_to_string(&path).expect("leitura");
+    std::fs::remove_file(&path).ok();
+
+    assert_eq!(saved, linear().report_with(Some(&data), None));
+    assert!(!saved.contains("x1"), "{}", saved);
+}
+
+#[test]
+fn negative_coefficients_use_minus_signs() {
+    let neuron = Neuron::from_weights(Activation::Sigmoid, vec![1.5, -0.25], -1.0).expect("parâmetros finitos");
+    let names: Vec<String> = vec!["a".into(), "b".into()];
+
+    assert_eq!(neuron.to_equation(None, 2).expect("ativação conhecida"), "y = sigmoid(1.50*x1 - 0.25*x2 - 1.00)");
+    assert_eq!(neuron.to_equation(Some(&names), 1).expect("ativação conhecida"), "y = sigmoid(1.5*a - 0.2*b - 1.0)");
+}
+
+/*
+ * Neurônio identidade com um coeficiente negativo e um nulo.
+ */
+fn mixed_signs() -> Neuron {
+    Neuron::from_weights(Activation::Identity, vec![3.0, -1.5, 0.0], 5.25).expect("parâmetros finitos")
+}
+
+#[test]
+fn equation_keeps_zero_coefficients_and_leading_minus() {
+    let names: Vec<String> = vec!["area".into(), "rooms".into(), "age".into()];
+    let leading = Neuron::from_weights(Activation::Identity, vec![-2.0, 1.0], -0.5).expect("parâmetros finitos");
+
+    assert_eq!(mixed_signs().to_equation(None, 2).expect("ativação conhecida"), "y = 3.00*x1 - 1.50*x2 + 0.00*x3 + 5.25");
+    assert_eq!(
+        mixed_signs().to_equation(Some(&names), 1).expect("ativação conhecida"),
+        "y = 3.0*area - 1.5*rooms + 0.0*age + 5.2"
+    );
+    assert_eq!(leading.to_equation(None, 1).expect("ativação conhecida"), "y = -2.0*x1 + 1.0*x2 - 0.5");
+    assert_eq!(leading.without_bias().to_equation(None, 0).expect("ativação conhecida"), "y = -2*x1 + 1*x2");
+}
+
+#[test]
+fn pmml_matches_golden_document() {
+    let names: Vec<String> = vec!["area".into(), "r&d".into(), "age".into()];
+
+    assert_eq!(mixed_signs().to_pmml(Some(&names)).expect("ativação conhecida"), include_str!("fixtures/linear_model.pmml"));
+}
+
+#[test]
+fn pmml_encodes_the_link_function_and_missing_bias() {
+    let neuron = Neuron::from_weights(Activation::Sigmoid, vec![0.5], 2.0).expect("parâmetros finitos").without_bias();
+    let xml = neuron.to_pmml(None).expect("ativação conhecida");
+
+    assert!(xml.contains("normalizationMethod=\"logit\""), "{}", xml);
+    assert!(xml.contains("<RegressionTable intercept=\"0\">"), "{}", xml);
+    assert!(xml.contains("<NumericPredictor name=\"x1\" exponent=\"1\" coefficient=\"0.5\"/>"), "{}", xml);
+}
+
+#[test]
+fn custom_activation_has_no_equation() {
+    fn relu(x: Float) -> Float {
+        x.max(0.0)
+    }
+    let neuron = Neuron::from_weights(Activation::Custom(relu), vec![1.0], 0.0).expect("parâmetros finitos");
+
+    assert_eq!(neuron.to_equation(None, 2), Err(NotLinear));
+    assert_eq!(neuron.to_pmml(None), Err(NotLinear));
+}
+
+#[test]
+fn bias_free_report_says_so() {
+    let report = linear().without_bias().report();
+
+    assert_eq!(report_line(&report, "Bias"), "Bias                : (sem bias)");
+    assert_eq!(report_line(&report, "Equação"), "Equação             : y = 3.0010*x1 + 1.9980*x2");
+}
+
+/*
+ * Exporta as predições de neuron em data e devolve as linhas do CSV.
+ */
+fn exported_lines(name: &str, neuron: &Neuron, data: &Dataset, header_names: Option<&[String]>) -> Vec<String> {
+    let path = temp_path(name);
+    export_predictions(neuron, data, &path, header_names).expect("exportação válida");
+    let csv = std::fs::read_to_string(&path).expect("leitura");
+    std::fs::remove_file(&path).ok();
+    csv.lines().map(String::from).collect()
+}
+
+#[test]
+fn exported_predictions_parse_back_to_compute_out() {
+    let data = make_linear(25, &[3.0, 2.0], 5.0, 0.3, (0.0, 1.0), 6).expect("parâmetros válidos").data;
+    let neuron = linear();
+    let lines = exported_lines("predictions.csv", &neuron, &data, None);
+
+    assert_eq!(lines.len(), data.len() + 1);
+    assert_eq!(lines[0], "x1,x2,y_true,y_pred,residual");
+    for (line, (x, &y)) in lines[1..].iter().zip(data.inputs().iter().zip(data.targets())) {
+        let values: Vec<Float> = line.split(',').map(|value| value.parse().expect("número")).collect();
+        let y_pred = neuron.compute_out(x);
+        assert_eq!(values[..2], x[..]);
+        assert_eq!(values[2].to_bits(), y.to_bits());
+        assert_eq!(values[3].to_bits(), y_pred.to_bits());
+        assert_eq!(values[4].to_bits(), (y - y_pred).to_bits());
+    }
+}
+
+#[test]
+fn exported_header_uses_dataset_names() {
+    let data = load_named("export-named.csv", "area,rooms,price\n1,2,12\n2,1,13\n");
+    let lines = exported_lines("export-named-out.csv", &linear(), &data, None);
+    assert_eq!(lines[0], "area,rooms,y_true,y_pred,residual");
+
+    let names: Vec<String> = vec!["a".into(), "b".into()];
+    let lines = exported_lines("export-override.csv", &linear(), &data, Some(&names));
+    assert_eq!(lines[0], "a,b,y_true,y_pred,residual");
+}
+
+#[test]
+fn quoted_header_names_round_trip() {
+    let data = load_named("export-quoted.csv", "\"price, usd\",\"say \"\"hi\"\"\",y\n1,2,12\n2,1,13\n");
+    let names = ["price, usd".to_string(), "say \"hi\"".to_string()];
+    assert_eq!(data.feature_names(), Some(&names[..]));
+
+    let path = temp_path("export-quoted-out.csv");
+    export_predictions(&linear(), &data, &path, None).expect("exportação válida");
+    let csv = std::fs::read_to_string(&path).expect("leitura");
+    let exported = Dataset::<Float>::from_csv(&path, 2, true);
+    std::fs::remove_file(&path).ok();
+
+    assert_eq!(csv.lines().next(), Some("\"price, usd\",\"say \"\"hi\"\"\",y_true,y_pred,residual"));
+    let exported = exported.expect("CSV exportado válido");
+    assert_eq!(exported.n_features() + 1, data.n_features() + 3);
+    let columns = exported.feature_names().expect("cabeçalho");
+    assert_eq!(columns[..2], names[..]);
+    assert_eq!(exported.targets(), data.targets());
+}
+
+#[test]
+fn export_rejects_wrong_header_count_and_width() {
+    let data = load_named("export-invalid.csv", "area,rooms,price\n1,2,12\n");
+    let path = temp_path("export-invalid-out.csv");
+    let names: Vec<String> = vec!["a".into()];
+
+    let header = export_predictions(&linear(), &data, &path, Some(&names));
+    assert!(matches!(header, Err(ExportError::HeaderNames { expected: 2, actual: 1 })), "{:?}", header);
+    let wide = Neuron::from_weights(Activation::Identity, vec![1.0, 1.0, 1.0], 0.0).expect("parâmetros finitos");
+    let width = export_predictions(&wide, &data, &path, None);
+    std::fs::remove_file(&path).ok();
+    assert!(matches!(width, Err(ExportError::Input(_))), "{:?}", width);
+}
+
+#[test]
+fn dot_has_one_node_per_input_and_one_edge_per_weight() {
+    let neuron = Neuron::from_weights(Activation::Identity, vec![0.5; 7], 1.0).expect("parâmetros finitos");
+    let options = DotOptions { weight_labels: false, ..DotOptions::default() };
+
+    for dot in [neuron.to_dot(DotOptions::default()), neuron.to_dot(options)] {
+        let nodes = dot.lines().filter(|line| line.contains("[shape=")).count();
+        let edges = dot.lines().filter(|line| line.contains(" -> ")).count();
+        assert_eq!((nodes, edges), (7 + 1, 7), "{}", dot);
+    }
+}
+
+#[test]
+fn dot_of_a_tiny_neuron_matches_golden_text() {
+    let neuron = Neuron::from_weights(Activation::Sigmoid, vec![2.0, -1.0], 0.5).expect("parâmetros finitos");
+    let options = DotOptions { precision: 1, ..DotOptions::default() };
+
+    assert_eq!(neuron.to_dot(options), include_str!("fixtures/sigmoid_neuron.dot"));
+}
+
+#[test]
+fn dot_without_labels_or_colors_has_bare_edges() {
+    let neuron = linear().without_bias();
+    let options = DotOptions { weight_labels: false, color_by_sign: false, ..DotOptions::default() };
+    let dot = neuron.to_dot(options);
+
+    assert!(dot.contains("    out [shape=doublecircle, label=\"identity\"];\n"), "{}", dot);
+    assert!(dot.contains("    x1 -> out;\n    x2 -> out;\n"), "{}", dot);
+}
@@ -0,0 +1,185 @@
+/*
+ * fallible.rs
+ *
+ * Testes das variantes try_ de custo e treinamento e das
+ * conferências de fit: cada violação de pré-condição gera o erro
+ * documentado sem alterar o neurônio, e o caminho feliz é idêntico
+ * ao das funções sem conferência.
+ */
+
+#![cfg(feature = "std")]
+
+use perceptron::data::{Column, DataError, DataIssue};
+use perceptron::error::CeptronError;
+use perceptron::netmath::{mse, Activation, Float};
+#[allow(deprecated)]
+use perceptron::neuralnet::{
+    compute_cost, compute_gradient, compute_weighted_cost, fit, train, train_weighted, try_compute_cost,
+    try_compute_gradient, try_compute_weighted_cost, try_train, try_train_weighted, TrainConfig,
+};
+use perceptron::neuron::{Neuron, ParamType};
+
+/*
+ * Amostras de y = 3x1 + 2x2 + 5 com entradas inteiras.
+ */
+fn samples() -> (Vec<Vec<Float>>, Vec<Float>) {
+    let x: Vec<Vec<Float>> = vec![vec![6.0, 1.0], vec![5.0, 0.0], vec![4.0, 1.0], vec![1.0, 4.0], vec![1.0, 2.0]];
+    let y = x.iter().map(|sample| 3.0 * sample[0] + 2.0 * sample[1] + 5.0).collect();
+    (x, y)
+}
+
+fn seeded() -> Neuron {
+    Neuron::new_seeded(Activation::Identity, 2, 7)
+}
+
+/*
+ * Confere que try_train falha com um erro que satisfaz check e que o
+ * neurônio não foi alterado.
+ */
+fn assert_rejected(x: &[Vec<Float>], y: &[Float], sample_size: usize, check: fn(&CeptronError) -> bool) {
+    let mut neuron = seeded();
+    let err = try_train(&mut neuron, mse, x, y, sample_size).expect_err("amostras inválidas");
+
+    assert!(check(&err), "{:?}", err);
+    assert_eq!(neuron.weights(), seeded().weights());
+    assert_eq!(neuron.bias(), seeded().bias());
+    let cost = try_compute_cost(&neuron, x, y, mse, sample_size).expect_err("amostras inválidas");
+    assert!(check(&cost), "{:?}", cost);
+}
+
+#[test]
+fn sample_preconditions_map_to_documented_variants() {
+    let (x, y) = samples();
+
+    assert_rejected(&x, &y, 0, |err| matches!(err, CeptronError::Data(DataError::Empty)));
+    assert_rejected(&x, &y[..3], 5, |err| matches!(err, CeptronError::InvalidConfig(_)));
+
+    let mut wide = x.clone();
+    wide[3].push(1.0);
+    assert_rejected(&wide, &y, 5, |err| {
+        matches!(err, CeptronError::DimensionMismatch { expected: 2, actual: 3, row: Some(3) })
+    });
+
+    let mut nan = x.clone();
+    nan[2][1] = Float::NAN;
+    assert_rejected(&nan, &y, 5, |err| {
+        matches!(err, CeptronError::InvalidData(DataIssue::NaN { row: 2, column: Column::Input(1) }))
+    });
+
+    let mut infinite = y.clone();
+    infinite[4] = Float::INFINITY;
+    assert_rejected(&x, &infinite, 5, |err| {
+        matches!(err, CeptronError::InvalidData(DataIssue::Infinite { row: 4, column: Column::Target }))
+    });
+
+    // Linhas além de sample_size não são conferidas
+    assert!(try_compute_cost(&seeded(), &nan, &y, mse, 2).is_ok());
+}
+
+#[test]
+fn sample_weight_preconditions_are_invalid_config() {
+    let (x, y) = samples();
+    let cases: [&[Float]; 4] = [&[1.0; 4], &[1.0, 1.0, -1.0, 1.0, 1.0], &[1.0, Float::NAN, 1.0, 1.0, 1.0], &[0.0; 5]];
+
+    for weights in cases {
+        let mut neuron = seeded();
+        let train = try_train_weighted(&mut neuron, mse, &x, &y, weights, 5);
+        let cost = try_compute_weighted_cost(&neuron, &x, &y, weights, mse, 5);
+
+        assert!(matches!(train, Err(CeptronError::InvalidConfig(_))), "{:?}: {:?}", weights, train);
+        assert!(matches!(cost, Err(CeptronError::InvalidConfig(_))), "{:?}: {:?}", weights, cost);
+        assert_eq!(neuron.weights(), seeded().weights());
+    }
+}
+
+/*
+ * Entradas, saídas esperadas, configuração e o erro esperado de fit.
+ */
+type FitCase<'a> = (&'a [Vec<Float>], &'a [Float], TrainConfig, fn(&CeptronError) -> bool);
+
+#[test]
+fn fit_checks_everything_before_training() {
+    let (x, y) = samples();
+    let config = TrainConfig { epochs: 10, ..TrainConfig::default() };
+    let mut wide = x.clone();
+    wide[1].push(0.0);
+    let mut nan = y.clone();
+    nan[0] = Float::NAN;
+
+    let cases: Vec<FitCase> = vec![
+        (&x, &y[..4], config.clone(), |err| {
+            matches!(err, CeptronError::InvalidConfig(msg) if msg == "sample_size = 5, mas há 5 entradas e 4 saídas esperadas")
+        }),
+        (&wide, &y, config.clone(), |err| {
+            matches!(err, CeptronError::DimensionMismatch { expected: 2, actual: 3, row: None })
+        }),
+        (&x, &y, TrainConfig { learning_rate: 0.0, ..config.clone() }, |err| {
+            matches!(err, CeptronError::InvalidConfig(msg) if msg.starts_with("learning_rate "))
+        }),
+        (&x, &y, TrainConfig { max_norm: Some(-1.0), ..config.clone() }, |err| {
+            matches!(err, CeptronError::InvalidConfig(msg) if msg == "max_norm deve ser positivo e finito (recebido -1)")
+        }),
+        (&x, &nan, config.clone(), |err| {
+            matches!(err, CeptronError::InvalidData(DataIssue::NaN { row: 0, column: Column::Target }))
+        }),
+        (&x, &y, TrainConfig { sample_weights: Some(vec![1.0; 3]), ..config.clone() }, |err| {
+            matches!(err, CeptronError::InvalidConfig(msg) if msg == "3 pesos para 5 amostras")
+        }),
+    ];
+
+    for (x, y, config, check) in cases {
+        let mut neuron = seeded();
+        let err = fit(&mut neuron, mse, x, y, 5, &config).expect_err("pré-condição violada");
+
+        assert!(check(&err), "{:?}", err);
+        assert_eq!(neuron.weights(), seeded().weights());
+        assert_eq!(neuron.bias(), seeded().bias());
+    }
+}
+
+#[test]
+#[allow(deprecated)]
+fn checked_happy_path_matches_unchecked_functions() {
+    let (x, y) = samples();
+    let weights: Vec<Float> = vec![1.0, 2.0, 0.5, 1.0, 3.0];
+    let (mut plain, mut checked) = (seeded(), seeded());
+    let (mut plain_weighted, mut checked_weighted) = (seeded(), seeded());
+
+    for _step in 0..200 {
+        train(&mut plain, mse, &x, &y, x.len());
+        try_train(&mut checked, mse, &x, &y, x.len()).expect("amostras válidas");
+        train_weighted(&mut plain_weighted, mse, &x, &y, &weights, x.len());
+        try_train_weighted(&mut checked_weighted, mse, &x, &y, &weights, x.len()).expect("amostras válidas");
+    }
+
+    assert_eq!(checked.weights(), plain.weights());
+    assert_eq!(checked.bias().to_bits(), plain.bias().to_bits());
+    assert_eq!(checked_weighted.weights(), plain_weighted.weights());
+    assert_eq!(
+        try_compute_cost(&checked, &x, &y, mse, x.len()).expect("amostras válidas").to_bits(),
+        compute_cost(&plain, &x, &y, mse, x.len()).to_bits()
+    );
+    assert_eq!(
+        try_compute_weighted_cost(&checked_weighted, &x, &y, &weights, mse, x.len()).expect("amostras válidas").to_bits(),
+        compute_weighted_cost(&plain_weighted, &x, &y, &weights, mse, x.len()).to_bits()
+    );
+    let gradient = |neuron: &mut Neuron| compute_gradient(neuron, mse, &x, &y, None, ParamType::Bias, x.len());
+    assert_eq!(
+        try_compute_gradient(&mut checked, mse, &x, &y, None, ParamType::Bias, x.len()).expect("amostras válidas"),
+        gradient(&mut plain)
+    );
+}
+
+#[test]
+fn checked_gradient_rejects_missing_params_and_bad_samples() {
+    let (x, y) = samples();
+    let mut neuron = seeded();
+
+    let missing = try_compute_gradient(&mut neuron, mse, &x, &y, None, ParamType::Weight(2), x.len());
+    assert!(matches!(missing, Err(CeptronError::InvalidConfig(_))), "{:?}", missing);
+    let empty = try_compute_gradient(&mut neuron, mse, &x, &y, None, ParamType::Bias, 0);
+    assert!(matches!(empty, Err(CeptronError::Data(DataError::Empty))), "{:?}", empty);
+    let zero_weights = try_compute_gradient(&mut neuron, mse, &x, &y, Some(&[0.0; 5]), ParamType::Bias, x.len());
+    assert!(matches!(zero_weights, Err(CeptronError::InvalidConfig(_))), "{:?}", zero_weights);
+    assert_eq!(neuron.weights(), seeded().weights());
+}
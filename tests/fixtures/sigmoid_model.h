@@ -0,0 +1,21 @@
+/* Gerado a partir de um neurônio treinado (ativação sigmoid). */
+#ifndef SENSOR_MODEL_H
+#define SENSOR_MODEL_H
+
+#include <math.h>
+
+#define SENSOR_N_INPUTS 3
+
+static const float sensor_weights[SENSOR_N_INPUTS] = {3.0012f, -1.5f, 0.1f};
+static const float sensor_bias = 4.998f;
+
+static inline float sensor_predict(const float* x) {
+    float z = 0.0f;
+    for (int i = 0; i < SENSOR_N_INPUTS; i++) {
+        z += x[i] * sensor_weights[i];
+    }
+    z += sensor_bias;
+    return 1.0f / (1.0f + expf(-z));
+}
+
+#endif /* SENSOR_MODEL_H */
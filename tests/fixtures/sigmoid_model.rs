@@ -0,0 +1,11 @@
+/// Gerado a partir de um neurônio treinado (3 entradas, ativação sigmoid).
+pub fn sigmoid_model(x: &[f32]) -> f32 {
+    const WEIGHTS: [f32; 3] = [3.0012, -1.5, 0.1];
+    assert_eq!(x.len(), WEIGHTS.len(), "número de entradas");
+    let mut z: f32 = 0.0;
+    for (input, weight) in x.iter().zip(WEIGHTS.iter()) {
+        z += input * weight;
+    }
+    z += 4.998;
+    1.0 / (1.0 + (-z).exp())
+}
@@ -0,0 +1,100 @@
+/*
+ * gzip.rs
+ *
+ * Testes dos arquivos de modelo e checkpoint comprimidos com gzip
+ * (feature "gzip").
+ */
+
+#![cfg(feature = "gzip")]
+
+use std::path::{Path, PathBuf};
+
+use perceptron::error::CeptronError;
+use perceptron::modelio::{Checkpoint, ModelIoError};
+use perceptron::netmath::{Activation, Float};
+use perceptron::neuron::Neuron;
+
+/*
+ * Caminho de um arquivo temporário exclusivo deste teste.
+ */
+fn temp_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("ceptron-{}-{}", std::process::id(), name))
+}
+
+/*
+ * Neurônio grande com pesos sorteados.
+ */
+fn large_neuron() -> Neuron {
+    Neuron::new_seeded(Activation::Sigmoid, 4096, 23)
+}
+
+fn file_len(path: &Path) -> u64 {
+    std::fs::metadata(path).expect("arquivo gravado").len()
+}
+
+/*
+ * Confere que dois neurônios dão exatamente as mesmas saídas.
+ */
+fn assert_same_predictions(a: &Neuron, b: &Neuron) {
+    for scale in [0.0, 1.0, -2.5] {
+        let x: Vec<Float> = (0..a.n_connections()).map(|i| scale * (i % 7) as Float).collect();
+        assert_eq!(a.compute_out(&x).to_bits(), b.compute_out(&x).to_bits());
+    }
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn compressed_json_is_at_most_half_the_size() {
+    let neuron = large_neuron();
+    let plain = temp_path("large.json");
+    let packed = temp_path("large.json.gz");
+    neuron.save(&plain).expect("gravação");
+    neuron.save_with(&packed, true).expect("gravação");
+    let (plain_len, packed_len) = (file_len(&plain), file_len(&packed));
+    let loaded: Result<Neuron, _> = Neuron::load(&packed);
+    std::fs::remove_file(&plain).ok();
+    std::fs::remove_file(&packed).ok();
+
+    assert!(2 * packed_len <= plain_len, "gzip {} bytes, JSON {} bytes", packed_len, plain_len);
+    assert_same_predictions(&neuron, &loaded.expect("leitura"));
+}
+
+#[test]
+fn compressed_bin_loads_to_identical_predictions() {
+    let neuron = large_neuron();
+    let path = temp_path("large.cept.gz");
+    neuron.save_bin_with(&path, true).expect("gravação");
+    let magic = std::fs::read(&path).expect("leitura")[..2].to_vec();
+    let loaded: Result<Neuron, _> = Neuron::load_bin(&path);
+    std::fs::remove_file(&path).ok();
+
+    assert_eq!(magic, [0x1f, 0x8b]);
+    assert_same_predictions(&neuron, &loaded.expect("leitura"));
+}
+
+#[test]
+fn compressed_checkpoint_is_detected_on_load() {
+    let neuron = large_neuron();
+    let path = temp_path("large.ckpt");
+    Checkpoint::save_with(&path, 40, 41, &neuron, true).expect("gravação");
+    let loaded: Result<Checkpoint, _> = Checkpoint::load(&path);
+    std::fs::remove_file(&path).ok();
+    let loaded = loaded.expect("leitura");
+
+    assert_eq!((loaded.epoch, loaded.config_hash), (40, 41));
+    assert_same_predictions(&neuron, &loaded.neuron);
+}
+
+#[test]
+fn gzip_magic_with_corrupt_payload_is_an_io_error() {
+    let path = temp_path("corrupt.cept.gz");
+    let mut bytes = vec![0x1f, 0x8b, 0x08, 0x00];
+    bytes.extend_from_slice(&[0xaa; 32]);
+    std::fs::write(&path, &bytes).expect("gravação");
+
+    let model = Neuron::<Float>::load_bin(&path);
+    let checkpoint = Checkpoint::<Float>::load(&path);
+    std::fs::remove_file(&path).ok();
+    assert!(matches!(model, Err(CeptronError::Io(ModelIoError::Io(_)))), "{:?}", model);
+    assert!(matches!(checkpoint, Err(ModelIoError::Io(_))), "{:?}", checkpoint.err());
+}
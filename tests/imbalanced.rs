@@ -0,0 +1,96 @@
+/*
+ * imbalanced.rs
+ *
+ * Testes dos remédios para classes desbalanceadas: pesos
+ * balanceados (balanced_weights) e sobreamostragem da classe
+ * minoritária (oversample_minority).
+ */
+
+#![cfg(feature = "std")]
+
+use perceptron::data::{balanced_weights, make_blobs, Dataset};
+use perceptron::netmath::{mse, Activation, Float};
+use perceptron::neuralnet::{fit_on, TrainConfig};
+use perceptron::neuron::Neuron;
+
+/*
+ * 95% das amostras na classe 0 e 5% na classe 1, com grupos
+ * sobrepostos: sem correção, o neurônio aprende a prever sempre 0.
+ */
+fn imbalanced() -> (Dataset, Vec<usize>) {
+    let (mut x, _) = make_blobs(380, &[vec![0.0, 0.0]], 1.0, 1).expect("centro válido");
+    let (minority, _) = make_blobs(20, &[vec![1.0, 1.0]], 1.0, 2).expect("centro válido");
+    x.extend(minority);
+    let labels: Vec<usize> = (0..400).map(|i| usize::from(i >= 380)).collect();
+    let targets = labels.iter().map(|&label| label as Float).collect();
+    (Dataset::new(x, targets).expect("dados válidos"), labels)
+}
+
+fn config() -> TrainConfig {
+    TrainConfig { epochs: 1000, learning_rate: 0.5, ..TrainConfig::default() }
+}
+
+/*
+ * Fração das amostras da classe 1 classificadas como 1.
+ */
+fn minority_recall(neuron: &Neuron, data: &Dataset) -> f64 {
+    let predicted = neuron.predict_labels(data.inputs(), 0.5);
+    let positives = data.targets().iter().filter(|&&y| y == 1.0).count();
+    let hits = predicted.iter().zip(data.targets()).filter(|&(&label, &y)| label && y == 1.0).count();
+    hits as f64 / positives as f64
+}
+
+fn trained(data: &Dataset, config: &TrainConfig) -> Neuron {
+    let mut neuron = Neuron::zeros(Activation::Sigmoid, 2);
+    fit_on(&mut neuron, mse, data, config).expect("treinamento válido");
+    neuron
+}
+
+#[test]
+fn unweighted_training_ignores_the_minority() {
+    let (data, _) = imbalanced();
+    let recall = minority_recall(&trained(&data, &config()), &data);
+    assert!(recall < 0.1, "recall {}", recall);
+}
+
+#[test]
+fn balanced_weights_recover_minority_recall() {
+    let (data, labels) = imbalanced();
+    let config = TrainConfig { sample_weights: Some(balanced_weights(&labels)), ..config() };
+    let recall = minority_recall(&trained(&data, &config), &data);
+    assert!(recall > 0.7, "recall {}", recall);
+}
+
+#[test]
+fn oversampling_recovers_minority_recall() {
+    let (data, _) = imbalanced();
+    let balanced = data.oversample_minority(3);
+    assert_eq!(balanced.len(), 2 * 380);
+    assert_eq!(balanced.targets().iter().filter(|&&y| y == 1.0).count(), 380);
+
+    let recall = minority_recall(&trained(&balanced, &config()), &data);
+    assert!(recall > 0.7, "recall {}", recall);
+}
+
+#[test]
+fn balanced_weights_accept_sparse_labels() {
+    let labels = [usize::MAX, 1_000_000_000, usize::MAX, usize::MAX];
+    let weights: Vec<Float> = balanced_weights(&labels);
+
+    let expected: [Float; 4] = [4.0 / 6.0, 2.0, 4.0 / 6.0, 4.0 / 6.0];
+    for (weight, expected) in weights.iter().zip(expected) {
+        assert!((weight - expected).abs() < 1e-6, "{:?}", weights);
+    }
+}
+
+#[test]
+fn balanced_weights_give_each_class_the_same_total() {
+    let labels = [0, 0, 0, 0, 0, 0, 1, 1, 2, 0, 0, 1];
+    let weights: Vec<Float> = balanced_weights(&labels);
+
+    let totals: Vec<Float> =
+        (0..3).map(|class| labels.iter().zip(&weights).filter(|&(&l, _)| l == class).map(|(_, &w)| w).sum()).collect();
+    for total in &totals {
+        assert!((total - 4.0).abs() < 1e-6, "{:?}", totals);
+    }
+}
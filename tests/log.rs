@@ -0,0 +1,106 @@
+/*
+ * log.rs
+ *
+ * Testes dos registros de treinamento (feature "log"): um logger de
+ * teste captura os registros de fit e confere os resumos de época e
+ * o aviso de divergência.
+ */
+
+#![cfg(feature = "log")]
+
+use std::cell::RefCell;
+use std::sync::Once;
+
+use log::{Level, LevelFilter, Log, Metadata, Record};
+
+use perceptron::error::CeptronError;
+use perceptron::netmath::{mse, Activation, Float};
+use perceptron::neuralnet::{fit, TrainConfig};
+use perceptron::neuron::Neuron;
+
+thread_local! {
+    // Cada teste roda em uma thread: os registros não se misturam
+    static RECORDS: RefCell<Vec<(Level, String)>> = const { RefCell::new(Vec::new()) };
+}
+
+/*
+ * Logger que guarda o nível e a mensagem de cada registro da thread
+ * atual.
+ */
+struct CaptureLogger;
+
+impl Log for CaptureLogger {
+    fn enabled(&self, _metadata: &Metadata) -> bool {
+        true
+    }
+
+    fn log(&self, record: &Record) {
+        RECORDS.with(|records| records.borrow_mut().push((record.level(), record.args().to_string())));
+    }
+
+    fn flush(&self) {}
+}
+
+static LOGGER: CaptureLogger = CaptureLogger;
+static INSTALL: Once = Once::new();
+
+/*
+ * Executa f e retorna os registros emitidos durante a execução.
+ */
+fn capture<R>(f: impl FnOnce() -> R) -> (R, Vec<(Level, String)>) {
+    INSTALL.call_once(|| {
+        log::set_logger(&LOGGER).expect("nenhum outro logger");
+        log::set_max_level(LevelFilter::Trace);
+    });
+    RECORDS.with(|records| records.borrow_mut().clear());
+    let result = f();
+    (result, RECORDS.with(|records| records.take()))
+}
+
+fn samples() -> (Vec<Vec<Float>>, Vec<Float>) {
+    let x: Vec<Vec<Float>> = vec![vec![6.0, 1.0], vec![5.0, 0.0], vec![4.0, 1.0], vec![1.0, 4.0], vec![1.0, 2.0]];
+    let y = x.iter().map(|sample| 3.0 * sample[0] + 2.0 * sample[1] + 5.0).collect();
+    (x, y)
+}
+
+#[test]
+fn epoch_summaries_respect_log_every() {
+    let (x, y) = samples();
+    let config = TrainConfig { epochs: 100, log_every: Some(25), ..TrainConfig::default() };
+    let mut neuron: Neuron = Neuron::zeros(Activation::Identity, 2);
+    let (result, records) = capture(|| fit(&mut neuron, mse, &x, &y, 5, &config));
+    result.expect("treinamento válido");
+
+    let epochs: Vec<&String> = records.iter().filter(|(level, _)| *level == Level::Info).map(|(_, text)| text).collect();
+    assert_eq!(epochs.len(), 4, "{:?}", records);
+    assert!(epochs[0].starts_with("epoch=25 cost=") && epochs[0].contains(" lr=0.001"), "{}", epochs[0]);
+    assert!(epochs[3].starts_with("epoch=100 "), "{}", epochs[3]);
+    assert!(records.iter().any(|(level, text)| *level == Level::Trace && text.starts_with("param=Weight(0) gradient=")));
+    assert!(!records.iter().any(|(level, _)| *level == Level::Warn), "{:?}", records);
+}
+
+#[test]
+fn no_epoch_summaries_without_log_every() {
+    let (x, y) = samples();
+    let config = TrainConfig { epochs: 20, ..TrainConfig::default() };
+    let mut neuron: Neuron = Neuron::zeros(Activation::Identity, 2);
+    let (result, records) = capture(|| fit(&mut neuron, mse, &x, &y, 5, &config));
+    result.expect("treinamento válido");
+
+    assert!(!records.iter().any(|(level, _)| *level == Level::Info), "{:?}", records);
+    assert_eq!(records.iter().filter(|(level, _)| *level == Level::Debug).count(), 2, "{:?}", records);
+}
+
+#[test]
+fn divergence_is_a_warning() {
+    let x: Vec<Vec<Float>> = vec![vec![1.0], vec![2.0]];
+    let y: Vec<Float> = vec![1.0, Float::INFINITY];
+    let config = TrainConfig { validate_data: false, log_every: Some(1), ..TrainConfig::default() };
+    let mut neuron: Neuron = Neuron::zeros(Activation::Identity, 1);
+    let (result, records) = capture(|| fit(&mut neuron, mse, &x, &y, 2, &config));
+
+    assert!(matches!(result, Err(CeptronError::Diverged(_))), "{:?}", result);
+    let warnings: Vec<&String> = records.iter().filter(|(level, _)| *level == Level::Warn).map(|(_, text)| text).collect();
+    assert_eq!(warnings.len(), 1, "{:?}", records);
+    assert!(warnings[0].starts_with("epoch=0 diverged param="), "{}", warnings[0]);
+}
@@ -0,0 +1,91 @@
+/*
+ * metadata.rs
+ *
+ * Testes dos metadados de treinamento gravados por fit ao lado do
+ * modelo (feature "serde").
+ */
+
+#![cfg(feature = "serde")]
+
+use std::path::PathBuf;
+
+use perceptron::data::{make_linear, Dataset};
+use perceptron::modelio::{downsample_history, metadata_path, ModelFile};
+use perceptron::netmath::{mse, Activation, Float};
+use perceptron::neuralnet::{fit_on, TrainConfig};
+use perceptron::neuron::Neuron;
+
+/*
+ * Caminho de um arquivo temporário exclusivo deste teste.
+ */
+fn temp_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("ceptron-{}-{}", std::process::id(), name))
+}
+
+fn data() -> Dataset {
+    make_linear(20, &[3.0, 2.0], 5.0, 0.1, (0.0, 1.0), 2)
+        .expect("parâmetros válidos")
+        .data
+}
+
+#[test]
+fn fit_writes_metadata_that_round_trips() {
+    let path = temp_path("model.cept");
+    let config = TrainConfig {
+        epochs: 120,
+        learning_rate: 0.05,
+        seed: Some(42),
+        save_path: Some(path.clone()),
+        history_points: Some(10),
+        ..TrainConfig::default()
+    };
+    let mut neuron = Neuron::new_seeded(Activation::Identity, 2, 42);
+    fit_on(&mut neuron, mse, &data(), &config).expect("treinamento válido");
+
+    let metadata = ModelFile::<Float>::metadata(&path);
+    std::fs::remove_file(&path).ok();
+    std::fs::remove_file(metadata_path(&path)).ok();
+    let metadata = metadata.expect("leitura").expect("metadados gravados por fit");
+
+    assert_eq!(metadata.library_version, env!("CARGO_PKG_VERSION"));
+    assert_eq!(metadata.model_fingerprint, neuron.fingerprint());
+    assert_eq!((metadata.config.epochs, metadata.config.seed), (120, Some(42)));
+    assert_eq!(metadata.config.learning_rate as Float, config.learning_rate);
+    assert_eq!((metadata.dataset.rows, metadata.dataset.features), (20, 2));
+    assert_eq!(metadata.dataset, data().fingerprint());
+    assert_eq!(metadata.history.len(), 10);
+    assert_eq!(metadata.history.first().map(|p| p.0), Some(1));
+    assert_eq!(metadata.history.last().map(|p| p.0), Some(120));
+}
+
+#[test]
+fn missing_metadata_is_none() {
+    let path = temp_path("no-metadata.cept");
+    assert!(ModelFile::<Float>::metadata(&path).expect("ausência não é erro").is_none());
+}
+
+#[test]
+fn dataset_hash_changes_with_one_value() {
+    let original = data();
+    let mut x = original.inputs().to_vec();
+    x[7][1] += 1e-3;
+    let changed = Dataset::new(x, original.targets().to_vec()).expect("dados válidos");
+
+    assert_eq!(original.fingerprint(), data().fingerprint());
+    assert_ne!(original.fingerprint().hash, changed.fingerprint().hash);
+    assert_eq!(original.fingerprint().rows, changed.fingerprint().rows);
+}
+
+#[test]
+fn downsampling_keeps_first_and_last() {
+    let history: Vec<(usize, f64)> = (1..=1000).map(|epoch| (epoch, 1.0 / epoch as f64)).collect();
+
+    for max_points in [0, 2, 3, 7, 100] {
+        let reduced = downsample_history(&history, max_points);
+        assert_eq!(reduced.len(), max_points.max(2));
+        assert_eq!(reduced.first(), history.first());
+        assert_eq!(reduced.last(), history.last());
+        assert!(reduced.windows(2).all(|pair| pair[0].0 < pair[1].0));
+    }
+    assert_eq!(downsample_history(&history[..5], 10), history[..5]);
+}
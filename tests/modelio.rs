@@ -0,0 +1,471 @@
+/*
+ * modelio.rs
+ *
+ * Testes dos formatos de arquivo de modelo que não dependem do
+ * serde: binário "CEPT", CSV de parâmetros, leitura de .npy e
+ * exportação ONNX.
+ */
+
+#![cfg(feature = "std")]
+
+use std::path::PathBuf;
+
+use perceptron::error::CeptronError;
+use perceptron::modelio::{ModelIoError, NpyError, ParamCsvError};
+use perceptron::netmath::{Activation, Float};
+use perceptron::neuron::Neuron;
+
+/*
+ * Caminho de um arquivo temporário exclusivo deste teste.
+ */
+fn temp_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("ceptron-{}-{}", std::process::id(), name))
+}
+
+/*
+ * Neurônio sigmoid com parâmetros sorteados (não representáveis
+ * exatamente em decimal curto).
+ */
+fn sample_neuron(n_connections: usize) -> Neuron {
+    Neuron::new_seeded(Activation::Sigmoid, n_connections, 17)
+}
+
+#[test]
+fn bin_round_trip_is_bit_identical() {
+    let neuron = sample_neuron(8);
+    let path = temp_path("round-trip.cept");
+    neuron.save_bin(&path).expect("gravação");
+    let loaded: Result<Neuron, _> = Neuron::load_bin(&path);
+    std::fs::remove_file(&path).ok();
+    let loaded = loaded.expect("leitura");
+
+    assert_eq!(loaded.weights(), neuron.weights());
+    assert_eq!(loaded.bias().to_bits(), neuron.bias().to_bits());
+    assert_eq!(loaded.activation().name(), Some("sigmoid"));
+}
+
+#[test]
+fn bin_round_trip_keeps_missing_bias() {
+    let neuron = sample_neuron(3).without_bias();
+    let loaded: Neuron = Neuron::from_bytes(&neuron.to_bytes().expect("ativação conhecida")).expect("leitura");
+
+    assert!(!loaded.use_bias());
+    assert_eq!(loaded.weights(), neuron.weights());
+}
+
+#[test]
+fn truncated_bin_is_rejected() {
+    let bytes = sample_neuron(4).to_bytes().expect("ativação conhecida");
+
+    for len in [6, bytes.len() - 1] {
+        let result = Neuron::<Float>::from_bytes(&bytes[..len]);
+        assert!(matches!(result, Err(ModelIoError::Length { .. })), "{} bytes: {:?}", len, result);
+    }
+}
+
+#[test]
+fn huge_connection_count_is_rejected_before_allocating() {
+    let mut bytes = sample_neuron(1).to_bytes().expect("ativação conhecida");
+    bytes[8..12].copy_from_slice(&u32::MAX.to_le_bytes());
+
+    let result = Neuron::<Float>::from_bytes(&bytes);
+    assert!(matches!(result, Err(ModelIoError::Length { .. })), "{:?}", result);
+}
+
+#[test]
+fn wrong_magic_is_rejected() {
+    let path = temp_path("wrong-magic.cept");
+    let mut bytes = sample_neuron(2).to_bytes().expect("ativação conhecida");
+    bytes[..4].copy_from_slice(b"NOPE");
+    std::fs::write(&path, &bytes).expect("gravação");
+
+    let result = Neuron::<Float>::load_bin(&path);
+    std::fs::remove_file(&path).ok();
+    assert!(matches!(result, Err(CeptronError::Io(ModelIoError::BadMagic))), "{:?}", result);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn bin_is_smaller_than_json() {
+    let neuron = sample_neuron(64);
+    let bin = temp_path("size.cept");
+    let json = temp_path("size.json");
+    neuron.save_bin(&bin).expect("gravação");
+    neuron.save_json(&json).expect("gravação");
+    let bin_len = std::fs::metadata(&bin).expect("arquivo gravado").len();
+    let json_len = std::fs::metadata(&json).expect("arquivo gravado").len();
+    std::fs::remove_file(&bin).ok();
+    std::fs::remove_file(&json).ok();
+
+    assert!(bin_len < json_len, "binário {} bytes, JSON {} bytes", bin_len, json_len);
+}
+
+#[cfg(not(feature = "gzip"))]
+#[test]
+fn gzip_without_the_feature_is_reported() {
+    let path = temp_path("no-gzip.cept");
+    let saved = sample_neuron(2).save_bin_with(&path, true);
+    std::fs::write(&path, [0x1f, 0x8b, 0x08, 0x00]).expect("gravação");
+    let loaded = Neuron::<Float>::load_bin(&path);
+    std::fs::remove_file(&path).ok();
+
+    assert!(matches!(saved, Err(CeptronError::Io(ModelIoError::GzipDisabled))), "{:?}", saved);
+    assert!(matches!(loaded, Err(CeptronError::Io(ModelIoError::GzipDisabled))), "{:?}", loaded);
+}
+
+/*
+ * Sem a feature "serde" não há save/load em JSON, mas o formato
+ * binário do modelo e do checkpoint continua disponível.
+ */
+#[cfg(not(feature = "serde"))]
+#[test]
+fn bin_paths_work_without_serde() {
+    use perceptron::modelio::Checkpoint;
+
+    let neuron = sample_neuron(5);
+    let model = temp_path("no-serde.cept");
+    let checkpoint = temp_path("no-serde.ckpt");
+    neuron.save_bin(&model).expect("gravação");
+    Checkpoint::save(&checkpoint, 12, 34, &neuron).expect("gravação");
+    let loaded: Result<Neuron, _> = Neuron::load_bin(&model);
+    let resumed: Result<Checkpoint<Float>, _> = Checkpoint::load(&checkpoint);
+    std::fs::remove_file(&model).ok();
+    std::fs::remove_file(&checkpoint).ok();
+
+    assert_eq!(loaded.expect("leitura").weights(), neuron.weights());
+    let resumed = resumed.expect("leitura");
+    assert_eq!((resumed.epoch, resumed.config_hash), (12, 34));
+    assert_eq!(resumed.neuron.weights(), neuron.weights());
+}
+
+/*
+ * Grava contents em um arquivo temporário e tenta importá-lo como
+ * CSV de parâmetros.
+ */
+fn import_text(name: &str, contents: &str) -> Result<Neuron, ModelIoError> {
+    let path = temp_path(name);
+    std::fs::write(&path, contents).expect("gravação");
+    let result = Neuron::import_csv(&path);
+    std::fs::remove_file(&path).ok();
+    result
+}
+
+#[test]
+fn csv_round_trip_keeps_full_precision() {
+    for seed in 0..20 {
+        let neuron: Neuron = Neuron::new_seeded(Activation::Sigmoid, 5, seed);
+        let path = temp_path(&format!("params-{}.csv", seed));
+        neuron.export_csv(&path).expect("gravação");
+        let loaded: Result<Neuron, _> = Neuron::import_csv(&path);
+        std::fs::remove_file(&path).ok();
+        let loaded = loaded.expect("leitura");
+
+        assert_eq!(loaded.weights(), neuron.weights());
+        assert_eq!(loaded.bias().to_bits(), neuron.bias().to_bits());
+    }
+}
+
+#[test]
+fn csv_has_one_row_per_parameter() {
+    let path = temp_path("rows.csv");
+    sample_neuron(4).export_csv(&path).expect("gravação");
+    let csv = std::fs::read_to_string(&path).expect("leitura");
+    std::fs::remove_file(&path).ok();
+
+    let rows: Vec<&str> = csv.lines().collect();
+    assert_eq!(rows[..2], ["name,index,value", "activation,,sigmoid"]);
+    assert_eq!(rows.iter().filter(|row| row.starts_with("weight,")).count(), 4);
+    assert_eq!(rows.iter().filter(|row| row.starts_with("bias,,")).count(), 1);
+    assert_eq!(rows.len(), 2 + 4 + 1);
+}
+
+#[test]
+fn hand_edited_csv_is_loaded() {
+    let neuron = import_text(
+        "edited.csv",
+        "name,index,value\nactivation,,identity\nweight,1, 2.0\nweight,0,3.0\n\nbias,,5\n",
+    )
+    .expect("CSV válido");
+
+    assert_eq!(neuron.weights(), &[3.0, 2.0]);
+    assert_eq!(neuron.compute_out(&[1.0, 2.0]), 12.0);
+
+    let without_bias = import_text("no-bias.csv", "name,index,value\nactivation,,identity\nweight,0,1.5\n")
+        .expect("CSV válido");
+    assert!(!without_bias.use_bias());
+}
+
+#[test]
+fn csv_rejects_bad_names_and_indices() {
+    let header = "name,index,value\nactivation,,identity\n";
+    let cases = [
+        ("index,name,value\n", ParamCsvError::Header),
+        (
+            &*format!("{}weights,,1.0\n", header),
+            ParamCsvError::Row { line: 3, content: "weights,,1.0".to_string() },
+        ),
+        (&*format!("{}weight,0,1.0\nweight,0,2.0\n", header), ParamCsvError::Index { line: 4, value: "0".to_string() }),
+        (&*format!("{}weight,-1,1.0\n", header), ParamCsvError::Index { line: 3, value: "-1".to_string() }),
+        (&*format!("{}bias,0,1.0\n", header), ParamCsvError::Index { line: 3, value: "0".to_string() }),
+        (&*format!("{}weight,0,abc\n", header), ParamCsvError::Value { line: 3, value: "abc".to_string() }),
+        (&*format!("{}weight,1,1.0\n", header), ParamCsvError::Missing { name: "weight", index: Some(0) }),
+        (
+            "name,index,value\nactivation,,relu\nweight,0,1.0\n",
+            ParamCsvError::Activation { line: 2, name: "relu".to_string() },
+        ),
+        (
+            &*format!("{}bias,,1.0\nbias,,2.0\nweight,0,1.0\n", header),
+            ParamCsvError::Duplicate { line: 4, name: "bias".to_string() },
+        ),
+        ("name,index,value\nweight,0,1.0\n", ParamCsvError::Missing { name: "activation", index: None }),
+    ];
+
+    for (i, (contents, expected)) in cases.into_iter().enumerate() {
+        match import_text(&format!("invalid-{}.csv", i), contents) {
+            Err(ModelIoError::Csv(err)) => assert_eq!(err, expected, "{}", contents),
+            other => panic!("{}: esperado {:?}, obtido {:?}", contents, expected, other),
+        }
+    }
+}
+
+/*
+ * Pesos [3.0, 2.0, -0.5] como float32, gravados por
+ * np.save("w.npy", np.array([3, 2, -0.5], dtype=np.float32)).
+ */
+const NPY_F4: &[u8] = b"\x93NUMPY\x01\x00\x76\x00\
+{'descr': '<f4', 'fortran_order': False, 'shape': (3,), }                                                            \n\
+\x00\x00\x40\x40\x00\x00\x00\x40\x00\x00\x00\xbf";
+
+/*
+ * Pesos [[3.0, 2.0]] como float64 (formato (1, 2), como o coef_
+ * do scikit-learn).
+ */
+const NPY_F8_ROW: &[u8] = b"\x93NUMPY\x01\x00\x76\x00\
+{'descr': '<f8', 'fortran_order': False, 'shape': (1, 2), }                                                          \n\
+\x00\x00\x00\x00\x00\x00\x08\x40\x00\x00\x00\x00\x00\x00\x00\x40";
+
+/*
+ * Monta um .npy versão 1 com o cabeçalho e os dados fornecidos.
+ */
+fn npy_file(header: &str, data: &[u8]) -> Vec<u8> {
+    let mut bytes = b"\x93NUMPY\x01\x00".to_vec();
+    bytes.extend_from_slice(&(header.len() as u16).to_le_bytes());
+    bytes.extend_from_slice(header.as_bytes());
+    bytes.extend_from_slice(data);
+    bytes
+}
+
+/*
+ * Grava bytes em um arquivo temporário e tenta carregá-lo como os
+ * pesos de um neurônio identidade com bias 5.
+ */
+fn load_npy_bytes(name: &str, bytes: &[u8]) -> Result<Neuron, ModelIoError> {
+    let path = temp_path(name);
+    std::fs::write(&path, bytes).expect("gravação");
+    let result = Neuron::load_npy(Activation::Identity, &path, 5.0);
+    std::fs::remove_file(&path).ok();
+    result
+}
+
+#[test]
+fn npy_fixtures_are_aligned_like_numpy() {
+    for fixture in [NPY_F4, NPY_F8_ROW] {
+        let header_len = usize::from(u16::from_le_bytes([fixture[8], fixture[9]]));
+        assert_eq!((10 + header_len) % 64, 0);
+        assert_eq!(fixture[10 + header_len - 1], b'\n');
+    }
+}
+
+#[test]
+fn npy_float32_weights_are_loaded() {
+    let neuron = load_npy_bytes("f4.npy", NPY_F4).expect("arquivo válido");
+
+    assert_eq!(neuron.weights(), &[3.0, 2.0, -0.5]);
+    assert_eq!(neuron.bias(), 5.0);
+    assert_eq!(neuron.compute_out(&[1.0, 2.0, 4.0]), 10.0);
+}
+
+#[test]
+fn npy_float64_row_vector_is_loaded() {
+    let neuron = load_npy_bytes("f8.npy", NPY_F8_ROW).expect("arquivo válido");
+
+    assert_eq!(neuron.weights(), &[3.0, 2.0]);
+    assert_eq!(neuron.compute_out(&[1.0, 2.0]), 12.0);
+}
+
+#[test]
+fn npy_rejects_unsupported_files() {
+    let four = [0u8; 4];
+    let cases: [(Vec<u8>, NpyError); 6] = [
+        (b"NUMPY\x01\x00".to_vec(), NpyError::BadMagic),
+        (
+            npy_file("{'descr': '<i4', 'fortran_order': False, 'shape': (1,), }\n", &four),
+            NpyError::Dtype { descr: "<i4".to_string() },
+        ),
+        (
+            npy_file("{'descr': '>f4', 'fortran_order': False, 'shape': (1,), }\n", &four),
+            NpyError::Dtype { descr: ">f4".to_string() },
+        ),
+        (npy_file("{'descr': '<f4', 'fortran_order': True, 'shape': (1,), }\n", &four), NpyError::FortranOrder),
+        (
+            npy_file("{'descr': '<f4', 'fortran_order': False, 'shape': (2,), }\n", &four),
+            NpyError::Length { expected: 8, actual: 4 },
+        ),
+        (npy_file("{'descr': '<f4', 'shape': (1,), }\n", &four), NpyError::Header),
+    ];
+
+    for (i, (bytes, expected)) in cases.into_iter().enumerate() {
+        match load_npy_bytes(&format!("invalid-{}.npy", i), &bytes) {
+            Err(ModelIoError::Npy(err)) => assert_eq!(err, expected),
+            other => panic!("caso {}: esperado {:?}, obtido {:?}", i, expected, other),
+        }
+    }
+}
+
+#[test]
+fn npy_rejects_matrix_for_a_single_neuron() {
+    let bytes = npy_file("{'descr': '<f4', 'fortran_order': False, 'shape': (2, 2), }\n", &[0u8; 16]);
+
+    match load_npy_bytes("matrix.npy", &bytes) {
+        Err(ModelIoError::Npy(err)) => assert_eq!(err, NpyError::Shape { shape: vec![2, 2] }),
+        other => panic!("esperado Shape, obtido {:?}", other),
+    }
+}
+
+/*
+ * Campo de uma mensagem protobuf: varint (tipo 0) ou bytes com
+ * tamanho (tipo 2), os únicos tipos usados por to_onnx.
+ */
+#[derive(Debug)]
+enum Field<'a> {
+    Varint(u64),
+    Bytes(&'a [u8]),
+}
+
+/*
+ * Lê um varint a partir de *pos, avançando a posição.
+ */
+fn read_varint(bytes: &[u8], pos: &mut usize) -> u64 {
+    let mut value = 0u64;
+    for shift in (0..64).step_by(7) {
+        let byte = *bytes.get(*pos).expect("varint truncado");
+        *pos += 1;
+        value |= u64::from(byte & 0x7f) << shift;
+        if byte & 0x80 == 0 {
+            return value;
+        }
+    }
+    panic!("varint com mais de 10 bytes");
+}
+
+/*
+ * Decodifica uma mensagem protobuf em (número do campo, valor),
+ * conferindo que as chaves e os tamanhos consomem exatamente os
+ * bytes da mensagem.
+ */
+fn parse_message(bytes: &[u8]) -> Vec<(u64, Field<'_>)> {
+    let mut fields = Vec::new();
+    let mut pos = 0;
+    while pos < bytes.len() {
+        let key = read_varint(bytes, &mut pos);
+        let value = match key & 7 {
+            0 => Field::Varint(read_varint(bytes, &mut pos)),
+            2 => {
+                let len = read_varint(bytes, &mut pos) as usize;
+                let value = bytes.get(pos..pos + len).expect("campo ultrapassa a mensagem");
+                pos += len;
+                Field::Bytes(value)
+            }
+            wire => panic!("tipo de campo inesperado {}", wire),
+        };
+        fields.push((key >> 3, value));
+    }
+    fields
+}
+
+/*
+ * Submensagens (ou textos) de um campo.
+ */
+fn bytes_of<'a>(fields: &[(u64, Field<'a>)], number: u64) -> Vec<&'a [u8]> {
+    fields
+        .iter()
+        .filter_map(|(n, value)| match value {
+            Field::Bytes(bytes) if *n == number => Some(*bytes),
+            _ => None,
+        })
+        .collect()
+}
+
+/*
+ * Texto do primeiro campo com esse número.
+ */
+fn text_of(fields: &[(u64, Field<'_>)], number: u64) -> String {
+    String::from_utf8(bytes_of(fields, number)[0].to_vec()).expect("texto UTF-8")
+}
+
+/*
+ * GraphProto de um modelo exportado por to_onnx.
+ */
+fn onnx_graph(model: &[u8]) -> Vec<(u64, Field<'_>)> {
+    let fields = parse_message(model);
+    assert!(matches!(fields[0], (1, Field::Varint(7))), "ir_version 7: {:?}", fields[0]);
+    parse_message(bytes_of(&fields, 7)[0])
+}
+
+#[test]
+fn onnx_graph_has_matmul_add_and_activation() {
+    let model = sample_neuron(3).to_onnx().expect("ativação conhecida");
+    let graph = onnx_graph(&model);
+
+    let ops: Vec<String> = bytes_of(&graph, 1).into_iter().map(|node| text_of(&parse_message(node), 4)).collect();
+    assert_eq!(ops, ["MatMul", "Add", "Sigmoid"]);
+    assert_eq!(text_of(&parse_message(bytes_of(&graph, 11)[0]), 1), "X");
+    assert_eq!(text_of(&parse_message(bytes_of(&graph, 12)[0]), 1), "Y");
+}
+
+#[test]
+fn onnx_has_one_initializer_per_parameter_tensor() {
+    let neuron = sample_neuron(3);
+    let model = neuron.to_onnx().expect("ativação conhecida");
+    let graph = onnx_graph(&model);
+
+    let initializers: Vec<_> = bytes_of(&graph, 5).into_iter().map(parse_message).collect();
+    assert_eq!(initializers.len(), 2);
+    let names: Vec<String> = initializers.iter().map(|tensor| text_of(tensor, 8)).collect();
+    assert_eq!(names, ["W", "B"]);
+
+    let dims: Vec<u64> = initializers[0]
+        .iter()
+        .filter_map(|(n, value)| match value {
+            Field::Varint(dim) if *n == 1 => Some(*dim),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(dims, [3, 1]);
+    let weights: Vec<u8> = neuron.weights().iter().flat_map(|w| w.to_le_bytes()).collect();
+    assert_eq!(bytes_of(&initializers[0], 9)[0], weights);
+    assert_eq!(bytes_of(&initializers[1], 9)[0], neuron.bias().to_le_bytes());
+}
+
+#[test]
+fn onnx_without_bias_skips_add() {
+    let model = sample_neuron(2).without_bias().to_onnx().expect("ativação conhecida");
+    let graph = onnx_graph(&model);
+
+    let ops: Vec<String> = bytes_of(&graph, 1).into_iter().map(|node| text_of(&parse_message(node), 4)).collect();
+    assert_eq!(ops, ["MatMul", "Sigmoid"]);
+    assert_eq!(bytes_of(&graph, 5).len(), 1);
+}
+
+#[test]
+fn onnx_rejects_custom_activation() {
+    fn relu(x: Float) -> Float {
+        x.max(0.0)
+    }
+    let neuron = Neuron::from_weights(Activation::Custom(relu), vec![1.0], 0.0).expect("parâmetros finitos");
+    let path = temp_path("custom.onnx");
+
+    let result = neuron.export_onnx(&path);
+    assert!(matches!(result, Err(ModelIoError::UnserializableActivation { layer: 0, neuron: 0 })), "{:?}", result);
+    assert!(!path.exists());
+}
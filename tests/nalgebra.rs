@@ -0,0 +1,80 @@
+/*
+ * nalgebra.rs
+ *
+ * Testes da conversão de e para vetores e matrizes do nalgebra
+ * (feature "nalgebra").
+ */
+
+#![cfg(feature = "nalgebra")]
+
+use nalgebra::{DMatrix, DVector};
+
+use perceptron::data::{DataError, Dataset};
+use perceptron::error::CeptronError;
+use perceptron::netmath::{Activation, Float};
+use perceptron::neuron::{InputDimError, Neuron, NeuronError};
+
+/*
+ * Matriz 5x3 com valores distintos em cada posição.
+ */
+fn matrix() -> DMatrix<Float> {
+    DMatrix::from_fn(5, 3, |i, j| (i as Float) * 0.5 - (j as Float) * 1.25 + 0.375)
+}
+
+#[test]
+fn dvector_round_trip_keeps_weights() {
+    let neuron: Neuron = Neuron::new_seeded(Activation::Sigmoid, 4, 13);
+    let rebuilt = Neuron::from_dvector(&neuron.weights_dvector(), neuron.bias(), Activation::Sigmoid)
+        .expect("parâmetros finitos");
+
+    assert_eq!(rebuilt.weights(), neuron.weights());
+    assert_eq!(rebuilt.bias(), neuron.bias());
+    assert_eq!(neuron.weights_dvector().len(), 4);
+}
+
+#[test]
+fn from_dvector_rejects_empty_and_non_finite_weights() {
+    let empty = Neuron::from_dvector(&DVector::<Float>::zeros(0), 0.0, Activation::Identity);
+    let nan = Neuron::from_dvector(&DVector::from_vec(vec![1.0, Float::NAN]), 0.0, Activation::Identity);
+
+    assert!(matches!(empty, Err(NeuronError::EmptyWeights)), "{:?}", empty);
+    assert!(matches!(nan, Err(NeuronError::NonFiniteWeight { index: 1, .. })), "{:?}", nan);
+}
+
+#[test]
+fn predict_matrix_matches_compute_out_per_row() {
+    let x = matrix();
+    let neuron: Neuron = Neuron::new_seeded(Activation::Sigmoid, 3, 13);
+    let predicted = neuron.predict_matrix(&x).expect("largura correta");
+
+    assert_eq!(predicted.len(), x.nrows());
+    for (i, row) in x.row_iter().enumerate() {
+        let sample: Vec<Float> = row.iter().copied().collect();
+        assert_eq!(predicted[i].to_bits(), neuron.compute_out(&sample).to_bits());
+    }
+}
+
+#[test]
+fn predict_matrix_rejects_wrong_width_without_panicking() {
+    let neuron: Neuron = Neuron::new_seeded(Activation::Identity, 2, 13);
+
+    assert_eq!(neuron.predict_matrix(&matrix()), Err(InputDimError { expected: 2, actual: 3 }));
+}
+
+#[test]
+fn dmatrix_dataset_round_trip() {
+    let x = matrix();
+    let y = DVector::from_fn(5, |i, _| i as Float);
+    let data = Dataset::from_dmatrix(&x, &y).expect("dados válidos");
+
+    assert_eq!(data.to_dmatrix(), x);
+    assert_eq!(data.targets(), y.as_slice());
+    assert_eq!(data.inputs()[1], vec![x[(1, 0)], x[(1, 1)], x[(1, 2)]]);
+}
+
+#[test]
+fn from_dmatrix_reports_length_mismatch() {
+    let result = Dataset::from_dmatrix(&matrix(), &DVector::zeros(4));
+
+    assert!(matches!(result, Err(CeptronError::Data(DataError::LengthMismatch { inputs: 5, targets: 4 }))), "{:?}", result);
+}
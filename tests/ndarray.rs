@@ -0,0 +1,95 @@
+/*
+ * ndarray.rs
+ *
+ * Testes da conversão de e para matrizes do ndarray (feature
+ * "ndarray").
+ */
+
+#![cfg(feature = "ndarray")]
+
+use ndarray::{array, s, Array1, Array2};
+
+use perceptron::data::{DataError, Dataset};
+use perceptron::error::CeptronError;
+use perceptron::netmath::{Activation, Float};
+use perceptron::neuron::{InputDimError, Neuron};
+
+/*
+ * Matriz 6x4 com valores distintos em cada posição.
+ */
+fn matrix() -> Array2<Float> {
+    Array2::from_shape_fn((6, 4), |(i, j)| (i as Float) * 0.75 - (j as Float) * 1.5 + 0.125)
+}
+
+fn neuron(n_connections: usize) -> Neuron {
+    Neuron::new_seeded(Activation::Sigmoid, n_connections, 9)
+}
+
+#[test]
+fn predict_array_matches_compute_out_per_row() {
+    let x = matrix();
+    let neuron = neuron(4);
+    let rows: Vec<Vec<Float>> = x.rows().into_iter().map(|row| row.to_vec()).collect();
+
+    let predicted = neuron.predict_array(x.view()).expect("largura correta");
+    let expected = neuron.predict_batch(&rows).expect("largura correta");
+    assert_eq!(predicted.len(), expected.len());
+    for (a, b) in predicted.iter().zip(&expected) {
+        assert_eq!(a.to_bits(), b.to_bits());
+    }
+}
+
+#[test]
+fn predict_array_accepts_non_contiguous_views() {
+    let x = matrix();
+    let neuron = neuron(2);
+    // Colunas 0 e 2 das linhas pares: nem linhas nem colunas contíguas
+    let view = x.slice(s![..;2, ..;2]);
+    assert!(!view.is_standard_layout());
+
+    let predicted = neuron.predict_array(view).expect("largura correta");
+    for (i, output) in predicted.iter().enumerate() {
+        let row = [x[[2 * i, 0]], x[[2 * i, 2]]];
+        assert_eq!(output.to_bits(), neuron.compute_out(&row).to_bits());
+    }
+}
+
+#[test]
+fn predict_array_rejects_wrong_width() {
+    let result = neuron(3).predict_array(matrix().view());
+
+    assert_eq!(result, Err(InputDimError { expected: 3, actual: 4 }));
+}
+
+#[test]
+fn from_ndarray_matches_vec_dataset() {
+    let x = matrix();
+    let y: Array1<Float> = x.column(0).mapv(|value| 2.0 * value + 1.0);
+    let from_arrays = Dataset::from_ndarray(x.slice(s![.., 1..]), y.view()).expect("dados válidos");
+    let rows: Vec<Vec<Float>> = x.rows().into_iter().map(|row| row.iter().skip(1).copied().collect()).collect();
+    let from_vecs = Dataset::new(rows, y.to_vec()).expect("dados válidos");
+
+    assert_eq!(from_arrays.inputs(), from_vecs.inputs());
+    assert_eq!(from_arrays.targets(), from_vecs.targets());
+}
+
+#[test]
+fn from_ndarray_reports_shape_errors() {
+    let x = matrix();
+    let y: Array1<Float> = Array1::zeros(5);
+    let result = Dataset::from_ndarray(x.view(), y.view());
+    assert!(matches!(result, Err(CeptronError::Data(DataError::LengthMismatch { inputs: 6, targets: 5 }))), "{:?}", result);
+
+    let empty: Array2<Float> = Array2::zeros((0, 3));
+    let result = Dataset::from_ndarray(empty.view(), Array1::zeros(0).view());
+    assert!(matches!(result, Err(CeptronError::Data(DataError::Empty))), "{:?}", result);
+}
+
+#[test]
+fn neuron_converts_to_weight_array_and_bias() {
+    let neuron = Neuron::from_weights(Activation::Identity, vec![3.0, -2.0], 5.0).expect("parâmetros finitos");
+    let (weights, bias): (Array1<Float>, Float) = (&neuron).into();
+
+    assert_eq!(weights, array![3.0, -2.0]);
+    assert_eq!(bias, 5.0);
+}
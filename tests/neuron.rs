@@ -0,0 +1,627 @@
+/*
+ * neuron.rs
+ *
+ * Testes da estrutura Neuron: saída, construção, validação dos
+ * parâmetros e formatação.
+ */
+
+use perceptron::assert_neuron_approx_eq;
+use perceptron::netmath::{ident, sigmoid, Activation, Float, Scalar};
+use perceptron::utils::{randn_with, randomize_with, seeded_rng};
+use perceptron::neuron::{
+    BatchInputError, BuildError, InitScheme, InputDimError, Neuron, Neuron32, Neuron64, NeuronBuilder, NeuronError,
+    OutputError, ParamType, SparseInputError,
+};
+
+/*
+ * Neurônio da relação y = 3x1 + 2x2 + 5 do exemplo.
+ */
+fn linear() -> Neuron {
+    Neuron::from_weights(Activation::Identity, vec![3.0, 2.0], 5.0).expect("parâmetros finitos")
+}
+
+#[test]
+fn compute_out_accepts_array_vec_and_subslice() {
+    let neuron = linear();
+    let owned: Vec<Float> = [1.0, 2.0].to_vec();
+    let buffer: Vec<Float> = [9.0, 1.0, 2.0, 9.0].to_vec();
+
+    assert_eq!(neuron.compute_out(&[1.0, 2.0]), 12.0);
+    assert_eq!(neuron.compute_out(&owned), 12.0);
+    assert_eq!(neuron.compute_out(&buffer[1..3]), 12.0);
+}
+
+#[test]
+fn try_compute_out_checks_input_width() {
+    let neuron = linear();
+
+    assert_eq!(neuron.try_compute_out(&[1.0, 2.0]), Ok(12.0));
+    assert_eq!(neuron.try_compute_out(&[1.0]), Err(InputDimError { expected: 2, actual: 1 }));
+    assert_eq!(neuron.try_compute_out(&[1.0, 2.0, 3.0]), Err(InputDimError { expected: 2, actual: 3 }));
+    assert_eq!(neuron.try_compute_out(&[]), Err(InputDimError { expected: 2, actual: 0 }));
+}
+
+#[test]
+#[should_panic(expected = "entrada com 1 valores, mas o neurônio espera 2")]
+fn compute_out_panics_with_both_widths() {
+    linear().compute_out(&[1.0]);
+}
+
+#[test]
+fn n_connections_always_matches_weights() {
+    let mut neuron = linear();
+    assert_eq!(neuron.n_connections(), neuron.weights().len());
+
+    assert_eq!(
+        neuron.set_params(&[1.0, 2.0, 3.0], 0.0),
+        Err(NeuronError::WeightCountMismatch { expected: 2, actual: 3 })
+    );
+    assert_eq!(neuron.weights(), &[3.0, 2.0]);
+    assert_eq!(neuron.n_connections(), 2);
+
+    neuron.set_weight(1, 4.0);
+    neuron.set_bias(-1.0);
+    assert_eq!(neuron.weights(), &[3.0, 4.0]);
+    assert_eq!(neuron.bias(), -1.0);
+    assert_eq!(neuron.n_connections(), neuron.weights().len());
+}
+
+#[test]
+#[should_panic]
+fn set_weight_rejects_missing_connection() {
+    linear().set_weight(2, 1.0);
+}
+
+/*
+ * Média e variância amostrais de todos os pesos dos neurônios.
+ */
+fn weight_stats<T: Scalar>(neurons: &[Neuron<T>]) -> (f64, f64) {
+    let values: Vec<f64> = neurons
+        .iter()
+        .flat_map(|neuron| neuron.weights().iter().map(|w| w.to_f64().unwrap_or(f64::NAN)))
+        .collect();
+    let n = values.len() as f64;
+    let mean = values.iter().sum::<f64>() / n;
+    let variance = values.iter().map(|w| (w - mean) * (w - mean)).sum::<f64>() / n;
+    (mean, variance)
+}
+
+#[test]
+fn xavier_variance_matches_theory() {
+    let neurons: Vec<Neuron> = (0..1000)
+        .map(|seed| Neuron::new_xavier_seeded(Activation::Sigmoid, 10, 1, seed))
+        .collect();
+    let (_, variance) = weight_stats(&neurons);
+    let expected = 2.0 / 11.0;
+
+    assert!((variance - expected).abs() < 0.1 * expected, "variância {}", variance);
+    assert!(neurons.iter().all(|neuron| neuron.bias() == 0.0));
+}
+
+#[test]
+fn xavier_keeps_wide_sigmoid_unsaturated() {
+    let neuron: Neuron = Neuron::new_xavier_seeded(Activation::Sigmoid, 100, 1, 7);
+    let mut rng = seeded_rng(8);
+
+    for _sample in 0..100 {
+        let x: Vec<Float> = (0..100).map(|_| randomize_with(&mut rng, -1.0, 1.0)).collect();
+        assert!(neuron.weighted_sum(&x).abs() < 4.0);
+    }
+}
+
+#[test]
+fn he_std_matches_theory() {
+    let neurons: Vec<Neuron> = (0..500)
+        .map(|seed| Neuron::new_he_seeded(Activation::Identity, 20, seed))
+        .collect();
+    let (mean, variance) = weight_stats(&neurons);
+    let expected = (2.0f64 / 20.0).sqrt();
+
+    assert!(mean.abs() < 0.02, "média {}", mean);
+    assert!((variance.sqrt() - expected).abs() < 0.05 * expected, "desvio {}", variance.sqrt());
+    assert!(neurons.iter().all(|neuron| neuron.bias() == 0.0));
+}
+
+#[test]
+fn randn_matches_mean_and_std() {
+    let mut rng = seeded_rng(12);
+    let draws: Vec<f64> = (0..20000).map(|_| randn_with(&mut rng, 3.0, 0.5)).collect();
+    let n = draws.len() as f64;
+    let mean = draws.iter().sum::<f64>() / n;
+    let std = (draws.iter().map(|d| (d - mean) * (d - mean)).sum::<f64>() / n).sqrt();
+
+    assert!((mean - 3.0).abs() < 0.02, "média {}", mean);
+    assert!((std - 0.5).abs() < 0.02, "desvio {}", std);
+}
+
+#[test]
+fn range_init_stays_inside_range() {
+    for seed in 0..50 {
+        let neuron: Neuron = Neuron::new_with_range_seeded(Activation::Identity, 8, 1000.0, 2000.0, seed)
+            .expect("intervalo válido");
+        let inside = |value: Float| (1000.0..2000.0).contains(&value);
+
+        assert!(neuron.weights().iter().all(|&w| inside(w)));
+        assert!(inside(neuron.bias()));
+        assert_eq!(neuron.init(), Some(InitScheme::Uniform { min: 1000.0, max: 2000.0 }));
+    }
+}
+
+#[test]
+fn range_init_rejects_degenerate_ranges() {
+    let build = |min: Float, max: Float| Neuron::new_with_range_seeded(Activation::Identity, 2, min, max, 1);
+
+    assert!(matches!(build(1.0, 1.0), Err(NeuronError::InvalidRange { .. })));
+    assert!(matches!(build(2.0, 1.0), Err(NeuronError::InvalidRange { .. })));
+    assert!(matches!(build(Float::NAN, 1.0), Err(NeuronError::InvalidRange { .. })));
+    assert!(matches!(build(0.0, Float::INFINITY), Err(NeuronError::InvalidRange { .. })));
+}
+
+#[test]
+fn same_seed_gives_same_neuron() {
+    let a: Neuron = Neuron::new_seeded(Activation::Sigmoid, 4, 42);
+    let b: Neuron = Neuron::new_seeded(Activation::Sigmoid, 4, 42);
+    let c: Neuron = Neuron::new_seeded(Activation::Sigmoid, 4, 43);
+
+    assert_eq!(a.weights(), b.weights());
+    assert_eq!(a.bias(), b.bias());
+    assert_ne!(a.weights(), c.weights());
+}
+
+#[test]
+fn fingerprint_is_pinned_for_fixed_parameters() {
+    let build32 = |activation| Neuron32::from_weights(activation, vec![1.5, -2.0], 0.25).expect("parâmetros finitos");
+    let neuron64 = Neuron64::from_weights(Activation::Identity, vec![1.5, -2.0], 0.25).expect("parâmetros finitos");
+
+    assert_eq!(build32(Activation::Identity).fingerprint(), 0x0583_a799_ee27_08db);
+    assert_eq!(build32(Activation::Sigmoid).fingerprint(), 0x13ba_1895_77c8_3fc7);
+    assert_eq!(neuron64.fingerprint(), 0x9f28_e4fa_86c8_cb20);
+}
+
+#[test]
+fn fingerprint_sees_every_bit() {
+    let build = |weight: f32, bias: f32| Neuron32::from_weights(Activation::Identity, vec![weight, 1.0], bias);
+    let original = build(1.5, 0.0).expect("parâmetros finitos");
+    let flipped = build(f32::from_bits(1.5f32.to_bits() ^ 1), 0.0).expect("parâmetros finitos");
+    let negative_zero = build(1.5, -0.0).expect("parâmetros finitos");
+
+    assert_eq!(original.fingerprint(), build(1.5, 0.0).expect("parâmetros finitos").fingerprint());
+    assert_ne!(original.fingerprint(), flipped.fingerprint());
+    assert_ne!(original.fingerprint(), negative_zero.fingerprint());
+}
+
+#[test]
+fn fingerprint_keeps_nan_payloads_of_f32() {
+    let with_bias = |bits: u32| {
+        let mut neuron: Neuron32 = Neuron::zeros(Activation::Identity, 1);
+        neuron.set_bias(f32::from_bits(bits));
+        neuron.fingerprint()
+    };
+
+    assert_ne!(with_bias(0x7f80_0001), with_bias(0x7fc0_0001));
+}
+
+#[test]
+fn zeros_identity_outputs_zero() {
+    let zeros: Neuron = Neuron::zeros(Activation::Identity, 3);
+
+    for x in [[0.0, 0.0, 0.0], [1.0, -2.0, 3.0], [1e6, 1e-6, -7.5]] {
+        assert_eq!(zeros.compute_out(&x), 0.0);
+    }
+}
+
+#[test]
+fn constant_sets_every_parameter() {
+    let neuron: Neuron = Neuron::constant(Activation::Identity, 3, 0.5, -1.0);
+
+    assert_eq!(neuron.weights(), &[0.5, 0.5, 0.5]);
+    assert_eq!(neuron.bias(), -1.0);
+    assert_eq!(neuron.compute_out(&[2.0, 2.0, 2.0]), 2.0);
+}
+
+#[test]
+fn from_weights_reproduces_linear_targets() {
+    let neuron = linear();
+    let x: [[Float; 2]; 6] = [[6.0, 1.0], [5.0, 0.0], [4.0, 1.0], [1.0, 4.0], [1.0, 2.0], [2.0, 3.0]];
+
+    for sample in &x {
+        assert_eq!(neuron.compute_out(sample), 3.0 * sample[0] + 2.0 * sample[1] + 5.0);
+    }
+}
+
+#[test]
+fn from_weights_rejects_invalid_parameters() {
+    let build = |weights: Vec<Float>, bias: Float| Neuron::from_weights(Activation::Identity, weights, bias);
+
+    assert!(matches!(build(Vec::new(), 0.0), Err(NeuronError::EmptyWeights)));
+    assert!(matches!(build(vec![1.0, Float::NAN], 0.0), Err(NeuronError::NonFiniteWeight { index: 1, .. })));
+    assert!(matches!(build(vec![1.0], Float::INFINITY), Err(NeuronError::NonFiniteBias { .. })));
+}
+
+#[test]
+#[should_panic(expected = "o neurônio precisa de pelo menos um peso")]
+fn zero_connections_panic_like_from_weights() {
+    Neuron::<Float>::zeros(Activation::Identity, 0);
+}
+
+#[test]
+fn zero_connections_are_reported_by_fallible_constructors() {
+    assert_eq!(
+        NeuronBuilder::<Float>::new(0).seed(1).build().map(|_| ()),
+        Err(BuildError::Invalid(NeuronError::EmptyWeights))
+    );
+    assert!(matches!(
+        Neuron::<Float>::new_with_range_seeded(Activation::Identity, 0, -1.0, 1.0, 1),
+        Err(NeuronError::EmptyWeights)
+    ));
+}
+
+#[test]
+fn display_shows_parameters_with_requested_precision() {
+    let neuron = linear();
+
+    assert_eq!(neuron.to_string(), "Neuron(2 inputs, w=[3.00, 2.00], b=5.00, act=identity)");
+    assert_eq!(format!("{:.1}", neuron), "Neuron(2 inputs, w=[3.0, 2.0], b=5.0, act=identity)");
+    assert_eq!(
+        linear().without_bias().to_string(),
+        "Neuron(2 inputs, w=[3.00, 2.00], b=none, act=identity)"
+    );
+}
+
+#[test]
+fn debug_names_fields_and_activation() {
+    let debug = format!("{:?}", linear());
+
+    assert!(debug.starts_with("Neuron { weights: [3.0, 2.0], bias: 5.0"), "{}", debug);
+    assert!(debug.contains("n_connections: 2"));
+    assert!(debug.contains("activation: \"identity\""));
+}
+
+#[test]
+fn apply_delta_updates_one_parameter() {
+    let mut neuron = linear();
+    neuron.apply_delta(ParamType::Weight(1), 0.5).expect("delta finito");
+    neuron.apply_delta(ParamType::Bias, -1.0).expect("delta finito");
+
+    assert_eq!(neuron.weights(), &[3.0, 2.5]);
+    assert_eq!(neuron.bias(), 4.0);
+}
+
+#[test]
+fn apply_delta_rejects_invalid_updates() {
+    let mut neuron = linear();
+
+    assert!(matches!(
+        neuron.apply_delta(ParamType::Weight(0), Float::NAN),
+        Err(NeuronError::NonFiniteDelta { param: ParamType::Weight(0), .. })
+    ));
+    assert!(matches!(
+        neuron.apply_delta(ParamType::Bias, Float::INFINITY),
+        Err(NeuronError::NonFiniteDelta { param: ParamType::Bias, .. })
+    ));
+    assert_eq!(
+        neuron.apply_delta(ParamType::Weight(2), 1.0),
+        Err(NeuronError::WeightIndexOutOfRange { index: 2, n_connections: 2 })
+    );
+    assert_eq!(neuron.weights(), &[3.0, 2.0]);
+    assert_eq!(neuron.bias(), 5.0);
+}
+
+#[test]
+fn set_params_validates_before_changing() {
+    let mut neuron = linear();
+
+    assert!(matches!(neuron.set_params(&[1.0, Float::NAN], 0.0), Err(NeuronError::NonFiniteWeight { index: 1, .. })));
+    assert!(matches!(neuron.set_params(&[1.0, 1.0], Float::NAN), Err(NeuronError::NonFiniteBias { .. })));
+    assert_eq!(neuron.weights(), &[3.0, 2.0]);
+
+    neuron.set_params(&[1.0, -1.0], 0.25).expect("parâmetros válidos");
+    assert_eq!(neuron.weights(), &[1.0, -1.0]);
+    assert_eq!(neuron.bias(), 0.25);
+}
+
+#[test]
+fn weighted_sum_is_pre_activation_output() {
+    let identity: Neuron = Neuron::new_seeded(Activation::Identity, 3, 5);
+    let logistic: Neuron = Neuron::new_seeded(Activation::Sigmoid, 3, 5);
+    let mut rng = seeded_rng(6);
+
+    for _sample in 0..50 {
+        let x: Vec<Float> = (0..3).map(|_| randomize_with(&mut rng, -5.0, 5.0)).collect();
+        assert_eq!(identity.weighted_sum(&x), identity.compute_out(&x));
+        assert_eq!(sigmoid(logistic.weighted_sum(&x)), logistic.compute_out(&x));
+    }
+}
+
+#[test]
+fn predict_batch_matches_compute_out() {
+    let neuron: Neuron = Neuron::new_seeded(Activation::Sigmoid, 2, 9);
+    let x: Vec<Vec<Float>> = vec![vec![0.0, 1.0], vec![-2.0, 3.5], vec![10.0, -10.0]];
+    let expected: Vec<Float> = x.iter().map(|sample| neuron.compute_out(sample)).collect();
+
+    assert_eq!(neuron.predict_batch(&x), Ok(expected));
+    assert_eq!(neuron.predict_batch(&[]), Ok(Vec::new()));
+}
+
+#[test]
+fn predict_batch_reports_first_bad_row() {
+    let x: Vec<Vec<Float>> = vec![vec![0.0, 1.0], vec![1.0], vec![1.0, 2.0, 3.0]];
+
+    assert_eq!(
+        linear().predict_batch(&x),
+        Err(BatchInputError { index: 1, source: InputDimError { expected: 2, actual: 1 } })
+    );
+}
+
+#[test]
+fn predict_batch_into_overwrites_buffer() {
+    let neuron = linear();
+    let mut out: Vec<Float> = vec![-1.0; 5];
+
+    neuron.predict_batch_into(&[vec![1.0, 1.0], vec![0.0, 0.0]], &mut out).expect("larguras corretas");
+    assert_eq!(out, [10.0, 5.0]);
+
+    assert!(neuron.predict_batch_into(&[vec![1.0]], &mut out).is_err());
+    assert!(out.is_empty());
+}
+
+#[test]
+fn reset_redraws_reproducibly() {
+    let mut a: Neuron = Neuron::new_seeded(Activation::Sigmoid, 4, 1);
+    let mut b = linear();
+    b.reset(Some(2));
+    let before = b.generation();
+
+    a.reset(Some(2));
+    b.reset(Some(2));
+    let fresh: Neuron = Neuron::new_seeded(Activation::Sigmoid, 4, 2);
+
+    assert_eq!(a.weights(), fresh.weights());
+    assert_eq!(a.bias(), fresh.bias());
+    assert_eq!(a.generation(), 1);
+    assert_eq!(b.generation(), before + 1);
+    assert_eq!(b.n_connections(), 2);
+}
+
+#[test]
+fn reset_keeps_width_and_activation() {
+    let mut neuron: Neuron = Neuron::new_xavier_seeded(Activation::Sigmoid, 6, 1, 3);
+    for seed in 0..20 {
+        neuron.reset(Some(seed));
+        assert_eq!(neuron.weights().len(), 6);
+        assert_eq!(neuron.activation().name(), Some("sigmoid"));
+        assert_eq!(neuron.bias(), 0.0);
+    }
+}
+
+#[test]
+fn builder_defaults_match_new() {
+    let built: Neuron = NeuronBuilder::new(3).seed(11).build().expect("opções válidas");
+    let direct: Neuron = Neuron::new_seeded(Activation::Identity, 3, 11);
+
+    assert_eq!(built.weights(), direct.weights());
+    assert_eq!(built.bias(), direct.bias());
+    assert_eq!(built.activation().name(), Some("identity"));
+    assert!(built.use_bias());
+
+    for _draw in 0..20 {
+        let unseeded: Neuron = NeuronBuilder::new(3).build().expect("opções válidas");
+        assert!(unseeded.weights().iter().chain([&unseeded.bias()]).all(|p| (-1.0..1.0).contains(p)));
+    }
+}
+
+#[test]
+fn builder_options_take_effect() {
+    let neuron: Neuron = NeuronBuilder::new(2)
+        .activation(Activation::Sigmoid)
+        .init(InitScheme::Constant { weight: 0.25, bias: 0.5 })
+        .build()
+        .expect("opções válidas");
+    assert_eq!(neuron.activation().name(), Some("sigmoid"));
+    assert_eq!(neuron.weights(), &[0.25, 0.25]);
+    assert_eq!(neuron.bias(), 0.5);
+
+    let xavier: Neuron = NeuronBuilder::new(2)
+        .init(InitScheme::Xavier { fan_out: 1 })
+        .seed(4)
+        .build()
+        .expect("opções válidas");
+    let direct: Neuron = Neuron::new_xavier_seeded(Activation::Identity, 2, 1, 4);
+    assert_eq!(xavier.weights(), direct.weights());
+
+    let no_bias: Neuron = NeuronBuilder::new(2).seed(4).bias(false).build().expect("opções válidas");
+    assert!(!no_bias.use_bias());
+    assert_eq!(no_bias.bias(), 0.0);
+
+    let explicit: Neuron = NeuronBuilder::new(2).weights(vec![3.0, 2.0], 5.0).build().expect("opções válidas");
+    assert_eq!(explicit.compute_out(&[1.0, 1.0]), 10.0);
+}
+
+#[test]
+fn builder_rejects_conflicting_options() {
+    let with_init = NeuronBuilder::<Float>::new(2)
+        .weights(vec![1.0, 1.0], 0.0)
+        .init(InitScheme::He)
+        .build();
+    let with_seed = NeuronBuilder::<Float>::new(2).weights(vec![1.0, 1.0], 0.0).seed(1).build();
+    let wrong_count = NeuronBuilder::<Float>::new(2).weights(vec![1.0], 0.0).build();
+
+    assert!(matches!(with_init, Err(BuildError::Conflict { option: "weights", other: "init" })));
+    assert!(matches!(with_seed, Err(BuildError::Conflict { option: "weights", other: "seed" })));
+    assert!(matches!(
+        wrong_count,
+        Err(BuildError::Invalid(NeuronError::WeightCountMismatch { expected: 2, actual: 1 }))
+    ));
+}
+
+#[test]
+fn predict_label_is_positive_at_threshold() {
+    let half: Neuron = Neuron::constant(Activation::Identity, 1, 0.0, 0.5);
+    let sigmoid_zero: Neuron = Neuron::zeros(Activation::Sigmoid, 1);
+
+    assert!(half.predict_label(&[3.0], 0.5));
+    assert!(!half.predict_label(&[3.0], 0.51));
+    assert!(sigmoid_zero.predict_label_default(&[1.0]));
+    assert_eq!(sigmoid_zero.decision_value(&[1.0]), 0.5);
+}
+
+#[test]
+fn predict_labels_thresholds_each_sample() {
+    let neuron: Neuron = Neuron::constant(Activation::Identity, 1, 1.0, 0.0);
+    let x: Vec<Vec<Float>> = vec![vec![-1.0], vec![0.0], vec![0.2], vec![2.0]];
+
+    assert_eq!(neuron.predict_labels(&x, 0.2), [false, false, true, true]);
+}
+
+#[test]
+fn weight_norm_excludes_bias() {
+    let neuron: Neuron = Neuron::from_weights(Activation::Identity, vec![3.0, 4.0], 100.0).expect("parâmetros finitos");
+    assert_eq!(neuron.weight_norm(), 5.0);
+
+    let mut clipped = neuron;
+    assert!(clipped.clip_weight_norm(2.5));
+    assert_eq!(clipped.weights(), &[1.5, 2.0]);
+    assert_eq!(clipped.bias(), 100.0);
+    assert!(!clipped.clip_weight_norm(10.0));
+}
+
+#[test]
+fn approx_eq_compares_within_tolerance() {
+    let base = linear();
+    let close = Neuron::from_weights(Activation::Identity, vec![3.0005, 1.9995], 5.0005).expect("parâmetros finitos");
+    let far = Neuron::from_weights(Activation::Identity, vec![3.0, 2.1], 5.0).expect("parâmetros finitos");
+    let wider = Neuron::from_weights(Activation::Identity, vec![3.0, 2.0, 0.0], 5.0).expect("parâmetros finitos");
+    let other_activation = Neuron::from_weights(Activation::Sigmoid, vec![3.0, 2.0], 5.0).expect("parâmetros finitos");
+
+    assert!(base.approx_eq(&linear(), 0.0));
+    assert!(base.approx_eq(&close, 1e-3));
+    assert!(!base.approx_eq(&far, 1e-3));
+    assert!(!base.approx_eq(&wider, 1.0));
+    assert!(!base.approx_eq(&other_activation, 1.0));
+    assert_neuron_approx_eq!(base, close, 1e-3);
+}
+
+#[test]
+#[should_panic(expected = "neurônios diferentes")]
+fn assert_neuron_approx_eq_reports_mismatch() {
+    let far = Neuron::from_weights(Activation::Identity, vec![3.0, 2.1], 5.0).expect("parâmetros finitos");
+    assert_neuron_approx_eq!(linear(), far, 1e-3);
+}
+
+fn relu(x: Float) -> Float {
+    x.max(0.0)
+}
+
+#[test]
+fn fn_pointers_map_to_named_activations() {
+    let identity: fn(Float) -> Float = ident;
+    let logistic: fn(Float) -> Float = sigmoid;
+    let custom: fn(Float) -> Float = relu;
+
+    assert!(Activation::from(identity) == Activation::Identity);
+    assert!(Activation::from(logistic) == Activation::Sigmoid);
+    assert!(Activation::from(custom) == Activation::Custom(custom));
+    assert_eq!(Activation::from(custom).name(), None);
+}
+
+#[cfg(feature = "std-rand")]
+#[test]
+#[allow(deprecated)]
+fn new_with_fn_shim_uses_named_activation() {
+    let neuron: Neuron = Neuron::new_with_fn(sigmoid, 2);
+    assert_eq!(neuron.activation().name(), Some("sigmoid"));
+}
+
+#[test]
+fn custom_activation_computes_output() {
+    let neuron: Neuron = Neuron::from_weights(Activation::Custom(relu), vec![1.0, -1.0], 0.0).expect("parâmetros finitos");
+
+    assert_eq!(neuron.compute_out(&[2.0, 1.0]), 1.0);
+    assert_eq!(neuron.compute_out(&[1.0, 2.0]), 0.0);
+    assert_eq!(neuron.to_string(), "Neuron(2 inputs, w=[1.00, -1.00], b=0.00, act=custom)");
+}
+
+#[test]
+fn compute_out_iter_consumes_exactly_n_connections() {
+    let neuron = linear();
+
+    assert_eq!(neuron.compute_out_iter([1.0, 2.0]), Ok(12.0));
+    assert_eq!(neuron.compute_out_iter([1.0]), Err(InputDimError { expected: 2, actual: 1 }));
+    assert!(neuron.compute_out_iter([1.0, 2.0, 3.0]).is_err());
+    assert_eq!(neuron.compute_out_iter(std::iter::empty()), Err(InputDimError { expected: 2, actual: 0 }));
+}
+
+#[test]
+fn compute_out_iter_accepts_lazy_sources() {
+    let neuron = linear();
+    let fields = "1.0;2.0";
+    let parsed = fields.split(';').map(|field| field.parse::<Float>().expect("número"));
+
+    assert_eq!(neuron.compute_out_iter(parsed), Ok(12.0));
+    assert_eq!(neuron.compute_out_iter((1..=2).map(|i| i as Float)), Ok(12.0));
+}
+
+#[test]
+fn injected_nan_weight_is_flagged() {
+    let mut neuron = linear();
+    assert!(neuron.is_finite());
+    assert_eq!(neuron.checked_compute_out(&[1.0, 1.0]), Ok(10.0));
+
+    neuron.set_weight(1, Float::NAN);
+    assert!(!neuron.is_finite());
+    assert_eq!(neuron.first_non_finite(), Some(ParamType::Weight(1)));
+    assert!(matches!(neuron.checked_compute_out(&[1.0, 1.0]), Err(OutputError::NonFinite { .. })));
+}
+
+fn densify(indices: &[usize], values: &[Float], width: usize) -> Vec<Float> {
+    let mut dense = vec![0.0; width];
+    for (&index, &value) in indices.iter().zip(values) {
+        dense[index] = value;
+    }
+    dense
+}
+
+#[test]
+fn sparse_output_matches_densified_input() {
+    let neuron = Neuron::new_seeded(Activation::Sigmoid, 50, 13);
+    let samples: Vec<(Vec<usize>, Vec<Float>)> = vec![
+        (vec![0, 7, 19, 49], vec![0.5, -1.25, 3.0, 0.1]),
+        (vec![3], vec![-2.0]),
+        (vec![], vec![]),
+    ];
+
+    for (indices, values) in &samples {
+        let dense = densify(indices, values, 50);
+        assert_eq!(neuron.compute_out_sparse(indices, values).unwrap().to_bits(), neuron.compute_out(&dense).to_bits());
+    }
+
+    let dense: Vec<Vec<Float>> = samples.iter().map(|(indices, values)| densify(indices, values, 50)).collect();
+    assert_eq!(neuron.predict_batch_sparse(&samples), Ok(neuron.predict_batch(&dense).unwrap()));
+}
+
+#[test]
+fn sparse_rejects_out_of_range_index() {
+    let neuron = linear();
+    assert_eq!(
+        neuron.compute_out_sparse(&[0, 2], &[1.0, 1.0]),
+        Err(SparseInputError::IndexOutOfRange { position: 1, index: 2, n_connections: 2 })
+    );
+}
+
+#[test]
+fn sparse_rejects_duplicated_index() {
+    let neuron = linear();
+    assert_eq!(
+        neuron.compute_out_sparse(&[1, 1], &[1.0, 1.0]),
+        Err(SparseInputError::NotIncreasing { position: 1, index: 1 })
+    );
+
+    let batch = vec![(vec![0], vec![1.0]), (vec![1, 1], vec![1.0, 1.0])];
+    assert_eq!(
+        neuron.predict_batch_sparse(&batch),
+        Err(SparseInputError::Batch {
+            sample: 1,
+            source: Box::new(SparseInputError::NotIncreasing { position: 1, index: 1 }),
+        })
+    );
+}
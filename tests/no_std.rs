@@ -0,0 +1,63 @@
+/*
+ * no_std.rs
+ *
+ * Testes do caminho de inferência que compila sem a feature "std"
+ * (neuron e netmath com libm). Este arquivo usa só esse caminho e
+ * roda nas duas configurações (`cargo test` e
+ * `cargo test --no-default-features`), comparando com os mesmos
+ * valores de referência.
+ *
+ * A exp da libm e a da plataforma podem diferir em um ulp, então a
+ * sigmoid é comparada com tolerância; o resto é aritmética exata.
+ */
+
+use perceptron::netmath::{from_f64, ident, sigmoid, Activation, Float};
+use perceptron::neuron::Neuron;
+
+fn relu(x: Float) -> Float {
+    if x > 0.0 { x } else { 0.0 }
+}
+
+/*
+ * Confere que value está a no máximo 2 ulps (relativos) de expected,
+ * calculado em f64.
+ */
+fn assert_close(value: Float, expected: f64) {
+    let expected: Float = from_f64(expected);
+    let tolerance = 2.0 * Float::EPSILON * expected.abs();
+    assert!((value - expected).abs() <= tolerance, "{} != {}", value, expected);
+}
+
+#[test]
+fn sigmoid_matches_reference_values() {
+    assert_eq!(sigmoid::<Float>(0.0), 0.5);
+    for x in [-20.0f64, -4.5, -1.0, -0.25, 0.125, 1.0, 3.0, 20.0] {
+        assert_close(sigmoid(from_f64::<Float>(x)), 1.0 / (1.0 + (-x).exp()));
+    }
+    assert_eq!(Activation::<Float>::Sigmoid.apply(1.0), sigmoid(1.0));
+    assert_close(Activation::<Float>::Sigmoid.derivative(0.0), 0.25);
+}
+
+#[test]
+fn relu_and_identity_are_exact() {
+    let inputs: [Float; 5] = [-2.5, -0.0, 0.0, 0.75, 1e6];
+    for x in inputs {
+        assert_eq!(Activation::Custom(relu).apply(x).to_bits(), relu(x).to_bits());
+        assert_eq!(Activation::<Float>::Identity.apply(x).to_bits(), ident(x).to_bits());
+    }
+    assert_eq!(Activation::Custom(relu).apply(-3.0), 0.0);
+}
+
+#[test]
+fn compute_out_from_weights_is_identical() {
+    let linear = Neuron::from_weights(Activation::Identity, vec![3.0, 2.0], 5.0).expect("parâmetros finitos");
+    let clipped = Neuron::from_weights(Activation::Custom(relu), vec![1.0, -2.0], 0.5).expect("parâmetros finitos");
+    let logistic = Neuron::from_weights(Activation::Sigmoid, vec![0.5, -1.5], 0.25).expect("parâmetros finitos");
+
+    assert_eq!(linear.compute_out(&[1.0, 2.0]), 12.0);
+    assert_eq!(linear.compute_out(&[0.5, -0.25]), 6.0);
+    assert_eq!(clipped.compute_out(&[1.0, 1.0]), 0.0);
+    assert_eq!(clipped.compute_out(&[2.0, 0.25]), 2.0);
+    assert_eq!(logistic.compute_out(&[1.0, 0.5]), sigmoid(0.0));
+    assert_close(logistic.compute_out(&[2.0, 0.0]), 1.0 / (1.0 + (-1.25f64).exp()));
+}
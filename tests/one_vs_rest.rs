@@ -0,0 +1,122 @@
+/*
+ * one_vs_rest.rs
+ *
+ * Testes do classificador multiclasse OneVsRest.
+ */
+
+#![cfg(feature = "std")]
+
+use perceptron::data::make_blobs;
+use perceptron::neuralnet::{ClassLabelError, OneVsRest, OneVsRestError, TrainConfig};
+use perceptron::netmath::Float;
+
+fn blobs() -> (Vec<Vec<Float>>, Vec<usize>) {
+    let centers = vec![vec![0.0, 0.0], vec![5.0, 0.0], vec![0.0, 5.0]];
+    make_blobs(150, &centers, 0.7, 3).expect("centros válidos")
+}
+
+fn config() -> TrainConfig {
+    TrainConfig {
+        epochs: 1500,
+        learning_rate: 0.5,
+        seed: Some(3),
+        ..TrainConfig::default()
+    }
+}
+
+#[test]
+fn fits_three_class_blobs() {
+    let (x, labels) = blobs();
+    let model = OneVsRest::fit(3, &x, &labels, &config()).expect("treinamento válido");
+
+    let correct = x
+        .iter()
+        .zip(&labels)
+        .filter(|&(sample, &label)| model.predict(sample) == label)
+        .count();
+    let accuracy = correct as f64 / x.len() as f64;
+    assert!(accuracy > 0.85, "acurácia de treino {}", accuracy);
+}
+
+#[test]
+fn predict_is_argmax_of_predict_proba() {
+    let (x, labels) = blobs();
+    let model = OneVsRest::fit(3, &x, &labels, &config()).expect("treinamento válido");
+
+    for sample in &x {
+        let scores = model.predict_proba(sample);
+        let argmax = (0..scores.len())
+            .fold(0, |best, class| if scores[class] > scores[best] { class } else { best });
+        assert_eq!(model.predict(sample), argmax);
+    }
+}
+
+#[test]
+fn seeded_fit_is_reproducible() {
+    let (x, labels) = blobs();
+    let config = TrainConfig { epochs: 10, ..config() };
+    let a = OneVsRest::fit(3, &x, &labels, &config).expect("treinamento válido");
+    let b = OneVsRest::fit(3, &x, &labels, &config).expect("treinamento válido");
+
+    for (a, b) in a.classifiers.iter().zip(&b.classifiers) {
+        assert_eq!(a.weights(), b.weights());
+        assert_eq!(a.bias(), b.bias());
+    }
+}
+
+#[test]
+fn rejects_label_count_mismatch() {
+    let (x, labels) = blobs();
+    match OneVsRest::fit(3, &x, &labels[1..], &config()) {
+        Err(OneVsRestError::LabelCount { samples: 150, labels: 149 }) => {}
+        other => panic!("esperado LabelCount, obtido {:?}", other.err()),
+    }
+}
+
+#[test]
+fn rejects_samples_without_inputs() {
+    let no_samples: Vec<Vec<Float>> = Vec::new();
+    let no_inputs: Vec<Vec<Float>> = vec![Vec::new(); 3];
+
+    for x in [no_samples, no_inputs] {
+        let labels = vec![0; x.len()];
+        match OneVsRest::fit(2, &x, &labels, &TrainConfig::default()) {
+            Err(OneVsRestError::Empty) => {}
+            other => panic!("esperado Empty para {} amostras, obtido {:?}", x.len(), other.err()),
+        }
+    }
+}
+
+#[test]
+fn rejects_out_of_range_label() {
+    let (x, mut labels) = blobs();
+    labels[7] = 3;
+    match OneVsRest::fit(3, &x, &labels, &config()) {
+        Err(OneVsRestError::ClassLabel(err)) => {
+            assert_eq!(err, ClassLabelError { index: 7, label: 3, n_classes: 3 });
+        }
+        other => panic!("esperado ClassLabel, obtido {:?}", other.err()),
+    }
+}
+
+#[test]
+fn reports_class_of_failed_fit() {
+    let (x, labels) = blobs();
+    let config = TrainConfig { learning_rate: -1.0, ..config() };
+    match OneVsRest::fit(3, &x, &labels, &config) {
+        Err(OneVsRestError::Fit { class: 0, .. }) => {}
+        other => panic!("esperado Fit na classe 0, obtido {:?}", other.err()),
+    }
+}
+
+#[test]
+fn predict_breaks_ties_toward_lower_label() {
+    use perceptron::netmath::Activation;
+    use perceptron::neuron::Neuron;
+
+    let neuron = |weight| Neuron::constant(Activation::Sigmoid, 2, weight, 0.0);
+    let model = OneVsRest { classifiers: vec![neuron(0.5), neuron(1.0), neuron(1.0)] };
+
+    assert_eq!(model.predict(&[1.0, 1.0]), 1);
+    assert_eq!(model.predict_proba(&[1.0, 1.0]).len(), 3);
+}
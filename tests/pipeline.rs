@@ -0,0 +1,74 @@
+/*
+ * pipeline.rs
+ *
+ * Testes do Pipeline (normalização + neurônio).
+ */
+
+#![cfg(feature = "std")]
+
+use perceptron::data::{make_linear, Dataset, StandardScaler};
+use perceptron::netmath::{mse, Activation};
+use perceptron::neuralnet::{fit_on, TrainConfig};
+use perceptron::neuron::Neuron;
+use perceptron::pipeline::{Pipeline, ScalerKind};
+
+/*
+ * y = 300x1 - 20x2 + 1000, com entradas em [0, 100]: escala grande
+ * demais para treinar sem normalização.
+ */
+fn wide_data() -> Dataset {
+    make_linear(50, &[300.0, -20.0], 1000.0, 0.0, (0.0, 100.0), 17)
+        .expect("parâmetros válidos")
+        .data
+}
+
+fn config() -> TrainConfig {
+    TrainConfig { epochs: 2000, learning_rate: 0.1, ..TrainConfig::default() }
+}
+
+#[test]
+fn predictions_match_manually_chained_steps() {
+    let data = wide_data();
+    let mut pipeline = Pipeline::new(Neuron::zeros(Activation::Identity, 2), Some(ScalerKind::Standard), true);
+    pipeline.fit(&data, &config()).expect("treinamento válido");
+
+    let mut scaler = StandardScaler::fit(&data);
+    scaler.fit_target(&data);
+    let inputs = scaler.transform(&data).expect("mesma largura");
+    let targets = data.targets().iter().map(|&y| scaler.transform_target(y)).collect();
+    let scaled = Dataset::new(inputs.inputs().to_vec(), targets).expect("dados válidos");
+    let mut neuron = Neuron::zeros(Activation::Identity, 2);
+    fit_on(&mut neuron, mse, &scaled, &config()).expect("treinamento válido");
+
+    for sample in data.inputs() {
+        let manual = scaler.inverse_transform_target(neuron.compute_out(&scaler.transform_sample(sample).unwrap()));
+        assert_eq!(pipeline.predict(sample).to_bits(), manual.to_bits());
+    }
+}
+
+#[test]
+fn predictions_are_in_original_target_units() {
+    let data = wide_data();
+    let mut pipeline = Pipeline::new(Neuron::zeros(Activation::Identity, 2), Some(ScalerKind::MinMax), true);
+    pipeline.fit(&data, &config()).expect("treinamento válido");
+
+    for (sample, &target) in data.inputs().iter().zip(data.targets()) {
+        let out = pipeline.predict(sample);
+        assert!((out - target).abs() < 1e-2 * target.abs().max(1.0), "{} != {}", out, target);
+    }
+    assert!(pipeline.try_predict(&[1.0]).is_err());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn pipeline_survives_json_round_trip() {
+    let data = wide_data();
+    let mut pipeline = Pipeline::new(Neuron::zeros(Activation::Identity, 2), Some(ScalerKind::Standard), true);
+    pipeline.fit(&data, &config()).expect("treinamento válido");
+
+    let json = serde_json::to_string(&pipeline).expect("pipeline serializável");
+    let loaded: Pipeline = serde_json::from_str(&json).expect("JSON válido");
+    for sample in data.inputs() {
+        assert_eq!(loaded.predict(sample).to_bits(), pipeline.predict(sample).to_bits());
+    }
+}
@@ -0,0 +1,54 @@
+/*
+ * prelude.rs
+ *
+ * Teste de compilação do prelude: o exemplo linear
+ * y = 3x1 + 2x2 + 5 é criado, treinado, avaliado e conferido usando
+ * apenas `use perceptron::prelude::*`.
+ */
+
+#![cfg(feature = "std")]
+
+use perceptron::prelude::*;
+
+/*
+ * Amostras exatas de y = 3x1 + 2x2 + 5 em uma grade de [0, 1]².
+ */
+fn linear_data() -> Result<Dataset, CeptronError> {
+    let mut x: Vec<Vec<Float>> = Vec::new();
+    for i in 0..5 {
+        for j in 0..5 {
+            x.push(vec![i as Float / 4.0, j as Float / 4.0]);
+        }
+    }
+    let y = x.iter().map(|sample| 3.0 * sample[0] + 2.0 * sample[1] + 5.0).collect();
+    Dataset::new(x, y)
+}
+
+/*
+ * O fluxo completo, com `?` propagando CeptronError.
+ * Retorna o neurônio treinado e o seu custo nas amostras de teste.
+ */
+fn train_linear_example() -> Result<(Neuron, Float), CeptronError> {
+    let data = linear_data()?;
+    let (train_data, test_data) = train_test_split(&data, 0.2, Some(7))?;
+
+    let mut neuron: Neuron = Neuron::new_seeded(Activation::Identity, 2, 7);
+    let config = TrainConfig { epochs: 3000, learning_rate: 0.1, seed: Some(7), ..TrainConfig::default() };
+    fit_on(&mut neuron, mse, &train_data, &config)?;
+
+    let prediction = Model::predict(&neuron, &[1.0, 1.0])?;
+    assert!((prediction - 10.0).abs() < 0.05, "{}", prediction);
+    let test_cost = try_compute_cost(&neuron, test_data.inputs(), test_data.targets(), mse, test_data.len())?;
+    Ok((neuron, test_cost))
+}
+
+#[test]
+fn prelude_alone_trains_the_linear_example() {
+    let (neuron, test_cost) = train_linear_example().expect("fluxo válido");
+
+    assert!(test_cost < 1e-3, "custo no teste: {}", test_cost);
+    for (&weight, &expected) in neuron.weights().iter().zip(&[3.0, 2.0]) {
+        assert!((weight - expected).abs() < 0.05, "{:?}", neuron.weights());
+    }
+    assert!((neuron.bias() - 5.0).abs() < 0.05, "{}", neuron.bias());
+}
@@ -0,0 +1,342 @@
+/*
+ * preprocessing.rs
+ *
+ * Testes das transformações de dados (data.rs): normalização,
+ * padronização e codificação de categorias.
+ */
+
+#![cfg(feature = "std")]
+
+use perceptron::data::{
+    apply_selection, make_linear, one_hot, select_by_correlation, Dataset, EncodeError, ImputeStrategy, Imputer, LabelEncoder, MissingColumnError, MinMaxScaler, OneHotEncoder, StandardScaler, UnknownCategory,
+    UnknownLabel,
+};
+use perceptron::netmath::{mse, Activation, Float};
+use perceptron::neuralnet::{fit_on, try_compute_cost, try_train, ClassLabelError, TrainConfig};
+use perceptron::neuron::Neuron;
+
+/*
+ * Entradas em escalas bem diferentes, com uma coluna constante.
+ */
+fn wide_data() -> Dataset {
+    let x: Vec<Vec<Float>> = (0..20)
+        .map(|i| {
+            let i = i as Float;
+            vec![1000.0 + 37.0 * i, -3.5 + 0.25 * i, 7.0, (i * 0.7).sin()]
+        })
+        .collect();
+    let y = x.iter().map(|s| s[0] / 1000.0 + s[1]).collect();
+    Dataset::new(x, y).expect("dados válidos")
+}
+
+#[test]
+fn min_max_maps_training_features_into_unit_interval() {
+    let data = wide_data();
+    let (scaler, scaled) = MinMaxScaler::fit_transform(&data);
+
+    for sample in scaled.inputs() {
+        assert!(sample.iter().all(|&v| (0.0..=1.0).contains(&v)), "{:?}", sample);
+        assert_eq!(sample[2], 0.0, "entrada constante deve virar 0.0");
+    }
+    assert_eq!(scaled.targets(), data.targets());
+    assert_eq!((scaler.min[0], scaler.max[0]), (1000.0, 1000.0 + 37.0 * 19.0));
+}
+
+#[test]
+fn min_max_inverse_undoes_transform() {
+    let data = wide_data();
+    let (scaler, scaled) = MinMaxScaler::fit_transform(&data);
+    let restored = scaler.inverse_transform(&scaled).expect("mesma largura");
+
+    for (original, back) in data.inputs().iter().zip(restored.inputs()) {
+        for (&a, &b) in original.iter().zip(back) {
+            assert!((a - b).abs() <= 1e-6 * a.abs().max(1.0), "{} != {}", a, b);
+        }
+    }
+}
+
+#[test]
+fn min_max_does_not_clamp_unseen_values() {
+    let scaler = MinMaxScaler::fit(&wide_data());
+
+    let below = scaler.transform_sample(&[900.0, -10.0, 7.0, 0.0]).expect("mesma largura");
+    let above = scaler.transform_sample(&[5000.0, 10.0, 8.0, 0.0]).expect("mesma largura");
+    assert!(below[0] < 0.0 && below[1] < 0.0, "{:?}", below);
+    assert!(above[0] > 1.0 && above[1] > 1.0, "{:?}", above);
+    assert_eq!(above[2], 0.0);
+}
+
+#[test]
+fn min_max_rejects_wrong_width() {
+    let scaler = MinMaxScaler::fit(&wide_data());
+    let err = scaler.transform_sample(&[1.0, 2.0]).unwrap_err();
+    assert_eq!((err.expected, err.actual), (4, 2));
+}
+
+/*
+ * Média e desvio padrão populacional de uma coluna.
+ */
+fn column_stats(data: &Dataset, column: usize) -> (Float, Float) {
+    let n = data.len() as Float;
+    let mean = data.inputs().iter().map(|s| s[column]).sum::<Float>() / n;
+    let variance = data.inputs().iter().map(|s| (s[column] - mean).powi(2)).sum::<Float>() / n;
+    (mean, variance.sqrt())
+}
+
+#[test]
+fn standardized_columns_have_zero_mean_and_unit_std() {
+    let (scaler, scaled) = StandardScaler::fit_transform(&wide_data());
+
+    for column in [0, 1, 3] {
+        let (mean, std) = column_stats(&scaled, column);
+        assert!(mean.abs() < 1e-5, "coluna {}: média {}", column, mean);
+        assert!((std - 1.0).abs() < 1e-5, "coluna {}: desvio {}", column, std);
+    }
+    assert_eq!(scaler.constant, [false, false, true, false]);
+    assert!(scaled.inputs().iter().all(|s| s[2] == 0.0));
+}
+
+#[test]
+fn standard_inverse_undoes_transform() {
+    let data = wide_data();
+    let mut scaler = StandardScaler::fit(&data);
+    scaler.fit_target(&data);
+    let scaled = scaler.transform(&data).expect("mesma largura");
+    let restored = scaler.inverse_transform(&scaled).expect("mesma largura");
+
+    for (original, back) in data.inputs().iter().zip(restored.inputs()) {
+        for (&a, &b) in original.iter().zip(back) {
+            assert!((a - b).abs() <= 1e-5 * a.abs().max(1.0), "{} != {}", a, b);
+        }
+    }
+    for &y in data.targets() {
+        let back = scaler.inverse_transform_target(scaler.transform_target(y));
+        assert!((y - back).abs() <= 1e-5 * y.abs().max(1.0), "{} != {}", y, back);
+    }
+}
+
+/*
+ * Número de passos de try_train (taxa fixa 0.001) até o custo, na
+ * escala original de y, ficar abaixo de `threshold`; None se não
+ * chegar em `max_steps`.
+ */
+fn steps_to_cost(data: &Dataset, scaler: Option<&StandardScaler>, threshold: Float, max_steps: usize) -> Option<usize> {
+    let train_data = match scaler {
+        Some(scaler) => scaler.transform(data).expect("mesma largura"),
+        None => Dataset::new(data.inputs().to_vec(), data.targets().to_vec()).expect("dados válidos"),
+    };
+    let mut neuron = Neuron::zeros(Activation::Identity, 2);
+
+    for step in 0..max_steps {
+        let out_pred: Vec<Float> = train_data
+            .inputs()
+            .iter()
+            .map(|s| {
+                let out = neuron.compute_out(s);
+                scaler.map_or(out, |scaler| scaler.inverse_transform_target(out))
+            })
+            .collect();
+        if mse(data.targets(), &out_pred, data.len()) < threshold {
+            return Some(step);
+        }
+        try_train(&mut neuron, mse, train_data.inputs(), train_data.targets(), train_data.len()).expect("passo válido");
+    }
+    None
+}
+
+#[test]
+fn standardization_speeds_up_the_fixed_step_trainer() {
+    // Exemplo linear y = 3x1 + 2x2 + 5 com entradas inteiras
+    let x: Vec<Vec<Float>> = vec![vec![6.0, 1.0], vec![5.0, 0.0], vec![4.0, 1.0], vec![1.0, 4.0], vec![1.0, 2.0], vec![2.0, 3.0]];
+    let y = x.iter().map(|s| 3.0 * s[0] + 2.0 * s[1] + 5.0).collect();
+    let data = Dataset::new(x, y).expect("dados válidos");
+
+    let mut scaler = StandardScaler::fit(&data);
+    scaler.fit_target(&data);
+
+    let raw = steps_to_cost(&data, None, 1e-2, 200_000).expect("converge sem padronização");
+    let standardized = steps_to_cost(&data, Some(&scaler), 1e-2, 200_000).expect("converge padronizado");
+    assert!(standardized * 2 < raw, "{} passos padronizado, {} sem padronização", standardized, raw);
+}
+
+#[test]
+fn argmax_of_one_hot_recovers_labels() {
+    let labels = [2, 0, 1, 1, 3, 0];
+    let encoded: Vec<Vec<Float>> = one_hot(&labels, 4).expect("rótulos válidos");
+
+    let decoded: Vec<usize> = encoded
+        .iter()
+        .map(|row| {
+            assert_eq!(row.iter().sum::<Float>(), 1.0);
+            row.iter().position(|&v| v == 1.0).expect("uma coluna ativa")
+        })
+        .collect();
+    assert_eq!(decoded, labels);
+}
+
+#[test]
+fn one_hot_rejects_out_of_range_label() {
+    assert_eq!(
+        one_hot::<Float>(&[0, 3, 1], 3),
+        Err(ClassLabelError { index: 1, label: 3, n_classes: 3 })
+    );
+}
+
+#[test]
+fn unknown_category_encodes_as_zeros_or_errors() {
+    let train = ["red", "green", "blue", "green"];
+
+    let zeros = OneHotEncoder::fit(&train, UnknownCategory::Zeros);
+    let encoded: Vec<Vec<Float>> = zeros.transform(&["green", "purple"]).expect("Zeros aceita desconhecidas");
+    // categorias em ordem crescente: blue, green, red
+    assert_eq!(encoded, [vec![0.0, 1.0, 0.0], vec![0.0, 0.0, 0.0]]);
+
+    let strict = OneHotEncoder::fit(&train, UnknownCategory::Error);
+    assert_eq!(
+        strict.transform::<Float>(&["red", "purple"]),
+        Err(EncodeError::UnknownCategory { row: 1, value: "\"purple\"".to_string() })
+    );
+}
+
+#[test]
+fn encoded_columns_are_appended_to_the_features() {
+    let mut x: Vec<Vec<Float>> = vec![vec![1.0, 2.0], vec![3.0, 4.0], vec![5.0, 6.0]];
+    let colors = ["red", "blue", "red"];
+    let sizes = [1u8, 2, 3];
+
+    let color_encoder = OneHotEncoder::fit(&colors, UnknownCategory::Error);
+    let size_encoder = OneHotEncoder::fit(&sizes, UnknownCategory::Error);
+    color_encoder.append_to(&mut x, &colors).expect("categorias conhecidas");
+    size_encoder.append_to(&mut x, &sizes).expect("categorias conhecidas");
+
+    assert!(x.iter().all(|row| row.len() == 2 + color_encoder.n_columns() + size_encoder.n_columns()));
+    assert_eq!(x[1], [3.0, 4.0, 1.0, 0.0, 0.0, 1.0, 0.0]);
+
+    let before = x.clone();
+    assert_eq!(
+        size_encoder.append_to(&mut x, &sizes[..2]),
+        Err(EncodeError::LengthMismatch { rows: 3, values: 2 })
+    );
+    assert_eq!(x, before);
+}
+
+fn names(values: &[&str]) -> Vec<String> {
+    values.iter().map(|v| v.to_string()).collect()
+}
+
+#[test]
+fn label_encoder_round_trips_in_sorted_order() {
+    let classes = names(&["versicolor", "setosa", "virginica", "setosa"]);
+    let encoder = LabelEncoder::fit(&classes);
+
+    assert_eq!(encoder.classes, names(&["setosa", "versicolor", "virginica"]));
+    let labels = encoder.transform(&classes).expect("classes conhecidas");
+    assert_eq!(labels, [1, 0, 2, 0]);
+    assert_eq!(encoder.inverse_transform(&labels), Ok(classes));
+}
+
+#[test]
+fn label_order_does_not_depend_on_sample_order() {
+    let a = LabelEncoder::fit(&names(&["b", "c", "a", "b"]));
+    let b = LabelEncoder::fit(&names(&["a", "a", "c", "b"]));
+    assert_eq!(a, b);
+}
+
+#[test]
+fn label_encoder_reports_offending_values() {
+    let encoder = LabelEncoder::fit(&names(&["cat", "dog"]));
+
+    assert_eq!(
+        encoder.transform(&names(&["dog", "bird"])),
+        Err(UnknownLabel { index: 1, label: "bird".to_string() })
+    );
+    assert_eq!(
+        encoder.inverse_transform(&[1, 0, 2]),
+        Err(ClassLabelError { index: 2, label: 2, n_classes: 2 })
+    );
+}
+
+/*
+ * Conjunto com entradas ausentes (NaN) nas duas colunas.
+ */
+fn with_missing() -> Dataset {
+    let nan = Float::NAN;
+    let x = vec![vec![1.0, nan], vec![nan, 10.0], vec![4.0, 20.0], vec![7.0, nan], vec![nan, 60.0]];
+    Dataset::new_with_missing(x, vec![0.0; 5]).expect("NaN só nas entradas")
+}
+
+#[test]
+fn mean_imputation_ignores_missing_entries() {
+    let data = with_missing();
+    let imputer = Imputer::fit(&data, ImputeStrategy::Mean).expect("colunas com valores");
+    assert_eq!(imputer.fill, [4.0, 30.0]);
+
+    let filled = imputer.transform(&data).expect("mesma largura");
+    assert!(!filled.has_missing());
+    assert_eq!(filled.inputs()[1], [4.0, 10.0]);
+    assert_eq!(filled.inputs()[3], [7.0, 30.0]);
+
+    let median = Imputer::fit(&data, ImputeStrategy::Median).expect("colunas com valores");
+    assert_eq!(median.fill, [4.0, 20.0]);
+}
+
+#[test]
+fn imputer_fills_test_data_with_training_statistics() {
+    let imputer = Imputer::fit(&with_missing(), ImputeStrategy::Mean).expect("colunas com valores");
+    let test = Dataset::new_with_missing(vec![vec![Float::NAN, 1000.0], vec![2000.0, Float::NAN]], vec![0.0, 0.0])
+        .expect("NaN só nas entradas");
+
+    let filled = imputer.transform(&test).expect("mesma largura");
+    assert_eq!(filled.inputs(), [vec![4.0, 1000.0], vec![2000.0, 30.0]]);
+}
+
+#[test]
+fn fully_missing_column_is_an_error() {
+    let x = vec![vec![1.0, Float::NAN], vec![2.0, Float::NAN]];
+    let data = Dataset::new_with_missing(x, vec![0.0, 1.0]).expect("NaN só nas entradas");
+
+    assert_eq!(Imputer::fit(&data, ImputeStrategy::Mean), Err(MissingColumnError { column: 1 }));
+    assert_eq!(Imputer::fit(&data, ImputeStrategy::Median), Err(MissingColumnError { column: 1 }));
+    let constant = Imputer::fit(&data, ImputeStrategy::Constant(-1.0)).expect("Constant não depende dos valores");
+    assert_eq!(constant.fill, [-1.0, -1.0]);
+}
+
+/*
+ * 6 entradas, das quais só x1 e x4 influenciam y; x6 é constante.
+ */
+fn mostly_noise() -> Dataset {
+    let generated = make_linear(150, &[3.0, 0.0, 0.0, 2.0, 0.0, 0.0], 1.0, 0.0, (0.0, 1.0), 14).expect("parâmetros válidos");
+    let x = generated.data.inputs().iter().map(|s| [&s[..5], &[0.5]].concat()).collect();
+    Dataset::new(x, generated.data.targets().to_vec()).expect("dados válidos")
+}
+
+#[test]
+fn correlation_selection_keeps_informative_columns() {
+    let data = mostly_noise();
+    let (reduced, kept) = select_by_correlation(&data, 0.3);
+
+    assert_eq!(kept, [0, 3]);
+    assert_eq!(reduced.n_features(), 2);
+    assert_eq!(reduced.inputs(), apply_selection(data.inputs(), &kept));
+    assert_eq!(reduced.targets(), data.targets());
+
+    // Com limiar 0, só a coluna constante (sem correlação definida) sai
+    let (_, all) = select_by_correlation(&data, 0.0);
+    assert_eq!(all, [0, 1, 2, 3, 4]);
+}
+
+#[test]
+fn reduced_fit_matches_full_fit() {
+    let data = mostly_noise();
+    let (reduced, _) = select_by_correlation(&data, 0.3);
+    let config = TrainConfig { epochs: 2000, learning_rate: 0.2, ..TrainConfig::default() };
+
+    let mut full = Neuron::zeros(Activation::Identity, 6);
+    let mut small = Neuron::zeros(Activation::Identity, 2);
+    fit_on(&mut full, mse, &data, &config).expect("treinamento válido");
+    fit_on(&mut small, mse, &reduced, &config).expect("treinamento válido");
+
+    let full_cost = try_compute_cost(&full, data.inputs(), data.targets(), mse, data.len()).unwrap();
+    let small_cost = try_compute_cost(&small, reduced.inputs(), reduced.targets(), mse, reduced.len()).unwrap();
+    assert!(small_cost < 1e-3 && full_cost < 1e-3, "custos {} (reduzido) e {} (completo)", small_cost, full_cost);
+}
@@ -0,0 +1,74 @@
+/*
+ * rng.rs
+ *
+ * Testes dos geradores de utils.rs: o SplitMix64 usado sem a
+ * feature "std-rand" e a ausência de aleatoriedade do sistema nessa
+ * configuração.
+ */
+
+use rand::{RngCore, SeedableRng};
+
+use perceptron::netmath::Activation;
+use perceptron::neuron::Neuron;
+use perceptron::utils::{rng_from, seeded_rng, SplitMix64};
+
+#[test]
+fn splitmix64_matches_reference_sequence() {
+    // Saídas da implementação de referência (Vigna) para a semente 0
+    let mut rng = SplitMix64::seed_from_u64(0);
+
+    assert_eq!(rng.next_u64(), 0xe220_a839_7b1d_cdaf);
+    assert_eq!(rng.next_u64(), 0x6e78_9e6a_a1b9_65f4);
+    assert_eq!(rng.next_u64(), 0x06c4_5d18_8009_454f);
+}
+
+#[test]
+fn splitmix64_is_deterministic_per_seed() {
+    let draw = |seed: u64| {
+        let mut rng = SplitMix64::seed_from_u64(seed);
+        (0..16).map(|_| rng.next_u64()).collect::<Vec<u64>>()
+    };
+
+    assert_eq!(draw(42), draw(42));
+    assert_ne!(draw(42), draw(43));
+    assert_eq!(SplitMix64::from_seed(42u64.to_le_bytes()).next_u64(), draw(42)[0]);
+}
+
+#[test]
+fn splitmix64_fills_partial_chunks_in_order() {
+    let mut words = SplitMix64::seed_from_u64(5);
+    let expected: Vec<u8> = [words.next_u64(), words.next_u64()].iter().flat_map(|word| word.to_le_bytes()).collect();
+    let mut bytes = [0u8; 11];
+    SplitMix64::seed_from_u64(5).fill_bytes(&mut bytes);
+
+    assert_eq!(bytes[..], expected[..11]);
+}
+
+/*
+ * Sem "std-rand", todo sorteio vem de uma semente: o gerador com
+ * semente é o SplitMix64 e os geradores "sem semente" repetem a
+ * sequência de FALLBACK_SEED em vez de consultar o sistema.
+ */
+#[cfg(not(feature = "std-rand"))]
+#[test]
+fn without_std_rand_every_draw_is_seeded() {
+    use perceptron::utils::{default_rng, SeededRng, FALLBACK_SEED};
+
+    let _: fn(u64) -> SeededRng = SplitMix64::seed_from_u64;
+    let fallback = SplitMix64::seed_from_u64(FALLBACK_SEED).next_u64();
+
+    assert_eq!(seeded_rng(9).next_u64(), SplitMix64::seed_from_u64(9).next_u64());
+    assert_eq!(rng_from(None).next_u64(), fallback);
+    assert_eq!(default_rng().next_u64(), fallback);
+    assert_eq!(default_rng().next_u64(), default_rng().next_u64());
+}
+
+#[test]
+fn seeded_constructor_is_reproducible_in_both_modes() {
+    let a: Neuron = Neuron::new_seeded(Activation::Identity, 4, 21);
+    let b: Neuron = Neuron::new_seeded(Activation::Identity, 4, 21);
+
+    assert_eq!(a.weights(), b.weights());
+    assert_eq!(a.bias(), b.bias());
+    assert_eq!(rng_from(Some(3)).next_u64(), seeded_rng(3).next_u64());
+}
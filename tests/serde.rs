@@ -0,0 +1,321 @@
+/*
+ * serde.rs
+ *
+ * Testes da gravação e leitura de modelos em JSON e MessagePack
+ * (feature "serde").
+ */
+
+#![cfg(feature = "serde")]
+
+use std::path::PathBuf;
+
+use perceptron::error::CeptronError;
+use perceptron::modelio::{Checkpoint, ModelFile, ModelIoError, BINARY_VERSION, FORMAT_VERSION};
+use perceptron::netmath::{mse, Activation, Float};
+use perceptron::neuralnet::{fit, TrainConfig};
+use perceptron::neuron::{Neuron, NeuronError};
+use perceptron::utils::{randomize_with, seeded_rng};
+
+/*
+ * Caminho de um arquivo temporário exclusivo deste teste.
+ */
+fn temp_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("ceptron-{}-{}", std::process::id(), name))
+}
+
+/*
+ * Neurônio sigmoid treinado por poucas épocas, com parâmetros
+ * "quebrados" (não representáveis exatamente em decimal curto).
+ */
+fn trained() -> Neuron {
+    let x: Vec<Vec<Float>> = vec![vec![6.0, 1.0], vec![5.0, 0.0], vec![1.0, 4.0], vec![1.0, 2.0]];
+    let y: Vec<Float> = vec![1.0, 1.0, 0.0, 0.0];
+    let mut neuron = Neuron::new_seeded(Activation::Sigmoid, 2, 5);
+    let config = TrainConfig { epochs: 50, learning_rate: 0.5, ..TrainConfig::default() };
+    fit(&mut neuron, mse, &x, &y, 4, &config).expect("treinamento válido");
+    neuron
+}
+
+/*
+ * Confere que dois neurônios dão exatamente as mesmas saídas.
+ */
+fn assert_same_predictions(a: &Neuron, b: &Neuron) {
+    for x in [[0.0, 0.0], [6.0, 1.0], [-3.5, 2.25], [1e3, -1e3]] {
+        assert_eq!(a.compute_out(&x).to_bits(), b.compute_out(&x).to_bits());
+    }
+}
+
+#[test]
+fn json_round_trip_is_bit_identical() {
+    let neuron = trained();
+    let path = temp_path("round-trip.json");
+    neuron.save_json(&path).expect("gravação");
+    let loaded: Neuron = Neuron::load_json(&path).expect("leitura");
+    std::fs::remove_file(&path).ok();
+
+    assert_eq!(loaded.weights(), neuron.weights());
+    assert_eq!(loaded.bias().to_bits(), neuron.bias().to_bits());
+    assert_same_predictions(&neuron, &loaded);
+}
+
+#[test]
+fn json_rejects_unknown_activation_and_weight_count() {
+    let unknown = temp_path("unknown.json");
+    std::fs::write(&unknown, r#"{"activation": "relu", "n_connections": 1, "weights": [1.0], "bias": 0.0}"#)
+        .expect("gravação");
+    let mismatch = temp_path("mismatch.json");
+    std::fs::write(&mismatch, r#"{"activation": "identity", "n_connections": 3, "weights": [1.0], "bias": 0.0}"#)
+        .expect("gravação");
+
+    let unknown_err = Neuron::<Float>::load_json(&unknown).expect_err("ativação desconhecida");
+    let mismatch_err = Neuron::<Float>::load_json(&mismatch).expect_err("pesos a menos");
+    std::fs::remove_file(&unknown).ok();
+    std::fs::remove_file(&mismatch).ok();
+
+    assert!(unknown_err.to_string().contains("relu"), "{}", unknown_err);
+    assert!(mismatch_err.to_string().contains("1 pesos"), "{}", mismatch_err);
+}
+
+#[test]
+fn activation_serializes_by_name() {
+    let path = temp_path("named.json");
+    trained().save_json(&path).expect("gravação");
+    let json = std::fs::read_to_string(&path).expect("leitura");
+    std::fs::remove_file(&path).ok();
+
+    assert!(json.contains("\"activation\": \"sigmoid\""), "{}", json);
+}
+
+#[test]
+fn custom_activation_is_not_serializable() {
+    fn relu(x: Float) -> Float {
+        x.max(0.0)
+    }
+    let neuron = Neuron::from_weights(Activation::Custom(relu), vec![1.0], 0.0).expect("parâmetros finitos");
+    let path = temp_path("custom.json");
+
+    assert!(matches!(neuron.save_json(&path), Err(CeptronError::Io(ModelIoError::UnserializableActivation { .. }))));
+    assert!(!path.exists());
+}
+
+#[test]
+fn versioned_save_predicts_identically_on_random_inputs() {
+    let neuron = trained();
+    let path = temp_path("versioned.json");
+    neuron.save(&path).expect("gravação");
+    let json = std::fs::read_to_string(&path).expect("leitura");
+    let loaded: Result<Neuron, _> = Neuron::load(&path);
+    std::fs::remove_file(&path).ok();
+    let loaded = loaded.expect("leitura");
+
+    assert!(json.contains(&format!("\"format_version\": {}", FORMAT_VERSION)), "{}", json);
+    let mut rng = seeded_rng(10);
+    for _i in 0..100 {
+        let x: Vec<Float> = (0..2).map(|_| randomize_with(&mut rng, -50.0, 50.0)).collect();
+        assert_eq!(loaded.compute_out(&x).to_bits(), neuron.compute_out(&x).to_bits());
+    }
+}
+
+#[test]
+fn corrupted_file_is_a_parse_error() {
+    let path = temp_path("corrupted.json");
+    let neuron = trained();
+    neuron.save(&path).expect("gravação");
+    let json = std::fs::read_to_string(&path).expect("leitura");
+    std::fs::write(&path, &json[..json.len() / 2]).expect("gravação");
+
+    let result = Neuron::<Float>::load(&path);
+    std::fs::remove_file(&path).ok();
+    assert!(matches!(result, Err(CeptronError::Io(ModelIoError::Json(_)))), "{:?}", result);
+}
+
+#[test]
+fn msgpack_and_json_round_trip_through_each_other() {
+    let neuron = trained();
+    let msgpack = temp_path("cross.msgpack");
+    let json = temp_path("cross.json");
+    neuron.save_msgpack(&msgpack).expect("gravação");
+    let from_msgpack: Neuron = Neuron::load_msgpack(&msgpack).expect("leitura");
+    from_msgpack.save(&json).expect("gravação");
+    let from_json: Result<Neuron, _> = Neuron::load(&json);
+    let msgpack_len = std::fs::metadata(&msgpack).expect("arquivo gravado").len();
+    let json_len = std::fs::metadata(&json).expect("arquivo gravado").len();
+    std::fs::remove_file(&msgpack).ok();
+    std::fs::remove_file(&json).ok();
+    let from_json = from_json.expect("leitura");
+
+    assert_eq!(from_json.weights(), neuron.weights());
+    assert_eq!(from_json.bias().to_bits(), neuron.bias().to_bits());
+    assert_same_predictions(&neuron, &from_msgpack);
+    assert_same_predictions(&neuron, &from_json);
+    assert!(msgpack_len < json_len, "MessagePack {} bytes, JSON {} bytes", msgpack_len, json_len);
+}
+
+#[test]
+fn truncated_msgpack_is_a_decode_error() {
+    let neuron = trained();
+    let model = temp_path("truncated.msgpack");
+    let checkpoint = temp_path("truncated-checkpoint.msgpack");
+    neuron.save_msgpack(&model).expect("gravação");
+    Checkpoint::save_msgpack(&checkpoint, 3, 4, &neuron).expect("gravação");
+    for path in [&model, &checkpoint] {
+        let bytes = std::fs::read(path).expect("leitura");
+        std::fs::write(path, &bytes[..bytes.len() - 5]).expect("gravação");
+    }
+
+    let loaded = Neuron::<Float>::load_msgpack(&model);
+    let resumed = Checkpoint::<Float>::load(&checkpoint);
+    std::fs::remove_file(&model).ok();
+    std::fs::remove_file(&checkpoint).ok();
+    assert!(matches!(loaded, Err(CeptronError::Io(ModelIoError::MsgPackDecode(_)))), "{:?}", loaded);
+    assert!(matches!(resumed, Err(ModelIoError::MsgPackDecode(_))), "{:?}", resumed.err());
+}
+
+#[test]
+fn future_version_is_reported_clearly() {
+    let path = temp_path("future.json");
+    let future = FORMAT_VERSION + 1;
+    let json = format!(
+        r#"{{"format_version": {}, "kind": "neuron", "model": {{"activation": "identity", "n_connections": 1, "weights": [1.0], "bias": 0.0}}}}"#,
+        future
+    );
+    std::fs::write(&path, json).expect("gravação");
+
+    let result = Neuron::<Float>::load(&path);
+    std::fs::remove_file(&path).ok();
+    match result {
+        Err(CeptronError::Io(err @ ModelIoError::VersionTooNew { .. })) => {
+            assert!(matches!(err, ModelIoError::VersionTooNew { found, supported } if found == future && supported == FORMAT_VERSION));
+            assert!(err.to_string().contains("mais nova"), "{}", err);
+        }
+        other => panic!("esperado VersionTooNew, obtido {:?}", other),
+    }
+}
+
+/*
+ * O mesmo neurônio (y = 3x1 + 2x2 + 5) em cada versão histórica do
+ * formato JSON.
+ */
+const JSON_V0: &str = r#"{"activation": "identity", "n_connections": 2, "weights": [3.0, 2.0], "bias": 5.0}"#;
+const JSON_V1: &str =
+    r#"{"format_version": 1, "activation": "identity", "n_connections": 2, "weights": [3.0, 2.0], "bias": 5.0}"#;
+const JSON_V2: &str = r#"{"format_version": 2, "kind": "neuron", "model": {"activation": "identity", "n_connections": 2, "weights": [3.0, 2.0], "bias": 5.0}}"#;
+
+/*
+ * O mesmo neurônio no formato binário versão 1: "CEPT", versão,
+ * ativação (0 = identity), flags (1 = com bias), 32 bits, 2 conexões
+ * e os valores em f32 little-endian.
+ */
+const BIN_V1: &[u8] = b"CEPT\x01\x00\x01\x20\x02\x00\x00\x00\
+\x00\x00\x40\x40\x00\x00\x00\x40\x00\x00\xa0\x40";
+
+/*
+ * Lê um arquivo de modelo com o conteúdo fornecido.
+ */
+fn load_fixture(name: &str, bytes: &[u8]) -> Result<Neuron, ModelIoError> {
+    let path = temp_path(name);
+    std::fs::write(&path, bytes).expect("gravação");
+    let result = ModelFile::load(&path);
+    std::fs::remove_file(&path).ok();
+    result.map(|ModelFile::Neuron(neuron)| neuron)
+}
+
+#[test]
+fn every_historical_version_loads_and_predicts() {
+    let fixtures: [(&str, &[u8]); 4] = [
+        ("v0.json", JSON_V0.as_bytes()),
+        ("v1.json", JSON_V1.as_bytes()),
+        ("v2.json", JSON_V2.as_bytes()),
+        ("v1.cept", BIN_V1),
+    ];
+
+    for (name, bytes) in fixtures {
+        let neuron = load_fixture(name, bytes).unwrap_or_else(|err| panic!("{}: {}", name, err));
+        assert_eq!(neuron.weights(), &[3.0, 2.0], "{}", name);
+        assert_eq!(neuron.compute_out(&[6.0, 1.0]), 25.0, "{}", name);
+        assert_eq!(*neuron.activation(), Activation::Identity, "{}", name);
+    }
+}
+
+#[test]
+fn old_versions_are_rewritten_in_the_current_format() {
+    let neuron = load_fixture("migrate.json", JSON_V0.as_bytes()).expect("versão 0");
+    let json = ModelFile::to_json_string(&neuron).expect("ativação conhecida");
+
+    assert!(json.contains(&format!("\"format_version\": {}", FORMAT_VERSION)), "{}", json);
+    assert!(json.contains("\"kind\": \"neuron\""), "{}", json);
+}
+
+#[test]
+fn newer_binary_version_is_too_new() {
+    let mut bytes = BIN_V1.to_vec();
+    bytes[4] = BINARY_VERSION + 1;
+
+    match load_fixture("future.cept", &bytes) {
+        Err(ModelIoError::VersionTooNew { found, supported }) => {
+            assert_eq!((found, supported), (u64::from(BINARY_VERSION) + 1, u64::from(BINARY_VERSION)));
+        }
+        other => panic!("esperado VersionTooNew, obtido {:?}", other),
+    }
+}
+
+/*
+ * Neurônio com uma ativação Custom equivalente à identidade.
+ */
+fn custom_identity() -> Neuron {
+    fn same(x: Float) -> Float {
+        x
+    }
+    Neuron::from_weights(Activation::Custom(same), vec![3.0, 2.0], 5.0).expect("parâmetros finitos")
+}
+
+#[test]
+fn every_save_path_rejects_custom_activation_up_front() {
+    let neuron = custom_identity();
+    type Save = fn(&Neuron, &std::path::Path) -> Result<(), CeptronError>;
+    let saves: [(&str, Save); 7] = [
+        ("save", |n, p| n.save(p)),
+        ("save_json", |n, p| n.save_json(p)),
+        ("save_msgpack", |n, p| n.save_msgpack(p)),
+        ("save_bin", |n, p| n.save_bin(p)),
+        ("export_csv", |n, p| Ok(n.export_csv(p)?)),
+        ("export_onnx", |n, p| Ok(n.export_onnx(p)?)),
+        ("checkpoint", |n, p| Ok(Checkpoint::save(p, 1, 0, n)?)),
+    ];
+
+    for (name, save) in saves {
+        let path = temp_path(&format!("custom-{}", name));
+        let result = save(&neuron, &path);
+        let created = path.exists();
+        std::fs::remove_file(&path).ok();
+
+        assert!(
+            matches!(result, Err(CeptronError::Io(ModelIoError::UnserializableActivation { layer: 0, neuron: 0 }))),
+            "{}: {:?}",
+            name,
+            result
+        );
+        assert!(!created, "{} não deve criar o arquivo", name);
+    }
+}
+
+#[test]
+fn renamed_activation_saves_and_loads() {
+    let neuron = custom_identity().with_named_activation("identity").expect("ativação conhecida");
+    let path = temp_path("renamed.json");
+    neuron.save(&path).expect("gravação");
+    let loaded: Result<Neuron, _> = Neuron::load(&path);
+    std::fs::remove_file(&path).ok();
+    let loaded = loaded.expect("leitura");
+
+    assert_eq!(*loaded.activation(), Activation::Identity);
+    assert_eq!(loaded.compute_out(&[6.0, 1.0]), custom_identity().compute_out(&[6.0, 1.0]));
+}
+
+#[test]
+fn unknown_activation_name_is_rejected() {
+    assert_eq!(
+        custom_identity().with_named_activation("relu").map(|_| ()),
+        Err(NeuronError::UnknownActivation { name: "relu".to_string() })
+    );
+}
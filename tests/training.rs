@@ -0,0 +1,451 @@
+/*
+ * training.rs
+ *
+ * Testes do treinamento (neuralnet.rs) no exemplo linear
+ * y = 3x1 + 2x2 + 5.
+ */
+
+#![cfg(feature = "std")]
+
+use perceptron::assert_neuron_approx_eq;
+use perceptron::data::{make_blobs, make_linear, train_test_split_with, Dataset};
+use perceptron::error::CeptronError;
+use perceptron::modelio::ModelIoError;
+use perceptron::netmath::{mse, Activation, Float, Scalar};
+use perceptron::neuralnet::{
+    fit, fit_on, partial_fit, try_compute_cost_on, try_compute_gradient, try_train, DivergenceError, FitError,
+    TrainConfig,
+};
+use perceptron::neuron::{Neuron, Neuron32, Neuron64, NeuronError, ParamType};
+use perceptron::pipeline::Pipeline;
+use perceptron::utils::CeptronRng;
+
+/*
+ * Amostras exatas (sem ruído) de y = 3x1 + 2x2 + 5, com entradas
+ * em [0, 1].
+ */
+fn linear_data() -> Dataset {
+    make_linear(40, &[3.0, 2.0], 5.0, 0.0, (0.0, 1.0), 7)
+        .expect("parâmetros válidos")
+        .data
+}
+
+/*
+ * Amostras de y = 3x1 + 2x2 + 5 com entradas inteiras.
+ */
+fn stream() -> (Vec<Vec<Float>>, Vec<Float>) {
+    let x: Vec<Vec<Float>> = vec![vec![6.0, 1.0], vec![5.0, 0.0], vec![4.0, 1.0], vec![1.0, 4.0], vec![1.0, 2.0]];
+    let y = x.iter().map(|sample| 3.0 * sample[0] + 2.0 * sample[1] + 5.0).collect();
+    (x, y)
+}
+
+/*
+ * O neurônio exato do exemplo linear.
+ */
+fn true_neuron() -> Neuron {
+    Neuron::from_weights(Activation::Identity, vec![3.0, 2.0], 5.0).expect("parâmetros finitos")
+}
+
+fn config() -> TrainConfig {
+    TrainConfig {
+        epochs: 2000,
+        learning_rate: 0.1,
+        seed: Some(7),
+        ..TrainConfig::default()
+    }
+}
+
+/*
+ * Treina um neurônio identidade com a semente fornecida.
+ */
+fn train_seeded(seed: u64) -> Neuron {
+    let mut neuron = Neuron::new_seeded(Activation::Identity, 2, seed);
+    fit_on(&mut neuron, mse, &linear_data(), &config()).expect("treinamento válido");
+    neuron
+}
+
+#[test]
+fn seeded_training_is_bit_identical() {
+    let a = train_seeded(7);
+    let b = train_seeded(7);
+
+    assert_eq!(a.weights(), b.weights());
+    assert_eq!(a.bias(), b.bias());
+}
+
+/*
+ * Execução completa a partir de uma única semente: geração dos dados
+ * (com ruído), embaralhamento, divisão treino/teste, inicialização e
+ * treino, cada etapa com a sua sub-semente (TrainConfig::derived_seed).
+ */
+fn full_run(seed: u64) -> Neuron {
+    let config = TrainConfig { epochs: 500, learning_rate: 0.1, seed: Some(seed), ..TrainConfig::default() };
+    let stage = |stream: u64| config.derived_seed(stream).expect("semente definida");
+
+    let data = make_linear(40, &[3.0, 2.0], 5.0, 0.1, (0.0, 1.0), stage(0)).expect("parâmetros válidos").data;
+    let data = data.shuffled(Some(stage(1)));
+    let (train_data, _test_data) =
+        train_test_split_with(&data, 0.25, &mut CeptronRng::seeded(stage(2))).expect("divisão válida");
+    let mut neuron = Neuron::new_with_rng(Activation::Identity, 2, &mut CeptronRng::seeded(stage(3)));
+    fit_on(&mut neuron, mse, &train_data, &config).expect("treinamento válido");
+    neuron
+}
+
+#[test]
+fn one_seed_reproduces_the_whole_run() {
+    let (a, b) = (full_run(11), full_run(11));
+    assert_eq!(a.fingerprint(), b.fingerprint());
+    assert_eq!(a.bias().to_bits(), b.bias().to_bits());
+
+    assert_ne!(full_run(12).fingerprint(), a.fingerprint());
+}
+
+#[test]
+fn training_from_zeros_converges() {
+    let data = linear_data();
+    let mut neuron = Neuron::zeros(Activation::Identity, 2);
+    fit_on(&mut neuron, mse, &data, &config()).expect("treinamento válido");
+
+    assert!(try_compute_cost_on(&neuron, &data, mse).expect("dados válidos") < 0.01);
+    assert_neuron_approx_eq!(neuron, true_neuron(), 0.05);
+}
+
+/*
+ * Treina y = 300x1 + 200x2 + 500 na precisão T e retorna o custo
+ * final. Com pesos dessa ordem, as diferenças finitas em f32 perdem
+ * precisão.
+ */
+fn final_cost<T: Scalar>(mut neuron: Neuron<T>) -> T {
+    let value = |x: f64| T::from(x).unwrap();
+    let data: Dataset<T> = make_linear(40, &[value(300.0), value(200.0)], value(500.0), T::zero(), (T::zero(), T::one()), 7)
+        .expect("parâmetros válidos")
+        .data;
+    let config = TrainConfig {
+        epochs: 5000,
+        learning_rate: value(0.1),
+        ..TrainConfig::default()
+    };
+    fit_on(&mut neuron, mse, &data, &config).expect("treinamento válido");
+    try_compute_cost_on(&neuron, &data, mse).expect("dados válidos")
+}
+
+#[test]
+fn f64_reaches_lower_cost_than_f32() {
+    let cost32 = final_cost(Neuron32::new_seeded(Activation::Identity, 2, 7));
+    let cost64 = final_cost(Neuron64::new_seeded(Activation::Identity, 2, 7));
+
+    assert!(cost64 < f64::from(cost32), "f32: {}, f64: {}", cost32, cost64);
+}
+
+#[test]
+fn float_follows_the_f64_feature() {
+    let cost: Float = final_cost(Neuron::new_seeded(Activation::Identity, 2, 7));
+
+    // Sem a feature, Float é f32 e o resultado é o mesmo de Neuron32
+    #[cfg(not(feature = "f64"))]
+    let (width, same_width) = (4, final_cost(Neuron32::new_seeded(Activation::Identity, 2, 7)));
+    #[cfg(feature = "f64")]
+    let (width, same_width) = (8, final_cost(Neuron64::new_seeded(Activation::Identity, 2, 7)));
+
+    assert_eq!(size_of::<Float>(), width);
+    assert_eq!(cost.to_bits(), same_width.to_bits());
+}
+
+#[test]
+fn partial_fit_stream_converges() {
+    let (x, y) = stream();
+    let mut neuron = Neuron::zeros(Activation::Identity, 2);
+
+    for _pass in 0..3000 {
+        for (sample, &target) in x.iter().zip(&y) {
+            partial_fit(&mut neuron, sample, target, 0.01, mse).expect("largura correta");
+        }
+    }
+
+    assert_neuron_approx_eq!(neuron, true_neuron(), 0.05);
+}
+
+#[test]
+fn partial_fit_matches_single_sample_steps() {
+    let (x, y) = stream();
+    let mut online = Neuron::new_seeded(Activation::Identity, 2, 3);
+    let mut batch = Neuron::new_seeded(Activation::Identity, 2, 3);
+
+    for _pass in 0..20 {
+        for i in 0..x.len() {
+            partial_fit(&mut online, &x[i], y[i], 0.001, mse).expect("largura correta");
+            try_train(&mut batch, mse, &x[i..=i], &y[i..=i], 1).expect("amostra válida");
+        }
+    }
+
+    assert_eq!(online.weights(), batch.weights());
+    assert_eq!(online.bias(), batch.bias());
+}
+
+#[test]
+fn partial_fit_rejects_wrong_width() {
+    let mut neuron = Neuron::zeros(Activation::Identity, 2);
+    let err = partial_fit(&mut neuron, &[1.0], 1.0, 0.01, mse).expect_err("largura errada");
+
+    assert!(matches!(err, CeptronError::DimensionMismatch { expected: 2, actual: 1, .. }), "{:?}", err);
+    assert_eq!(neuron.weights(), &[0.0, 0.0]);
+}
+
+#[test]
+fn partial_fit_divergence_leaves_neuron_unchanged() {
+    let mut neuron = Neuron::from_weights(Activation::Identity, vec![0.5, -0.5], 0.25).expect("parâmetros finitos");
+    // Amostra finita: o gradiente de Weight(0) é finito, mas o peso
+    // atualizado faz a saída transbordar antes do gradiente de Weight(1)
+    let large = Float::MAX.sqrt() / 4.0;
+    let err = partial_fit(&mut neuron, &[large, 1.0], 0.0, 1.0, mse).expect_err("gradiente infinito");
+
+    assert!(
+        matches!(err, CeptronError::Diverged(DivergenceError { epoch: 0, param: ParamType::Weight(1) })),
+        "{:?}",
+        err
+    );
+    assert_eq!(neuron.weights(), &[0.5, -0.5]);
+    assert_eq!(neuron.bias(), 0.25);
+
+    // O neurônio continua utilizável no fluxo
+    partial_fit(&mut neuron, &[1.0, 2.0], 1.0, 0.01, mse).expect("amostra válida");
+    assert!(neuron.weights().iter().all(|w| w.is_finite()));
+}
+
+#[test]
+fn custom_loop_over_params_matches_train() {
+    let (x, y) = stream();
+    let mut custom = Neuron::new_seeded(Activation::Identity, 2, 3);
+    let mut trained = Neuron::new_seeded(Activation::Identity, 2, 3);
+    let learning_rate: Float = 0.001;
+
+    assert_eq!(custom.param_count(), 3);
+    for _step in 0..50 {
+        for param in custom.params() {
+            let gradient = try_compute_gradient(&mut custom, mse, &x, &y, None, param, x.len()).expect("amostras válidas");
+            let value = custom.get_param(param).expect("parâmetro existente");
+            custom.set_param(param, value + -learning_rate * gradient).expect("valor finito");
+        }
+        try_train(&mut trained, mse, &x, &y, x.len()).expect("amostras válidas");
+    }
+
+    assert_eq!(custom.weights(), trained.weights());
+    assert_eq!(custom.bias().to_bits(), trained.bias().to_bits());
+}
+
+#[test]
+fn missing_params_are_errors() {
+    let mut neuron = Neuron::zeros(Activation::Identity, 2);
+
+    assert_eq!(
+        neuron.params().collect::<Vec<_>>(),
+        [ParamType::Weight(0), ParamType::Weight(1), ParamType::Bias]
+    );
+    assert!(matches!(
+        neuron.get_param(ParamType::Weight(2)),
+        Err(NeuronError::WeightIndexOutOfRange { index: 2, n_connections: 2 })
+    ));
+    assert!(matches!(
+        neuron.set_param(ParamType::Weight(5), 1.0),
+        Err(NeuronError::WeightIndexOutOfRange { index: 5, n_connections: 2 })
+    ));
+    assert!(matches!(
+        neuron.set_param(ParamType::Weight(0), Float::NAN),
+        Err(NeuronError::NonFiniteWeight { index: 0, .. })
+    ));
+    assert_eq!(neuron.weights(), &[0.0, 0.0]);
+}
+
+#[test]
+fn seeded_linear_example_learns_true_weights() {
+    let neuron = train_seeded(7);
+
+    assert_neuron_approx_eq!(neuron, true_neuron(), 0.05);
+}
+
+#[test]
+fn training_after_reset_matches_fresh_neuron() {
+    let mut reused = train_seeded(1);
+    reused.reset(Some(7));
+    fit_on(&mut reused, mse, &linear_data(), &config()).expect("treinamento válido");
+    let fresh = train_seeded(7);
+
+    assert_eq!(reused.weights(), fresh.weights());
+    assert_eq!(reused.bias(), fresh.bias());
+}
+
+#[test]
+fn identical_seeded_runs_share_a_fingerprint() {
+    assert_eq!(train_seeded(7).fingerprint(), train_seeded(7).fingerprint());
+    let untrained: Neuron = Neuron::new_seeded(Activation::Identity, 2, 7);
+    assert_ne!(train_seeded(7).fingerprint(), untrained.fingerprint());
+}
+
+#[test]
+fn sigmoid_separates_blobs() {
+    let centers = vec![vec![1.0, 1.0], vec![4.0, 4.0]];
+    let (x, labels) = make_blobs(100, &centers, 0.6, 11).expect("centros válidos");
+    let targets = labels.iter().map(|&label| label as Float).collect();
+    let data = Dataset::new(x, targets).expect("dados válidos");
+
+    let mut neuron = Neuron::new_seeded(Activation::Sigmoid, 2, 11);
+    let config = TrainConfig { epochs: 1000, learning_rate: 0.5, ..TrainConfig::default() };
+    fit_on(&mut neuron, mse, &data, &config).expect("treinamento válido");
+
+    let predicted = neuron.predict_labels(data.inputs(), 0.5);
+    let correct = predicted.iter().zip(&labels).filter(|&(&p, &label)| p == (label == 1)).count();
+    assert!(correct as f64 / data.len() as f64 > 0.95, "{} de {}", correct, data.len());
+}
+
+#[test]
+fn bias_free_training_keeps_bias_zero() {
+    // Dados centrados, com ruído: y ≈ 2x1 - x2
+    let x: Vec<Vec<Float>> = vec![vec![1.0, 2.0], vec![-1.0, 0.0], vec![2.0, -1.0], vec![-2.0, -1.0]];
+    let y: Vec<Float> = vec![0.1, -2.2, 5.1, -3.0];
+    let mut neuron = Neuron::zeros(Activation::Identity, 2).without_bias();
+    let config = TrainConfig { epochs: 50000, learning_rate: 0.01, ..TrainConfig::default() };
+    fit_on(&mut neuron, mse, &Dataset::new(x.clone(), y.clone()).expect("dados válidos"), &config)
+        .expect("treinamento válido");
+
+    // Mínimos quadrados sem intercepto: (XᵀX) w = Xᵀy
+    let (mut a, mut b, mut c, mut p, mut q) = (0.0, 0.0, 0.0, 0.0, 0.0);
+    for (sample, &target) in x.iter().zip(&y) {
+        a += sample[0] * sample[0];
+        b += sample[0] * sample[1];
+        c += sample[1] * sample[1];
+        p += sample[0] * target;
+        q += sample[1] * target;
+    }
+    let det = a * c - b * b;
+    let expected = [(c * p - b * q) / det, (a * q - b * p) / det];
+
+    assert_eq!(neuron.bias().to_bits(), Float::to_bits(0.0));
+    for (&weight, &closed_form) in neuron.weights().iter().zip(&expected) {
+        assert!((weight - closed_form).abs() < 1e-3, "{} != {}", weight, closed_form);
+    }
+}
+
+#[test]
+fn loose_max_norm_changes_nothing() {
+    let data = linear_data();
+    let short = TrainConfig { epochs: 20, ..config() };
+    let mut free = Neuron::new_seeded(Activation::Identity, 2, 2);
+    let mut constrained = Neuron::new_seeded(Activation::Identity, 2, 2);
+    fit_on(&mut free, mse, &data, &short).expect("treinamento válido");
+    fit_on(&mut constrained, mse, &data, &TrainConfig { max_norm: Some(100.0), ..short })
+        .expect("treinamento válido");
+
+    assert_eq!(free.weights(), constrained.weights());
+    assert_eq!(free.bias(), constrained.bias());
+}
+
+#[test]
+fn tight_max_norm_bounds_every_epoch() {
+    let data = linear_data();
+    let step = TrainConfig { epochs: 1, max_norm: Some(1.0), ..config() };
+    let mut neuron = Neuron::new_seeded(Activation::Identity, 2, 2);
+
+    for _epoch in 0..200 {
+        fit_on(&mut neuron, mse, &data, &step).expect("treinamento válido");
+        assert!(neuron.weight_norm() <= 1.0 + 1e-6, "norma {}", neuron.weight_norm());
+    }
+}
+
+#[test]
+fn infinite_sample_diverges_at_first_epoch() {
+    let (x, mut y) = stream();
+    y[2] = Float::INFINITY;
+
+    for debug_checks in [false, true] {
+        let mut neuron = Neuron::zeros(Activation::Identity, 2);
+        let config = TrainConfig { validate_data: false, debug_checks, ..config() };
+        match fit(&mut neuron, mse, &x, &y, x.len(), &config) {
+            Err(CeptronError::Diverged(DivergenceError { epoch: 0, param: ParamType::Weight(0) })) => {}
+            other => panic!("esperado Diverged na época 0, obtido {:?}", other),
+        }
+    }
+}
+
+#[test]
+fn debug_checks_do_not_change_finite_training() {
+    let data = linear_data();
+    let mut plain = Neuron::new_seeded(Activation::Identity, 2, 4);
+    let mut checked = Neuron::new_seeded(Activation::Identity, 2, 4);
+    fit_on(&mut plain, mse, &data, &config()).expect("treinamento válido");
+    fit_on(&mut checked, mse, &data, &TrainConfig { debug_checks: true, ..config() }).expect("treinamento válido");
+
+    assert_eq!(plain.weights(), checked.weights());
+    assert_eq!(plain.bias(), checked.bias());
+}
+
+/*
+ * y = 300x1 + 200x2 + 5000 em f32: o custo inicial (~10⁷) é grande
+ * demais para o gradiente por diferenças finitas em precisão simples.
+ */
+fn large_target_data() -> Dataset<f32> {
+    make_linear(40, &[300.0, 200.0], 5000.0, 0.0, (0.0, 1.0), 3)
+        .expect("parâmetros válidos")
+        .data
+}
+
+fn large_target_config(scale_target: bool) -> TrainConfig<f32> {
+    TrainConfig { epochs: 5000, learning_rate: 0.1, scale_target, ..TrainConfig::default() }
+}
+
+#[test]
+fn scale_target_trains_where_raw_targets_stall() {
+    let data = large_target_data();
+
+    let mut raw: Neuron32 = Neuron::zeros(Activation::Identity, 2);
+    fit_on(&mut raw, mse, &data, &large_target_config(false)).expect("treinamento válido");
+    assert!(try_compute_cost_on(&raw, &data, mse).expect("dados válidos") > 1e6, "sem padronização o gradiente se perde no arredondamento");
+
+    let mut scaled: Neuron32 = Neuron::zeros(Activation::Identity, 2);
+    fit_on(&mut scaled, mse, &data, &large_target_config(true)).expect("treinamento válido");
+    let expected = Neuron::from_weights(Activation::Identity, vec![300.0, 200.0], 5000.0).expect("parâmetros finitos");
+    assert_neuron_approx_eq!(scaled, expected, 0.1);
+}
+
+#[test]
+fn scale_target_predicts_in_original_units_like_a_pipeline() {
+    let data = large_target_data();
+
+    let mut neuron: Neuron32 = Neuron::zeros(Activation::Identity, 2);
+    fit_on(&mut neuron, mse, &data, &large_target_config(true)).expect("treinamento válido");
+    let mut pipeline = Pipeline::new(Neuron::zeros(Activation::Identity, 2), None, true);
+    pipeline.fit(&data, &large_target_config(false)).expect("treinamento válido");
+
+    for (sample, &target) in data.inputs().iter().zip(data.targets()) {
+        let (direct, chained) = (neuron.compute_out(sample), pipeline.predict(sample));
+        assert!((direct - chained).abs() < 0.05, "{} != {}", direct, chained);
+        assert!((direct - target).abs() < 0.05, "{} != {}", direct, target);
+    }
+}
+
+#[test]
+fn scale_target_rejects_nonlinear_activations() {
+    let data = large_target_data();
+    let mut neuron: Neuron32 = Neuron::zeros(Activation::Sigmoid, 2);
+    let err = fit_on(&mut neuron, mse, &data, &large_target_config(true)).expect_err("ativação não linear");
+    assert!(matches!(&err, CeptronError::InvalidConfig(msg) if msg.contains("scale_target")), "{:?}", err);
+    assert_eq!(neuron.weights(), [0.0, 0.0]);
+}
+
+#[test]
+fn unsavable_model_fails_before_training() {
+    fn relu(x: Float) -> Float {
+        x.max(0.0)
+    }
+    let path = std::env::temp_dir().join(format!("ceptron-{}-custom-fit.cept", std::process::id()));
+    let config = TrainConfig { epochs: 50, save_path: Some(path.clone()), ..TrainConfig::default() };
+    let mut neuron = Neuron::from_weights(Activation::Custom(relu), vec![0.5, 0.5], 0.0).expect("parâmetros finitos");
+
+    let result = fit_on(&mut neuron, mse, &linear_data(), &config);
+    assert!(
+        matches!(result, Err(CeptronError::Fit(FitError::Save(ModelIoError::UnserializableActivation { .. })))),
+        "{:?}",
+        result
+    );
+    assert_eq!((neuron.weights(), neuron.bias()), (&[0.5, 0.5][..], 0.0));
+    assert!(!path.exists());
+}
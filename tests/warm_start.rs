@@ -0,0 +1,132 @@
+/*
+ * warm_start.rs
+ *
+ * Testes de fit_from: continuar o treino de um modelo já treinado
+ * em vez de começar de pesos aleatórios.
+ */
+
+#![cfg(feature = "std")]
+
+use std::path::PathBuf;
+
+use perceptron::data::{make_linear, Dataset};
+use perceptron::error::CeptronError;
+use perceptron::netmath::{mse, Activation, Float};
+use perceptron::neuralnet::{fit_from, fit_on, try_compute_cost_on, FitError, TrainConfig};
+use perceptron::neuron::Neuron;
+
+/*
+ * Caminho de um arquivo temporário exclusivo deste teste.
+ */
+fn temp_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("ceptron-{}-{}", std::process::id(), name))
+}
+
+/*
+ * Amostras de y = w·x + b com pouco ruído e semente fixa.
+ */
+fn linear(weights: &[Float], bias: Float) -> Dataset {
+    make_linear(40, weights, bias, 0.01, (0.0, 1.0), 7)
+        .expect("parâmetros válidos")
+        .data
+}
+
+fn config(epochs: usize) -> TrainConfig {
+    TrainConfig { epochs, learning_rate: 0.1, seed: Some(3), ..TrainConfig::default() }
+}
+
+fn cost(neuron: &Neuron, data: &Dataset) -> Float {
+    try_compute_cost_on(neuron, data, mse).expect("dados válidos")
+}
+
+/*
+ * Um neurônio treinado até convergir em y = 3x1 + 2x2 + 5.
+ */
+fn converged() -> Neuron {
+    let mut neuron = Neuron::new_seeded(Activation::Identity, 2, 11);
+    fit_on(&mut neuron, mse, &linear(&[3.0, 2.0], 5.0), &config(5000)).expect("treinamento válido");
+    neuron
+}
+
+/*
+ * Treina uma época por vez até o custo ficar abaixo de tol e
+ * retorna quantas épocas foram necessárias.
+ */
+fn epochs_to(mut neuron: Neuron, data: &Dataset, tol: Float) -> usize {
+    for epoch in 1..=20_000 {
+        neuron = fit_from(neuron, mse, data, &config(1)).expect("treinamento válido");
+        if cost(&neuron, data) < tol {
+            return epoch;
+        }
+    }
+    panic!("o custo não chegou a {}", tol);
+}
+
+#[test]
+fn warm_start_on_the_same_data_barely_moves() {
+    let data = linear(&[3.0, 2.0], 5.0);
+    let start = converged();
+    let before = cost(&start, &data);
+
+    let resumed = fit_from(converged(), mse, &data, &config(200)).expect("treinamento válido");
+
+    assert!((cost(&resumed, &data) - before).abs() < 1e-4, "{} -> {}", before, cost(&resumed, &data));
+    assert!(resumed.approx_eq(&start, 1e-2));
+}
+
+#[test]
+fn warm_start_adapts_faster_than_cold_start() {
+    let shifted = linear(&[3.2, 1.9], 5.3);
+    let tol = 1e-3;
+
+    let warm = epochs_to(converged(), &shifted, tol);
+    let cold = epochs_to(Neuron::new_seeded(Activation::Identity, 2, 11), &shifted, tol);
+
+    assert!(warm < cold, "warm {} épocas, cold {} épocas", warm, cold);
+}
+
+#[test]
+fn width_mismatch_errors_before_touching_the_model() {
+    let path = temp_path("warm-width.cept");
+    converged().save_bin(&path).expect("gravação");
+    let saved = std::fs::read(&path).expect("leitura");
+    let wide = linear(&[3.0, 2.0, 1.0], 5.0);
+    let overwrite = TrainConfig { save_path: Some(path.clone()), ..config(10) };
+
+    let from_model = fit_from(converged(), mse, &wide, &overwrite);
+    let from_path = fit_from(path.as_path(), mse, &wide, &overwrite);
+    let after = std::fs::read(&path).expect("leitura");
+    std::fs::remove_file(&path).ok();
+
+    for result in [from_model, from_path] {
+        match result {
+            Err(CeptronError::DimensionMismatch { expected: 2, actual: 3, .. }) => {}
+            other => panic!("esperado FeatureWidth, obtido {:?}", other),
+        }
+    }
+    assert_eq!(after, saved);
+}
+
+#[test]
+fn warm_start_from_a_path_matches_the_model() {
+    let path = temp_path("warm-path.cept");
+    let start = converged();
+    start.save_bin(&path).expect("gravação");
+    let shifted = linear(&[3.2, 1.9], 5.3);
+
+    let from_path = fit_from(path.as_path(), mse, &shifted, &config(50));
+    std::fs::remove_file(&path).ok();
+    let from_model = fit_from(start, mse, &shifted, &config(50)).expect("treinamento válido");
+
+    assert_eq!(from_path.expect("treinamento válido").fingerprint(), from_model.fingerprint());
+}
+
+#[test]
+fn missing_file_is_a_load_error() {
+    let path = temp_path("warm-missing.cept");
+
+    match fit_from(path, mse, &linear(&[3.0, 2.0], 5.0), &config(10)) {
+        Err(CeptronError::Fit(FitError::Load(_))) => {}
+        other => panic!("esperado Load, obtido {:?}", other),
+    }
+}